@@ -0,0 +1,541 @@
+use crate::{busy_indicator, language, tr};
+
+use egui::{Button, ComboBox, Context, Grid, ScrollArea, Ui};
+use polars::prelude::{pivot::pivot_stable, *};
+use std::fmt;
+use tokio::{
+    runtime::Handle,
+    sync::oneshot::{self, Receiver, error::TryRecvError},
+};
+
+/// Name of the aggregated column before pivoting.
+const VALUE_COLUMN: &str = "value";
+
+/// Aggregation applied to the values of a pivot table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Aggregation {
+    /// Number of rows (or non-null values, if a value column is chosen).
+    #[default]
+    Count,
+    Sum,
+    Mean,
+    Median,
+    Min,
+    Max,
+    First,
+}
+
+impl Aggregation {
+    /// All aggregations, in display order.
+    pub const ALL: [Aggregation; 7] = [
+        Aggregation::Count,
+        Aggregation::Sum,
+        Aggregation::Mean,
+        Aggregation::Median,
+        Aggregation::Min,
+        Aggregation::Max,
+        Aggregation::First,
+    ];
+
+    /// Builds the aggregation expression over the optional value column.
+    ///
+    /// Only `Count` can be computed without a value column.
+    pub fn expr(&self, values: Option<&str>) -> PolarsResult<Expr> {
+        let Some(values) = values else {
+            return match self {
+                Aggregation::Count => Ok(len()),
+                _ => {
+                    polars_bail!(InvalidOperation: "select a value column to aggregate with {}", self)
+                }
+            };
+        };
+
+        let values = col(values);
+        Ok(match self {
+            Aggregation::Count => values.count(),
+            Aggregation::Sum => values.sum(),
+            Aggregation::Mean => values.mean(),
+            Aggregation::Median => values.median(),
+            Aggregation::Min => values.min(),
+            Aggregation::Max => values.max(),
+            Aggregation::First => values.first(),
+        })
+    }
+}
+
+impl fmt::Display for Aggregation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Aggregation::Count => "count",
+            Aggregation::Sum => "sum",
+            Aggregation::Mean => "mean",
+            Aggregation::Median => "median",
+            Aggregation::Min => "min",
+            Aggregation::Max => "max",
+            Aggregation::First => "first",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Specification of a pivot table.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PivotSpec {
+    /// Columns whose distinct values become the rows of the pivot table.
+    pub rows: Vec<String>,
+    /// Columns whose distinct values become the columns of the pivot table.
+    pub columns: Vec<String>,
+    /// Column to aggregate (optional for `Count`).
+    pub values: Option<String>,
+    /// Aggregation applied to the values of each cell.
+    pub aggregation: Aggregation,
+}
+
+/// Computes a pivot table.
+///
+/// The data is first grouped by the row and column dimensions and aggregated,
+/// then the column dimensions are pivoted into columns.
+/// Without column dimensions, the result is the grouped data itself.
+pub fn pivot_table(df: &DataFrame, spec: &PivotSpec) -> PolarsResult<DataFrame> {
+    if spec.rows.is_empty() {
+        polars_bail!(InvalidOperation: "select at least one row dimension");
+    }
+
+    let aggregation = spec.aggregation.expr(spec.values.as_deref())?;
+
+    let keys: Vec<Expr> = spec.rows.iter().chain(&spec.columns).map(col).collect();
+    let rows: Vec<Expr> = spec.rows.iter().map(col).collect();
+
+    let grouped = df
+        .clone()
+        .lazy()
+        .group_by(keys)
+        .agg([aggregation.alias(VALUE_COLUMN)])
+        .sort_by_exprs(
+            rows,
+            SortMultipleOptions::default().with_maintain_order(true),
+        )
+        .collect()?;
+
+    if spec.columns.is_empty() {
+        // Name the aggregated column after the aggregation, e.g. "sum(Valor)".
+        let mut grouped = grouped;
+        let name = match &spec.values {
+            Some(values) => format!("{}({})", spec.aggregation, values),
+            None => spec.aggregation.to_string(),
+        };
+        grouped.rename(VALUE_COLUMN, name.into())?;
+        return Ok(grouped);
+    }
+
+    // Each (rows, columns) group is unique, so no further aggregation is needed.
+    pivot_stable(
+        &grouped,
+        spec.columns.iter().map(String::as_str),
+        Some(spec.rows.iter().map(String::as_str)),
+        Some([VALUE_COLUMN]),
+        true,
+        None,
+        None,
+    )
+}
+
+/// Side-panel pane to build pivot tables from the current data.
+#[derive(Debug, Default)]
+pub struct PivotPane {
+    /// The pivot table being built.
+    pub spec: PivotSpec,
+    /// Pivot table computed in the background.
+    pending: Option<Receiver<PolarsResult<DataFrame>>>,
+}
+
+impl PivotPane {
+    /// Computes the pivot table of `df` in the background.
+    fn start(&mut self, df: &Arc<DataFrame>, handle: &Handle, ctx: &Context) {
+        let (tx, rx) = oneshot::channel();
+        let data = Arc::clone(df);
+        let spec = self.spec.clone();
+        let ctx = ctx.clone();
+
+        handle.spawn_blocking(move || {
+            let _ = tx.send(pivot_table(&data, &spec));
+            ctx.request_repaint();
+        });
+
+        self.pending = Some(rx);
+    }
+
+    /// The pivot table, once computed.
+    fn check_pending(&mut self) -> Option<PolarsResult<DataFrame>> {
+        let mut pending = self.pending.take()?;
+
+        match pending.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => {
+                self.pending = Some(pending); // Still running.
+                None
+            }
+            Err(TryRecvError::Closed) => Some(Err(PolarsError::ComputeError(
+                "the pivot was interrupted".into(),
+            ))),
+        }
+    }
+
+    /// Renders the pivot builder; the pivot table is computed on `handle`.
+    ///
+    /// Returns the pivot table (or the error) once computed, after the user clicks "Pivot".
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        df: &Arc<DataFrame>,
+        handle: &Handle,
+    ) -> Option<PolarsResult<DataFrame>> {
+        let result = self.check_pending();
+        let lang = language(ui.ctx());
+        let column_names: Vec<String> = df
+            .get_column_names()
+            .into_iter()
+            .map(|name| name.to_string())
+            .collect();
+
+        // Drop dimensions that no longer exist in the current data.
+        self.spec.rows.retain(|name| column_names.contains(name));
+        self.spec.columns.retain(|name| column_names.contains(name));
+        if let Some(values) = &self.spec.values {
+            if !column_names.contains(values) {
+                self.spec.values = None;
+            }
+        }
+
+        Grid::new("pivot_grid")
+            .num_columns(2)
+            .spacing([10.0, 10.0])
+            .show(ui, |ui| {
+                ui.label(tr("Rows:", lang));
+                select_columns(ui, tr("Rows", lang), &column_names, &mut self.spec.rows);
+                ui.end_row();
+
+                ui.label(tr("Columns:", lang));
+                let columns = tr("Columns", lang);
+                select_columns(ui, columns, &column_names, &mut self.spec.columns);
+                ui.end_row();
+
+                ui.label(tr("Values:", lang));
+                let rows = tr("(rows)", lang);
+                ComboBox::from_id_salt("pivot_values")
+                    .selected_text(self.spec.values.as_deref().unwrap_or(rows))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.spec.values, None, rows);
+                        for name in &column_names {
+                            ui.selectable_value(&mut self.spec.values, Some(name.clone()), name);
+                        }
+                    });
+                ui.end_row();
+
+                ui.label(tr("Aggregation:", lang));
+                ComboBox::from_id_salt("pivot_aggregation")
+                    .selected_text(self.spec.aggregation.to_string())
+                    .show_ui(ui, |ui| {
+                        for aggregation in Aggregation::ALL {
+                            ui.selectable_value(
+                                &mut self.spec.aggregation,
+                                aggregation,
+                                aggregation.to_string(),
+                            );
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("");
+                ui.horizontal(|ui| {
+                    let running = self.pending.is_some();
+                    if ui
+                        .add_enabled(!running, Button::new(tr("Pivot", lang)))
+                        .clicked()
+                    {
+                        self.start(df, handle, ui.ctx());
+                    }
+                    if running {
+                        busy_indicator(ui);
+                    }
+                });
+                ui.end_row();
+            });
+
+        result
+    }
+}
+
+/// Returns a mask of the rows whose key columns (all columns if `keys` is empty)
+/// are equal to those of at least one other row.
+pub fn duplicated_mask(df: &DataFrame, keys: &[String]) -> PolarsResult<BooleanChunked> {
+    match keys {
+        [] => df.is_duplicated(),
+        keys => df.select(keys.iter().map(String::as_str))?.is_duplicated(),
+    }
+}
+
+/// Summary of the duplicated rows of a DataFrame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DuplicateCount {
+    /// Number of rows that have at least one duplicate (all occurrences).
+    pub duplicated_rows: usize,
+    /// Number of redundant copies, i.e. rows that would be removed by deduplication.
+    pub extra_copies: usize,
+}
+
+/// Counts the duplicated rows over the key columns (all columns if `keys` is empty).
+pub fn count_duplicates(df: &DataFrame, keys: &[String]) -> PolarsResult<DuplicateCount> {
+    let mask = duplicated_mask(df, keys)?;
+    let duplicated_rows = mask.sum().unwrap_or(0) as usize;
+
+    // Deduplication keeps one row per distinct key.
+    let subset = (!keys.is_empty()).then(|| keys.to_vec());
+    let distinct_rows = df
+        .clone()
+        .lazy()
+        .unique(subset, UniqueKeepStrategy::Any)
+        .collect()?
+        .height();
+
+    Ok(DuplicateCount {
+        duplicated_rows,
+        extra_copies: df.height() - distinct_rows,
+    })
+}
+
+/// Side-panel pane to detect duplicated rows.
+#[derive(Debug, Default)]
+pub struct DuplicatesPane {
+    /// Key columns compared to detect duplicates (all columns if empty).
+    pub keys: Vec<String>,
+    /// Result of the last count.
+    count: Option<PolarsResult<DuplicateCount>>,
+    /// Count running in the background.
+    pending: Option<Receiver<PolarsResult<DuplicateCount>>>,
+}
+
+impl DuplicatesPane {
+    /// Counts the duplicated rows of `df` in the background.
+    fn start(&mut self, df: &Arc<DataFrame>, handle: &Handle, ctx: &Context) {
+        let (tx, rx) = oneshot::channel();
+        let data = Arc::clone(df);
+        let keys = self.keys.clone();
+        let ctx = ctx.clone();
+
+        handle.spawn_blocking(move || {
+            let _ = tx.send(count_duplicates(&data, &keys));
+            ctx.request_repaint();
+        });
+
+        self.count = None;
+        self.pending = Some(rx);
+    }
+
+    /// Stores the count once computed.
+    fn check_pending(&mut self) {
+        let Some(mut pending) = self.pending.take() else {
+            return;
+        };
+
+        match pending.try_recv() {
+            Ok(count) => self.count = Some(count),
+            Err(TryRecvError::Empty) => self.pending = Some(pending), // Still running.
+            Err(TryRecvError::Closed) => {
+                self.count = Some(Err(PolarsError::ComputeError(
+                    "the count of duplicates was interrupted".into(),
+                )))
+            }
+        }
+    }
+
+    /// Renders the duplicate detection controls; the duplicates are counted on `handle`.
+    ///
+    /// Returns the key columns when the user asks to show only the duplicated rows.
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        df: &Arc<DataFrame>,
+        handle: &Handle,
+    ) -> Option<Vec<String>> {
+        self.check_pending();
+        let lang = language(ui.ctx());
+        let column_names: Vec<String> = df
+            .get_column_names()
+            .into_iter()
+            .map(|name| name.to_string())
+            .collect();
+
+        let keys_before = self.keys.clone();
+        self.keys.retain(|name| column_names.contains(name));
+
+        ui.horizontal(|ui| {
+            ui.label(tr("Key columns:", lang));
+            let all = tr("All columns", lang);
+            select_columns(ui, all, &column_names, &mut self.keys);
+        });
+
+        // A previous count is no longer valid for other keys.
+        if self.keys != keys_before {
+            self.count = None;
+            self.pending = None;
+        }
+
+        let mut show_duplicates = None;
+
+        ui.horizontal(|ui| {
+            let running = self.pending.is_some();
+            if ui
+                .add_enabled(!running, Button::new(tr("Count duplicates", lang)))
+                .clicked()
+            {
+                self.start(df, handle, ui.ctx());
+            }
+            if running {
+                busy_indicator(ui);
+            }
+
+            if ui
+                .button(tr("Show only duplicates", lang))
+                .on_hover_text(tr("Filter the table to the duplicated rows", lang))
+                .clicked()
+            {
+                show_duplicates = Some(self.keys.clone());
+            }
+        });
+
+        match &self.count {
+            Some(Ok(count)) => {
+                ui.label(format!(
+                    "{} {} ({} {})",
+                    count.duplicated_rows,
+                    tr("duplicated rows", lang),
+                    count.extra_copies,
+                    tr("extra copies", lang)
+                ));
+            }
+            Some(Err(error)) => {
+                ui.colored_label(ui.visuals().error_fg_color, error.to_string());
+            }
+            None => {}
+        }
+
+        show_duplicates
+    }
+}
+
+/// Renders a menu of checkboxes to select a subset of the columns.
+fn select_columns(ui: &mut Ui, label: &str, column_names: &[String], selected: &mut Vec<String>) {
+    let text = match selected.len() {
+        0 => format!("{label}: {}", tr("none", language(ui.ctx()))),
+        _ => selected.join(", "),
+    };
+
+    ui.menu_button(text, |ui| {
+        ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+            for name in column_names {
+                let mut checked = selected.contains(name);
+                if ui.checkbox(&mut checked, name).changed() {
+                    if checked {
+                        selected.push(name.clone()); // Keep the selection order.
+                    } else {
+                        selected.retain(|selected_name| selected_name != name);
+                    }
+                }
+            }
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sales() -> PolarsResult<DataFrame> {
+        df![
+            "year" => [2020, 2020, 2020, 2021, 2021],
+            "kind" => ["a", "b", "a", "a", "b"],
+            "amount" => [1.0, 2.0, 3.0, 4.0, 5.0],
+        ]
+    }
+
+    #[test]
+    fn test_pivot_table() -> PolarsResult<()> {
+        let spec = PivotSpec {
+            rows: vec!["year".to_string()],
+            columns: vec!["kind".to_string()],
+            values: Some("amount".to_string()),
+            aggregation: Aggregation::Sum,
+        };
+
+        let pivot = pivot_table(&sales()?, &spec)?;
+        let expected = df![
+            "year" => [2020, 2021],
+            "a" => [4.0, 4.0],
+            "b" => [2.0, 5.0],
+        ]?;
+        assert!(pivot.equals_missing(&expected));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pivot_table_without_columns() -> PolarsResult<()> {
+        let spec = PivotSpec {
+            rows: vec!["kind".to_string()],
+            ..Default::default()
+        };
+
+        let pivot = pivot_table(&sales()?, &spec)?;
+        let expected = df![
+            "kind" => ["a", "b"],
+            "count" => [3 as IdxSize, 2],
+        ]?;
+        assert!(pivot.equals_missing(&expected));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_duplicates() -> PolarsResult<()> {
+        let df = df![
+            "id" => [1, 2, 1, 3, 1, 2],
+            "kind" => ["a", "b", "a", "c", "x", "b"],
+        ]?;
+
+        // Rows 0, 2 (id=1, a) and 1, 5 (id=2, b) are full duplicates.
+        let count = count_duplicates(&df, &[])?;
+        assert_eq!(
+            count,
+            DuplicateCount {
+                duplicated_rows: 4,
+                extra_copies: 2
+            }
+        );
+
+        // Over the "id" key, row 4 is also a duplicate.
+        let count = count_duplicates(&df, &["id".to_string()])?;
+        assert_eq!(
+            count,
+            DuplicateCount {
+                duplicated_rows: 5,
+                extra_copies: 3
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pivot_table_requires_values() -> PolarsResult<()> {
+        let spec = PivotSpec {
+            rows: vec!["kind".to_string()],
+            aggregation: Aggregation::Sum,
+            ..Default::default()
+        };
+
+        assert!(pivot_table(&sales()?, &spec).is_err());
+
+        Ok(())
+    }
+}
@@ -1,6 +1,11 @@
-use clap::Parser;
+use clap::{
+    ArgGroup, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum, parser::ValueSource,
+};
+use std::{ffi::OsString, path::PathBuf};
 
-use crate::SQL_COMMANDS;
+use crate::{
+    Codec, CsvColumn, CsvImportOptions, DEFAULT_BENCH_ITERATIONS, SQL_COMMANDS, SortState,
+};
 
 // https://stackoverflow.com/questions/74068168/clap-rs-not-printing-colors-during-help
 fn get_styles() -> clap::builder::Styles {
@@ -37,10 +42,17 @@ const APPLET_TEMPLATE: &str = "\
     next_line_help = true,
     help_template = APPLET_TEMPLATE,
     styles=get_styles(),
+    group(ArgGroup::new("input").args(["filename", "files"]).multiple(true)),
 )]
 pub struct Arguments {
     /// CSV delimiter.
-    #[arg(short, long, default_value = ";", help = "CSV delimiter character")]
+    #[arg(
+        short,
+        long,
+        default_value = ";",
+        value_parser = parse_delimiter,
+        help = "CSV delimiter character"
+    )]
     pub delimiter: String,
 
     /// Set the parquet filename.
@@ -52,16 +64,53 @@ pub struct Arguments {
     )]
     pub filename: Option<String>,
 
+    /// Data files to open: the first is displayed, the others open in tabs and are registered
+    /// as SQL tables.
+    #[arg(
+        value_name = "FILES",
+        help = "Data files: the first is opened, the others open in tabs and can be queried as tables named after their file"
+    )]
+    pub files: Vec<String>,
+
+    /// Reopen a session saved from the File menu.
+    #[arg(
+        short,
+        long,
+        value_name = "SESSION",
+        help = "Path to a session file (.parqbench.json) to restore",
+        conflicts_with = "input"
+    )]
+    pub session: Option<PathBuf>,
+
     /// Set the query.
     #[arg(
         short,
         long,
         default_value = SQL_COMMANDS[0],
         help = "SQL query to apply to the data",
-        requires = "filename"
+        requires = "input"
     )]
     pub query: Option<String>,
 
+    /// Sort the data on startup; repeat to break the ties on further columns.
+    #[arg(
+        long,
+        value_name = "COLUMN[:asc|desc]",
+        help = "Sort by a column, e.g. \"amount:desc\" (repeatable)",
+        requires = "input"
+    )]
+    pub sort: Vec<SortState>,
+
+    /// Run the query several times and print the timings as JSON, without the GUI.
+    #[arg(
+        short,
+        long,
+        value_name = "ITERATIONS",
+        help = "Benchmark the query N times and print the results as JSON",
+        requires = "input"
+    )]
+    pub bench: Option<usize>,
+
     /// Set the table_name.
     #[arg(
         short,
@@ -71,11 +120,302 @@ pub struct Arguments {
         requires = "query"
     )]
     pub table_name: String,
+
+    /// Worker threads of the Tokio runtime, overriding the Settings for this run.
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Number of worker threads for loading and queries (0 for one per core)"
+    )]
+    pub threads: Option<usize>,
+
+    /// The CSV file has no header row; the columns are named `column_1`, `column_2`…
+    #[arg(long, help = "The CSV file has no header row")]
+    pub no_header: bool,
+
+    /// Lines skipped at the start of the CSV file, before the header.
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 0,
+        help = "Lines skipped at the start of the CSV file"
+    )]
+    pub skip_rows: usize,
+
+    /// Quote character of the CSV file.
+    #[arg(
+        long,
+        value_name = "CHAR",
+        value_parser = parse_ascii_char,
+        help = "CSV quote character [default: \"]",
+        conflicts_with = "no_quote"
+    )]
+    pub quote: Option<u8>,
+
+    /// Read the quotes of the CSV file as ordinary characters.
+    #[arg(long, help = "Read CSV quotes as ordinary characters")]
+    pub no_quote: bool,
+
+    /// Values read as null in the CSV file, replacing the default ones.
+    #[arg(
+        long = "null-value",
+        value_name = "VALUE",
+        help = "Value read as null in the CSV file (repeatable)"
+    )]
+    pub null_values: Vec<String>,
+
+    /// Rows read to infer the types of the CSV columns.
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Rows read to infer the CSV column types (0 for the whole file) [default: 200]"
+    )]
+    pub infer_rows: Option<usize>,
+
+    /// Keep as strings the CSV values that look like dates.
+    #[arg(long, help = "Do not parse the CSV values that look like dates")]
+    pub no_parse_dates: bool,
+
+    /// Formats of the date columns of the CSV file.
+    #[arg(
+        long,
+        value_name = "COLUMN=FORMAT",
+        value_parser = parse_date_format,
+        help = "Parse a CSV column with a date format, e.g. \"day=%d/%m/%Y\" (repeatable)"
+    )]
+    pub date_format: Vec<(String, String)>,
+
+    /// Open a new window rather than handing the file over to a running instance.
+    #[arg(
+        long,
+        help = "Open a new window, even if PolarsView is already running"
+    )]
+    pub new_window: bool,
+
+    /// Run a command without the GUI.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// The query was typed on the command line, rather than left to its default.
+    #[arg(skip)]
+    pub query_given: bool,
+}
+
+/// Commands run without the GUI.
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Print the schema changes between two files (Parquet or CSV) as a changelog.
+    SchemaDiff {
+        /// The old file.
+        old: PathBuf,
+        /// The new file.
+        new: PathBuf,
+    },
+    /// Run a SQL query on a file (Parquet or CSV) and write the result.
+    Query {
+        /// The data file.
+        file: String,
+        /// SQL query to apply to the data.
+        #[arg(long, default_value = SQL_COMMANDS[0])]
+        sql: String,
+        /// Output file, its format given by the extension (.csv or .parquet); `-` prints CSV.
+        #[arg(short, long, default_value = "-")]
+        output: String,
+        /// Table name for SQL queries.
+        #[arg(short, long, default_value = "AllData")]
+        table_name: String,
+        /// CSV delimiter.
+        #[arg(short, long, default_value = ";", value_parser = parse_delimiter)]
+        delimiter: String,
+    },
+    /// Convert a file (Parquet or CSV) to CSV, Parquet, NDJSON or Arrow IPC.
+    Convert {
+        /// The input file.
+        input: String,
+        /// The output file, its format given by the extension
+        /// (.csv, .parquet, .ndjson, .arrow); `-` prints CSV.
+        output: String,
+        /// Parquet compression: uncompressed, snappy, lz4, gzip or zstd, with an optional
+        /// level such as `zstd:9`.
+        #[arg(short, long)]
+        compression: Option<Codec>,
+    },
+    /// Run a SQL query several times on a file and print its timings.
+    Bench {
+        /// The data file (Parquet or CSV).
+        file: String,
+        /// SQL query to benchmark.
+        #[arg(long, default_value = SQL_COMMANDS[0])]
+        sql: String,
+        /// Number of runs; the first (cold) run is reported separately.
+        #[arg(short, long, default_value_t = DEFAULT_BENCH_ITERATIONS)]
+        iterations: usize,
+        /// Print the results as JSON, for performance regression tracking.
+        #[arg(long)]
+        json: bool,
+        /// Table name for SQL queries.
+        #[arg(short, long, default_value = "AllData")]
+        table_name: String,
+        /// CSV delimiter.
+        #[arg(short, long, default_value = ";", value_parser = parse_delimiter)]
+        delimiter: String,
+    },
+    /// Print the schema, row count, row groups and key-value metadata of a file.
+    Schema {
+        /// The data file (Parquet or CSV).
+        file: String,
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = SchemaFormat::Text)]
+        format: SchemaFormat,
+    },
+    /// Register PolarsView as the application opening .parquet files (Linux and Windows).
+    Register,
+}
+
+/// Output format of the `schema` command.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaFormat {
+    /// Human-readable text.
+    Text,
+    /// Pretty-printed JSON.
+    Json,
+}
+
+/// Parses a `COLUMN=FORMAT` date format.
+fn parse_date_format(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((column, format)) if !column.is_empty() && !format.is_empty() => {
+            Ok((column.to_string(), format.to_string()))
+        }
+        _ => Err(format!("expected COLUMN=FORMAT, got '{value}'")),
+    }
+}
+
+/// Parses a character of the CSV format, which must be a single ASCII character.
+fn parse_ascii_char(value: &str) -> Result<u8, String> {
+    match value.as_bytes() {
+        [byte] if byte.is_ascii() => Ok(*byte),
+        _ => Err(format!("expected a single ASCII character, got '{value}'")),
+    }
+}
+
+/// Parses a CSV delimiter, kept as text.
+fn parse_delimiter(value: &str) -> Result<String, String> {
+    parse_ascii_char(value).map(|_| value.to_string())
 }
 
 impl Arguments {
     /// Build Arguments struct
+    ///
+    /// Without `--filename`, the first positional file is the one opened.
     pub fn build() -> Arguments {
-        Arguments::parse()
+        Self::build_from(std::env::args_os()).unwrap_or_else(|error| error.exit())
+    }
+
+    /// Builds the Arguments struct from the given command line.
+    pub fn build_from<I, T>(command_line: I) -> Result<Arguments, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let matches = Arguments::command().try_get_matches_from(command_line)?;
+        let mut args = Arguments::from_arg_matches(&matches)
+            .map_err(|error| error.format(&mut Arguments::command()))?;
+        args.query_given = matches.value_source("query") == Some(ValueSource::CommandLine);
+
+        if args.filename.is_none() && !args.files.is_empty() {
+            args.filename = Some(args.files.remove(0));
+        }
+        Ok(args)
+    }
+
+    /// Whether a single file is given, without query, sort or session: it is handed over
+    /// to the running instance, if any.
+    pub fn only_file(&self) -> bool {
+        self.filename.is_some()
+            && !self.query_given
+            && self.sort.is_empty()
+            && self.files.is_empty()
+            && self.session.is_none()
+    }
+
+    /// CSV read options given on the command line; `None` if all are left to their defaults.
+    pub fn csv_options(&self) -> Option<CsvImportOptions> {
+        let defaults = CsvImportOptions::default();
+        let options = CsvImportOptions {
+            delimiter: self.delimiter.bytes().next().unwrap_or(defaults.delimiter),
+            null_values: match self.null_values.is_empty() {
+                true => defaults.null_values.clone(),
+                false => self.null_values.clone(),
+            },
+            has_header: !self.no_header,
+            skip_rows: self.skip_rows,
+            quote: match (self.no_quote, self.quote) {
+                (true, _) => None,
+                (false, Some(quote)) => Some(quote),
+                (false, None) => defaults.quote,
+            },
+            infer_rows: match self.infer_rows {
+                Some(0) => None,
+                Some(rows) => Some(rows),
+                None => defaults.infer_rows,
+            },
+            try_parse_dates: !self.no_parse_dates,
+            columns: self
+                .date_format
+                .iter()
+                .map(|(column, format)| CsvColumn::with_date_format(column, format))
+                .collect(),
+        };
+
+        // The delimiter alone is already handled by `DataFilters::csv_delimiter`.
+        let defaults = CsvImportOptions {
+            delimiter: options.delimiter,
+            ..defaults
+        };
+        (options != defaults).then_some(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_file() -> Result<(), clap::Error> {
+        // The default query does not count as one typed.
+        let args = Arguments::build_from(["polars-view", "data.parquet"])?;
+        assert_eq!(args.query.as_deref(), Some(SQL_COMMANDS[0]));
+        assert!(args.only_file());
+        assert!(Arguments::build_from(["polars-view", "-f", "data.parquet"])?.only_file());
+
+        for command_line in [
+            &["polars-view", "data.parquet", "-q", "SELECT 1"][..],
+            &["polars-view", "data.parquet", "--sort", "id"],
+            &["polars-view", "data.parquet", "other.csv"],
+        ] {
+            assert!(!Arguments::build_from(command_line)?.only_file());
+        }
+        assert!(!Arguments::build_from(["polars-view"])?.only_file());
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_characters() -> Result<(), clap::Error> {
+        let args = Arguments::build_from(["polars-view", "-d", "|", "--quote", "'"])?;
+        let options = args.csv_options().unwrap_or_default();
+        assert_eq!((options.delimiter, options.quote), (b'|', Some(b'\'')));
+
+        // A non-ASCII or longer character is refused, rather than dropped.
+        for command_line in [
+            &["polars-view", "--quote", "«"][..],
+            &["polars-view", "--quote", "ab"],
+            &["polars-view", "-d", "§"],
+            &["polars-view", "-d", ""],
+            &["polars-view", "query", "data.csv", "-d", "é"],
+        ] {
+            assert!(Arguments::build_from(command_line).is_err());
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,313 @@
+use crate::{DataFilters, DataFrameContainer, busy_indicator};
+
+use egui::{DragValue, Grid, Ui};
+use serde::Serialize;
+use std::{
+    fmt, fs,
+    time::{Duration, Instant},
+};
+use tokio::{
+    runtime::Runtime,
+    sync::oneshot::{self, Receiver, error::TryRecvError},
+};
+
+/// Default number of query runs of a benchmark.
+pub const DEFAULT_BENCH_ITERATIONS: usize = 10;
+
+/// Timings of a query executed several times against the same file.
+///
+/// The first (cold) run is reported separately: it pays for the file system
+/// cache and allocator warm-up, while the remaining (warm) runs measure the
+/// steady-state scan and query performance.
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    pub filename: String,
+    pub query: String,
+    /// Number of rows of the query result.
+    pub rows: usize,
+    /// Size of the data file on disk.
+    pub file_bytes: u64,
+    /// Duration of every run, in execution order.
+    pub timings: Vec<Duration>,
+}
+
+/// Summary of a [`BenchResult`], serializable to JSON.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BenchSummary {
+    pub filename: String,
+    pub query: String,
+    pub iterations: usize,
+    pub rows: usize,
+    pub file_bytes: u64,
+    pub cold_ms: f64,
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub p95_ms: f64,
+    pub mean_ms: f64,
+    pub rows_per_sec: f64,
+    pub bytes_per_sec: f64,
+}
+
+impl BenchResult {
+    /// Duration of the first (cold) run.
+    pub fn cold(&self) -> Duration {
+        self.timings.first().copied().unwrap_or_default()
+    }
+
+    /// Durations of the warm runs, sorted in ascending order.
+    ///
+    /// With a single run, the cold run is the only available timing.
+    pub fn warm(&self) -> Vec<Duration> {
+        let mut warm = match self.timings.len() {
+            0 | 1 => self.timings.clone(),
+            _ => self.timings[1..].to_vec(),
+        };
+        warm.sort();
+        warm
+    }
+
+    /// Summarizes the warm runs: min/median/p95/mean latency and throughput.
+    pub fn summary(&self) -> BenchSummary {
+        let warm = self.warm();
+        let mean = match warm.len() {
+            0 => Duration::ZERO,
+            n => warm.iter().sum::<Duration>() / n as u32,
+        };
+        let median = percentile(&warm, 50.0);
+
+        // Throughput is based on the median warm run.
+        let per_sec = |amount: f64| match median.as_secs_f64() {
+            0.0 => 0.0,
+            secs => amount / secs,
+        };
+
+        BenchSummary {
+            filename: self.filename.clone(),
+            query: self.query.clone(),
+            iterations: self.timings.len(),
+            rows: self.rows,
+            file_bytes: self.file_bytes,
+            cold_ms: millis(self.cold()),
+            min_ms: millis(warm.first().copied().unwrap_or_default()),
+            median_ms: millis(median),
+            p95_ms: millis(percentile(&warm, 95.0)),
+            mean_ms: millis(mean),
+            rows_per_sec: per_sec(self.rows as f64),
+            bytes_per_sec: per_sec(self.file_bytes as f64),
+        }
+    }
+}
+
+impl BenchSummary {
+    /// Labels and formatted values of the timings and throughput.
+    pub fn table(&self) -> [(&'static str, String); 10] {
+        [
+            ("Runs", self.iterations.to_string()),
+            ("Rows", self.rows.to_string()),
+            ("File size", format!("{} bytes", self.file_bytes)),
+            ("Cold", format!("{:.2} ms", self.cold_ms)),
+            ("Min", format!("{:.2} ms", self.min_ms)),
+            ("Median", format!("{:.2} ms", self.median_ms)),
+            ("p95", format!("{:.2} ms", self.p95_ms)),
+            ("Mean", format!("{:.2} ms", self.mean_ms)),
+            ("Rows/s", format!("{:.0}", self.rows_per_sec)),
+            ("MB/s", format!("{:.2}", self.bytes_per_sec / 1e6)),
+        ]
+    }
+
+    /// Serializes the summary as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| format!("JSON error: {}", e))
+    }
+}
+
+impl fmt::Display for BenchSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "File: {}", self.filename)?;
+        writeln!(f, "Query: {}", self.query)?;
+        for (label, value) in self.table() {
+            writeln!(f, "{:<10} {}", format!("{label}:"), value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs the query of the filters `iterations` times and measures each run.
+pub async fn run_benchmark(filters: DataFilters, iterations: usize) -> Result<BenchResult, String> {
+    let Some(filename) = filters.filename.clone() else {
+        return Err("No filename".to_string());
+    };
+
+    let filename = shellexpand::full(&filename)
+        .map_err(|err| err.to_string())?
+        .to_string();
+
+    let file_bytes = fs::metadata(&filename)
+        .map_err(|e| format!("Error reading {}: {}", filename, e))?
+        .len();
+
+    let mut timings = Vec::with_capacity(iterations.max(1));
+    let mut rows = 0;
+
+    for _ in 0..iterations.max(1) {
+        let start = Instant::now();
+        let data = DataFrameContainer::load_data_with_sql(filters.clone()).await?;
+        timings.push(start.elapsed());
+        rows = data.df.height();
+    }
+
+    Ok(BenchResult {
+        filename,
+        query: filters.query.unwrap_or_default(),
+        rows,
+        file_bytes,
+        timings,
+    })
+}
+
+/// Side-panel pane to benchmark the current query.
+pub struct BenchPane {
+    /// Number of query runs.
+    pub iterations: usize,
+    /// Channel for receiving the result of a running benchmark.
+    pending: Option<Receiver<Result<BenchResult, String>>>,
+    /// Summary (or error) of the last benchmark.
+    result: Option<Result<BenchSummary, String>>,
+}
+
+impl Default for BenchPane {
+    fn default() -> Self {
+        BenchPane {
+            iterations: DEFAULT_BENCH_ITERATIONS,
+            pending: None,
+            result: None,
+        }
+    }
+}
+
+impl BenchPane {
+    /// Renders the benchmark controls and the results of the last run.
+    ///
+    /// Benchmarks run in the background on the given runtime.
+    pub fn render(&mut self, ui: &mut Ui, filters: &DataFilters, runtime: &Runtime) {
+        self.check_pending();
+
+        ui.horizontal(|ui| {
+            ui.label("Iterations:");
+            ui.add(DragValue::new(&mut self.iterations).range(1..=1000));
+
+            if self.pending.is_some() {
+                busy_indicator(ui);
+            } else if ui
+                .button("Run")
+                .on_hover_text("Run the current query several times and measure it")
+                .clicked()
+            {
+                let (tx, rx) = oneshot::channel();
+                let future = run_benchmark(filters.clone(), self.iterations);
+                let ctx = ui.ctx().clone();
+                runtime.spawn(async move {
+                    let _ = tx.send(future.await);
+                    ctx.request_repaint();
+                });
+                self.pending = Some(rx);
+            }
+        });
+
+        match &self.result {
+            Some(Ok(summary)) => {
+                Grid::new("bench_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (label, value) in summary.table() {
+                            ui.label(label);
+                            ui.label(value);
+                            ui.end_row();
+                        }
+                    });
+
+                if ui.button("Copy JSON").clicked() {
+                    if let Ok(json) = summary.to_json() {
+                        ui.ctx().copy_text(json);
+                    }
+                }
+            }
+            Some(Err(error)) => {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+            None => {}
+        }
+    }
+
+    /// Stores the result of a finished benchmark.
+    fn check_pending(&mut self) {
+        let Some(mut pending) = self.pending.take() else {
+            return;
+        };
+
+        match pending.try_recv() {
+            Ok(result) => self.result = Some(result.map(|bench| bench.summary())),
+            Err(TryRecvError::Empty) => self.pending = Some(pending), // Still running.
+            Err(TryRecvError::Closed) => {
+                self.result = Some(Err("Benchmark was interrupted.".to_string()))
+            }
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted durations.
+fn percentile(sorted: &[Duration], percent: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (percent / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bench_summary() {
+        let result = BenchResult {
+            filename: "data.parquet".to_string(),
+            query: "SELECT * FROM AllData;".to_string(),
+            rows: 1000,
+            file_bytes: 2_000_000,
+            // A slow cold run followed by 20 warm runs of 1..=20 ms.
+            timings: std::iter::once(100)
+                .chain((1..=20).rev())
+                .map(Duration::from_millis)
+                .collect(),
+        };
+
+        let summary = result.summary();
+        assert_eq!(summary.iterations, 21);
+        assert_eq!(summary.cold_ms, 100.0);
+        assert_eq!(summary.min_ms, 1.0);
+        assert_eq!(summary.median_ms, 10.0);
+        assert_eq!(summary.p95_ms, 19.0);
+        assert_eq!(summary.mean_ms, 10.5);
+        assert_eq!(summary.rows_per_sec, 100_000.0);
+        assert_eq!(summary.bytes_per_sec, 200_000_000.0);
+
+        let json = summary.to_json().unwrap();
+        assert!(json.contains("\"median_ms\": 10.0"));
+        assert!(summary.to_string().contains("Median:    10.00 ms"));
+    }
+
+    #[test]
+    fn test_percentile() {
+        let sorted: Vec<Duration> = (1..=4).map(Duration::from_secs).collect();
+        assert_eq!(percentile(&sorted, 50.0), Duration::from_secs(2));
+        assert_eq!(percentile(&sorted, 95.0), Duration::from_secs(4));
+        assert_eq!(percentile(&[], 50.0), Duration::ZERO);
+    }
+}
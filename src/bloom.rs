@@ -0,0 +1,327 @@
+use crate::busy_indicator;
+
+use egui::{ComboBox, Context, Grid, TextEdit, Ui};
+use parquet::{
+    basic::Type as PhysicalType,
+    bloom_filter::Sbbf,
+    file::{
+        metadata::ParquetMetaData,
+        properties::ReaderProperties,
+        reader::FileReader,
+        serialized_reader::{ReadOptionsBuilder, SerializedFileReader},
+    },
+};
+use std::fs::File;
+use tokio::{
+    runtime::Handle,
+    sync::oneshot::{self, Receiver, error::TryRecvError},
+};
+
+/// Outcome of probing the bloom filter of a column chunk for a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BloomProbe {
+    /// The column chunk has no bloom filter: the row group must be read.
+    NoFilter,
+    /// The value may be present: the row group must be read.
+    MightContain,
+    /// The value is definitely absent: the row group can be pruned.
+    Absent,
+}
+
+impl BloomProbe {
+    /// Describes the outcome for the user.
+    pub fn description(&self) -> &'static str {
+        match self {
+            BloomProbe::NoFilter => "no bloom filter (read)",
+            BloomProbe::MightContain => "might contain (read)",
+            BloomProbe::Absent => "absent (pruned)",
+        }
+    }
+}
+
+/// Returns, for each column of the file, whether any of its chunks has a bloom filter.
+pub fn bloom_filter_columns(metadata: &ParquetMetaData) -> Vec<bool> {
+    let columns = metadata.file_metadata().schema_descr().num_columns();
+    (0..columns)
+        .map(|col_idx| {
+            metadata
+                .row_groups()
+                .iter()
+                .any(|row_group| row_group.column(col_idx).bloom_filter_offset().is_some())
+        })
+        .collect()
+}
+
+/// Checks a value, parsed as the physical type of the column, against a bloom filter.
+fn check_value(sbbf: &Sbbf, physical_type: PhysicalType, value: &str) -> Result<bool, String> {
+    let error = |e: &dyn std::fmt::Display| format!("Invalid {} value: {}", physical_type, e);
+
+    match physical_type {
+        PhysicalType::BOOLEAN => Ok(sbbf.check(&value.parse::<bool>().map_err(|e| error(&e))?)),
+        PhysicalType::INT32 => Ok(sbbf.check(&value.parse::<i32>().map_err(|e| error(&e))?)),
+        PhysicalType::INT64 => Ok(sbbf.check(&value.parse::<i64>().map_err(|e| error(&e))?)),
+        PhysicalType::FLOAT => Ok(sbbf.check(&value.parse::<f32>().map_err(|e| error(&e))?)),
+        PhysicalType::DOUBLE => Ok(sbbf.check(&value.parse::<f64>().map_err(|e| error(&e))?)),
+        PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY => Ok(sbbf.check(&value)),
+        PhysicalType::INT96 => Err("INT96 columns are not supported.".to_string()),
+    }
+}
+
+/// Probes the bloom filters of a column in every row group of a Parquet file.
+///
+/// The value is parsed as the physical type of the column (e.g. a number of days for dates).
+pub fn probe_bloom_filters(
+    filename: &str,
+    col_idx: usize,
+    value: &str,
+) -> Result<Vec<BloomProbe>, String> {
+    let file = File::open(filename).map_err(|e| format!("Error opening file: {}", e))?;
+
+    // Bloom filters are only read when requested.
+    let properties = ReaderProperties::builder()
+        .set_read_bloom_filter(true)
+        .build();
+    let options = ReadOptionsBuilder::new()
+        .with_reader_properties(properties)
+        .build();
+    let reader = SerializedFileReader::new_with_options(file, options)
+        .map_err(|e| format!("Error creating Parquet reader: {}", e))?;
+
+    let physical_type = reader
+        .metadata()
+        .file_metadata()
+        .schema_descr()
+        .column(col_idx)
+        .physical_type();
+
+    (0..reader.num_row_groups())
+        .map(|rg_idx| {
+            let row_group = reader
+                .get_row_group(rg_idx)
+                .map_err(|e| format!("Error reading row group {}: {}", rg_idx, e))?;
+
+            match row_group.get_column_bloom_filter(col_idx) {
+                None => Ok(BloomProbe::NoFilter),
+                Some(sbbf) => match check_value(sbbf, physical_type, value)? {
+                    true => Ok(BloomProbe::MightContain),
+                    false => Ok(BloomProbe::Absent),
+                },
+            }
+        })
+        .collect()
+}
+
+/// Side-panel pane listing the bloom filters of a Parquet file, with a membership tester.
+#[derive(Debug, Default)]
+pub struct BloomFilterTester {
+    /// Index of the tested column.
+    column: usize,
+    /// Value entered by the user.
+    value: String,
+    /// Outcome per row group of the last test.
+    results: Option<Result<Vec<BloomProbe>, String>>,
+    /// Test reading the bloom filters in the background.
+    pending: Option<Receiver<Result<Vec<BloomProbe>, String>>>,
+}
+
+impl BloomFilterTester {
+    /// Probes the bloom filters of the file in the background.
+    fn start(&mut self, filename: &str, handle: &Handle, ctx: &Context) {
+        let (tx, rx) = oneshot::channel();
+        let filename = filename.to_string();
+        let (column, value) = (self.column, self.value.clone());
+        let ctx = ctx.clone();
+
+        handle.spawn_blocking(move || {
+            let _ = tx.send(probe_bloom_filters(&filename, column, &value));
+            ctx.request_repaint();
+        });
+
+        self.pending = Some(rx);
+        self.results = None;
+    }
+
+    /// Stores the outcome of the test once done.
+    fn check_pending(&mut self) {
+        let Some(mut pending) = self.pending.take() else {
+            return;
+        };
+
+        match pending.try_recv() {
+            Ok(result) => self.results = Some(result),
+            Err(TryRecvError::Empty) => self.pending = Some(pending), // Still running.
+            Err(TryRecvError::Closed) => {
+                self.results = Some(Err("Bloom filter test was interrupted.".to_string()))
+            }
+        }
+    }
+
+    /// Renders the columns with bloom filters and the membership tester.
+    ///
+    /// The bloom filters are read on `handle`, not while rendering.
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        metadata: &ParquetMetaData,
+        filename: &str,
+        handle: &Handle,
+    ) {
+        self.check_pending();
+        let has_filter = bloom_filter_columns(metadata);
+        let columns = metadata.file_metadata().schema_descr().columns();
+
+        if !has_filter.contains(&true) {
+            ui.label("No column has a bloom filter.");
+            return;
+        }
+
+        let with_filters: Vec<&str> = columns
+            .iter()
+            .zip(&has_filter)
+            .filter(|(_, has_filter)| **has_filter)
+            .map(|(column, _)| column.name())
+            .collect();
+        ui.label(format!(
+            "Columns with bloom filters: {}",
+            with_filters.join(", ")
+        ));
+
+        // Select a column with a bloom filter.
+        if !has_filter.get(self.column).copied().unwrap_or(false) {
+            self.column = has_filter.iter().position(|has| *has).unwrap_or(0);
+        }
+
+        let column_before = self.column;
+        ComboBox::from_label("Column")
+            .selected_text(columns[self.column].name())
+            .show_ui(ui, |ui| {
+                for (idx, column) in columns.iter().enumerate() {
+                    if has_filter[idx] {
+                        ui.selectable_value(&mut self.column, idx, column.name());
+                    }
+                }
+            });
+
+        let response = ui.add(
+            TextEdit::singleline(&mut self.value)
+                .hint_text(format!("{} value", columns[self.column].physical_type())),
+        );
+
+        // A previous result is no longer valid for another column or value.
+        if self.column != column_before || response.changed() {
+            self.results = None;
+            self.pending = None;
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Test").clicked() {
+                self.start(filename, handle, ui.ctx());
+            }
+            if self.pending.is_some() {
+                busy_indicator(ui);
+            }
+        });
+
+        match &self.results {
+            Some(Ok(probes)) => {
+                let pruned = probes.iter().filter(|p| **p == BloomProbe::Absent).count();
+                ui.label(format!("{} of {} row groups pruned", pruned, probes.len()));
+
+                Grid::new("bloom_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (rg_idx, probe) in probes.iter().enumerate() {
+                            ui.label(format!("Row group {rg_idx}"));
+                            ui.label(probe.description());
+                            ui.end_row();
+                        }
+                    });
+            }
+            Some(Err(error)) => {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::{
+        data_type::{ByteArray, ByteArrayType, Int64Type},
+        file::{properties::WriterProperties, writer::SerializedFileWriter},
+        schema::parser::parse_message_type,
+    };
+    use std::sync::Arc;
+
+    /// Writes two row groups (ids 0..100 and 100..200) with bloom filters on both columns.
+    fn write_test_file(path: &std::path::Path) {
+        let schema = Arc::new(
+            parse_message_type(
+                "message test { REQUIRED INT64 id; REQUIRED BYTE_ARRAY name (UTF8); }",
+            )
+            .unwrap(),
+        );
+        let properties = Arc::new(
+            WriterProperties::builder()
+                .set_bloom_filter_enabled(true)
+                .build(),
+        );
+        let file = File::create(path).unwrap();
+        let mut writer = SerializedFileWriter::new(file, schema, properties).unwrap();
+
+        for range in [0..100_i64, 100..200] {
+            let ids: Vec<i64> = range.collect();
+            let names: Vec<ByteArray> = ids
+                .iter()
+                .map(|id| format!("name {id}").as_str().into())
+                .collect();
+
+            let mut row_group = writer.next_row_group().unwrap();
+            let mut column = row_group.next_column().unwrap().unwrap();
+            column
+                .typed::<Int64Type>()
+                .write_batch(&ids, None, None)
+                .unwrap();
+            column.close().unwrap();
+            let mut column = row_group.next_column().unwrap().unwrap();
+            column
+                .typed::<ByteArrayType>()
+                .write_batch(&names, None, None)
+                .unwrap();
+            column.close().unwrap();
+            row_group.close().unwrap();
+        }
+
+        writer.close().unwrap();
+    }
+
+    #[test]
+    fn test_probe_bloom_filters() -> Result<(), String> {
+        let path = std::env::temp_dir().join("polars_view_bloom.parquet");
+        write_test_file(&path);
+        let filename = path.to_string_lossy().to_string();
+
+        let reader = SerializedFileReader::new(File::open(&path).unwrap()).unwrap();
+        assert_eq!(bloom_filter_columns(reader.metadata()), vec![true, true]);
+
+        // Bloom filters have no false negatives: the row group with the value is always read.
+        let probes = probe_bloom_filters(&filename, 0, "42")?;
+        assert_eq!(probes[0], BloomProbe::MightContain);
+
+        let probes = probe_bloom_filters(&filename, 1, "name 150")?;
+        assert_eq!(probes[1], BloomProbe::MightContain);
+
+        // A value absent from the file is (almost surely) pruned everywhere.
+        let probes = probe_bloom_filters(&filename, 0, "123456789")?;
+        assert_eq!(probes, vec![BloomProbe::Absent; 2]);
+
+        assert!(probe_bloom_filters(&filename, 0, "not a number").is_err());
+
+        let _ = std::fs::remove_file(path);
+        Ok(())
+    }
+}
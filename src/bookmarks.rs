@@ -0,0 +1,212 @@
+use crate::cell_text;
+
+use polars::prelude::DataFrame;
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Arc,
+};
+
+/// Rows bookmarked in the table, by index or by the value of a key column.
+///
+/// Bookmarks by index refer to the rows of one result; bookmarks by key are
+/// found again in the results of later queries and sorts of the same file.
+#[derive(Debug, Clone, Default)]
+pub struct RowBookmarks {
+    key_column: Option<String>,
+    /// Bookmarked row indices, without a key column.
+    rows: BTreeSet<usize>,
+    /// Bookmarked key values, with a key column.
+    keys: BTreeSet<String>,
+    /// Rows of the key values of the last result, to list the bookmarks without a scan.
+    index: Option<KeyIndex>,
+}
+
+/// Rows of each value of the key column of a result.
+#[derive(Debug, Clone)]
+struct KeyIndex {
+    df: Arc<DataFrame>,
+    column: String,
+    rows: HashMap<String, Vec<usize>>,
+}
+
+impl KeyIndex {
+    fn new(df: &Arc<DataFrame>, column: &str) -> Self {
+        let mut rows: HashMap<String, Vec<usize>> = HashMap::new();
+        if let Ok(values) = df.column(column) {
+            for (row, value) in values.as_materialized_series().iter().enumerate() {
+                rows.entry(cell_text(&value)).or_default().push(row);
+            }
+        }
+        KeyIndex {
+            df: Arc::clone(df),
+            column: column.to_string(),
+            rows,
+        }
+    }
+}
+
+impl RowBookmarks {
+    /// Column identifying the bookmarked rows, if any.
+    pub fn key_column(&self) -> Option<&str> {
+        self.key_column.as_deref()
+    }
+
+    /// Identifies the bookmarked rows of `df` by another column, or by index with `None`.
+    pub fn set_key_column(&mut self, column: Option<String>, df: &Arc<DataFrame>) {
+        let rows = self.rows(df);
+        self.key_column = column;
+        self.rows.clear();
+        self.keys.clear();
+        for row in rows {
+            self.insert(df, row);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self.key_column {
+            Some(_) => self.keys.len(),
+            None => self.rows.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.rows.clear();
+        self.keys.clear();
+    }
+
+    /// Keeps the bookmarks that still apply to a new result: those by key, of the same file.
+    pub fn data_replaced(&mut self, same_file: bool) {
+        self.rows.clear();
+        if !same_file {
+            self.keys.clear();
+        }
+    }
+
+    /// Key value of a row, as displayed.
+    fn key(&self, df: &DataFrame, row: usize) -> Option<String> {
+        let column = df.column(self.key_column.as_deref()?).ok()?;
+        column.get(row).ok().map(|value| cell_text(&value))
+    }
+
+    pub fn contains(&self, df: &DataFrame, row: usize) -> bool {
+        match self.key_column {
+            Some(_) => self
+                .key(df, row)
+                .is_some_and(|key| self.keys.contains(&key)),
+            None => self.rows.contains(&row),
+        }
+    }
+
+    fn insert(&mut self, df: &DataFrame, row: usize) {
+        match self.key_column {
+            Some(_) => {
+                if let Some(key) = self.key(df, row) {
+                    self.keys.insert(key);
+                }
+            }
+            None => {
+                self.rows.insert(row);
+            }
+        }
+    }
+
+    /// Bookmarks a row of `df`, or removes its bookmark.
+    pub fn toggle(&mut self, df: &DataFrame, row: usize) {
+        if !self.contains(df, row) {
+            self.insert(df, row);
+        } else if let Some(key) = self.key(df, row) {
+            self.keys.remove(&key);
+        } else {
+            self.rows.remove(&row);
+        }
+    }
+
+    /// Bookmarked rows of `df`, in order.
+    ///
+    /// By key, the rows are looked up in an index of the key column, built once per result.
+    pub fn rows(&mut self, df: &Arc<DataFrame>) -> Vec<usize> {
+        match self.key_column.as_deref() {
+            Some(column) => {
+                let index = match self.index.take() {
+                    Some(index) if Arc::ptr_eq(&index.df, df) && index.column == column => index,
+                    _ => KeyIndex::new(df, column),
+                };
+                let mut rows: Vec<usize> = self
+                    .keys
+                    .iter()
+                    .filter_map(|key| index.rows.get(key))
+                    .flatten()
+                    .copied()
+                    .collect();
+                rows.sort_unstable();
+                self.index = Some(index);
+                rows
+            }
+            None => self
+                .rows
+                .iter()
+                .copied()
+                .filter(|row| *row < df.height())
+                .collect(),
+        }
+    }
+
+    /// First bookmarked row after `row`, starting over from the top after the last one.
+    pub fn next(&mut self, df: &Arc<DataFrame>, row: usize) -> Option<usize> {
+        let rows = self.rows(df);
+        rows.iter()
+            .copied()
+            .find(|bookmark| *bookmark > row)
+            .or(rows.first().copied())
+    }
+
+    /// Last bookmarked row before `row`, starting over from the bottom before the first one.
+    pub fn previous(&mut self, df: &Arc<DataFrame>, row: usize) -> Option<usize> {
+        let rows = self.rows(df);
+        rows.iter()
+            .copied()
+            .rfind(|bookmark| *bookmark < row)
+            .or(rows.last().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_bookmarks_by_key() -> Result<(), String> {
+        let df = Arc::new(
+            df!["id" => ["a", "b", "c", "d"], "qty" => [1, 2, 3, 4]].map_err(|e| e.to_string())?,
+        );
+
+        let mut bookmarks = RowBookmarks::default();
+        bookmarks.toggle(&df, 1);
+        bookmarks.toggle(&df, 3);
+        assert_eq!(bookmarks.rows(&df), [1, 3]);
+        assert_eq!(bookmarks.next(&df, 1), Some(3));
+        assert_eq!(bookmarks.next(&df, 3), Some(1)); // Starts over from the top.
+        assert_eq!(bookmarks.previous(&df, 2), Some(1));
+
+        // Bookmarks by index are lost with the result; by key, they follow the rows.
+        let mut by_index = bookmarks.clone();
+        by_index.data_replaced(true);
+        assert!(by_index.is_empty());
+
+        bookmarks.set_key_column(Some("id".to_string()), &df);
+        bookmarks.data_replaced(true);
+        let requeried = Arc::new(df!["id" => ["d", "c", "b"]].map_err(|e| e.to_string())?);
+        assert_eq!(bookmarks.rows(&requeried), [0, 2]);
+
+        bookmarks.toggle(&requeried, 0);
+        assert_eq!(bookmarks.rows(&requeried), [2]);
+        bookmarks.data_replaced(false);
+        assert!(bookmarks.is_empty());
+        Ok(())
+    }
+}
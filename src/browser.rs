@@ -0,0 +1,276 @@
+use crate::{FooterCache, format_bytes};
+
+use egui::{Button, CollapsingHeader, Context, Label, Sense, Ui};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use tokio::{runtime::Handle, sync::oneshot};
+
+/// Extensions of the files listed by the browser.
+const DATA_EXTENSIONS: [&str; 2] = ["parquet", "csv"];
+
+/// Request of the Files pane.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BrowserAction {
+    /// Choose the directory to browse.
+    ChooseRoot,
+    /// Open a data file.
+    Open(String),
+}
+
+/// A subdirectory or data file of a listed directory.
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    path: PathBuf,
+    is_dir: bool,
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+/// Row count of a Parquet file, with the modification time of the file it was read for.
+type RowCount = (Option<SystemTime>, Option<i64>);
+
+/// File tree of the Parquet and CSV files under a chosen directory.
+#[derive(Debug, Default)]
+pub struct DirectoryBrowser {
+    /// Entries of the directories expanded so far.
+    listings: HashMap<PathBuf, Result<Vec<Entry>, String>>,
+    /// Row counts read from the Parquet footers.
+    row_counts: HashMap<PathBuf, RowCount>,
+    /// Files shown without an up-to-date row count in this frame.
+    uncounted: Vec<(PathBuf, Option<SystemTime>)>,
+    /// Row counts being read in the background.
+    counting: Option<oneshot::Receiver<Vec<(PathBuf, RowCount)>>>,
+}
+
+impl DirectoryBrowser {
+    /// Forgets the listings, so that the directories are read again.
+    pub fn refresh(&mut self) {
+        self.listings.clear();
+    }
+
+    /// Shows the tree of `root`; a data file is opened with a double-click.
+    ///
+    /// Row counts are read from `footers` in the background, which is then saved.
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        root: Option<&Path>,
+        footers: &FooterCache,
+        handle: &Handle,
+    ) -> Option<BrowserAction> {
+        let mut action = None;
+        self.check_counting();
+
+        ui.horizontal(|ui| {
+            if ui.button("Choose folder…").clicked() {
+                action = Some(BrowserAction::ChooseRoot);
+            }
+            if ui
+                .add_enabled(root.is_some(), Button::new("Refresh"))
+                .clicked()
+            {
+                self.refresh();
+            }
+        });
+
+        let Some(root) = root else {
+            ui.label("Choose a folder to browse its Parquet and CSV files.");
+            return action;
+        };
+
+        ui.weak(root.display().to_string());
+        self.render_dir(ui, root, &mut action);
+        self.count_rows(footers, handle, ui.ctx());
+        action
+    }
+
+    /// Stores the row counts once read.
+    fn check_counting(&mut self) {
+        let Some(mut counting) = self.counting.take() else {
+            return;
+        };
+
+        match counting.try_recv() {
+            Ok(counts) => self.row_counts.extend(counts),
+            Err(oneshot::error::TryRecvError::Empty) => self.counting = Some(counting),
+            Err(oneshot::error::TryRecvError::Closed) => {}
+        }
+    }
+
+    /// Reads the row counts of the files shown without one, unless a read is running.
+    fn count_rows(&mut self, footers: &FooterCache, handle: &Handle, ctx: &Context) {
+        let uncounted = std::mem::take(&mut self.uncounted);
+        if uncounted.is_empty() || self.counting.is_some() {
+            return;
+        }
+
+        let (tx, rx) = oneshot::channel();
+        let footers = footers.clone();
+        let ctx = ctx.clone();
+
+        handle.spawn_blocking(move || {
+            let counts = uncounted
+                .into_iter()
+                .map(|(path, modified)| {
+                    let rows = read_row_count(&footers, &path).ok();
+                    (path, (modified, rows))
+                })
+                .collect();
+            if let Err(message) = footers.save() {
+                tracing::warn!("{message}");
+            }
+            let _ = tx.send(counts);
+            ctx.request_repaint();
+        });
+
+        self.counting = Some(rx);
+    }
+
+    fn render_dir(&mut self, ui: &mut Ui, dir: &Path, action: &mut Option<BrowserAction>) {
+        let listing = self
+            .listings
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| list_dir(dir))
+            .clone();
+
+        let entries = match listing {
+            Ok(entries) if entries.is_empty() => {
+                ui.weak("No Parquet or CSV files");
+                return;
+            }
+            Ok(entries) => entries,
+            Err(message) => {
+                ui.colored_label(ui.visuals().error_fg_color, message);
+                return;
+            }
+        };
+
+        for entry in entries {
+            let name = entry
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            if entry.is_dir {
+                CollapsingHeader::new(format!("📁 {name}"))
+                    .id_salt(&entry.path)
+                    .show(ui, |ui| self.render_dir(ui, &entry.path, action));
+                continue;
+            }
+
+            let size = format_bytes(entry.size as i64);
+            let details = match self.row_count(&entry) {
+                Some(Some(rows)) => format!("{size} · {rows} rows"),
+                Some(None) => size,
+                None => format!("{size} · counting rows…"),
+            };
+
+            ui.horizontal(|ui| {
+                let response = ui
+                    .add(Label::new(name).sense(Sense::click()))
+                    .on_hover_text("Double-click to open");
+                if response.double_clicked() {
+                    *action = Some(BrowserAction::Open(entry.path.display().to_string()));
+                }
+                ui.weak(details);
+            });
+        }
+    }
+
+    /// Row count of a Parquet file, read from its footer once per modification;
+    /// `None` while it is read, `Some(None)` if unknown.
+    fn row_count(&mut self, entry: &Entry) -> Option<Option<i64>> {
+        if entry.path.extension().is_none_or(|ext| ext != "parquet") {
+            return Some(None);
+        }
+
+        match self.row_counts.get(&entry.path) {
+            Some((modified, rows)) if *modified == entry.modified => Some(*rows),
+            _ => {
+                self.uncounted.push((entry.path.clone(), entry.modified));
+                None
+            }
+        }
+    }
+}
+
+/// Subdirectories and data files of a directory, the directories first, by name.
+///
+/// Hidden entries are skipped.
+fn list_dir(dir: &Path) -> Result<Vec<Entry>, String> {
+    let read_dir =
+        fs::read_dir(dir).map_err(|e| format!("Error reading {}: {}", dir.display(), e))?;
+
+    let mut entries: Vec<Entry> = read_dir
+        .flatten()
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|entry| {
+            let path = entry.path();
+            let metadata = entry.metadata().ok()?;
+            let is_data = path
+                .extension()
+                .is_some_and(|ext| DATA_EXTENSIONS.iter().any(|data| ext == *data));
+            (metadata.is_dir() || is_data).then(|| Entry {
+                is_dir: metadata.is_dir(),
+                size: metadata.len(),
+                modified: metadata.modified().ok(),
+                path,
+            })
+        })
+        .collect();
+
+    entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.path.cmp(&b.path)));
+    Ok(entries)
+}
+
+/// Row count in the footer of a Parquet file, from the footer cache.
+fn read_row_count(footers: &FooterCache, path: &Path) -> Result<i64, String> {
+    footers.get(path).map(|footer| footer.num_rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+    use std::fs::File;
+
+    #[test]
+    fn test_directory_listing() -> Result<(), String> {
+        let dir =
+            std::env::temp_dir().join(format!("polars_view_test_browser_{}", std::process::id()));
+        fs::create_dir_all(dir.join("archive")).map_err(|e| e.to_string())?;
+
+        let parquet = dir.join("sales.parquet");
+        let mut df = df!["id" => [1, 2, 3]].map_err(|e| e.to_string())?;
+        let file = File::create(&parquet).map_err(|e| e.to_string())?;
+        ParquetWriter::new(file)
+            .finish(&mut df)
+            .map_err(|e| e.to_string())?;
+        fs::write(dir.join("customers.csv"), "id\n1\n").map_err(|e| e.to_string())?;
+        fs::write(dir.join("notes.txt"), "").map_err(|e| e.to_string())?;
+
+        let entries = list_dir(&dir);
+        let rows = read_row_count(&FooterCache::default(), &parquet);
+        let _ = fs::remove_dir_all(&dir);
+
+        let names: Vec<String> = entries?
+            .iter()
+            .map(|entry| {
+                entry
+                    .path
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(names, ["archive", "customers.csv", "sales.parquet"]);
+        assert_eq!(rows, Ok(3));
+        Ok(())
+    }
+}
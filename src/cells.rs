@@ -0,0 +1,306 @@
+use crate::{CellRenderer, TableViewOptions};
+
+use polars::prelude::{AnyValue, Column, DataFrame};
+use std::{collections::HashMap, fmt, sync::Arc};
+use tokio::{
+    runtime::Handle,
+    sync::oneshot::{self, Receiver, error::TryRecvError},
+};
+
+/// Cells kept at most; beyond, the cache starts over, as after a long scroll through a wide table.
+const MAX_CACHED_CELLS: usize = 200_000;
+
+/// Pages of rows formatted ahead of the scroll at most, however fast it goes.
+const MAX_PREFETCH_PAGES: usize = 4;
+
+/// Text of a cell value; floats are formatted from their typed value, so NaN and infinities stay readable.
+pub fn format_cell(
+    value: AnyValue,
+    renderer: Option<&Arc<dyn CellRenderer>>,
+    options: &TableViewOptions,
+    decimals: usize,
+) -> String {
+    match (value, renderer) {
+        (AnyValue::Null, _) => "".to_string(), // Display "" for Null values.
+        (av, Some(renderer)) => renderer.render(&av), // Added by a plugin.
+        (AnyValue::String(s), None) => s.to_string(),
+        (AnyValue::Float64(f), None) => options.format_float(f, decimals),
+        (AnyValue::Float32(f), None) => options.format_float(f.into(), decimals),
+        (av, None) => av.to_string(), // Fallback to Debug formatting for other types.
+    }
+}
+
+/// Whether a column holds tax rates ("Alíquota" in Portuguese), shown with 4 decimals, centered.
+pub fn is_tax_rate(column: &str) -> bool {
+    column.contains("Alíquota")
+}
+
+/// Decimals of the floats of a column: tax rate columns show 4, the others as configured.
+pub fn column_decimals(column: &str, options: &TableViewOptions) -> usize {
+    match is_tax_rate(column) {
+        true => 4,
+        false => options.decimals,
+    }
+}
+
+/// Text of the cell of `column` at `row`.
+pub fn format_column_cell(
+    column: &Column,
+    row: usize,
+    renderer: Option<&Arc<dyn CellRenderer>>,
+    options: &TableViewOptions,
+) -> String {
+    let decimals = column_decimals(column.name(), options);
+    column
+        .get(row)
+        .map(|value| format_cell(value, renderer, options, decimals))
+        .unwrap_or_else(|_| "Error: This is not a value!".to_string())
+}
+
+/// Cells formatted in the background, with the data and options they were formatted from.
+type Prefetched = (
+    Arc<DataFrame>,
+    TableViewOptions,
+    HashMap<(usize, usize), String>,
+);
+
+/// Text of the table cells, formatted once instead of in every frame.
+///
+/// The text is kept for one result shown with one set of view options:
+/// other data, or other decimals, separators or notation, start over.
+#[derive(Default)]
+pub struct CellCache {
+    /// Result the cells were formatted from, kept to tell it from the next one.
+    data: Option<Arc<DataFrame>>,
+    /// Options the cells were formatted with.
+    options: Option<TableViewOptions>,
+    /// Text by (row, column) index.
+    cells: HashMap<(usize, usize), String>,
+    /// Rows being formatted ahead of the scroll.
+    pending: Option<Receiver<Prefetched>>,
+    /// Runtime formatting the rows ahead of the scroll; without one, rows are formatted when shown.
+    runtime: Option<Handle>,
+}
+
+// A copy keeps the cells, not the rows still being formatted.
+impl Clone for CellCache {
+    fn clone(&self) -> Self {
+        CellCache {
+            data: self.data.clone(),
+            options: self.options,
+            cells: self.cells.clone(),
+            pending: None,
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+impl fmt::Debug for CellCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CellCache")
+            .field("cells", &self.cells.len())
+            .finish()
+    }
+}
+
+impl CellCache {
+    /// Formats the rows ahead of the scroll on the blocking threads of `runtime`.
+    pub fn set_runtime(&mut self, runtime: Handle) {
+        self.runtime = Some(runtime);
+    }
+
+    /// Runtime formatting the rows ahead of the scroll, if any.
+    pub fn runtime(&self) -> Option<&Handle> {
+        self.runtime.as_ref()
+    }
+
+    /// Keeps the cells formatted from `df` with `options`, and forgets them otherwise.
+    pub fn validate(&mut self, df: &Arc<DataFrame>, options: &TableViewOptions) {
+        let same_data = self.data.as_ref().is_some_and(|data| Arc::ptr_eq(data, df));
+        if !same_data || self.options.as_ref() != Some(options) {
+            self.data = Some(Arc::clone(df));
+            self.options = Some(*options);
+            self.cells.clear();
+        }
+    }
+
+    /// Text of a cell, formatted by `format` the first time only.
+    pub fn get_or_format(
+        &mut self,
+        row: usize,
+        column: usize,
+        format: impl FnOnce() -> String,
+    ) -> &str {
+        if self.cells.len() >= MAX_CACHED_CELLS && !self.cells.contains_key(&(row, column)) {
+            self.cells.clear();
+        }
+        self.cells.entry((row, column)).or_insert_with(format)
+    }
+
+    /// Formats in the background the rows the table scrolls toward, so that fast scrolling
+    /// finds their text ready; `velocity` is the number of rows scrolled since the previous
+    /// frame, negative upward, and sets how many pages of `visible_rows` are formatted.
+    pub fn prefetch(
+        &mut self,
+        renderers: &[Option<Arc<dyn CellRenderer>>],
+        first_row: usize,
+        visible_rows: usize,
+        velocity: isize,
+    ) {
+        self.check_pending();
+        let (Some(df), Some(options), Some(runtime)) =
+            (self.data.clone(), self.options, self.runtime.as_ref())
+        else {
+            return;
+        };
+        if self.pending.is_some() || velocity == 0 || visible_rows == 0 {
+            return;
+        }
+
+        let pages = (velocity.unsigned_abs() / visible_rows + 1).min(MAX_PREFETCH_PAGES);
+        let rows = match velocity > 0 {
+            true => {
+                let start = (first_row + visible_rows).min(df.height());
+                start..(start + pages * visible_rows).min(df.height())
+            }
+            false => first_row.saturating_sub(pages * visible_rows)..first_row,
+        };
+        // The rows formatted already are skipped.
+        let rows: Vec<usize> = rows
+            .filter(|row| !self.cells.contains_key(&(*row, 0)))
+            .collect();
+        if rows.is_empty() || df.width() == 0 {
+            return;
+        }
+
+        let renderers = renderers.to_vec();
+        let (tx, rx) = oneshot::channel();
+        runtime.spawn_blocking(move || {
+            let mut cells = HashMap::new();
+            for row in rows {
+                for (index, column) in df.get_columns().iter().enumerate() {
+                    let renderer = renderers.get(index).and_then(Option::as_ref);
+                    let text = format_column_cell(column, row, renderer, &options);
+                    cells.insert((row, index), text);
+                }
+            }
+            let _ = tx.send((df, options, cells));
+        });
+        self.pending = Some(rx);
+    }
+
+    /// Adds the rows formatted in the background, unless the data or options changed meanwhile.
+    fn check_pending(&mut self) {
+        let Some(mut pending) = self.pending.take() else {
+            return;
+        };
+
+        match pending.try_recv() {
+            Ok(prefetched) => self.add_prefetched(prefetched),
+            Err(TryRecvError::Empty) => self.pending = Some(pending), // Still running.
+            Err(TryRecvError::Closed) => {}
+        }
+    }
+
+    /// Adds rows formatted in the background, if formatted from the current data and options.
+    fn add_prefetched(&mut self, (df, options, cells): Prefetched) {
+        let current = self
+            .data
+            .as_ref()
+            .is_some_and(|data| Arc::ptr_eq(data, &df))
+            && self.options == Some(options);
+        if current {
+            if self.cells.len() + cells.len() > MAX_CACHED_CELLS {
+                self.cells.clear();
+            }
+            self.cells.extend(cells);
+        }
+    }
+
+    /// Number of cells cached.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Whether no cell is cached.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_cell_cache() -> Result<(), String> {
+        let df = Arc::new(df!["x" => [1.5, 2.5]].map_err(|e| e.to_string())?);
+        let options = TableViewOptions::default();
+        let mut cache = CellCache::default();
+        let mut formatted = 0;
+
+        cache.validate(&df, &options);
+        for _frame in 0..3 {
+            let text = cache.get_or_format(0, 0, || {
+                formatted += 1;
+                "1,50".to_string()
+            });
+            assert_eq!(text, "1,50");
+        }
+        assert_eq!((formatted, cache.len()), (1, 1));
+
+        // The same data keeps its cells; other data or other decimals start over.
+        cache.validate(&df, &options);
+        assert_eq!(cache.len(), 1);
+        let other = Arc::new(df.as_ref().clone());
+        cache.validate(&other, &options);
+        assert!(cache.is_empty());
+        cache.get_or_format(1, 0, || "2,50".to_string());
+        let options = TableViewOptions {
+            decimals: 4,
+            ..options
+        };
+        cache.validate(&other, &options);
+        assert!(cache.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_prefetch_cells() -> Result<(), String> {
+        let df = Arc::new(df!["x" => (0..100).collect::<Vec<i32>>()].map_err(|e| e.to_string())?);
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .map_err(|e| e.to_string())?;
+        let mut cache = CellCache::default();
+        cache.validate(&df, &TableViewOptions::default());
+
+        // Without a runtime, the rows are only formatted when shown.
+        cache.prefetch(&[None], 20, 10, 30);
+        assert!(cache.pending.is_none());
+        cache.set_runtime(runtime.handle().clone());
+
+        // Waits for the rows being formatted in the background.
+        let wait = |cache: &mut CellCache| -> Result<(), String> {
+            let pending = cache.pending.take().ok_or("Nothing is being formatted")?;
+            let prefetched = pending.blocking_recv().map_err(|e| e.to_string())?;
+            cache.add_prefetched(prefetched);
+            Ok(())
+        };
+
+        // Scrolling down 30 rows a frame with 10 rows visible formats 4 pages below them.
+        cache.prefetch(&[None], 20, 10, 30);
+        wait(&mut cache)?;
+        assert_eq!(cache.len(), 40);
+        assert!(cache.cells.contains_key(&(30, 0)) && cache.cells.contains_key(&(69, 0)));
+        assert_eq!(cache.get_or_format(69, 0, || unreachable!()), "69");
+
+        // Scrolling up formats the page above; a still table formats nothing.
+        cache.prefetch(&[None], 20, 10, -5);
+        wait(&mut cache)?;
+        assert_eq!(cache.len(), 50);
+        cache.prefetch(&[None], 20, 10, 0);
+        assert!(cache.pending.is_none());
+        Ok(())
+    }
+}
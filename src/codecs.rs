@@ -0,0 +1,309 @@
+use crate::{Popover, busy_indicator};
+
+use egui::{Context, Grid, RichText, Window};
+use parquet::basic::Compression;
+use polars::prelude::*;
+use std::{
+    fmt,
+    io::Cursor,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{
+    runtime::Runtime,
+    sync::oneshot::{self, Receiver, error::TryRecvError},
+};
+
+/// Parquet compression codec and level evaluated by [`compare_codecs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    Uncompressed,
+    Snappy,
+    Lz4,
+    /// Gzip with a level from 0 to 10.
+    Gzip(u8),
+    /// Zstd with a level from 1 to 22.
+    Zstd(i32),
+}
+
+/// Codecs and levels compared by default, from the fastest to the smallest.
+pub const CODEC_CANDIDATES: [Codec; 10] = [
+    Codec::Uncompressed,
+    Codec::Snappy,
+    Codec::Lz4,
+    Codec::Gzip(1),
+    Codec::Gzip(6),
+    Codec::Gzip(9),
+    Codec::Zstd(1),
+    Codec::Zstd(3),
+    Codec::Zstd(9),
+    Codec::Zstd(19),
+];
+
+impl Codec {
+    /// Converts the codec into the Polars Parquet compression setting.
+    pub fn to_parquet(self) -> PolarsResult<ParquetCompression> {
+        Ok(match self {
+            Codec::Uncompressed => ParquetCompression::Uncompressed,
+            Codec::Snappy => ParquetCompression::Snappy,
+            Codec::Lz4 => ParquetCompression::Lz4Raw,
+            Codec::Gzip(level) => ParquetCompression::Gzip(Some(GzipLevel::try_new(level)?)),
+            Codec::Zstd(level) => ParquetCompression::Zstd(Some(ZstdLevel::try_new(level)?)),
+        })
+    }
+
+    /// The codec of a column chunk read by the parquet crate; `None` for the codecs
+    /// Polars does not write. The level is not stored in the file, so the default is kept.
+    pub fn from_parquet(compression: Compression) -> Option<Codec> {
+        match compression {
+            Compression::UNCOMPRESSED => Some(Codec::Uncompressed),
+            Compression::SNAPPY => Some(Codec::Snappy),
+            Compression::LZ4 | Compression::LZ4_RAW => Some(Codec::Lz4),
+            Compression::GZIP(_) => Some(Codec::Gzip(6)),
+            Compression::ZSTD(_) => Some(Codec::Zstd(3)),
+            Compression::BROTLI(_) | Compression::LZO => None,
+        }
+    }
+}
+
+impl fmt::Display for Codec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Codec::Uncompressed => write!(f, "uncompressed"),
+            Codec::Snappy => write!(f, "snappy"),
+            Codec::Lz4 => write!(f, "lz4"),
+            Codec::Gzip(level) => write!(f, "gzip (level {level})"),
+            Codec::Zstd(level) => write!(f, "zstd (level {level})"),
+        }
+    }
+}
+
+/// Parses `uncompressed`, `snappy`, `lz4`, `gzip` or `zstd`, with an optional level
+/// such as `zstd:9` (gzip defaults to level 6, zstd to level 3).
+impl FromStr for Codec {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (name, level) = match value.split_once(':') {
+            Some((name, level)) => (name, Some(level)),
+            None => (value, None),
+        };
+        let level = |default: i32| -> Result<i32, String> {
+            level.map_or(Ok(default), |level| {
+                level
+                    .parse()
+                    .map_err(|e| format!("Invalid compression level '{}': {}", level, e))
+            })
+        };
+
+        let codec = match name.to_lowercase().as_str() {
+            "uncompressed" | "none" => Codec::Uncompressed,
+            "snappy" => Codec::Snappy,
+            "lz4" => Codec::Lz4,
+            "gzip" => Codec::Gzip(u8::try_from(level(6)?).map_err(|e| e.to_string())?),
+            "zstd" => Codec::Zstd(level(3)?),
+            _ => {
+                return Err(format!(
+                    "Unknown compression '{}' (expected uncompressed, snappy, lz4, gzip or zstd)",
+                    value
+                ));
+            }
+        };
+        codec.to_parquet().map_err(|e| e.to_string())?; // Checks the level.
+        Ok(codec)
+    }
+}
+
+/// Size and timings of the data written with one codec.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodecResult {
+    pub codec: Codec,
+    /// Size of the Parquet file, in bytes.
+    pub bytes: usize,
+    /// Time to write (encode and compress) the file.
+    pub write: Duration,
+    /// Time to read (decompress and decode) the file back.
+    pub read: Duration,
+}
+
+/// Rewrites the DataFrame as Parquet with each codec and measures the size and write/read times.
+///
+/// Files are written to memory, so the timings exclude disk I/O.
+pub fn compare_codecs(df: &DataFrame, codecs: &[Codec]) -> PolarsResult<Vec<CodecResult>> {
+    codecs
+        .iter()
+        .map(|&codec| {
+            let mut buffer = Vec::new();
+
+            let start = Instant::now();
+            ParquetWriter::new(&mut buffer)
+                .with_compression(codec.to_parquet()?)
+                .finish(&mut df.clone())?;
+            let write = start.elapsed();
+
+            let bytes = buffer.len();
+
+            let start = Instant::now();
+            ParquetReader::new(Cursor::new(buffer)).finish()?;
+            let read = start.elapsed();
+
+            Ok(CodecResult {
+                codec,
+                bytes,
+                write,
+                read,
+            })
+        })
+        .collect()
+}
+
+/// Popover comparing the Parquet compression codecs on the current data.
+///
+/// The comparison runs in the background; the window shows a spinner until it completes.
+pub struct CodecComparison {
+    /// Channel for receiving the results of the comparison.
+    pending: Option<Receiver<Result<Vec<CodecResult>, String>>>,
+    /// Results (or error) of the comparison.
+    results: Option<Result<Vec<CodecResult>, String>>,
+}
+
+impl CodecComparison {
+    /// Starts the comparison of [`CODEC_CANDIDATES`] on the given runtime.
+    pub fn new(df: Arc<DataFrame>, runtime: &Runtime, ctx: &Context) -> Self {
+        let (tx, rx) = oneshot::channel();
+        let ctx = ctx.clone();
+
+        // Compression is CPU-bound: keep it off the async worker threads.
+        runtime.spawn_blocking(move || {
+            let results = compare_codecs(&df, &CODEC_CANDIDATES)
+                .map_err(|e| format!("Codec comparison error: {}", e));
+            let _ = tx.send(results);
+            ctx.request_repaint();
+        });
+
+        CodecComparison {
+            pending: Some(rx),
+            results: None,
+        }
+    }
+
+    /// Stores the results of a finished comparison.
+    fn check_pending(&mut self) {
+        let Some(mut pending) = self.pending.take() else {
+            return;
+        };
+
+        match pending.try_recv() {
+            Ok(results) => self.results = Some(results),
+            Err(TryRecvError::Empty) => self.pending = Some(pending), // Still running.
+            Err(TryRecvError::Closed) => {
+                self.results = Some(Err("Codec comparison was interrupted.".to_string()))
+            }
+        }
+    }
+}
+
+impl Popover for CodecComparison {
+    /// Shows the comparison table: size, ratio and write/read times per codec.
+    fn show(&mut self, ctx: &Context) -> bool {
+        self.check_pending();
+
+        let mut open = true;
+
+        Window::new("Compression codecs")
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| match &self.results {
+                None => {
+                    ui.horizontal(|ui| {
+                        busy_indicator(ui);
+                        ui.label("Rewriting the data with each codec…");
+                    });
+                }
+                Some(Err(error)) => {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                Some(Ok(results)) => {
+                    // Sizes are compared to the uncompressed file.
+                    let baseline = results
+                        .iter()
+                        .find(|result| result.codec == Codec::Uncompressed)
+                        .map(|result| result.bytes);
+                    let smallest = results.iter().map(|result| result.bytes).min();
+
+                    Grid::new("codecs_grid")
+                        .num_columns(5)
+                        .spacing([16.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for header in ["Codec", "Size", "Ratio", "Write", "Read"] {
+                                ui.strong(header);
+                            }
+                            ui.end_row();
+
+                            for result in results {
+                                let codec = RichText::new(result.codec.to_string());
+                                match Some(result.bytes) == smallest {
+                                    true => ui.label(codec.strong()).on_hover_text("Smallest file"),
+                                    false => ui.label(codec),
+                                };
+                                ui.label(format!("{:.2} MB", result.bytes as f64 / 1e6));
+                                ui.label(match baseline {
+                                    Some(baseline) if result.bytes > 0 => {
+                                        format!("{:.2}x", baseline as f64 / result.bytes as f64)
+                                    }
+                                    _ => "-".to_string(),
+                                });
+                                ui.label(format!("{:.1} ms", result.write.as_secs_f64() * 1e3));
+                                ui.label(format!("{:.1} ms", result.read.as_secs_f64() * 1e3));
+                                ui.end_row();
+                            }
+                        });
+                }
+            });
+
+        open
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_codecs() -> PolarsResult<()> {
+        // Distinct but highly redundant strings compress well.
+        let values: Vec<String> = (0..10_000)
+            .map(|i| format!("row {i} of the parquet codec comparison"))
+            .collect();
+        let df = df!["value" => values]?;
+
+        let results = compare_codecs(&df, &CODEC_CANDIDATES)?;
+        assert_eq!(results.len(), CODEC_CANDIDATES.len());
+
+        let uncompressed = results[0].bytes;
+        assert!(results.iter().all(|result| result.bytes > 0));
+        assert!(
+            results[1..]
+                .iter()
+                .all(|result| result.bytes <= uncompressed)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_codec_levels() {
+        assert!(Codec::Zstd(3).to_parquet().is_ok());
+        assert!(Codec::Zstd(99).to_parquet().is_err());
+        assert!(Codec::Gzip(11).to_parquet().is_err());
+        assert_eq!(Codec::Gzip(6).to_string(), "gzip (level 6)");
+
+        assert_eq!("zstd".parse(), Ok(Codec::Zstd(3)));
+        assert_eq!("ZSTD:9".parse(), Ok(Codec::Zstd(9)));
+        assert_eq!("snappy".parse(), Ok(Codec::Snappy));
+        assert!("zstd:99".parse::<Codec>().is_err());
+        assert!("brotli".parse::<Codec>().is_err());
+    }
+}
@@ -0,0 +1,302 @@
+use crate::{Popover, busy_indicator, format_bytes};
+
+use egui::{Color32, Context, Grid, RichText, ScrollArea, Window};
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use std::{collections::BTreeSet, fs::File, path::Path};
+use tokio::{
+    runtime::Handle,
+    sync::oneshot::{self, Receiver, error::TryRecvError},
+};
+
+/// Metadata of a Parquet file, as compared by [`FileComparison`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileProfile {
+    pub path: String,
+    pub file_bytes: u64,
+    pub created_by: String,
+    pub rows: i64,
+    pub row_groups: usize,
+    pub min_group_rows: i64,
+    pub max_group_rows: i64,
+    pub compressed_bytes: i64,
+    pub uncompressed_bytes: i64,
+    /// Codecs used by the column chunks, sorted.
+    pub codecs: Vec<String>,
+    /// Column paths and their physical (and logical) types, in schema order.
+    pub columns: Vec<(String, String)>,
+}
+
+impl FileProfile {
+    /// Reads the metadata of a Parquet file.
+    pub fn from_path(path: &Path) -> Result<Self, String> {
+        let file =
+            File::open(path).map_err(|e| format!("Error opening {}: {}", path.display(), e))?;
+        let file_bytes = file
+            .metadata()
+            .map_err(|e| format!("Error reading {}: {}", path.display(), e))?
+            .len();
+        let reader = SerializedFileReader::new(file)
+            .map_err(|e| format!("Error creating Parquet reader: {}", e))?;
+
+        let metadata = reader.metadata();
+        let file_metadata = metadata.file_metadata();
+        let row_groups = metadata.row_groups();
+
+        let codecs: BTreeSet<String> = row_groups
+            .iter()
+            .flat_map(|row_group| row_group.columns())
+            .map(|chunk| chunk.compression().to_string())
+            .collect();
+
+        let columns = file_metadata
+            .schema_descr()
+            .columns()
+            .iter()
+            .map(|column| {
+                let dtype = match column.logical_type() {
+                    Some(logical) => format!("{} ({:?})", column.physical_type(), logical),
+                    None => column.physical_type().to_string(),
+                };
+                (column.path().string(), dtype)
+            })
+            .collect();
+
+        Ok(FileProfile {
+            path: path.display().to_string(),
+            file_bytes,
+            created_by: file_metadata.created_by().unwrap_or_default().to_string(),
+            rows: file_metadata.num_rows(),
+            row_groups: row_groups.len(),
+            min_group_rows: row_groups.iter().map(|rg| rg.num_rows()).min().unwrap_or(0),
+            max_group_rows: row_groups.iter().map(|rg| rg.num_rows()).max().unwrap_or(0),
+            compressed_bytes: row_groups.iter().map(|rg| rg.compressed_size()).sum(),
+            uncompressed_bytes: row_groups.iter().map(|rg| rg.total_byte_size()).sum(),
+            codecs: codecs.into_iter().collect(),
+            columns,
+        })
+    }
+}
+
+/// A compared property: its label and its value in each file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComparisonRow {
+    pub label: String,
+    pub left: String,
+    pub right: String,
+}
+
+impl ComparisonRow {
+    fn new(label: impl ToString, left: impl ToString, right: impl ToString) -> Self {
+        ComparisonRow {
+            label: label.to_string(),
+            left: left.to_string(),
+            right: right.to_string(),
+        }
+    }
+
+    /// Whether the property differs between the files.
+    pub fn differs(&self) -> bool {
+        self.left != self.right
+    }
+}
+
+/// Lists the properties of two files side by side, followed by their columns.
+///
+/// Columns are matched by path; a column missing from one of the files shows "—".
+pub fn compare_profiles(left: &FileProfile, right: &FileProfile) -> Vec<ComparisonRow> {
+    let group_rows = |p: &FileProfile| format!("{} - {}", p.min_group_rows, p.max_group_rows);
+
+    let mut rows = vec![
+        ComparisonRow::new(
+            "File size",
+            format_bytes(left.file_bytes as i64),
+            format_bytes(right.file_bytes as i64),
+        ),
+        ComparisonRow::new("Created by", &left.created_by, &right.created_by),
+        ComparisonRow::new("Rows", left.rows, right.rows),
+        ComparisonRow::new("Columns", left.columns.len(), right.columns.len()),
+        ComparisonRow::new("Row groups", left.row_groups, right.row_groups),
+        ComparisonRow::new("Rows per group", group_rows(left), group_rows(right)),
+        ComparisonRow::new(
+            "Compressed",
+            format_bytes(left.compressed_bytes),
+            format_bytes(right.compressed_bytes),
+        ),
+        ComparisonRow::new(
+            "Uncompressed",
+            format_bytes(left.uncompressed_bytes),
+            format_bytes(right.uncompressed_bytes),
+        ),
+        ComparisonRow::new("Codecs", left.codecs.join(", "), right.codecs.join(", ")),
+    ];
+
+    // Columns of the left file first, then those only in the right file.
+    let mut names: Vec<&String> = left.columns.iter().map(|(name, _)| name).collect();
+    for (name, _) in &right.columns {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    let dtype = |profile: &FileProfile, name: &String| {
+        profile
+            .columns
+            .iter()
+            .find(|(column, _)| column == name)
+            .map_or("—".to_string(), |(_, dtype)| dtype.clone())
+    };
+    for name in names {
+        rows.push(ComparisonRow::new(
+            format!("column {name}"),
+            dtype(left, name),
+            dtype(right, name),
+        ));
+    }
+
+    rows
+}
+
+/// Popover comparing the metadata of two Parquet files side by side.
+pub struct FileComparison {
+    /// Paths of the compared files.
+    paths: [String; 2],
+    /// Compared properties, or an error if a file could not be read; `None` while read.
+    rows: Option<Result<Vec<ComparisonRow>, String>>,
+    pending: Option<Receiver<Result<Vec<ComparisonRow>, String>>>,
+}
+
+impl FileComparison {
+    /// Reads and compares the metadata of two Parquet files in the background.
+    pub fn new(left: &Path, right: &Path, handle: &Handle, ctx: &Context) -> Self {
+        let (tx, rx) = oneshot::channel();
+        let (left, right) = (left.to_path_buf(), right.to_path_buf());
+        let paths = [left.display().to_string(), right.display().to_string()];
+        let ctx = ctx.clone();
+
+        handle.spawn_blocking(move || {
+            let rows = FileProfile::from_path(&left).and_then(|left_profile| {
+                let right_profile = FileProfile::from_path(&right)?;
+                Ok(compare_profiles(&left_profile, &right_profile))
+            });
+            let _ = tx.send(rows);
+            ctx.request_repaint();
+        });
+
+        FileComparison {
+            paths,
+            rows: None,
+            pending: Some(rx),
+        }
+    }
+
+    /// Stores the comparison once the files are read.
+    fn check_pending(&mut self) {
+        let Some(mut pending) = self.pending.take() else {
+            return;
+        };
+
+        match pending.try_recv() {
+            Ok(rows) => self.rows = Some(rows),
+            Err(TryRecvError::Empty) => self.pending = Some(pending), // Still running.
+            Err(TryRecvError::Closed) => {
+                self.rows = Some(Err("File comparison was interrupted.".to_string()))
+            }
+        }
+    }
+}
+
+impl Popover for FileComparison {
+    /// Shows the comparison grid; differing properties are highlighted.
+    fn show(&mut self, ctx: &Context) -> bool {
+        self.check_pending();
+        let mut open = true;
+
+        Window::new("Compare files")
+            .collapsible(false)
+            .open(&mut open)
+            .default_size([700.0, 500.0])
+            .show(ctx, |ui| {
+                let rows = match &self.rows {
+                    Some(Ok(rows)) => rows,
+                    None => {
+                        ui.horizontal(|ui| {
+                            busy_indicator(ui);
+                            ui.label("Reading the metadata…");
+                        });
+                        return;
+                    }
+                    Some(Err(error)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                        return;
+                    }
+                };
+
+                let differences = rows.iter().filter(|row| row.differs()).count();
+                ui.label(format!("{differences} differences"));
+
+                let highlight = Color32::from_rgb(230, 160, 40);
+
+                ScrollArea::both().show(ui, |ui| {
+                    Grid::new("compare_files_grid")
+                        .num_columns(3)
+                        .spacing([16.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("");
+                            ui.strong(&self.paths[0]);
+                            ui.strong(&self.paths[1]);
+                            ui.end_row();
+
+                            for row in rows {
+                                let text = |value: &str| match row.differs() {
+                                    true => RichText::new(value).color(highlight),
+                                    false => RichText::new(value),
+                                };
+                                ui.label(text(&row.label));
+                                ui.label(text(&row.left));
+                                ui.label(text(&row.right));
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+
+        open
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_compare_profiles() -> PolarsResult<()> {
+        let dir = std::env::temp_dir();
+        let left_path = dir.join("polars_view_compare_left.parquet");
+        let right_path = dir.join("polars_view_compare_right.parquet");
+
+        let mut left = df!["id" => [1i64, 2, 3], "name" => ["a", "b", "c"]]?;
+        ParquetWriter::new(File::create(&left_path)?).finish(&mut left)?;
+
+        let mut right = df!["id" => [1i64, 2, 3], "score" => [1.0, 2.0, 3.0]]?;
+        ParquetWriter::new(File::create(&right_path)?)
+            .with_compression(ParquetCompression::Snappy)
+            .finish(&mut right)?;
+
+        let left = FileProfile::from_path(&left_path).unwrap();
+        let right = FileProfile::from_path(&right_path).unwrap();
+        let rows = compare_profiles(&left, &right);
+
+        let row = |label: &str| rows.iter().find(|row| row.label == label).unwrap();
+        assert!(!row("Rows").differs());
+        assert!(row("Codecs").differs());
+        assert!(!row("column id").differs());
+        assert_eq!(row("column name").right, "—");
+        assert_eq!(row("column score").left, "—");
+
+        let _ = std::fs::remove_file(left_path);
+        let _ = std::fs::remove_file(right_path);
+        Ok(())
+    }
+}
@@ -1,30 +1,50 @@
 use crate::{
-    ExtraInteractions,
-    data::{DataFilters, DataFrameContainer, SortState},
+    CastType, CellCache, CellEditor, ColumnMasks, ColumnTransform, ExtraInteractions, MaskKind,
+    MetadataSummary, NumberLocale, Popover, RowBookmarks, busy_indicator, cell_renderer, cell_text,
+    column_decimals,
+    data::{DataFilters, DataFrameContainer, SortState, is_sortable},
+    format_any_value, format_cell, format_column_cell, fuzzy_score, is_tax_rate, language, tr,
 };
 
-use egui::{Color32, Direction, Frame, Grid, Layout, Stroke, TextStyle, Ui};
+use egui::{
+    Align, Button, CollapsingHeader, Color32, Context, Direction, Frame, Grid, Key, Label, Layout,
+    Response, RichText, ScrollArea, Sense, Stroke, TextEdit, TextStyle, Ui, WidgetInfo, WidgetType,
+    Window,
+};
 use egui_extras::{Column, TableBuilder, TableRow};
 use parquet::{
-    basic::ColumnOrder,
+    basic::{ColumnOrder, ConvertedType, Encoding, LogicalType, PageType, SortOrder},
     file::{
-        metadata::ParquetMetaData,
+        metadata::{ColumnChunkMetaData, ParquetMetaData},
         reader::{FileReader, SerializedFileReader},
+        statistics::Statistics,
     },
+    schema::types::ColumnDescriptor,
 };
 use polars::prelude::*;
 use rfd::AsyncFileDialog;
-use std::{fs::File, path::Path};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs::File,
+    path::{Path, PathBuf},
+};
+use tokio::{runtime::Handle, sync::oneshot};
 
 // Enum to represent file metadata, supporting Parquet and CSV
 pub enum FileMetadata {
-    Parquet(ParquetMetadataWrapper),
+    Parquet(Box<ParquetMetadataWrapper>),
     Csv(CsvMetadataWrapper),
 }
 
 // Wrapper struct for Parquet metadata
 pub struct ParquetMetadataWrapper {
     metadata: ParquetMetaData, // Parquet metadata.
+    filename: String,          // Path of the Parquet file, to read its pages on demand.
+    pages: Option<Result<Vec<Vec<PageStatistics>>, String>>, // Pages counted by "Scan pages".
+    scanning: Option<oneshot::Receiver<Result<Vec<Vec<PageStatistics>>, String>>>, // Scan running.
+    ranges: Vec<ColumnRange>,  // Range of each column, from the chunk statistics.
 }
 
 // Wrapper struct for CSV metadata.
@@ -54,9 +74,17 @@ impl FileMetadata {
                     .map_err(|error| format!("Error creating Parquet reader: {}", error))?;
 
                 // Extract and store the Parquet metadata.
-                Ok(FileMetadata::Parquet(ParquetMetadataWrapper {
-                    metadata: reader.metadata().to_owned(),
-                }))
+                let metadata = reader.metadata().to_owned();
+                let ranges = (0..metadata.file_metadata().schema_descr().num_columns())
+                    .map(|column| ColumnRange::from_metadata(&metadata, column))
+                    .collect();
+                Ok(FileMetadata::Parquet(Box::new(ParquetMetadataWrapper {
+                    metadata,
+                    filename: filename.to_string(),
+                    pages: None,
+                    scanning: None,
+                    ranges,
+                })))
             }
             "csv" => {
                 // For CSV, we need the schema to display column information.
@@ -71,34 +99,97 @@ impl FileMetadata {
         }
     }
 
-    /// Renders the file metadata in the UI using egui.
-    pub fn render_metadata(&self, ui: &mut Ui) {
+    /// Returns the Parquet metadata, if the file is a Parquet file.
+    pub fn parquet(&self) -> Option<&ParquetMetaData> {
+        match self {
+            FileMetadata::Parquet(parquet_metadata) => Some(&parquet_metadata.metadata),
+            FileMetadata::Csv(_) => None,
+        }
+    }
+
+    /// Returns the path of the file, if the file is a Parquet file.
+    pub fn parquet_filename(&self) -> Option<&str> {
+        match self {
+            FileMetadata::Parquet(parquet_metadata) => Some(&parquet_metadata.filename),
+            FileMetadata::Csv(_) => None,
+        }
+    }
+
+    /// Summary of the metadata, independent of the UI.
+    pub fn summary(&self) -> MetadataSummary {
         match self {
             FileMetadata::Parquet(parquet_metadata) => {
-                parquet_metadata.render_metadata(ui);
+                MetadataSummary::from_parquet(&parquet_metadata.metadata)
             }
             FileMetadata::Csv(csv_metadata) => {
-                csv_metadata.render_metadata(ui);
+                MetadataSummary::from_schema("csv", &csv_metadata.schema, csv_metadata.row_count)
             }
         }
     }
 
-    /// Renders the file schema information in the UI using egui.
-    pub fn render_schema(&self, ui: &mut Ui) {
+    /// Renders the file metadata in the UI using egui; pages are scanned on `handle`.
+    /// The statistics of the columns in `masks` are hidden.
+    pub fn render_metadata(&mut self, ui: &mut Ui, handle: &Handle, masks: &ColumnMasks) {
         match self {
             FileMetadata::Parquet(parquet_metadata) => {
-                parquet_metadata.render_schema(ui);
+                parquet_metadata.render_metadata(ui, handle, masks);
             }
             FileMetadata::Csv(csv_metadata) => {
-                csv_metadata.render_schema(ui);
+                csv_metadata.render_metadata(ui);
+            }
+        }
+    }
+
+    /// Renders the file schema information in the UI using egui.
+    ///
+    /// The box above the columns narrows them by fuzzy match of their names, best matches first.
+    /// Returns the column to show in the table, if one was chosen. The statistics of the
+    /// columns in `masks` are hidden.
+    pub fn render_schema(
+        &self,
+        ui: &mut Ui,
+        filter: &mut String,
+        masks: &ColumnMasks,
+    ) -> Option<String> {
+        ui.add(
+            TextEdit::singleline(filter)
+                .hint_text("🔍 Filter columns")
+                .desired_width(f32::INFINITY),
+        );
+
+        match self {
+            FileMetadata::Parquet(parquet_metadata) => {
+                parquet_metadata.render_schema(ui, filter, masks)
             }
+            FileMetadata::Csv(csv_metadata) => csv_metadata.render_schema(ui, filter),
         }
     }
 }
 
+/// The items whose name matches `filter`, best matches first; without a filter, all of them.
+fn fuzzy_filter<T>(
+    ui: &mut Ui,
+    filter: &str,
+    items: impl ExactSizeIterator<Item = T>,
+    name: impl Fn(&T) -> &str,
+) -> Vec<T> {
+    let total = items.len();
+    if filter.trim().is_empty() {
+        return items.collect();
+    }
+
+    let mut matches: Vec<(i32, T)> = items
+        .filter_map(|item| fuzzy_score(filter, name(&item)).map(|score| (score, item)))
+        .collect();
+    matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+    ui.weak(format!("{} of {} columns", matches.len(), total));
+    matches.into_iter().map(|(_, item)| item).collect()
+}
+
 impl ParquetMetadataWrapper {
     /// Renders the file metadata in the UI using egui.
-    pub fn render_metadata(&self, ui: &mut Ui) {
+    pub fn render_metadata(&mut self, ui: &mut Ui, handle: &Handle, masks: &ColumnMasks) {
         let file_metadata = self.metadata.file_metadata();
 
         // Use a frame to visually group the metadata.
@@ -127,15 +218,242 @@ impl ParquetMetadataWrapper {
                         ui.end_row();
                     });
             });
+
+        self.render_row_groups(ui, handle, masks);
+        self.render_key_value_metadata(ui);
+    }
+
+    /// Renders the key-value metadata (e.g. pandas, Spark or Arrow schemas) of the file.
+    ///
+    /// JSON values are pretty-printed; every value can be copied to the clipboard.
+    fn render_key_value_metadata(&self, ui: &mut Ui) {
+        let key_values = self
+            .metadata
+            .file_metadata()
+            .key_value_metadata()
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        ui.collapsing(format!("Key-value metadata ({})", key_values.len()), |ui| {
+            for (idx, key_value) in key_values.iter().enumerate() {
+                CollapsingHeader::new(&key_value.key)
+                    .id_salt(("key_value", idx))
+                    .show(ui, |ui| {
+                        let Some(value) = &key_value.value else {
+                            ui.label("no value");
+                            return;
+                        };
+
+                        let text = pretty_json(value).unwrap_or_else(|| value.clone());
+
+                        if ui.button("Copy").clicked() {
+                            ui.ctx().copy_text(text.clone());
+                        }
+
+                        ScrollArea::both()
+                            .id_salt(("key_value_scroll", idx))
+                            .max_height(300.0)
+                            .show(ui, |ui| {
+                                ui.add(
+                                    Label::new(RichText::new(text).monospace()).selectable(true),
+                                );
+                            });
+                    });
+            }
+        });
+    }
+
+    /// Reads all the pages of the file in the background, to count them.
+    fn scan_pages(&mut self, handle: &Handle, ctx: &Context) {
+        let (tx, rx) = oneshot::channel();
+        let filename = self.filename.clone();
+        let ctx = ctx.clone();
+
+        handle.spawn_blocking(move || {
+            let _ = tx.send(PageStatistics::scan_file(&filename));
+            ctx.request_repaint();
+        });
+
+        self.scanning = Some(rx);
+    }
+
+    /// Stores the page counts once scanned.
+    fn check_scanning(&mut self) {
+        let Some(mut scanning) = self.scanning.take() else {
+            return;
+        };
+
+        match scanning.try_recv() {
+            Ok(pages) => self.pages = Some(pages),
+            Err(oneshot::error::TryRecvError::Empty) => self.scanning = Some(scanning),
+            Err(oneshot::error::TryRecvError::Closed) => {
+                self.pages = Some(Err("Page scan was interrupted.".to_string()))
+            }
+        }
+    }
+
+    /// Renders a collapsible tree with the details of each row group and column chunk.
+    fn render_row_groups(&mut self, ui: &mut Ui, handle: &Handle, masks: &ColumnMasks) {
+        self.check_scanning();
+        let row_groups = self.metadata.row_groups();
+        let mut scan = false;
+
+        ui.collapsing(format!("Row groups ({})", row_groups.len()), |ui| {
+            // Writers may omit the page encoding stats: the pages can then be read and counted.
+            let recorded = row_groups
+                .iter()
+                .flat_map(|row_group| row_group.columns())
+                .all(|chunk| chunk.page_encoding_stats().is_some());
+
+            if !recorded {
+                match &self.pages {
+                    None if self.scanning.is_some() => {
+                        ui.horizontal(|ui| {
+                            busy_indicator(ui);
+                            ui.label("Scanning pages…");
+                        });
+                    }
+                    None => {
+                        if ui
+                            .button("Scan pages")
+                            .on_hover_text(
+                                "Page statistics are not recorded in this file: read all pages to count them",
+                            )
+                            .clicked()
+                        {
+                            scan = true;
+                        }
+                    }
+                    Some(Err(error)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                    }
+                    Some(Ok(_)) => {}
+                }
+            }
+
+            let scanned = match &self.pages {
+                Some(Ok(pages)) => Some(pages),
+                _ => None,
+            };
+
+            for (rg_idx, row_group) in row_groups.iter().enumerate() {
+                CollapsingHeader::new(format!("Row group {rg_idx}"))
+                    .id_salt(("row_group", rg_idx))
+                    .show(ui, |ui| {
+                        ui.label(format!("rows: {}", row_group.num_rows()));
+                        ui.label(format!(
+                            "compressed: {}",
+                            format_bytes(row_group.compressed_size())
+                        ));
+                        ui.label(format!(
+                            "uncompressed: {}",
+                            format_bytes(row_group.total_byte_size())
+                        ));
+
+                        // One node per column chunk.
+                        for (col_idx, chunk) in row_group.columns().iter().enumerate() {
+                            CollapsingHeader::new(chunk.column_path().string())
+                                .id_salt(("column_chunk", rg_idx, col_idx))
+                                .show(ui, |ui| {
+                                    let encodings: Vec<String> = chunk
+                                        .encodings()
+                                        .iter()
+                                        .map(|encoding| encoding.to_string())
+                                        .collect();
+
+                                    ui.label(format!("codec: {}", chunk.compression()));
+                                    ui.label(format!("encodings: {}", encodings.join(", ")));
+                                    ui.label(format!(
+                                        "compressed: {}",
+                                        format_bytes(chunk.compressed_size())
+                                    ));
+                                    ui.label(format!(
+                                        "uncompressed: {}",
+                                        format_bytes(chunk.uncompressed_size())
+                                    ));
+
+                                    // Page counts, from the metadata or from a scan.
+                                    let pages = PageStatistics::from_metadata(chunk).or_else(|| {
+                                        scanned?.get(rg_idx)?.get(col_idx).cloned()
+                                    });
+                                    match pages {
+                                        Some(pages) => {
+                                            let encodings: Vec<String> = pages
+                                                .encodings
+                                                .iter()
+                                                .map(|(encoding, count)| {
+                                                    format!("{encoding} ×{count}")
+                                                })
+                                                .collect();
+                                            ui.label(format!(
+                                                "pages: {} data, {} dictionary",
+                                                pages.data_pages, pages.dictionary_pages
+                                            ));
+                                            ui.label(format!(
+                                                "page encodings: {}",
+                                                encodings.join(", ")
+                                            ));
+                                        }
+                                        None => {
+                                            ui.label("pages: not recorded");
+                                        }
+                                    }
+
+                                    // Statistics are optional in Parquet files; those of a
+                                    // masked column would show its raw values.
+                                    let column = chunk.column_path().parts().first();
+                                    let masked =
+                                        column.is_some_and(|name| masks.get(name).is_some());
+                                    match chunk.statistics() {
+                                        Some(_) if masked => {
+                                            ui.label("statistics: masked");
+                                        }
+                                        Some(statistics) => {
+                                            let (min, max) = statistics_min_max(statistics);
+                                            let null_count = statistics.null_count_opt();
+                                            let unknown = || "unknown".to_string();
+                                            ui.label(format!(
+                                                "min: {}",
+                                                min.unwrap_or_else(unknown)
+                                            ));
+                                            ui.label(format!(
+                                                "max: {}",
+                                                max.unwrap_or_else(unknown)
+                                            ));
+                                            ui.label(format!(
+                                                "nulls: {}",
+                                                null_count.map_or_else(unknown, |n| n.to_string())
+                                            ));
+                                        }
+                                        None => {
+                                            ui.label("statistics: none");
+                                        }
+                                    }
+                                });
+                        }
+                    });
+            }
+        });
+
+        if scan {
+            self.scan_pages(handle, ui.ctx());
+        }
     }
 
     /// Renders the file schema information in the UI using egui.
-    pub fn render_schema(&self, ui: &mut Ui) {
+    ///
+    /// Only the columns matching `filter` are listed; returns the column to show in the table.
+    pub fn render_schema(&self, ui: &mut Ui, filter: &str, masks: &ColumnMasks) -> Option<String> {
         let file_metadata = self.metadata.file_metadata();
+        let columns = file_metadata.schema_descr().columns().iter().enumerate();
+        let mut reveal = None;
+
         // Iterate over the columns in the schema.
-        for (idx, field) in file_metadata.schema_descr().columns().iter().enumerate() {
+        for (idx, field) in fuzzy_filter(ui, filter, columns, |(_, field)| field.name()) {
+            let range = self.ranges.get(idx).cloned().unwrap_or_default();
+
             // Create a collapsing header for each column to show its details.
-            ui.collapsing(field.name(), |ui| {
+            let response = ui.collapsing(field.name(), |ui| {
                 // Determine the field type and format it as a string.
                 let field_type = field.self_type();
                 let field_type_str = if field_type.is_primitive() {
@@ -155,8 +473,336 @@ impl ParquetMetadataWrapper {
                         _ => "undefined".to_string(),
                     }
                 ));
+
+                // Range of the values, from the column chunk statistics, unless masked.
+                let column = field.path().parts().first();
+                if column.is_some_and(|name| masks.get(name).is_some()) {
+                    ui.label("min, max: masked");
+                } else {
+                    ui.label(format!(
+                        "min: {}",
+                        range.min.as_deref().unwrap_or("unknown")
+                    ));
+                    ui.label(format!(
+                        "max: {}",
+                        range.max.as_deref().unwrap_or("unknown")
+                    ));
+                }
+                ui.label(format!(
+                    "nulls: {}",
+                    range
+                        .null_count
+                        .map_or_else(|| "unknown".to_string(), |n| n.to_string())
+                ));
+
+                // A nested field is shown by its top-level column.
+                if ui.button("Show in table").clicked() {
+                    reveal = field.path().parts().first().cloned();
+                }
             });
+            response.header_response.on_hover_text(range.to_string());
         }
+
+        reveal
+    }
+}
+
+/// Page counts of a column chunk, by page type and encoding.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PageStatistics {
+    pub dictionary_pages: usize,
+    pub data_pages: usize,
+    /// Number of (dictionary and data) pages of each encoding.
+    pub encodings: BTreeMap<String, usize>,
+}
+
+impl PageStatistics {
+    /// Counts `count` pages of the given type and encoding; index pages are ignored.
+    fn add(&mut self, page_type: PageType, encoding: Encoding, count: usize) {
+        match page_type {
+            PageType::DICTIONARY_PAGE => self.dictionary_pages += count,
+            PageType::DATA_PAGE | PageType::DATA_PAGE_V2 => self.data_pages += count,
+            PageType::INDEX_PAGE => return,
+        }
+        *self.encodings.entry(encoding.to_string()).or_default() += count;
+    }
+
+    /// Page counts recorded in the column chunk metadata (page encoding stats), if any.
+    pub fn from_metadata(chunk: &ColumnChunkMetaData) -> Option<Self> {
+        let mut statistics = PageStatistics::default();
+        for stats in chunk.page_encoding_stats()? {
+            statistics.add(stats.page_type, stats.encoding, stats.count.max(0) as usize);
+        }
+        Some(statistics)
+    }
+
+    /// Reads all the pages of a Parquet file and counts them per column chunk.
+    ///
+    /// The result is indexed by row group, then by column.
+    pub fn scan_file(filename: &str) -> Result<Vec<Vec<Self>>, String> {
+        let file = File::open(filename).map_err(|e| format!("Error opening file: {}", e))?;
+        let reader = SerializedFileReader::new(file)
+            .map_err(|e| format!("Error creating Parquet reader: {}", e))?;
+
+        (0..reader.num_row_groups())
+            .map(|rg_idx| {
+                let row_group = reader
+                    .get_row_group(rg_idx)
+                    .map_err(|e| format!("Error reading row group {}: {}", rg_idx, e))?;
+
+                (0..row_group.num_columns())
+                    .map(|col_idx| {
+                        let mut pages = row_group
+                            .get_column_page_reader(col_idx)
+                            .map_err(|e| format!("Error reading column chunk: {}", e))?;
+
+                        let mut statistics = PageStatistics::default();
+                        while let Some(page) = pages
+                            .get_next_page()
+                            .map_err(|e| format!("Error reading page: {}", e))?
+                        {
+                            statistics.add(page.page_type(), page.encoding(), 1);
+                        }
+                        Ok(statistics)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Pretty-prints the value if it is a JSON object or array, with the keys of its objects sorted.
+///
+/// The keys are sorted explicitly: the `json` feature of Polars makes serde_json keep
+/// the order of the file.
+pub fn pretty_json(value: &str) -> Option<String> {
+    match serde_json::from_str::<serde_json::Value>(value).ok()? {
+        mut json @ (serde_json::Value::Object(_) | serde_json::Value::Array(_)) => {
+            json.sort_all_objects();
+            serde_json::to_string_pretty(&json).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Formats a size in bytes with a binary unit (B, KiB, MiB, GiB).
+pub fn format_bytes(bytes: i64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size.abs() >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    match unit {
+        0 => format!("{bytes} B"),
+        _ => format!("{size:.2} {}", UNITS[unit]),
+    }
+}
+
+/// Formats the min and max values of column chunk statistics.
+///
+/// Byte arrays are shown as text when they are valid UTF-8.
+fn statistics_min_max(statistics: &Statistics) -> (Option<String>, Option<String>) {
+    // Numeric statistics are displayed as is.
+    macro_rules! min_max {
+        ($statistics:expr) => {
+            (
+                $statistics.min_opt().map(|min| min.to_string()),
+                $statistics.max_opt().map(|max| max.to_string()),
+            )
+        };
+    }
+
+    let bytes = |value: &[u8]| match std::str::from_utf8(value) {
+        Ok(text) => text.to_string(),
+        Err(_) => format!("{value:?}"),
+    };
+
+    match statistics {
+        Statistics::Boolean(s) => min_max!(s),
+        Statistics::Int32(s) => min_max!(s),
+        Statistics::Int64(s) => min_max!(s),
+        Statistics::Int96(s) => min_max!(s),
+        Statistics::Float(s) => min_max!(s),
+        Statistics::Double(s) => min_max!(s),
+        Statistics::ByteArray(s) => (
+            s.min_opt().map(|min| bytes(min.data())),
+            s.max_opt().map(|max| bytes(max.data())),
+        ),
+        Statistics::FixedLenByteArray(s) => (
+            s.min_opt().map(|min| bytes(min.data())),
+            s.max_opt().map(|max| bytes(max.data())),
+        ),
+    }
+}
+
+/// A statistics value, comparable with the values of the other chunks of its column.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+enum StatisticsValue {
+    Boolean(bool),
+    Integer(i64),
+    /// Unsigned integers, stored in the signed physical types.
+    Unsigned(u64),
+    Float32(f32),
+    Float(f64),
+    /// Unscaled decimal, with the scale of its column.
+    Decimal(i128, i32),
+    Bytes(Vec<u8>),
+}
+
+impl fmt::Display for StatisticsValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatisticsValue::Boolean(value) => write!(f, "{value}"),
+            StatisticsValue::Integer(value) => write!(f, "{value}"),
+            StatisticsValue::Unsigned(value) => write!(f, "{value}"),
+            StatisticsValue::Float32(value) => write!(f, "{value}"),
+            StatisticsValue::Float(value) => write!(f, "{value}"),
+            StatisticsValue::Decimal(unscaled, scale) => {
+                let scale = (*scale).max(0) as usize;
+                let digits = format!("{:0>width$}", unscaled.unsigned_abs(), width = scale + 1);
+                let (integer, fraction) = digits.split_at(digits.len() - scale);
+                let sign = if *unscaled < 0 { "-" } else { "" };
+                match fraction.is_empty() {
+                    true => write!(f, "{sign}{integer}"),
+                    false => write!(f, "{sign}{integer}.{fraction}"),
+                }
+            }
+            StatisticsValue::Bytes(value) => match std::str::from_utf8(value) {
+                Ok(text) => write!(f, "{text}"),
+                Err(_) => write!(f, "{value:?}"),
+            },
+        }
+    }
+}
+
+/// Decimal stored as a big-endian two's complement integer, in (fixed length) byte arrays.
+fn decimal_from_bytes(bytes: &[u8]) -> Option<i128> {
+    if bytes.is_empty() || bytes.len() > 16 {
+        return None;
+    }
+    let fill = if bytes[0] & 0x80 != 0 { 0xFF } else { 0 }; // Sign extension.
+    let mut buffer = [fill; 16];
+    buffer[16 - bytes.len()..].copy_from_slice(bytes);
+    Some(i128::from_be_bytes(buffer))
+}
+
+/// Min and max values of column chunk statistics, if recorded in a comparable type.
+///
+/// The values are compared in the order of the column: unsigned integers as unsigned,
+/// decimals by their value, other byte arrays byte by byte. Columns without a defined
+/// order, such as intervals or half floats, have no bounds.
+fn statistics_bounds(
+    statistics: &Statistics,
+    column: &ColumnDescriptor,
+) -> Option<(StatisticsValue, StatisticsValue)> {
+    use StatisticsValue::*;
+
+    let decimal = match column.logical_type() {
+        Some(LogicalType::Decimal { scale, .. }) => Some(scale),
+        _ if column.converted_type() == ConvertedType::DECIMAL => Some(column.type_scale()),
+        _ => None,
+    };
+    let unsigned = column.sort_order() == SortOrder::UNSIGNED;
+    if column.sort_order() == SortOrder::UNDEFINED
+        || matches!(column.logical_type(), Some(LogicalType::Float16))
+    {
+        return None;
+    }
+
+    let bytes = |value: &[u8]| match decimal {
+        Some(scale) => decimal_from_bytes(value).map(|unscaled| Decimal(unscaled, scale)),
+        None => Some(Bytes(value.to_vec())),
+    };
+
+    match statistics {
+        Statistics::Boolean(s) => Some((Boolean(*s.min_opt()?), Boolean(*s.max_opt()?))),
+        Statistics::Int32(s) => {
+            let value = |v: i32| match (decimal, unsigned) {
+                (Some(scale), _) => Decimal(v.into(), scale),
+                (None, true) => Unsigned((v as u32).into()),
+                (None, false) => Integer(v.into()),
+            };
+            Some((value(*s.min_opt()?), value(*s.max_opt()?)))
+        }
+        Statistics::Int64(s) => {
+            let value = |v: i64| match (decimal, unsigned) {
+                (Some(scale), _) => Decimal(v.into(), scale),
+                (None, true) => Unsigned(v as u64),
+                (None, false) => Integer(v),
+            };
+            Some((value(*s.min_opt()?), value(*s.max_opt()?)))
+        }
+        Statistics::Float(s) => Some((Float32(*s.min_opt()?), Float32(*s.max_opt()?))),
+        Statistics::Double(s) => Some((Float(*s.min_opt()?), Float(*s.max_opt()?))),
+        Statistics::ByteArray(s) => {
+            Some((bytes(s.min_opt()?.data())?, bytes(s.max_opt()?.data())?))
+        }
+        Statistics::FixedLenByteArray(s) => {
+            Some((bytes(s.min_opt()?.data())?, bytes(s.max_opt()?.data())?))
+        }
+        Statistics::Int96(_) => None, // Legacy timestamps, without a defined order.
+    }
+}
+
+/// Min, max and null count of a column over all its row groups, from the column chunk
+/// statistics: the data is not read.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColumnRange {
+    pub min: Option<String>,
+    pub max: Option<String>,
+    /// Unknown if a column chunk does not record its nulls.
+    pub null_count: Option<u64>,
+}
+
+impl ColumnRange {
+    /// Aggregates the statistics of a column of the file; unknown if a chunk has none.
+    pub fn from_metadata(metadata: &ParquetMetaData, column: usize) -> Self {
+        let descriptor = metadata.file_metadata().schema_descr().column(column);
+        let mut min: Option<StatisticsValue> = None;
+        let mut max: Option<StatisticsValue> = None;
+        let mut null_count = Some(0);
+
+        for row_group in metadata.row_groups() {
+            let Some(statistics) = row_group.column(column).statistics() else {
+                return ColumnRange::default();
+            };
+
+            null_count = null_count
+                .zip(statistics.null_count_opt())
+                .map(|(a, b)| a + b);
+            if let Some((low, high)) = statistics_bounds(statistics, &descriptor) {
+                if min.as_ref().is_none_or(|value| low < *value) {
+                    min = Some(low);
+                }
+                if max.as_ref().is_none_or(|value| high > *value) {
+                    max = Some(high);
+                }
+            }
+        }
+
+        ColumnRange {
+            min: min.map(|value| value.to_string()),
+            max: max.map(|value| value.to_string()),
+            null_count,
+        }
+    }
+}
+
+impl fmt::Display for ColumnRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unknown = "?";
+        write!(
+            f,
+            "{} … {}, {} nulls",
+            self.min.as_deref().unwrap_or(unknown),
+            self.max.as_deref().unwrap_or(unknown),
+            self.null_count
+                .map_or_else(|| unknown.to_string(), |n| n.to_string())
+        )
     }
 }
 
@@ -193,21 +839,240 @@ impl CsvMetadataWrapper {
     }
 
     /// Renders the file schema information in the UI using egui.
-    pub fn render_schema(&self, ui: &mut Ui) {
+    ///
+    /// Only the columns matching `filter` are listed; returns the column to show in the table.
+    pub fn render_schema(&self, ui: &mut Ui, filter: &str) -> Option<String> {
+        let mut reveal = None;
+
         // Iterate over the fields in the schema.
-        for (name, dtype) in self.schema.iter() {
+        for (name, dtype) in fuzzy_filter(ui, filter, self.schema.iter(), |(name, _)| name.as_str())
+        {
             // Create a collapsing header for each column to show its details.
             ui.collapsing(name.to_string(), |ui| {
                 // Display the field data type.
                 ui.label(format!("type: {}", dtype));
+
+                if ui.button("Show in table").clicked() {
+                    reveal = Some(name.to_string());
+                }
             });
         }
+
+        reveal
+    }
+}
+
+/// Vertical spacing of the table rows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RowDensity {
+    /// Rows are as tall as a single line of text.
+    #[default]
+    Compact,
+    /// Rows have extra padding around the text.
+    Comfortable,
+}
+
+impl RowDensity {
+    /// Returns the row height for the given text height.
+    pub fn row_height(&self, text_height: f32, padding: f32) -> f32 {
+        match self {
+            RowDensity::Compact => text_height,
+            RowDensity::Comfortable => text_height + padding,
+        }
+    }
+}
+
+/// View settings applied when rendering the table.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TableViewOptions {
+    /// Alternate the background color of the rows.
+    pub striped: bool,
+    /// Highlight the row under the mouse pointer.
+    pub hover_highlight: bool,
+    /// Vertical spacing of the rows.
+    pub density: RowDensity,
+    /// Decimals of the float columns ("Alíquota" columns always show 4).
+    pub decimals: usize,
+    /// Thousands and decimal separators of the float columns.
+    pub locale: NumberLocale,
+    /// Maximum number of rows displayed; 0 displays all rows.
+    pub row_limit: usize,
+    /// Floats of magnitude 10^n or more use scientific notation.
+    pub scientific_above: i32,
+    /// Nonzero floats of magnitude below 10^n use scientific notation.
+    pub scientific_below: i32,
+    /// Show the type of each column, and whether it has nulls, next to its name.
+    pub type_icons: bool,
+}
+
+impl Default for TableViewOptions {
+    fn default() -> Self {
+        TableViewOptions {
+            striped: false,
+            hover_highlight: true,
+            density: RowDensity::default(),
+            decimals: 2,
+            locale: NumberLocale::default(),
+            row_limit: 0,
+            scientific_above: 15,
+            scientific_below: -4,
+            type_icons: true,
+        }
+    }
+}
+
+/// Glyph shown before the name of a column of the given type.
+pub fn type_glyph(dtype: &DataType) -> &'static str {
+    match dtype {
+        dtype if dtype.is_primitive_numeric() || dtype.is_decimal() => "🔢",
+        dtype if dtype.is_temporal() => "📅",
+        dtype if dtype.is_string() || dtype.is_categorical() || dtype.is_enum() => "🔤",
+        DataType::Boolean => "☑",
+        dtype if dtype.is_nested() => "⧉",
+        _ => "",
+    }
+}
+
+impl TableViewOptions {
+    /// Formats a float cell, in scientific notation outside the configured magnitudes.
+    pub fn format_float(&self, value: f64, decimals: usize) -> String {
+        let magnitude = value.abs();
+        let scientific = value.is_finite()
+            && value != 0.0
+            && (magnitude >= 10f64.powi(self.scientific_above)
+                || magnitude < 10f64.powi(self.scientific_below));
+
+        match scientific {
+            true => self.locale.format_scientific(value, decimals),
+            false => self.locale.format_float(value, decimals),
+        }
+    }
+}
+
+/// Vertical position of the table, restored when a sort, filter or reload replaces the rows,
+/// and the rows bookmarked to navigate between them.
+#[derive(Debug, Clone, Default)]
+pub struct TableScroll {
+    /// First row visible in the last frame.
+    pub anchor_row: usize,
+    /// Number of rows visible in the last frame.
+    pub visible_rows: usize,
+    /// Row to scroll to when the next data is shown.
+    restore: Option<usize>,
+    pub bookmarks: RowBookmarks,
+    /// Column whose header is highlighted, such as one chosen in the schema.
+    pub highlighted_column: Option<String>,
+    /// Scroll the highlighted column into view in the next frame.
+    reveal: bool,
+    /// Text of the cells formatted in the previous frames.
+    cells: CellCache,
+}
+
+impl TableScroll {
+    /// Keeps the anchor row in view once the new data arrives, with the bookmarks by key.
+    pub fn keep(&mut self) {
+        self.restore = Some(self.anchor_row);
+        self.bookmarks.data_replaced(true);
+    }
+
+    /// Scrolls back to the first row, without bookmarks or highlight, as for another file.
+    pub fn reset(&mut self) {
+        self.scroll_to(0);
+        self.bookmarks.data_replaced(false);
+        self.highlighted_column = None;
+    }
+
+    /// Highlights a column and scrolls it into view in the next frame.
+    pub fn reveal_column(&mut self, column: String) {
+        self.highlighted_column = Some(column);
+        self.reveal = true;
+    }
+
+    /// Scrolls to the next bookmarked row below the top of the table.
+    pub fn next_bookmark(&mut self, df: &Arc<DataFrame>) {
+        if let Some(row) = self.bookmarks.next(df, self.anchor_row) {
+            self.scroll_to(row);
+        }
+    }
+
+    /// Scrolls to the previous bookmarked row above the top of the table.
+    pub fn previous_bookmark(&mut self, df: &Arc<DataFrame>) {
+        if let Some(row) = self.bookmarks.previous(df, self.anchor_row) {
+            self.scroll_to(row);
+        }
+    }
+
+    /// Scrolls to a row in the next frame.
+    pub fn scroll_to(&mut self, row: usize) {
+        self.anchor_row = row;
+        self.restore = Some(row);
+    }
+
+    /// Formats the rows ahead of the scroll on the blocking threads of `runtime`.
+    pub fn set_runtime(&mut self, runtime: Handle) {
+        self.cells.set_runtime(runtime);
+    }
+
+    /// Runtime formatting the rows ahead of the scroll, if any.
+    pub fn runtime(&self) -> Option<&Handle> {
+        self.cells.runtime()
     }
 }
 
+/// Action requested by the user through the table header.
+#[derive(Debug, Clone)]
+pub enum TableAction {
+    /// Re-sort the data with the updated filters.
+    Sort(Box<DataFilters>),
+    /// Show the histogram of the named (numeric) column.
+    Histogram(String),
+    /// Show the frequency of each distinct value of the named column.
+    ValueCounts(String),
+    /// Count the rows of each distinct value of the named column with a GROUP BY query.
+    GroupBy(String),
+    /// Rename or cast a column.
+    Transform(ColumnTransform),
+    /// Mask the values of the named column, or unmask them with `None`.
+    Mask(String, Option<MaskKind>),
+}
+
+/// Range of the zoom factor applied to the table text.
+pub const TABLE_ZOOM_RANGE: std::ops::RangeInclusive<f32> = 0.5..=3.0;
+
 impl DataFrameContainer {
     /// Renders the DataFrame as a table using egui.
-    pub fn render_table(&self, ui: &mut Ui) -> Option<DataFilters> {
+    ///
+    /// Ctrl+scroll over the table updates `zoom`, which scales the table text.
+    /// In edit mode, a double-clicked cell is edited in place and its new value
+    /// recorded in the changeset of `editor`. The columns in `masks` show masked values.
+    /// `scroll` tracks the first visible row, to restore it after the data is replaced.
+    pub fn render_table(
+        &self,
+        ui: &mut Ui,
+        options: &TableViewOptions,
+        zoom: &mut f32,
+        editor: &mut CellEditor,
+        masks: &ColumnMasks,
+        scroll: &mut TableScroll,
+    ) -> Option<TableAction> {
+        let lang = language(ui.ctx());
+
+        // Ctrl+scroll (or pinch) over the table zooms its text in or out.
+        if ui.ui_contains_pointer() {
+            let zoom_delta = ui.input(|i| i.zoom_delta());
+            if zoom_delta != 1.0 {
+                *zoom =
+                    (*zoom * zoom_delta).clamp(*TABLE_ZOOM_RANGE.start(), *TABLE_ZOOM_RANGE.end());
+            }
+        }
+
+        // Scale the text styles (and the header button height) of this Ui only.
+        for font_id in ui.style_mut().text_styles.values_mut() {
+            font_id.size *= *zoom;
+        }
+        ui.style_mut().spacing.interact_size.y *= *zoom;
+
         let style = ui.style().as_ref();
 
         /// Checks if a given column is currently sorted.
@@ -222,10 +1087,13 @@ impl DataFrameContainer {
             }
         }
 
-        let mut filters: Option<DataFilters> = None; // The `DataFilters` to be returned if sorting is applied.
+        let mut action: Option<TableAction> = None; // The action to be returned if the user clicked the header.
         let mut sorted_column = self.filters.sort.clone(); // The current sort state of the table.
 
         let text_height = TextStyle::Body.resolve(style).size; // Height of a text line, used for row height calculation.
+        let row_height = options
+            .density
+            .row_height(text_height, 2.0f32 * style.spacing.item_spacing.y); // Height of a table row.
 
         let initial_col_width =
             (ui.available_width() - style.spacing.scroll.bar_width) / (self.df.width() + 1) as f32; // Initial column width, based on available width.
@@ -248,8 +1116,9 @@ impl DataFrameContainer {
 
         // Defines a closure to render the table header.  This creates the interactive sort buttons.
         let analyze_header = |mut table_row: TableRow<'_, '_>| {
-            // Iterate over the column names in the DataFrame.
-            for column_name in self.df.get_column_names() {
+            // Iterate over the columns in the DataFrame.
+            for column in self.df.get_columns() {
+                let column_name = column.name().as_str();
                 table_row.col(|ui| {
                     // Determine the current sort state of the column.
                     let column_label = if is_sorted_column(&sorted_column, column_name) {
@@ -258,61 +1127,264 @@ impl DataFrameContainer {
                         SortState::NotSorted(column_name.to_string()) // Default to "not sorted".
                     };
 
+                    if scroll.highlighted_column.as_deref() == Some(column_name) {
+                        let rect = ui.max_rect();
+                        ui.painter()
+                            .rect_filled(rect, 2.0, ui.visuals().selection.bg_fill);
+                        if std::mem::take(&mut scroll.reveal) {
+                            ui.scroll_to_rect(rect, Some(Align::Center));
+                        }
+                    }
+
                     // Create a centered layout for the sort button.
                     ui.horizontal_centered(|ui| {
-                        // Creates the sort button using the ExtraInteractions trait.
-                        let response = ui.sort_button(&mut sorted_column, column_label.clone());
-                        if response.clicked() {
+                        let glyph = type_glyph(column.dtype());
+                        if options.type_icons && !glyph.is_empty() {
+                            ui.label(RichText::new(glyph).small())
+                                .on_hover_text(column.dtype().to_string());
+                        }
+
+                        // Creates the sort button using the ExtraInteractions trait; the columns
+                        // that cannot be sorted get a dimmed label, which keeps the context menu.
+                        let sortable = is_sortable(column.dtype());
+                        let response = match sortable {
+                            true => ui.sort_button(&mut sorted_column, column_label.clone()),
+                            false => ui
+                                .add(
+                                    Label::new(RichText::new(column_name).weak())
+                                        .sense(Sense::click()),
+                                )
+                                .on_hover_text(format!(
+                                    "Columns of type {} cannot be sorted",
+                                    column.dtype()
+                                )),
+                        };
+                        // Screen readers announce the column, its type and its sort order.
+                        let order = match &column_label {
+                            SortState::Ascending(_) => "sorted ascending",
+                            SortState::Descending(_) => "sorted descending",
+                            SortState::NotSorted(_) => "not sorted",
+                        };
+                        let description = format!(
+                            "{} {column_name}, {}, {}",
+                            tr("Column", lang),
+                            column.dtype(),
+                            tr(order, lang)
+                        );
+                        response.widget_info(|| {
+                            let sorted = !matches!(column_label, SortState::NotSorted(_));
+                            WidgetInfo::selected(WidgetType::Button, sortable, sorted, &description)
+                        });
+
+                        // A badge marks the columns with nulls.
+                        let null_count = column.null_count();
+                        if options.type_icons && null_count > 0 {
+                            ui.label(RichText::new("∅").small().weak())
+                                .on_hover_text(format!("{null_count} null values"));
+                        }
+
+                        if sortable && response.clicked() {
                             // If the sort button is clicked, create a DataFilters to trigger a resort.
-                            filters = Some(DataFilters {
+                            action = Some(TableAction::Sort(Box::new(DataFilters {
                                 sort: sorted_column.clone(), // Updates the filters with the new sort state.
+                                then_by: Vec::new(),         // A header sorts on its column only.
                                 ..self.filters.clone()       // Inherit other filter settings.
-                            });
+                            })));
                         }
+
+                        // Right-click menu with column actions.
+                        response.context_menu(|ui| {
+                            // The summaries of a masked column would show its raw values.
+                            let masked = masks.get(column_name).is_some();
+                            let numeric = column.dtype().is_primitive_numeric();
+                            let histogram = ui
+                                .add_enabled(numeric && !masked, Button::new(tr("Histogram", lang)))
+                                .on_disabled_hover_text(match masked {
+                                    true => tr("Unavailable while the column is masked", lang),
+                                    false => tr("Only available for numeric columns", lang),
+                                });
+                            if histogram.clicked() {
+                                action = Some(TableAction::Histogram(column_name.to_string()));
+                                ui.close_menu();
+                            }
+
+                            let value_counts = ui
+                                .add_enabled(!masked, Button::new(tr("Value counts", lang)))
+                                .on_disabled_hover_text(tr(
+                                    "Unavailable while the column is masked",
+                                    lang,
+                                ));
+                            if value_counts.clicked() {
+                                action = Some(TableAction::ValueCounts(column_name.to_string()));
+                                ui.close_menu();
+                            }
+
+                            // The query of the result is grouped; its column transforms are not.
+                            let group_by = ui
+                                .add_enabled(
+                                    self.filters.transforms.is_empty() && !masked,
+                                    Button::new(tr("Group by this column", lang)),
+                                )
+                                .on_disabled_hover_text(match masked {
+                                    true => tr("Unavailable while the column is masked", lang),
+                                    false => {
+                                        tr("Only available without renamed or cast columns", lang)
+                                    }
+                                });
+                            if group_by.clicked() {
+                                action = Some(TableAction::GroupBy(column_name.to_string()));
+                                ui.close_menu();
+                            }
+
+                            ui.separator();
+
+                            ui.menu_button(tr("Rename…", lang), |ui| {
+                                // The new name is kept in the Ui memory while typed.
+                                let id = ui.make_persistent_id(("rename_column", column_name));
+                                let mut name = ui
+                                    .data_mut(|d| d.get_temp::<String>(id))
+                                    .unwrap_or_else(|| column_name.to_string());
+
+                                let response = ui.text_edit_singleline(&mut name);
+                                let entered = response.lost_focus()
+                                    && ui.input(|i| i.key_pressed(Key::Enter));
+                                let name_trimmed = name.trim();
+                                let valid = !name_trimmed.is_empty() && name_trimmed != column_name;
+
+                                if (ui
+                                    .add_enabled(valid, Button::new(tr("Rename", lang)))
+                                    .clicked()
+                                    || entered)
+                                    && valid
+                                {
+                                    action =
+                                        Some(TableAction::Transform(ColumnTransform::Rename {
+                                            from: column_name.to_string(),
+                                            to: name_trimmed.to_string(),
+                                        }));
+                                    ui.data_mut(|d| d.remove::<String>(id));
+                                    ui.close_menu();
+                                } else {
+                                    ui.data_mut(|d| d.insert_temp(id, name));
+                                }
+                            });
+
+                            ui.menu_button(tr("Mask", lang), |ui| {
+                                let current = masks.masks.get(column_name).copied();
+                                for mask in MaskKind::ALL {
+                                    if ui.radio(current == Some(mask), mask.to_string()).clicked() {
+                                        action = Some(TableAction::Mask(
+                                            column_name.to_string(),
+                                            Some(mask),
+                                        ));
+                                        ui.close_menu();
+                                    }
+                                }
+                                if ui.radio(current.is_none(), tr("None", lang)).clicked() {
+                                    action = Some(TableAction::Mask(column_name.to_string(), None));
+                                    ui.close_menu();
+                                }
+                            });
+
+                            ui.menu_button(tr("Cast to", lang), |ui| {
+                                for cast_type in CastType::ALL {
+                                    let current = column.dtype() == &cast_type.data_type();
+                                    if ui
+                                        .add_enabled(!current, Button::new(cast_type.to_string()))
+                                        .clicked()
+                                    {
+                                        action =
+                                            Some(TableAction::Transform(ColumnTransform::Cast {
+                                                column: column_name.to_string(),
+                                                to: cast_type,
+                                            }));
+                                        ui.close_menu();
+                                    }
+                                }
+                            });
+                        });
                     });
                 });
             }
         };
 
+        // Renderers added by plugins, by column.
+        let renderers: Vec<_> = self
+            .df
+            .get_columns()
+            .iter()
+            .map(|column| cell_renderer(column.name(), column.dtype()))
+            .collect();
+
+        // The text of the cells is formatted once for this data and these options.
+        scroll.cells.validate(&self.df, options);
+
         // Defines a closure to render the table rows.
         // This displays the data from each cell.
         let analyze_rows = |mut table_row: TableRow<'_, '_>| {
             let row_index = table_row.index(); // Gets the current row index.
 
+            if !options.hover_highlight {
+                table_row.set_hovered(false); // Suppress the hover highlight.
+            }
+
+            // Bookmarked rows are highlighted; any cell bookmarks its row from its context menu.
+            let bookmarked = scroll.bookmarks.contains(&self.df, row_index);
+            table_row.set_selected(bookmarked);
+            let mut toggle_bookmark = false;
+            let mut bookmark_menu = |response: Response| {
+                response.context_menu(|ui| {
+                    let label = match bookmarked {
+                        true => tr("Remove bookmark", lang),
+                        false => tr("Bookmark row", lang),
+                    };
+                    if ui.button(label).clicked() {
+                        toggle_bookmark = true;
+                        ui.close_menu();
+                    }
+                });
+            };
+
             // Iterate over the columns in the DataFrame.
-            for column in self.df.get_columns() {
-                // Convert the AnyValue in the cell to a String for display.
-                let mut value: String = column
-                    .get(row_index)
-                    .map(|any_value| {
-                        match any_value {
-                            AnyValue::String(s) => s.to_string(),
-                            AnyValue::Null => "".to_string(), // Display "" for Null values.
-                            av => av.to_string(), // Fallback to Debug formatting for other types.
-                        }
-                    })
-                    .unwrap_or_else(|_| "Error: This is not a value!".to_string());
+            for (column_index, (column, renderer)) in
+                self.df.get_columns().iter().zip(&renderers).enumerate()
+            {
+                let column_name = column.name().as_str();
+                let edited = editor.changes.get(column_name, row_index);
+
+                // Masked columns show their masked values, and cannot be edited.
+                if let Some(mask) = masks.get(column_name) {
+                    let raw = edited
+                        .clone()
+                        .map_or_else(|| column.get(row_index), Ok)
+                        .map(|any_value| cell_text(&any_value))
+                        .unwrap_or_default();
+                    table_row.col(|ui| {
+                        let label = Label::new(mask.apply(&raw)).sense(Sense::click());
+                        bookmark_menu(ui.add(label));
+                    });
+                    continue;
+                }
+
+                // Convert the AnyValue in the cell (or its new value) to a String for display.
+                // The cells of the data are cached; the new values of the edited ones are not.
+                let value: String = match &edited {
+                    Some(new_value) => {
+                        let decimals = column_decimals(column_name, options);
+                        format_cell(new_value.clone(), renderer.as_ref(), options, decimals)
+                    }
+                    None => scroll
+                        .cells
+                        .get_or_format(row_index, column_index, || {
+                            format_column_cell(column, row_index, renderer.as_ref(), options)
+                        })
+                        .to_string(),
+                };
 
                 // Determine the layout based on the data type for alignment.
                 let layout = if column.dtype().is_float() {
-                    // Check if the column name contains "Alíquota" (tax rate in Portuguese)
-                    let col_aliquota = column.name().contains("Alíquota");
-
-                    // Convert string to floating point number and format it
-                    value = match value.trim().parse::<f64>() {
-                        Ok(float) => {
-                            // If column is Alíquota format to 4 decimal places, else to 2.
-                            if col_aliquota {
-                                format!("{float:0.4}")
-                            } else {
-                                format!("{float:0.2}")
-                            }
-                        }
-                        Err(_) => value, // If parsing fails, keep the original string.
-                    };
-
-                    // Align center if it's an "Alíquota" column, otherwise align right.
-                    if col_aliquota {
+                    // Align center if it's a tax rate column, otherwise align right.
+                    if is_tax_rate(column_name) {
                         Layout::centered_and_justified(Direction::LeftToRight)
                     } else {
                         Layout::right_to_left(egui::Align::Center)
@@ -325,31 +1397,204 @@ impl DataFrameContainer {
                     Layout::left_to_right(egui::Align::Center)
                 };
 
+                let is_editing = editor
+                    .editing
+                    .as_ref()
+                    .is_some_and(|cell| cell.column == column_name && cell.row == row_index);
+
                 // Add the cell to the table row.
                 table_row.col(|ui| {
+                    if is_editing {
+                        self.render_cell_editor(ui, editor);
+                        return;
+                    }
+
                     // Display the value within the determined layout.
                     // Disable wrapping to prevent text overflow.
                     ui.with_layout(layout.with_main_wrap(false), |ui| {
-                        ui.label(value); // Display the value.
+                        let text = match edited {
+                            Some(_) => RichText::new(&value).color(ui.visuals().warn_fg_color),
+                            None => RichText::new(&value),
+                        };
+                        let response = ui.add(Label::new(text).sense(Sense::click()));
+                        // Screen readers announce the column and row of the cell with its value.
+                        response.widget_info(|| {
+                            let row = row_index + 1;
+                            let label =
+                                format!("{column_name}, {} {row}: {value}", tr("row", lang));
+                            WidgetInfo::labeled(WidgetType::Label, true, label)
+                        });
+                        if editor.enabled && response.double_clicked() {
+                            let current = editor
+                                .changes
+                                .get(column_name, row_index)
+                                .map_or_else(|| column.get(row_index), Ok)
+                                .unwrap_or(AnyValue::Null);
+                            editor.start(column_name, row_index, &current);
+                        }
+                        bookmark_menu(response);
                     });
                 });
             }
+
+            if toggle_bookmark {
+                scroll.bookmarks.toggle(&self.df, row_index);
+            }
         };
 
+        let num_rows = match options.row_limit {
+            0 => self.df.height(),
+            limit => self.df.height().min(limit),
+        };
+        let row_spacing = row_height + ui.spacing().item_spacing.y; // Rows are laid out with the item spacing.
+
         // Build the table using egui_extras::TableBuilder.
-        TableBuilder::new(ui)
-            .striped(false) // Disable striped rows.
+        let mut table = TableBuilder::new(ui)
+            .striped(options.striped) // Alternate row colors if enabled.
             .columns(column, self.df.width()) // Set up the columns.
             .column(Column::remainder())
-            .auto_shrink([false, false]) // Disable auto-shrinking to fit content.
-            .min_scrolled_height(1000.0) // Set a minimum height for the table.
+            .auto_shrink([false, self.df.height() == 0]) // Fill the panel, unless there are no rows.
+            .min_scrolled_height(1000.0); // Set a minimum height for the table.
+
+        // After a sort, filter or reload, scroll back to the row that was at the top.
+        if let Some(row) = scroll.restore.take() {
+            table = match row.min(num_rows.saturating_sub(1)) {
+                0 => table.vertical_scroll_offset(0.0),
+                row => table.scroll_to_row(row, Some(Align::TOP)),
+            };
+        }
+
+        let output = table
             .header(header_height, analyze_header) // Render the table header.
             .body(|body| {
-                let num_rows = self.df.height();
-                body.rows(text_height, num_rows, analyze_rows); // Render the table rows.
+                body.rows(row_height, num_rows, analyze_rows); // Render the table rows.
+            });
+        let first_row = (output.state.offset.y / row_spacing).round() as usize;
+        let velocity = first_row as isize - scroll.anchor_row as isize;
+        scroll.anchor_row = first_row;
+
+        // The rows the table scrolls toward are formatted ahead, more of them when scrolling fast.
+        let visible_rows = (output.inner_rect.height() / row_spacing).ceil() as usize;
+        scroll.visible_rows = visible_rows;
+        scroll
+            .cells
+            .prefetch(&renderers, first_row, visible_rows, velocity);
+
+        // An empty result keeps its header, with a note instead of the rows.
+        if self.df.height() == 0 {
+            ui.vertical_centered(|ui| {
+                ui.label("0 rows");
             });
+        }
+
+        action // Returns the action requested through the header, if any.
+    }
+
+    /// Text field of the cell being edited: Enter validates the value, Escape cancels.
+    fn render_cell_editor(&self, ui: &mut Ui, editor: &mut CellEditor) {
+        let Some(cell) = editor.editing.as_mut() else {
+            return;
+        };
+
+        let lang = language(ui.ctx());
+        let (set_null, mut response) = ui
+            .horizontal(|ui| {
+                // A null is set explicitly: an empty text is an empty string.
+                let set_null = ui
+                    .small_button("∅")
+                    .on_hover_text(tr("Set the cell to null", lang))
+                    .clicked();
 
-        filters // Returns the DataFilters if sorting has been applied.
+                let hint = match cell.null {
+                    true => "null",
+                    false => "",
+                };
+                let mut text_edit = TextEdit::singleline(&mut cell.text)
+                    .hint_text(hint)
+                    .desired_width(f32::INFINITY);
+                if cell.error.is_some() {
+                    text_edit = text_edit.text_color(ui.visuals().error_fg_color);
+                }
+                (set_null, ui.add(text_edit))
+            })
+            .inner;
+        if let Some(error) = &cell.error {
+            response = response.on_hover_text(error);
+        }
+        response.request_focus();
+
+        if set_null {
+            cell.text.clear();
+            cell.null = true;
+            editor.commit(&self.df);
+        } else if ui.input(|i| i.key_pressed(Key::Escape)) {
+            editor.editing = None;
+        } else if response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+            editor.commit(&self.df);
+        } else if response.changed() {
+            cell.null = false;
+            cell.error = None;
+        }
+    }
+}
+
+/// Popover displaying a secondary result (e.g. a pivot table) in its own window.
+pub struct DataFrameWindow {
+    /// Title of the window.
+    pub title: String,
+    /// The DataFrame to display.
+    pub df: DataFrame,
+}
+
+impl Popover for DataFrameWindow {
+    /// Shows the DataFrame as a scrollable table.
+    fn show(&mut self, ctx: &Context) -> bool {
+        let mut open = true;
+
+        Window::new(&self.title)
+            .collapsible(false)
+            .open(&mut open)
+            .default_size([600.0, 400.0])
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "{} rows × {} columns",
+                    self.df.height(),
+                    self.df.width()
+                ));
+
+                let text_height = TextStyle::Body.resolve(ui.style()).size;
+                let row_height = text_height + ui.spacing().item_spacing.y;
+
+                ScrollArea::horizontal().show(ui, |ui| {
+                    TableBuilder::new(ui)
+                        .striped(true)
+                        .columns(
+                            Column::auto().at_least(60.0).resizable(true).clip(true),
+                            self.df.width(),
+                        )
+                        .cell_layout(Layout::left_to_right(Align::Center))
+                        .header(row_height, |mut header| {
+                            for name in self.df.get_column_names() {
+                                header.col(|ui| {
+                                    ui.strong(name.as_str());
+                                });
+                            }
+                        })
+                        .body(|body| {
+                            body.rows(row_height, self.df.height(), |mut row| {
+                                let index = row.index();
+                                for column in self.df.get_columns() {
+                                    let value = column.get(index).unwrap_or(AnyValue::Null);
+                                    row.col(|ui| {
+                                        ui.label(format_any_value(&value));
+                                    });
+                                }
+                            });
+                        });
+                });
+            });
+
+        open
     }
 }
 
@@ -362,3 +1607,231 @@ pub async fn file_dialog() -> Result<String, String> {
         None => Err("No file loaded.".to_string()),       // Return an error if no file is selected.
     }
 }
+
+/// Asynchronously opens a file dialog with a title, returning the full path of the selected file.
+///
+/// `filters` are (name, extensions) pairs, e.g. `("Parquet", &["parquet"])`.
+pub async fn pick_file_dialog(title: &str, filters: &[(&str, &[&str])]) -> Result<PathBuf, String> {
+    let mut dialog = AsyncFileDialog::new().set_title(title);
+    for (name, extensions) in filters {
+        dialog = dialog.add_filter(*name, extensions);
+    }
+    let opt_file_handle = dialog.pick_file().await;
+
+    match opt_file_handle {
+        Some(file_handle) => Ok(file_handle.path().to_path_buf()),
+        None => Err("No file selected.".to_string()),
+    }
+}
+
+/// Asynchronously opens a dialog to choose a directory.
+pub async fn pick_folder_dialog(title: &str) -> Result<PathBuf, String> {
+    match AsyncFileDialog::new().set_title(title).pick_folder().await {
+        Some(file_handle) => Ok(file_handle.path().to_path_buf()),
+        None => Err("No folder selected.".to_string()),
+    }
+}
+
+/// Asynchronously opens a save dialog, suggesting a file name and the given file type filters.
+pub async fn save_file_dialog(
+    file_name: &str,
+    filters: &[(&str, &[&str])],
+) -> Result<PathBuf, String> {
+    let dialog = filters
+        .iter()
+        .fold(AsyncFileDialog::new(), |dialog, (name, extensions)| {
+            dialog.add_filter(*name, extensions)
+        });
+
+    match dialog.set_file_name(file_name).save_file().await {
+        Some(file_handle) => Ok(file_handle.path().to_path_buf()),
+        None => Err("No file selected.".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pretty_json() {
+        let pandas = r#"{"index_columns": [], "columns": [{"name": "a"}]}"#;
+        let pretty = pretty_json(pandas).unwrap();
+        assert!(pretty.starts_with("{\n  \"columns\""));
+
+        // Plain strings and numbers are displayed as is.
+        assert_eq!(pretty_json("parquet-cpp-arrow"), None);
+        assert_eq!(pretty_json("42"), None);
+    }
+
+    #[test]
+    fn test_type_glyph() {
+        assert_eq!(type_glyph(&DataType::Int64), "🔢");
+        assert_eq!(type_glyph(&DataType::Date), "📅");
+        assert_eq!(type_glyph(&DataType::String), "🔤");
+        assert_eq!(type_glyph(&DataType::List(Box::new(DataType::Int32))), "⧉");
+        assert_eq!(type_glyph(&DataType::Binary), "");
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.00 MiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 * 1024), "5.00 GiB");
+    }
+
+    #[test]
+    fn test_scan_page_statistics() -> Result<(), String> {
+        let path = std::env::temp_dir().join("polars_view_pages.parquet");
+
+        let mut df =
+            df!["value" => (0..10_000).collect::<Vec<i64>>()].map_err(|e| e.to_string())?;
+        let file = File::create(&path).map_err(|e| e.to_string())?;
+        ParquetWriter::new(file)
+            .with_row_group_size(Some(5_000))
+            .finish(&mut df)
+            .map_err(|e| e.to_string())?;
+
+        let pages = PageStatistics::scan_file(&path.to_string_lossy())?;
+        let _ = std::fs::remove_file(path);
+
+        assert_eq!(pages.len(), 2); // Two row groups of one column.
+        for row_group in pages {
+            let chunk = &row_group[0];
+            assert!(chunk.data_pages >= 1);
+            let counted: usize = chunk.encodings.values().sum();
+            assert_eq!(counted, chunk.data_pages + chunk.dictionary_pages);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_column_range() -> Result<(), String> {
+        let path = std::env::temp_dir().join("polars_view_test_column_range.parquet");
+        let mut df = df![
+            "id" => (0..10).collect::<Vec<i32>>(),
+            "name" => ["j", "i", "h", "g", "f", "e", "d", "c", "b", "a"],
+            "score" => [Some(1.5), None, Some(-2.0), Some(9.0), None, Some(0.0), Some(3.0), Some(4.0), Some(5.0), Some(6.0)],
+        ]
+        .map_err(|e| e.to_string())?;
+        let file = File::create(&path).map_err(|e| e.to_string())?;
+        ParquetWriter::new(file)
+            .with_statistics(StatisticsOptions::full())
+            .with_row_group_size(Some(4))
+            .finish(&mut df)
+            .map_err(|e| e.to_string())?;
+
+        let metadata = FileMetadata::from_filename(&path.to_string_lossy(), "parquet", None, None);
+        let _ = std::fs::remove_file(&path);
+        let FileMetadata::Parquet(wrapper) = metadata? else {
+            return Err("not a Parquet file".to_string());
+        };
+
+        let range = |min: &str, max: &str, nulls| ColumnRange {
+            min: Some(min.to_string()),
+            max: Some(max.to_string()),
+            null_count: Some(nulls),
+        };
+        assert!(wrapper.metadata.num_row_groups() > 1); // Aggregated over the row groups.
+        assert_eq!(
+            wrapper.ranges,
+            [range("0", "9", 0), range("a", "j", 0), range("-2", "9", 2)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_statistics_min_max() {
+        let statistics = Statistics::int32(Some(-3), Some(42), None, Some(0), false);
+        assert_eq!(
+            statistics_min_max(&statistics),
+            (Some("-3".to_string()), Some("42".to_string()))
+        );
+
+        let statistics = Statistics::byte_array(
+            Some("apple".into()),
+            Some("pear".into()),
+            None,
+            Some(1),
+            false,
+        );
+        assert_eq!(
+            statistics_min_max(&statistics),
+            (Some("apple".to_string()), Some("pear".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_statistics_bounds() -> Result<(), String> {
+        use parquet::{
+            basic::Type as PhysicalType,
+            data_type::FixedLenByteArray,
+            schema::types::{ColumnPath, Type},
+        };
+
+        let column = |physical, logical, length| -> Result<ColumnDescriptor, String> {
+            let primitive = Type::primitive_type_builder("c", physical)
+                .with_logical_type(Some(logical))
+                .with_length(length)
+                .with_precision(9)
+                .with_scale(2)
+                .build()
+                .map_err(|e| e.to_string())?;
+            Ok(ColumnDescriptor::new(
+                Arc::new(primitive),
+                0,
+                0,
+                ColumnPath::new(Vec::new()),
+            ))
+        };
+        let bounds = |statistics, column| {
+            statistics_bounds(&statistics, &column)
+                .map(|(min, max)| (min.to_string(), max.to_string()))
+        };
+
+        // Unsigned integers are stored in the signed types: 3_000_000_000 reads as negative.
+        let uint32 = LogicalType::Integer {
+            bit_width: 32,
+            is_signed: false,
+        };
+        let statistics = Statistics::int32(
+            Some(7),
+            Some(3_000_000_000_u32 as i32),
+            None,
+            Some(0),
+            false,
+        );
+        let (min, max) = statistics_bounds(&statistics, &column(PhysicalType::INT32, uint32, -1)?)
+            .ok_or("no bounds")?;
+        assert!(min < max);
+        assert_eq!(max.to_string(), "3000000000");
+
+        // Decimals compare by value, negative ones included, and show their scale.
+        let decimal = LogicalType::Decimal {
+            scale: 2,
+            precision: 9,
+        };
+        let flba = |value: i32| Some(FixedLenByteArray::from(value.to_be_bytes().to_vec()));
+        let statistics =
+            Statistics::fixed_len_byte_array(flba(-150), flba(2), None, Some(0), false);
+        let (min, max) = statistics_bounds(
+            &statistics,
+            &column(PhysicalType::FIXED_LEN_BYTE_ARRAY, decimal.clone(), 4)?,
+        )
+        .ok_or("no bounds")?;
+        assert!(min < max && min < StatisticsValue::Decimal(-149, 2));
+        assert_eq!(
+            (min.to_string(), max.to_string()),
+            ("-1.50".to_string(), "0.02".to_string())
+        );
+
+        let statistics = Statistics::int64(Some(-5), Some(12345), None, Some(0), false);
+        assert_eq!(
+            bounds(statistics, column(PhysicalType::INT64, decimal, -1)?),
+            Some(("-0.05".to_string(), "123.45".to_string()))
+        );
+        Ok(())
+    }
+}
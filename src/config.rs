@@ -0,0 +1,601 @@
+use crate::{
+    Action, EngineOptions, Keymap, Language, MyStyle, ParquetParallelism, Popover, RowDensity,
+    SqlFunction, TableViewOptions, capture_shortcut, language, set_language, set_low_power, tr,
+};
+
+use egui::{
+    CollapsingHeader, ComboBox, Context, DragValue, Grid, Slider, TextEdit, ThemePreference, Window,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use tokio::runtime::{Builder, Runtime};
+
+/// Key of the [`AppConfig`] in the eframe storage.
+pub const APP_CONFIG_KEY: &str = "app_config";
+
+/// Range of the body font size, in points.
+pub const FONT_SIZE_RANGE: std::ops::RangeInclusive<f32> = 10.0..=28.0;
+
+/// Formatting of the numbers displayed in the table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NumberLocale {
+    /// No thousands separator, point as decimal separator: `1234567.89`.
+    #[default]
+    Plain,
+    /// Comma as thousands separator, point as decimal separator: `1,234,567.89`.
+    English,
+    /// Point as thousands separator, comma as decimal separator: `1.234.567,89`.
+    Portuguese,
+}
+
+impl NumberLocale {
+    pub const ALL: [NumberLocale; 3] = [
+        NumberLocale::Plain,
+        NumberLocale::English,
+        NumberLocale::Portuguese,
+    ];
+
+    /// Formats a float with the given number of decimals.
+    pub fn format_float(&self, value: f64, decimals: usize) -> String {
+        if let Some(special) = format_non_finite(value) {
+            return special;
+        }
+
+        let formatted = format!("{value:.decimals$}");
+
+        let (thousands, decimal) = match self {
+            NumberLocale::Plain => return formatted,
+            NumberLocale::English => (',', '.'),
+            NumberLocale::Portuguese => ('.', ','),
+        };
+
+        let (sign, unsigned) = match formatted.strip_prefix('-') {
+            Some(unsigned) => ("-", unsigned),
+            None => ("", formatted.as_str()),
+        };
+        let (integer, fraction) = match unsigned.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (unsigned, None),
+        };
+
+        let mut result = String::from(sign);
+        for (idx, digit) in integer.chars().enumerate() {
+            if idx > 0 && (integer.len() - idx) % 3 == 0 {
+                result.push(thousands);
+            }
+            result.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            result.push(decimal);
+            result.push_str(fraction);
+        }
+        result
+    }
+
+    /// Formats a float in scientific notation, as `1.23e15`, with the decimal separator of the locale.
+    pub fn format_scientific(&self, value: f64, decimals: usize) -> String {
+        if let Some(special) = format_non_finite(value) {
+            return special;
+        }
+
+        let formatted = format!("{value:.decimals$e}");
+        match self {
+            NumberLocale::Portuguese => formatted.replace('.', ","),
+            _ => formatted,
+        }
+    }
+}
+
+/// NaN and the infinities, which have no digits to format.
+fn format_non_finite(value: f64) -> Option<String> {
+    match value {
+        v if v.is_nan() => Some("NaN".to_string()),
+        f64::INFINITY => Some("∞".to_string()),
+        f64::NEG_INFINITY => Some("-∞".to_string()),
+        _ => None,
+    }
+}
+
+impl fmt::Display for NumberLocale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberLocale::Plain => write!(f, "Plain (1234.56)"),
+            NumberLocale::English => write!(f, "English (1,234.56)"),
+            NumberLocale::Portuguese => write!(f, "Portuguese (1.234,56)"),
+        }
+    }
+}
+
+/// Application settings, persisted between sessions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub theme: ThemePreference,
+    /// Size of the body text, in points.
+    pub font_size: f32,
+    /// Worker threads of the Tokio runtime; 0 uses one per CPU core. Applied on restart.
+    pub runtime_threads: usize,
+    /// Threads of the Tokio pool for blocking work (exports, file dialogs);
+    /// 0 uses the Tokio default of 512. Applied on restart.
+    pub runtime_blocking_threads: usize,
+    /// CSV delimiter proposed for SQL queries when the loaded data has none.
+    pub default_delimiter: String,
+    /// Table settings (rows, decimals, locale).
+    pub table: TableViewOptions,
+    /// Keyboard shortcuts of the actions.
+    pub keymap: Keymap,
+    /// Files estimated to need more memory than this, in MiB, are not loaded; 0 for no limit.
+    pub memory_limit_mib: u64,
+    /// Options of the Polars engine: streaming, Parquet parallelism, low memory and optimizations.
+    pub engine: EngineOptions,
+    /// Repaint only on input and task completion, with still busy indicators.
+    pub low_power: bool,
+    /// Language of the user interface.
+    pub language: Language,
+    /// Queries and exports taking more seconds than this, finished while the window
+    /// is unfocused, are notified to the desktop; 0 never notifies.
+    pub notify_after_secs: u64,
+    /// Reopen a recent file with its last query, sort and filters.
+    pub restore_file_state: bool,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        AppConfig {
+            theme: ThemePreference::Dark,
+            font_size: 16.0,
+            runtime_threads: 0,
+            runtime_blocking_threads: 0,
+            default_delimiter: ";".to_string(),
+            table: TableViewOptions::default(),
+            keymap: Keymap::default(),
+            memory_limit_mib: 0,
+            engine: EngineOptions::default(),
+            low_power: false,
+            language: Language::default(),
+            notify_after_secs: 10,
+            restore_file_state: false,
+        }
+    }
+}
+
+impl AppConfig {
+    /// Reads the configuration from the eframe storage, falling back to the defaults.
+    pub fn load(storage: Option<&dyn eframe::Storage>) -> Self {
+        storage
+            .and_then(|storage| eframe::get_value(storage, APP_CONFIG_KEY))
+            .unwrap_or_default()
+    }
+
+    /// Writes the configuration to the eframe storage.
+    pub fn save(&self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, APP_CONFIG_KEY, self);
+    }
+
+    /// Applies the theme, font size, low-power mode and language to the egui context.
+    pub fn apply(&self, ctx: &Context) {
+        ctx.set_theme(self.theme);
+        ctx.set_font_size(self.font_size);
+        set_low_power(ctx, self.low_power);
+        set_language(ctx, self.language);
+    }
+
+    /// Builds the Tokio runtime with the configured number of worker and blocking threads;
+    /// `threads`, given by the `--threads` command line option, takes precedence over
+    /// the saved setting.
+    pub fn build_runtime(&self, threads: Option<usize>) -> Runtime {
+        let mut builder = Builder::new_multi_thread();
+        let worker_threads = threads.unwrap_or(self.runtime_threads);
+        if worker_threads > 0 {
+            builder.worker_threads(worker_threads);
+        }
+        if self.runtime_blocking_threads > 0 {
+            builder.max_blocking_threads(self.runtime_blocking_threads);
+        }
+        builder
+            .enable_all()
+            .build()
+            .expect("Failed to build Tokio runtime")
+    }
+}
+
+/// Settings popover, editing a copy of the [`AppConfig`].
+pub struct Settings {
+    /// Configuration being edited.
+    config: AppConfig,
+    /// Configuration applied by the user, taken by the application.
+    applied: Option<AppConfig>,
+    /// Action waiting for a key press to be rebound.
+    rebinding: Option<Action>,
+}
+
+impl Settings {
+    pub fn new(config: &AppConfig) -> Self {
+        Settings {
+            config: config.clone(),
+            applied: None,
+            rebinding: None,
+        }
+    }
+}
+
+impl Popover for Settings {
+    fn show(&mut self, ctx: &Context) -> bool {
+        let lang = language(ctx);
+        let mut open = true;
+        let config = &mut self.config;
+
+        // The next key pressed is the new shortcut of the action being rebound.
+        if let Some(action) = self.rebinding {
+            if let Some(shortcut) = capture_shortcut(ctx) {
+                if shortcut.is_some() {
+                    config.keymap.bind(action, shortcut);
+                }
+                self.rebinding = None;
+            }
+        }
+
+        Window::new(tr("Settings", lang))
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                Grid::new("settings_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 8.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label(tr("Theme:", lang));
+                        ui.horizontal(|ui| {
+                            ui.radio_value(
+                                &mut config.theme,
+                                ThemePreference::Dark,
+                                tr("Dark", lang),
+                            );
+                            ui.radio_value(
+                                &mut config.theme,
+                                ThemePreference::Light,
+                                tr("Light", lang),
+                            );
+                            ui.radio_value(
+                                &mut config.theme,
+                                ThemePreference::System,
+                                tr("System", lang),
+                            );
+                        });
+                        ui.end_row();
+
+                        ui.label(tr("Language:", lang));
+                        ComboBox::from_id_salt("settings_language")
+                            .selected_text(config.language.to_string())
+                            .show_ui(ui, |ui| {
+                                for language in Language::ALL {
+                                    let text = language.to_string();
+                                    ui.selectable_value(&mut config.language, language, text);
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label(tr("Font size:", lang));
+                        ui.add(Slider::new(&mut config.font_size, FONT_SIZE_RANGE).suffix(" pt"));
+                        ui.end_row();
+
+                        ui.label(tr("Number format:", lang));
+                        ComboBox::from_id_salt("settings_locale")
+                            .selected_text(config.table.locale.to_string())
+                            .show_ui(ui, |ui| {
+                                for locale in NumberLocale::ALL {
+                                    ui.selectable_value(
+                                        &mut config.table.locale,
+                                        locale,
+                                        locale.to_string(),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label(tr("Decimals:", lang));
+                        ui.add(DragValue::new(&mut config.table.decimals).range(0..=10));
+                        ui.end_row();
+
+                        let hint = "Floats outside these magnitudes use scientific notation";
+                        ui.label(tr("Scientific notation:", lang))
+                            .on_hover_text(hint);
+                        ui.horizontal(|ui| {
+                            ui.label(tr("≥ 1e", lang));
+                            ui.add(
+                                DragValue::new(&mut config.table.scientific_above).range(1..=308),
+                            );
+                            ui.label(tr("or < 1e", lang));
+                            ui.add(
+                                DragValue::new(&mut config.table.scientific_below).range(-308..=0),
+                            );
+                        });
+                        ui.end_row();
+
+                        let hint = "Maximum number of rows displayed in the table (0 for all)";
+                        ui.label(tr("Row limit:", lang)).on_hover_text(hint);
+                        ui.add(DragValue::new(&mut config.table.row_limit).speed(100));
+                        ui.end_row();
+
+                        let hint = "Repaint only on input and when a task completes, with the \
+                            progress updated once a second, to save battery";
+                        ui.label(tr("Low power:", lang)).on_hover_text(hint);
+                        ui.checkbox(&mut config.low_power, tr("Reduce repaints", lang));
+                        ui.end_row();
+
+                        let hint = "Reopen a recent file with the query, sort and filters \
+                            it had when last opened";
+                        ui.label(tr("Reopen files:", lang)).on_hover_text(hint);
+                        ui.checkbox(
+                            &mut config.restore_file_state,
+                            tr("Restore last state", lang),
+                        );
+                        ui.end_row();
+
+                        let hint = "Notify the desktop when a query or export taking longer \
+                            finishes while the window is unfocused (0 never notifies)";
+                        ui.label(tr("Notify after:", lang)).on_hover_text(hint);
+                        ui.add(
+                            DragValue::new(&mut config.notify_after_secs)
+                                .range(0..=3600)
+                                .suffix(" s"),
+                        );
+                        ui.end_row();
+
+                        ui.label(tr("Row density:", lang));
+                        ui.horizontal(|ui| {
+                            let density = &mut config.table.density;
+                            ui.radio_value(density, RowDensity::Compact, tr("Compact", lang));
+                            ui.radio_value(
+                                density,
+                                RowDensity::Comfortable,
+                                tr("Comfortable", lang),
+                            );
+                        });
+                        ui.end_row();
+
+                        let hint = "Worker threads for loading and queries (0 for one per core), \
+                            applied on restart";
+                        ui.label(tr("Runtime threads:", lang)).on_hover_text(hint);
+                        ui.add(DragValue::new(&mut config.runtime_threads).range(0..=256));
+                        ui.end_row();
+
+                        let hint = "Threads for blocking work such as exports \
+                            (0 for the Tokio default of 512), applied on restart";
+                        ui.label(tr("Blocking threads:", lang)).on_hover_text(hint);
+                        ui.add(
+                            DragValue::new(&mut config.runtime_blocking_threads).range(0..=1024),
+                        );
+                        ui.end_row();
+
+                        let hint =
+                            "Files estimated to need more memory are not loaded (0 for no limit)";
+                        ui.label(tr("Memory limit:", lang)).on_hover_text(hint);
+                        ui.add(
+                            DragValue::new(&mut config.memory_limit_mib)
+                                .speed(64)
+                                .suffix(" MiB"),
+                        );
+                        ui.end_row();
+
+                        let hint = "Sort and query out of core with the Polars streaming engine, \
+                            spilling to the POLARS_TEMP_DIR directory (the system temporary \
+                            directory by default)";
+                        ui.label(tr("Streaming engine:", lang)).on_hover_text(hint);
+                        ui.checkbox(
+                            &mut config.engine.streaming,
+                            tr("Spill large sorts to disk", lang),
+                        );
+                        ui.end_row();
+
+                        let hint = "How Parquet files are split between threads: by column \
+                            for wide files, by row group for long files";
+                        ui.label(tr("Parquet parallelism:", lang))
+                            .on_hover_text(hint);
+                        ComboBox::from_id_salt("settings_parquet_parallelism")
+                            .selected_text(config.engine.parallel.to_string())
+                            .show_ui(ui, |ui| {
+                                for parallel in ParquetParallelism::ALL {
+                                    let text = parallel.to_string();
+                                    ui.selectable_value(
+                                        &mut config.engine.parallel,
+                                        parallel,
+                                        text,
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        let hint = "Read Parquet files in smaller chunks: slower, but with a \
+                            lower peak memory";
+                        ui.label(tr("Low memory:", lang)).on_hover_text(hint);
+                        ui.checkbox(
+                            &mut config.engine.low_memory,
+                            tr("Read in smaller chunks", lang),
+                        );
+                        ui.end_row();
+
+                        let hint = "Skip the Parquet row groups whose min/max statistics rule \
+                            out the query filters";
+                        ui.label(tr("Statistics:", lang)).on_hover_text(hint);
+                        ui.checkbox(
+                            &mut config.engine.use_statistics,
+                            tr("Prune row groups", lang),
+                        );
+                        ui.end_row();
+
+                        let hint = "Optimizations of the query plan, pushed down to the scan: \
+                            the filters, the columns selected and the LIMIT/OFFSET rows; \
+                            turn them off to compare timings";
+                        ui.label(tr("Pushdown:", lang)).on_hover_text(hint);
+                        ui.horizontal(|ui| {
+                            let engine = &mut config.engine;
+                            ui.checkbox(&mut engine.predicate_pushdown, tr("Filters", lang));
+                            ui.checkbox(&mut engine.projection_pushdown, tr("Columns", lang));
+                            ui.checkbox(&mut engine.slice_pushdown, tr("Slices", lang));
+                        });
+                        ui.end_row();
+
+                        let functions = &mut config.engine.functions;
+                        let signatures = |family: &[SqlFunction]| {
+                            family
+                                .iter()
+                                .map(|f| f.to_string())
+                                .collect::<Vec<_>>()
+                                .join("\n")
+                        };
+                        ui.label(tr("SQL functions:", lang))
+                            .on_hover_text("Functions added to the SQL queries");
+                        ui.vertical(|ui| {
+                            ui.checkbox(
+                                &mut functions.documents,
+                                tr("CPF and CNPJ validators", lang),
+                            )
+                            .on_hover_text(signatures(&[
+                                SqlFunction::IsValidCpf,
+                                SqlFunction::IsValidCnpj,
+                            ]));
+                            ui.checkbox(
+                                &mut functions.text,
+                                tr("Digits and regex extraction", lang),
+                            )
+                            .on_hover_text(signatures(&[
+                                SqlFunction::Digits,
+                                SqlFunction::RegexExtract,
+                            ]));
+                            ui.checkbox(&mut functions.periods, tr("Fiscal periods", lang))
+                                .on_hover_text(signatures(&[SqlFunction::FiscalPeriod]));
+                        });
+                        ui.end_row();
+
+                        ui.label(tr("Default CSV delimiter:", lang));
+                        ui.add(
+                            TextEdit::singleline(&mut config.default_delimiter).desired_width(30.0),
+                        );
+                        ui.end_row();
+                    });
+
+                CollapsingHeader::new(tr("Keyboard shortcuts", lang)).show(ui, |ui| {
+                    Grid::new("settings_shortcuts_grid")
+                        .num_columns(3)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for action in Action::ALL {
+                                ui.label(action.label(lang));
+
+                                let text = match self.rebinding == Some(action) {
+                                    true => tr("Press a key…", lang).to_string(),
+                                    false => match config.keymap.shortcut(action) {
+                                        Some(shortcut) => ctx.format_shortcut(&shortcut),
+                                        None => "—".to_string(),
+                                    },
+                                };
+                                let hint = "Click, then press the new shortcut (Escape to cancel)";
+                                if ui.button(text).on_hover_text(hint).clicked() {
+                                    self.rebinding = Some(action);
+                                }
+
+                                if ui.button(tr("Clear", lang)).clicked() {
+                                    config.keymap.bind(action, None);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    let valid = config.default_delimiter.len() == 1;
+                    if ui
+                        .add_enabled(valid, egui::Button::new(tr("Apply", lang)))
+                        .clicked()
+                    {
+                        self.applied = Some(config.clone());
+                    }
+                    if ui.button(tr("Restore defaults", lang)).clicked() {
+                        *config = AppConfig::default();
+                    }
+                    if !valid {
+                        ui.colored_label(
+                            ui.visuals().error_fg_color,
+                            tr("The CSV delimiter must be a single character.", lang),
+                        );
+                    }
+                });
+            });
+
+        open
+    }
+
+    fn take_config(&mut self) -> Option<AppConfig> {
+        self.applied.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_float() {
+        let value = -1234567.891;
+        assert_eq!(NumberLocale::Plain.format_float(value, 2), "-1234567.89");
+        assert_eq!(
+            NumberLocale::English.format_float(value, 2),
+            "-1,234,567.89"
+        );
+        assert_eq!(
+            NumberLocale::Portuguese.format_float(value, 2),
+            "-1.234.567,89"
+        );
+        assert_eq!(NumberLocale::Portuguese.format_float(999.5, 0), "1.000");
+        assert_eq!(NumberLocale::English.format_float(12.0, 3), "12.000");
+        assert_eq!(NumberLocale::English.format_float(f64::NAN, 2), "NaN");
+        assert_eq!(NumberLocale::Plain.format_float(f64::NEG_INFINITY, 2), "-∞");
+        assert_eq!(
+            NumberLocale::Portuguese.format_scientific(1.5e20, 2),
+            "1,50e20"
+        );
+
+        let options = crate::TableViewOptions::default();
+        assert_eq!(options.format_float(2.5e18, 2), "2.50e18");
+        assert_eq!(options.format_float(-3.0e-7, 1), "-3.0e-7");
+        assert_eq!(options.format_float(0.0, 2), "0.00");
+        assert_eq!(options.format_float(1234.5, 1), "1234.5");
+        assert_eq!(options.format_float(f64::INFINITY, 2), "∞");
+    }
+
+    #[test]
+    fn test_app_config_serde() -> Result<(), serde_json::Error> {
+        let config = AppConfig {
+            font_size: 20.0,
+            default_delimiter: "|".to_string(),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&config)?;
+        assert_eq!(serde_json::from_str::<AppConfig>(&json)?, config);
+
+        // Missing fields (e.g. from an older version) take their default values.
+        let partial: AppConfig = serde_json::from_str(r#"{"font_size": 12.0}"#)?;
+        assert_eq!(partial.font_size, 12.0);
+        assert_eq!(partial.table, TableViewOptions::default());
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_runtime() {
+        let config = AppConfig {
+            runtime_threads: 2,
+            runtime_blocking_threads: 4,
+            ..Default::default()
+        };
+        let runtime = config.build_runtime(None);
+        assert_eq!(runtime.metrics().num_workers(), 2);
+        assert_eq!(runtime.block_on(async { 1 + 1 }), 2);
+
+        // The command line option takes precedence over the setting.
+        let runtime = config.build_runtime(Some(3));
+        assert_eq!(runtime.metrics().num_workers(), 3);
+    }
+}
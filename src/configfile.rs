@@ -0,0 +1,229 @@
+use crate::{AppConfig, Language, NumberLocale};
+
+use egui::ThemePreference;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+use toml_edit::{DocumentMut, Item, Table};
+
+/// Interval between two checks of the config file for changes.
+pub const CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The config file: `~/.config/parqbench/config.toml` on Linux, and its equivalent elsewhere.
+pub fn config_file_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("parqbench").join("config.toml"))
+}
+
+/// Defaults read from a TOML file, applied over the saved settings at startup
+/// and again whenever the file changes on disk.
+///
+/// ```toml
+/// delimiter = ";"
+/// theme = "dark"        # dark, light or system
+/// locale = "pt-BR"      # language of the interface: en, pt or es
+/// row_limit = 10000
+///
+/// [format]
+/// decimals = 2
+/// number_format = "portuguese"  # plain, english or portuguese
+/// scientific_above = 15
+/// scientific_below = -5
+/// ```
+#[derive(Debug, Default)]
+pub struct ConfigFile {
+    path: Option<PathBuf>,
+    /// Modification time of the file read last; `None` while there is no file.
+    modified: Option<SystemTime>,
+    /// When the file was last checked.
+    checked: Option<Instant>,
+    /// Whether the file existed when last checked.
+    exists: bool,
+}
+
+impl ConfigFile {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        ConfigFile {
+            path,
+            ..Default::default()
+        }
+    }
+
+    /// Whether the file existed when last checked, to be checked for changes.
+    pub fn exists(&self) -> bool {
+        self.exists
+    }
+
+    /// Applies the file, if any, to the configuration.
+    pub fn load(&mut self, config: &mut AppConfig) -> Result<(), String> {
+        self.checked = Some(Instant::now());
+        let Some(path) = self.path.as_ref() else {
+            return Ok(());
+        };
+        self.modified = modified(path);
+        self.exists = self.modified.is_some();
+        if self.modified.is_none() {
+            return Ok(());
+        }
+
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Error reading {}: {}", path.display(), e))?;
+        apply_toml(&text, config).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Reloads the file into the configuration if it changed on disk since it was read;
+    /// returns the outcome of a reload, if any.
+    pub fn check(&mut self, config: &mut AppConfig) -> Option<Result<(), String>> {
+        if self
+            .checked
+            .is_some_and(|checked| checked.elapsed() < CONFIG_POLL_INTERVAL)
+        {
+            return None;
+        }
+        self.checked = Some(Instant::now());
+
+        let modified = self.path.as_deref().and_then(modified);
+        self.exists = modified.is_some();
+        if modified.is_none() || modified == self.modified {
+            return None;
+        }
+        Some(self.load(config))
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Applies the keys of a TOML document to the configuration; unknown keys are ignored
+/// with a warning, invalid values fail without changing anything.
+pub fn apply_toml(text: &str, config: &mut AppConfig) -> Result<(), String> {
+    let document: DocumentMut = text.parse().map_err(|e| format!("Invalid TOML: {}", e))?;
+    let mut updated = config.clone();
+
+    apply_table(document.as_table(), "", &mut updated)?;
+    *config = updated;
+    Ok(())
+}
+
+fn apply_table(table: &Table, prefix: &str, config: &mut AppConfig) -> Result<(), String> {
+    for (key, item) in table.iter() {
+        let key = format!("{prefix}{key}");
+        match (key.as_str(), item) {
+            ("format", Item::Table(format)) => apply_table(format, "format.", config)?,
+            ("delimiter", item) => config.default_delimiter = string(&key, item)?.to_string(),
+            ("theme", item) => {
+                config.theme = match string(&key, item)? {
+                    "dark" => ThemePreference::Dark,
+                    "light" => ThemePreference::Light,
+                    "system" => ThemePreference::System,
+                    other => return Err(invalid(&key, other)),
+                }
+            }
+            ("locale", item) => {
+                let locale = string(&key, item)?;
+                config.language = match locale.split(['-', '_']).next() {
+                    Some("en") => Language::English,
+                    Some("pt") => Language::Portuguese,
+                    Some("es") => Language::Spanish,
+                    _ => return Err(invalid(&key, locale)),
+                }
+            }
+            ("row_limit", item) => {
+                config.table.row_limit = integer(&key, item, 0..=i64::MAX)? as usize
+            }
+            ("format.decimals", item) => {
+                config.table.decimals = integer(&key, item, 0..=10)? as usize
+            }
+            ("format.number_format", item) => {
+                config.table.locale = match string(&key, item)? {
+                    "plain" => NumberLocale::Plain,
+                    "english" => NumberLocale::English,
+                    "portuguese" => NumberLocale::Portuguese,
+                    other => return Err(invalid(&key, other)),
+                }
+            }
+            ("format.scientific_above", item) => {
+                config.table.scientific_above = integer(&key, item, 1..=308)? as i32
+            }
+            ("format.scientific_below", item) => {
+                config.table.scientific_below = integer(&key, item, -308..=0)? as i32
+            }
+            _ => tracing::warn!("Unknown key in the config file: {}", key),
+        }
+    }
+    Ok(())
+}
+
+fn string<'a>(key: &str, item: &'a Item) -> Result<&'a str, String> {
+    item.as_str()
+        .ok_or_else(|| format!("{key} must be a string"))
+}
+
+fn integer(key: &str, item: &Item, range: std::ops::RangeInclusive<i64>) -> Result<i64, String> {
+    item.as_integer()
+        .filter(|value| range.contains(value))
+        .ok_or_else(|| format!("{key} must be an integer in {range:?}"))
+}
+
+fn invalid(key: &str, value: &str) -> String {
+    format!("Invalid {key}: {value:?}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_file() -> Result<(), String> {
+        let mut config = AppConfig::default();
+        let text = r#"
+            delimiter = "|"
+            theme = "light"
+            locale = "pt-BR"
+            row_limit = 5000
+
+            [format]
+            decimals = 3
+            number_format = "english"
+            unknown = "ignored"
+        "#;
+        apply_toml(text, &mut config)?;
+        assert_eq!(config.default_delimiter, "|");
+        assert_eq!(config.theme, ThemePreference::Light);
+        assert_eq!(config.language, Language::Portuguese);
+        assert_eq!((config.table.row_limit, config.table.decimals), (5000, 3));
+        assert_eq!(config.table.locale, NumberLocale::English);
+
+        // An invalid value leaves the configuration unchanged.
+        let before = config.clone();
+        assert!(apply_toml("delimiter = \";\"\ntheme = \"blue\"", &mut config).is_err());
+        assert!(apply_toml("row_limit = -1", &mut config).is_err());
+        assert_eq!(config, before);
+
+        // The file is read again once changed on disk.
+        let path = std::env::temp_dir().join("polars_view_test_config.toml");
+        fs::write(&path, "row_limit = 10").map_err(|e| e.to_string())?;
+        let mut file = ConfigFile::new(Some(path.clone()));
+        file.load(&mut config)?;
+        assert_eq!(config.table.row_limit, 10);
+        assert!(file.exists());
+        file.checked = None;
+        assert!(file.check(&mut config).is_none());
+
+        fs::write(&path, "row_limit = 20").map_err(|e| e.to_string())?;
+        file.modified = Some(SystemTime::UNIX_EPOCH);
+        file.checked = None;
+        assert_eq!(file.check(&mut config), Some(Ok(())));
+        assert_eq!(config.table.row_limit, 20);
+
+        // A removed file is noticed at the next check.
+        let _ = fs::remove_file(&path);
+        assert!(file.exists());
+        file.checked = None;
+        assert!(file.check(&mut config).is_none());
+        assert!(!file.exists());
+        Ok(())
+    }
+}
@@ -0,0 +1,567 @@
+use crate::{DataFilters, NULL_VALUES, Popover, render_dataframe_grid};
+
+use egui::{ComboBox, Context, DragValue, Grid, ScrollArea, TextEdit, Window};
+use polars::prelude::*;
+use std::{fmt, sync::Arc};
+
+/// Number of rows shown in the preview of a CSV import.
+pub const CSV_PREVIEW_ROWS: u32 = 20;
+
+/// Delimiters tried, in order, when inferring the schema of a CSV file.
+pub const CSV_DELIMITERS: [u8; 4] = [b',', b';', b'|', b'\t'];
+
+/// Type chosen by the user for a CSV column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsvColumnType {
+    /// Keep the type inferred by Polars.
+    Inferred,
+    String,
+    Int64,
+    Float64,
+    Boolean,
+    Date,
+    Datetime,
+}
+
+impl CsvColumnType {
+    pub const ALL: [CsvColumnType; 7] = [
+        CsvColumnType::Inferred,
+        CsvColumnType::String,
+        CsvColumnType::Int64,
+        CsvColumnType::Float64,
+        CsvColumnType::Boolean,
+        CsvColumnType::Date,
+        CsvColumnType::Datetime,
+    ];
+
+    /// The Polars type, or `None` to keep the inferred type.
+    pub fn data_type(&self) -> Option<DataType> {
+        match self {
+            CsvColumnType::Inferred => None,
+            CsvColumnType::String => Some(DataType::String),
+            CsvColumnType::Int64 => Some(DataType::Int64),
+            CsvColumnType::Float64 => Some(DataType::Float64),
+            CsvColumnType::Boolean => Some(DataType::Boolean),
+            CsvColumnType::Date => Some(DataType::Date),
+            CsvColumnType::Datetime => Some(DataType::Datetime(TimeUnit::Microseconds, None)),
+        }
+    }
+
+    /// Whether the type is parsed with a date format.
+    pub fn is_temporal(&self) -> bool {
+        matches!(self, CsvColumnType::Date | CsvColumnType::Datetime)
+    }
+}
+
+impl fmt::Display for CsvColumnType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.data_type() {
+            Some(dtype) => write!(f, "{dtype}"),
+            None => write!(f, "inferred"),
+        }
+    }
+}
+
+/// Type override of a CSV column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvColumn {
+    pub name: String,
+    /// Type inferred by Polars.
+    pub inferred: DataType,
+    /// Type chosen by the user.
+    pub dtype: CsvColumnType,
+    /// Format of Date and Datetime columns (e.g. `%d/%m/%Y`); empty to let Polars guess.
+    pub date_format: String,
+}
+
+impl CsvColumn {
+    /// A column parsed with a date format, before its type is inferred;
+    /// formats with a time (`%H`, `%T`) give datetimes.
+    pub fn with_date_format(name: &str, format: &str) -> Self {
+        let dtype = match format.contains("%H") || format.contains("%T") {
+            true => CsvColumnType::Datetime,
+            false => CsvColumnType::Date,
+        };
+        CsvColumn {
+            name: name.to_string(),
+            inferred: DataType::Unknown(Default::default()),
+            dtype,
+            date_format: format.to_string(),
+        }
+    }
+}
+
+/// Options used to read a CSV file, as reviewed by the user before loading.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvImportOptions {
+    pub delimiter: u8,
+    /// Values read as null.
+    pub null_values: Vec<String>,
+    /// Whether the first row holds the column names.
+    pub has_header: bool,
+    /// Lines skipped at the start of the file, before the header.
+    pub skip_rows: usize,
+    /// Quote character; `None` reads quotes as ordinary characters.
+    pub quote: Option<u8>,
+    /// Rows read to infer the column types; `None` reads the whole file.
+    pub infer_rows: Option<usize>,
+    /// Parse the columns whose values look like dates.
+    pub try_parse_dates: bool,
+    /// Columns in file order, with their type overrides.
+    pub columns: Vec<CsvColumn>,
+}
+
+impl Default for CsvImportOptions {
+    fn default() -> Self {
+        CsvImportOptions {
+            delimiter: b';',
+            null_values: NULL_VALUES.iter().map(|s| s.to_string()).collect(),
+            has_header: true,
+            skip_rows: 0,
+            quote: Some(b'"'),
+            infer_rows: Some(200),
+            try_parse_dates: true,
+            columns: Vec::new(),
+        }
+    }
+}
+
+impl CsvImportOptions {
+    /// Infers the columns of a CSV file with the given delimiter and null values.
+    pub fn infer_with(
+        filename: &str,
+        delimiter: u8,
+        null_values: Vec<String>,
+    ) -> Result<Self, String> {
+        CsvImportOptions {
+            delimiter,
+            null_values,
+            ..Default::default()
+        }
+        .infer_columns(filename)
+    }
+
+    /// Infers the columns of a CSV file with these options; type overrides are reset.
+    pub fn infer_columns(mut self, filename: &str) -> Result<Self, String> {
+        let delimiter = self.delimiter;
+        let schema = self
+            .reader(filename, None)
+            .finish()
+            .and_then(|mut lazyframe| lazyframe.collect_schema())
+            .map_err(|e| {
+                format!(
+                    "Error reading CSV with delimiter '{}': {}",
+                    delimiter as char, e
+                )
+            })?;
+
+        self.columns = schema
+            .iter()
+            .map(|(name, dtype)| CsvColumn {
+                name: name.to_string(),
+                inferred: dtype.clone(),
+                dtype: CsvColumnType::Inferred,
+                date_format: String::new(),
+            })
+            .collect();
+
+        Ok(self)
+    }
+
+    /// Infers the columns of a CSV file, trying the [`CSV_DELIMITERS`] in turn.
+    pub fn infer(filename: &str) -> Result<Self, String> {
+        Self::default().infer_delimiter(filename)
+    }
+
+    /// Infers the columns with these options, trying the [`CSV_DELIMITERS`] in turn.
+    pub fn infer_delimiter(&self, filename: &str) -> Result<Self, String> {
+        CSV_DELIMITERS
+            .into_iter()
+            .filter_map(|delimiter| {
+                let options = CsvImportOptions {
+                    delimiter,
+                    ..self.clone()
+                };
+                options.infer_columns(filename).ok()
+            })
+            .find(|options| options.columns.len() > 1) // A single column means a wrong delimiter.
+            .ok_or_else(|| "Failed to infer the CSV schema with common delimiters.".to_string())
+    }
+
+    /// Configures the CSV reader, overwriting the types given in `dtypes`.
+    fn reader(&self, filename: &str, dtypes: Option<SchemaRef>) -> LazyCsvReader {
+        let null_values: Vec<PlSmallStr> =
+            self.null_values.iter().map(|s| s.as_str().into()).collect();
+
+        LazyCsvReader::new(filename)
+            .with_encoding(CsvEncoding::LossyUtf8)
+            .with_has_header(self.has_header)
+            .with_skip_rows(self.skip_rows)
+            .with_quote_char(self.quote)
+            .with_try_parse_dates(self.try_parse_dates)
+            .with_separator(self.delimiter)
+            .with_infer_schema_length(self.infer_rows)
+            .with_ignore_errors(true)
+            .with_missing_is_null(true)
+            .with_null_values(Some(NullValues::AllColumns(null_values)))
+            .with_dtype_overwrite(dtypes)
+    }
+
+    /// Reads the CSV file with the type overrides applied.
+    ///
+    /// Dates with a format are read as strings, then parsed; values that do not match
+    /// the format become null.
+    pub fn lazy_frame(&self, filename: &str) -> PolarsResult<LazyFrame> {
+        let mut dtypes = Schema::default();
+        let mut parse_dates = Vec::new();
+
+        for column in &self.columns {
+            let Some(dtype) = column.dtype.data_type() else {
+                continue;
+            };
+
+            if column.dtype.is_temporal() && !column.date_format.trim().is_empty() {
+                dtypes.insert(column.name.as_str().into(), DataType::String);
+
+                let options = StrptimeOptions {
+                    format: Some(column.date_format.trim().into()),
+                    strict: false,
+                    ..Default::default()
+                };
+                parse_dates.push(col(column.name.as_str()).str().strptime(
+                    dtype,
+                    options,
+                    lit("raise"),
+                ));
+            } else {
+                dtypes.insert(column.name.as_str().into(), dtype);
+            }
+        }
+
+        let dtypes = (!dtypes.is_empty()).then(|| Arc::new(dtypes));
+        let lazyframe = self.reader(filename, dtypes).finish()?;
+
+        Ok(match parse_dates.is_empty() {
+            true => lazyframe,
+            false => lazyframe.with_columns(parse_dates),
+        })
+    }
+
+    /// Reads the first `rows` rows with the type overrides applied.
+    pub fn preview(&self, filename: &str, rows: u32) -> PolarsResult<DataFrame> {
+        self.lazy_frame(filename)?.limit(rows).collect()
+    }
+}
+
+/// Popover previewing a CSV file, where the user reviews the inferred schema before loading.
+pub struct CsvImport {
+    filename: String,
+    /// Delimiter being edited.
+    delimiter: String,
+    /// Null values being edited, separated by commas.
+    null_values: String,
+    /// Quote character being edited; empty to disable quoting.
+    quote: String,
+    /// Header, skipped rows, inference and date settings being edited.
+    settings: CsvImportOptions,
+    options: Result<CsvImportOptions, String>,
+    preview: Option<PolarsResult<DataFrame>>,
+    requested_filters: Option<DataFilters>,
+}
+
+impl CsvImport {
+    /// Infers the schema of the file and reads the preview.
+    pub fn new(filename: impl ToString) -> Self {
+        let filename = filename.to_string();
+        let options = CsvImportOptions::infer(&filename);
+
+        let (delimiter, null_values) = match &options {
+            Ok(options) => (
+                (options.delimiter as char).to_string(),
+                options.null_values.join(","),
+            ),
+            Err(_) => (";".to_string(), NULL_VALUES.join(",")),
+        };
+
+        let mut import = CsvImport {
+            filename,
+            delimiter,
+            null_values,
+            quote: "\"".to_string(),
+            settings: CsvImportOptions::default(),
+            options,
+            preview: None,
+            requested_filters: None,
+        };
+        import.refresh_preview();
+        import
+    }
+
+    /// Infers the schema again with the edited delimiter and null values.
+    fn reinfer(&mut self) {
+        let delimiter = match self.delimiter.as_bytes() {
+            [delimiter] => *delimiter,
+            _ => {
+                let msg = "The CSV delimiter must be a single character.";
+                self.options = Err(msg.to_string());
+                return;
+            }
+        };
+        let quote = match self.quote.as_bytes() {
+            [] => None,
+            [quote] => Some(*quote),
+            _ => {
+                let msg = "The quote must be a single character, or empty for none.";
+                self.options = Err(msg.to_string());
+                return;
+            }
+        };
+
+        let options = CsvImportOptions {
+            delimiter,
+            null_values: self.null_values.split(',').map(String::from).collect(),
+            quote,
+            ..self.settings.clone()
+        };
+        self.options = options.infer_columns(&self.filename);
+        self.refresh_preview();
+    }
+
+    fn refresh_preview(&mut self) {
+        self.preview = self
+            .options
+            .as_ref()
+            .ok()
+            .map(|options| options.preview(&self.filename, CSV_PREVIEW_ROWS));
+    }
+}
+
+impl Popover for CsvImport {
+    /// Shows the import settings, the column types and the preview.
+    fn show(&mut self, ctx: &Context) -> bool {
+        let mut open = true;
+        let mut reinfer = false;
+        let mut refresh = false;
+
+        Window::new("Import CSV")
+            .collapsible(false)
+            .open(&mut open)
+            .default_size([800.0, 600.0])
+            .show(ctx, |ui| {
+                ui.label(&self.filename);
+
+                Grid::new("csv_import_settings")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Delimiter:");
+                        ui.add(TextEdit::singleline(&mut self.delimiter).desired_width(30.0));
+                        ui.end_row();
+
+                        ui.label("Null values:")
+                            .on_hover_text("Values read as null, separated by commas");
+                        ui.text_edit_singleline(&mut self.null_values);
+                        ui.end_row();
+
+                        ui.label("Quote:")
+                            .on_hover_text("Quote character; empty to read quotes as text");
+                        ui.add(TextEdit::singleline(&mut self.quote).desired_width(30.0));
+                        ui.end_row();
+
+                        ui.label("Header:");
+                        ui.checkbox(&mut self.settings.has_header, "First row holds the names");
+                        ui.end_row();
+
+                        ui.label("Skip rows:")
+                            .on_hover_text("Lines skipped at the start of the file");
+                        ui.add(DragValue::new(&mut self.settings.skip_rows).range(0..=10_000));
+                        ui.end_row();
+
+                        let mut whole_file = self.settings.infer_rows.is_none();
+                        let mut infer_rows = self.settings.infer_rows.unwrap_or(200);
+                        ui.label("Infer types from:");
+                        ui.horizontal(|ui| {
+                            ui.add_enabled(
+                                !whole_file,
+                                DragValue::new(&mut infer_rows)
+                                    .range(1..=1_000_000)
+                                    .suffix(" rows"),
+                            );
+                            ui.checkbox(&mut whole_file, "whole file");
+                        });
+                        self.settings.infer_rows = (!whole_file).then_some(infer_rows);
+                        ui.end_row();
+
+                        ui.label("Dates:");
+                        ui.checkbox(
+                            &mut self.settings.try_parse_dates,
+                            "Parse the values that look like dates",
+                        );
+                        ui.end_row();
+                    });
+
+                if ui.button("Infer schema").clicked() {
+                    reinfer = true;
+                }
+
+                let options = match &mut self.options {
+                    Ok(options) => options,
+                    Err(error) => {
+                        ui.colored_label(ui.visuals().error_fg_color, error.as_str());
+                        return;
+                    }
+                };
+
+                ui.separator();
+
+                ScrollArea::vertical()
+                    .id_salt("csv_import_columns")
+                    .max_height(250.0)
+                    .show(ui, |ui| {
+                        Grid::new("csv_import_columns_grid")
+                            .num_columns(4)
+                            .spacing([10.0, 4.0])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.strong("Column");
+                                ui.strong("Inferred");
+                                ui.strong("Type");
+                                ui.strong("Date format");
+                                ui.end_row();
+
+                                for (idx, column) in options.columns.iter_mut().enumerate() {
+                                    ui.label(&column.name);
+                                    ui.label(column.inferred.to_string());
+
+                                    let before = column.dtype;
+                                    ComboBox::from_id_salt(("csv_import_type", idx))
+                                        .selected_text(column.dtype.to_string())
+                                        .show_ui(ui, |ui| {
+                                            for dtype in CsvColumnType::ALL {
+                                                ui.selectable_value(
+                                                    &mut column.dtype,
+                                                    dtype,
+                                                    dtype.to_string(),
+                                                );
+                                            }
+                                        });
+                                    refresh |= column.dtype != before;
+
+                                    let response = ui.add_enabled(
+                                        column.dtype.is_temporal(),
+                                        TextEdit::singleline(&mut column.date_format)
+                                            .hint_text("%d/%m/%Y")
+                                            .desired_width(100.0),
+                                    );
+                                    refresh |= response.lost_focus();
+                                    ui.end_row();
+                                }
+                            });
+                    });
+
+                ui.horizontal(|ui| {
+                    if ui.button("Refresh preview").clicked() {
+                        refresh = true;
+                    }
+                    if ui.button("Load").clicked() {
+                        self.requested_filters = Some(DataFilters {
+                            csv_delimiter: (options.delimiter as char).to_string(),
+                            csv_options: Some(options.clone()),
+                            ..DataFilters::new(&self.filename)
+                        });
+                    }
+                });
+
+                ui.separator();
+
+                match &self.preview {
+                    Some(Ok(df)) => render_dataframe_grid(ui, "csv_import_preview", df),
+                    Some(Err(error)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, error.to_string());
+                    }
+                    None => {}
+                }
+            });
+
+        if reinfer {
+            self.reinfer();
+        } else if refresh {
+            self.refresh_preview();
+        }
+
+        // The window closes once the data is requested.
+        open && self.requested_filters.is_none()
+    }
+
+    fn take_filters(&mut self) -> Option<DataFilters> {
+        self.requested_filters.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_import_overrides() -> Result<(), String> {
+        let path = std::env::temp_dir().join("polars_view_csv_import.csv");
+        let csv = "id|code|day|amount\n1|007|31/01/2024|1.5\n2|042|NA|2.5\n3|100|15/02/2024|\n";
+        std::fs::write(&path, csv).map_err(|e| e.to_string())?;
+        let filename = path.to_string_lossy().to_string();
+
+        let mut options = CsvImportOptions::infer(&filename)?;
+        assert_eq!(options.delimiter, b'|');
+        assert_eq!(options.columns[1].inferred, DataType::Int64);
+
+        // Keep the leading zeros, parse the dates and read "NA" as null.
+        options.columns[1].dtype = CsvColumnType::String;
+        options.columns[2].dtype = CsvColumnType::Date;
+        options.columns[2].date_format = "%d/%m/%Y".to_string();
+        options.null_values.push("NA".to_string());
+
+        let df = options
+            .preview(&filename, CSV_PREVIEW_ROWS)
+            .map_err(|e| e.to_string())?;
+        let _ = std::fs::remove_file(path);
+
+        assert_eq!(df.height(), 3);
+        assert_eq!(
+            df.column("code").unwrap().get(0).unwrap(),
+            AnyValue::String("007")
+        );
+        assert_eq!(df.column("day").unwrap().dtype(), &DataType::Date);
+        assert_eq!(df.column("day").unwrap().null_count(), 1);
+        assert_eq!(df.column("amount").unwrap().null_count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_read_options() -> Result<(), String> {
+        let path = std::env::temp_dir().join("polars_view_csv_read_options.csv");
+        let csv = "exported on 2024-03-01\n'a;b';01.02.2024;-\n'c';15.02.2024;7\n";
+        std::fs::write(&path, csv).map_err(|e| e.to_string())?;
+        let filename = path.to_string_lossy().to_string();
+
+        let options = CsvImportOptions {
+            has_header: false,
+            skip_rows: 1,
+            quote: Some(b'\''),
+            infer_rows: None,
+            null_values: vec!["-".to_string()],
+            columns: vec![CsvColumn::with_date_format("column_2", "%d.%m.%Y")],
+            ..Default::default()
+        };
+        let df = options
+            .preview(&filename, CSV_PREVIEW_ROWS)
+            .map_err(|e| e.to_string())?;
+        let _ = std::fs::remove_file(path);
+
+        assert_eq!(df.shape(), (2, 3));
+        assert_eq!(
+            df.column("column_1").unwrap().get(0).unwrap(),
+            AnyValue::String("a;b")
+        );
+        assert_eq!(df.column("column_2").unwrap().dtype(), &DataType::Date);
+        assert_eq!(df.column("column_3").unwrap().null_count(), 1);
+        Ok(())
+    }
+}
@@ -1,18 +1,30 @@
-use crate::{Arguments, SQL_COMMANDS, get_extension};
+use crate::{
+    Arguments, ColumnTransform, CsvImportOptions, EngineOptions, LoadProgress, SQL_COMMANDS,
+    SqlError, TempView, apply_transforms, duplicated_mask, execute_statements, get_extension,
+    load_with_plugin, referenced_columns, sql_context,
+};
 use egui::{
-    Align, CollapsingHeader, Color32, Frame, Grid, Hyperlink, Layout, Stroke, TextEdit, Ui, Vec2,
+    Align, CollapsingHeader, Color32, FontSelection, Frame, Grid, Hyperlink, Id, Layout, RichText,
+    Stroke, TextEdit, TextFormat, Ui, Vec2, text::LayoutJob,
+};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fs::File,
+    future::Future,
+    str::FromStr,
+    sync::{Arc, Mutex},
 };
-use polars::{prelude::*, sql::SQLContext};
-use std::{fs::File, future::Future, sync::Arc};
 
 pub type DataResult = Result<DataFrameContainer, String>;
 pub type DataFuture = Box<dyn Future<Output = DataResult> + Unpin + Send + 'static>;
 
 // Set values that will be interpreted as missing/null.
-static NULL_VALUES: &[&str] = &["", " ", "<N/D>", "*DIVERSOS*"];
+pub(crate) static NULL_VALUES: &[&str] = &["", " ", "<N/D>", "*DIVERSOS*"];
 
 /// Represents the sorting state for a column.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum SortState {
     /// The column is not sorted.
     NotSorted(String),
@@ -22,6 +34,27 @@ pub enum SortState {
     Descending(String),
 }
 
+/// Parses `column`, `column:asc` or `column:desc`, as given to `--sort`.
+impl FromStr for SortState {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (column, order) = match value.rsplit_once(':') {
+            Some((column, order)) if ["asc", "desc"].contains(&order.to_lowercase().as_str()) => {
+                (column, order.to_lowercase())
+            }
+            _ => (value, "asc".to_string()),
+        };
+        if column.trim().is_empty() {
+            return Err(format!("Missing column name in sort '{}'", value));
+        }
+        Ok(match order.as_str() {
+            "desc" => SortState::Descending(column.to_string()),
+            _ => SortState::Ascending(column.to_string()),
+        })
+    }
+}
+
 /// Holds filters to be applied to the data.
 #[derive(Clone, Debug, Default)]
 pub struct DataFilters {
@@ -29,12 +62,25 @@ pub struct DataFilters {
     pub filename: Option<String>,
     /// Table name for registering with Polars SQL Context.
     pub table_name: String,
+    /// Further files registered with the SQL Context, each named after its file stem.
+    pub tables: Vec<String>,
     /// CSV delimiter.
     pub csv_delimiter: String,
     /// Optional SQL query to apply to the data.
     pub query: Option<String>,
     /// Optional column sorting state.
     pub sort: Option<SortState>,
+    /// Further sort columns breaking the ties of `sort`, given on the command line.
+    pub then_by: Vec<SortState>,
+    /// CSV options reviewed by the user on import; they take precedence over `csv_delimiter`.
+    pub csv_options: Option<CsvImportOptions>,
+    /// Pipeline applied to the query result: renames, casts, replacements,
+    /// filters, computed columns and sorts, in order.
+    pub transforms: Vec<ColumnTransform>,
+    /// Options of the Polars engine (streaming, Parquet parallelism, optimizations).
+    pub engine: EngineOptions,
+    /// Results registered under a name with the SQL Context, for the later queries to build on.
+    pub views: Vec<TempView>,
 }
 
 impl DataFilters {
@@ -54,9 +100,15 @@ impl DataFilters {
         DataFilters {
             filename: args.filename.clone(),
             table_name: args.table_name.clone(),
+            tables: args.files.clone(),
             csv_delimiter: args.delimiter.clone(),
             query: args.query.clone(),
-            sort: None,
+            sort: args.sort.first().cloned(),
+            then_by: args.sort.iter().skip(1).cloned().collect(),
+            csv_options: args.csv_options(),
+            transforms: Vec::new(),
+            engine: EngineOptions::default(),
+            views: Vec::new(),
         }
     }
 
@@ -66,15 +118,58 @@ impl DataFilters {
             filename: args.filename.clone(),
             query: args.query.clone(),
             table_name: args.table_name.clone(),
+            tables: args.files.clone(),
             csv_delimiter: args.delimiter.clone(),
-            sort: None,
+            sort: args.sort.first().cloned(),
+            then_by: args.sort.iter().skip(1).cloned().collect(),
+            csv_options: args.csv_options(),
+            transforms: Vec::new(),
+            engine: EngineOptions::default(),
+            views: Vec::new(),
         };
 
         dbg!(data_filters);
     }
 
+    /// SQL names of the further files, in order: their [`sql_table_name`], with a suffix
+    /// (`_2`, `_3`...) if the main table or an earlier file already has it.
+    pub fn table_names(&self) -> Vec<String> {
+        let mut taken = vec![self.table_name.to_lowercase()];
+        self.tables
+            .iter()
+            .map(|path| {
+                let stem = sql_table_name(path);
+                let name = (1..)
+                    .map(|n| match n {
+                        1 => stem.clone(),
+                        n => format!("{stem}_{n}"),
+                    })
+                    .find(|name| !taken.contains(&name.to_lowercase()))
+                    .unwrap_or_default();
+                taken.push(name.to_lowercase());
+                name
+            })
+            .collect()
+    }
+
+    /// Whether the filename, table name, delimiter and query needed to run a query are set.
+    pub fn is_complete(&self) -> bool {
+        let filled = |value: &str| !value.trim().is_empty();
+        self.filename.as_deref().is_some_and(filled)
+            && filled(&self.table_name)
+            && filled(&self.csv_delimiter)
+            && self.query.as_deref().is_some_and(filled)
+    }
+
+    /// Id of the SQL editor of the query pane, focused from the keyboard.
+    pub fn query_editor_id() -> Id {
+        Id::new("sql_query_editor")
+    }
+
     /// Renders the query pane UI for configuring data filters.
-    pub fn render_filter(&mut self, ui: &mut Ui) -> Option<DataFilters> {
+    ///
+    /// The token where the last query failed, if any, is underlined until the query is edited.
+    pub fn render_filter(&mut self, ui: &mut Ui, error: Option<&SqlError>) -> Option<DataFilters> {
         // Create mutable copies of the filter values to allow editing.
         let mut filename = self.filename.clone()?;
         let mut table_name = self.table_name.clone();
@@ -109,6 +204,15 @@ impl DataFilters {
                         .on_hover_text("Enter table name for SQL queries...");
                     ui.end_row();
 
+                    if !self.tables.is_empty() {
+                        ui.label("Other Tables:");
+                        let names: Vec<String> =
+                            self.table_names();
+                        ui.label(names.join(", "))
+                            .on_hover_text(self.tables.join("\n"));
+                        ui.end_row();
+                    }
+
                     ui.label("CSV Delimiter:");
                     let csv_delimiter_edit =
                         TextEdit::singleline(&mut csv_delimiter).desired_width(width_max);
@@ -117,15 +221,51 @@ impl DataFilters {
                     ui.end_row();
 
                     ui.label("SQL Query:");
-                    let query_edit = TextEdit::multiline(&mut query).desired_width(width_max);
-                    ui.add(query_edit)
-                        .on_hover_text("Enter SQL query to filter and transform the data...");
+                    let error = error.filter(|error| error.query == query);
+                    let mut layouter = |ui: &Ui, text: &str, wrap_width: f32| {
+                        let format = TextFormat {
+                            font_id: FontSelection::default().resolve(ui.style()),
+                            color: ui.visuals().text_color(),
+                            ..Default::default()
+                        };
+                        let underlined = TextFormat {
+                            underline: Stroke::new(2.0, ui.visuals().error_fg_color),
+                            color: ui.visuals().error_fg_color,
+                            ..format.clone()
+                        };
+                        let range = error
+                            .and_then(SqlError::range)
+                            .filter(|range| range.end <= text.len())
+                            .unwrap_or(0..0);
+
+                        let mut job = LayoutJob::default();
+                        job.append(&text[..range.start], 0.0, format.clone());
+                        job.append(&text[range.clone()], 0.0, underlined);
+                        job.append(&text[range.end..], 0.0, format);
+                        job.wrap.max_width = wrap_width;
+                        ui.fonts(|fonts| fonts.layout_job(job))
+                    };
+                    ui.vertical(|ui| {
+                        let query_edit = TextEdit::multiline(&mut query)
+                            .id(Self::query_editor_id())
+                            .desired_width(width_max)
+                            .layouter(&mut layouter);
+                        ui.add(query_edit)
+                            .on_hover_text("Enter SQL query to filter and transform the data...");
+                        if let Some(error) = error {
+                            ui.label(RichText::new(&error.message).color(ui.visuals().error_fg_color));
+                        }
+                    });
                     ui.end_row();
 
                     // Add the button to the grid.
                     ui.label(""); // Empty label to align with the label column.
                     ui.with_layout(Layout::top_down(Align::Center), |ui| {
-                        if ui.button("Apply SQL Commands").clicked() {
+                        if ui
+                            .button("Apply SQL Commands")
+                            .on_hover_text("Shift+click opens the result in a new tab")
+                            .clicked()
+                        {
                             // Only create and return DataFilters if the required fields are not empty.
                             if !filename.trim().is_empty()
                                 && !table_name.trim().is_empty()
@@ -135,9 +275,15 @@ impl DataFilters {
                                 result = Some(DataFilters {
                                     filename: Some(filename.clone()),
                                     table_name: table_name.clone(),
+                                    tables: self.tables.clone(),
                                     csv_delimiter: csv_delimiter.clone(),
                                     query: Some(query.clone()),
                                     sort: self.sort.clone(), // Preserve existing sort state
+                                    then_by: self.then_by.clone(),
+                                    csv_options: self.csv_options.clone(), // Preserve the CSV import options
+                                    transforms: self.transforms.clone(), // Keep the pipeline
+                                    engine: self.engine,
+                                    views: self.views.clone(), // Keep the views of the session
                                 });
                             } else {
                                 // Handle the case where required fields are empty.
@@ -193,11 +339,107 @@ pub struct DataFrameContainer {
     pub filters: DataFilters,
     /// String with "parquet" or "csv"
     pub table_type: String,
+    /// Sorted versions of this result, shared by the containers derived from it by sorting.
+    pub sort_cache: SortCache,
+    /// Rows were filtered out after the query, such as all but the duplicates: the result
+    /// is not the whole result of its filters.
+    pub filtered: bool,
+}
+
+/// Number of sorted versions of a result kept in its [`SortCache`].
+pub const SORT_CACHE_SIZE: usize = 4;
+
+/// A result sorted as given.
+type SortedData = (SortState, Arc<DataFrame>);
+
+/// Most recently used sorted versions of a result, keyed by their sort.
+///
+/// Toggling the sort of a column back and forth reuses the sorted data instead of sorting again.
+#[derive(Debug, Clone, Default)]
+pub struct SortCache {
+    entries: Arc<Mutex<VecDeque<SortedData>>>,
+}
+
+impl SortCache {
+    /// Key of a sort: all unsorted states keep the loaded order, whatever their column.
+    fn key(sort: &SortState) -> SortState {
+        match sort {
+            SortState::NotSorted(_) => SortState::NotSorted(String::new()),
+            sort => sort.clone(),
+        }
+    }
+
+    /// The data sorted as requested, if cached; it becomes the most recently used.
+    pub fn get(&self, sort: &SortState) -> Option<Arc<DataFrame>> {
+        let key = Self::key(sort);
+        let mut entries = self.entries.lock().ok()?;
+        let idx = entries.iter().position(|(cached, _)| *cached == key)?;
+        let entry = entries.remove(idx)?;
+        let df = Arc::clone(&entry.1);
+        entries.push_front(entry);
+        Some(df)
+    }
+
+    /// Caches the data sorted as given, evicting the least recently used entry when full.
+    pub fn insert(&self, sort: &SortState, df: Arc<DataFrame>) {
+        let key = Self::key(sort);
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.retain(|(cached, _)| *cached != key);
+            entries.push_front((key, df));
+            entries.truncate(SORT_CACHE_SIZE);
+        }
+    }
+}
+
+/// Whether the values of a column can be ordered: nested, binary and object columns cannot.
+pub fn is_sortable(dtype: &DataType) -> bool {
+    !(dtype.is_nested()
+        || dtype.is_object()
+        || matches!(dtype, DataType::Binary | DataType::BinaryOffset))
+}
+
+/// Name of the SQL table of a further file: its file stem, with other characters
+/// than letters, digits and underscores replaced by underscores; `table` without a stem.
+pub fn sql_table_name(path: &str) -> String {
+    let stem = std::path::Path::new(path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match stem.is_empty() {
+        true => "table".to_string(),
+        false => stem
+            .chars()
+            .map(|c| match c.is_alphanumeric() || c == '_' {
+                true => c,
+                false => '_',
+            })
+            .collect(),
+    }
 }
 
 impl DataFrameContainer {
     /// Loads data from a file (Parquet or CSV) using Polars.
     pub async fn load_data(filename: impl AsRef<str>) -> Result<Self, String> {
+        Self::load_data_with_engine(filename, EngineOptions::default()).await
+    }
+
+    /// Wraps a DataFrame that was not loaded from a file, such as one built by another app.
+    pub fn from_dataframe(df: DataFrame) -> Self {
+        Self {
+            filename: String::new(),
+            df: Arc::new(df),
+            filters: DataFilters::default(),
+            table_type: "dataframe".to_string(),
+            sort_cache: SortCache::default(),
+            filtered: false,
+        }
+    }
+
+    /// Loads data from a file (Parquet or CSV) with the given engine options.
+    pub async fn load_data_with_engine(
+        filename: impl AsRef<str>,
+        engine: EngineOptions,
+    ) -> Result<Self, String> {
         let filename = shellexpand::full(&filename)
             .map_err(|err| err.to_string())?
             .to_string();
@@ -206,12 +448,12 @@ impl DataFrameContainer {
 
         // Determine file type based on extension and load accordingly.
         let (df, table_type) = match get_extension(&filename).as_deref() {
-            Some("parquet") => (Self::read_parquet(&filename).await?, "parquet".to_string()),
+            Some("parquet") => (
+                Self::read_parquet(&filename, engine).await?,
+                "parquet".to_string(),
+            ),
             Some("csv") => (Self::read_csv(&filename).await?, "csv".to_string()),
-            _ => {
-                let msg = format!("Unknown file type: {:#?}", filename);
-                return Err(msg);
-            }
+            _ => load_with_plugin(&filename)?, // Formats added by plugins.
         };
 
         Ok(Self {
@@ -219,6 +461,8 @@ impl DataFrameContainer {
             df: Arc::new(df),
             filters: DataFilters::default(),
             table_type,
+            sort_cache: SortCache::default(),
+            filtered: false,
         })
     }
 
@@ -228,20 +472,114 @@ impl DataFrameContainer {
             Self::load_data_with_sql(filters).await
         } else {
             let filename = filters.filename.clone().unwrap_or_default();
-            let mut data = Self::load_data(filename).await?;
+            let mut data = Self::load_data_with_engine(filename, filters.engine).await?;
             data.filters = filters;
             Ok(data)
         }
     }
 
+    /// Loads data with the filters (and their SQL query, if any), then sorts the result.
+    pub async fn load_data_with_sort(filters: DataFilters) -> Result<Self, String> {
+        let data = Self::load_data_with_filters(filters.clone()).await?;
+        data.sort(Some(filters)).await
+    }
+
     /// Reads a Parquet file into a Polars DataFrame.
-    async fn read_parquet(filename: &str) -> Result<DataFrame, String> {
+    ///
+    /// When the load reports its progress, the row groups are read one at a time,
+    /// so that the load can be followed and cancelled between them.
+    /// A glob such as `data/*.parquet` scans all the matching files in parallel.
+    async fn read_parquet(filename: &str, engine: EngineOptions) -> Result<DataFrame, String> {
+        Self::read_parquet_columns(filename, engine, None).await
+    }
+
+    /// Reads the given columns of a Parquet file, or all of them with `None`;
+    /// the other columns are not decoded.
+    async fn read_parquet_columns(
+        filename: &str,
+        engine: EngineOptions,
+        columns: Option<Vec<String>>,
+    ) -> Result<DataFrame, String> {
+        if filename.contains(['*', '?', '[']) {
+            return LazyFrame::scan_parquet(filename, engine.scan_args())
+                .map(|lazyframe| match &columns {
+                    Some(columns) => lazyframe.select(columns.iter().map(col).collect::<Vec<_>>()),
+                    None => lazyframe,
+                })
+                .and_then(|lazyframe| engine.optimize(lazyframe).collect())
+                .map_err(|e| format!("Error scanning parquet files: {}", e));
+        }
+
         let file = File::open(filename).map_err(|e| format!("Error opening file: {}", e))?;
-        let df = ParquetReader::new(file)
-            .finish()
-            .map_err(|e| format!("Error reading parquet: {}", e))?;
+        let share = |file: &File| {
+            file.try_clone()
+                .map_err(|e| format!("Error opening file: {}", e))
+        };
+        let mut reader = ParquetReader::new(share(&file)?);
+        let metadata = reader
+            .get_metadata()
+            .map_err(|e| format!("Error reading parquet metadata: {}", e))?
+            .clone();
 
-        Ok(df)
+        // The row groups share the open file and the metadata parsed above.
+        let configure = |reader: ParquetReader<File>| {
+            reader
+                .with_columns(columns.clone())
+                .read_parallel(engine.parallel.strategy())
+                .set_low_memory(engine.low_memory)
+                .use_statistics(engine.use_statistics)
+        };
+
+        let progress = LoadProgress::current().filter(|_| metadata.row_groups.len() > 1);
+        let Some(progress) = progress else {
+            return configure(reader)
+                .finish()
+                .map_err(|e| format!("Error reading parquet: {}", e));
+        };
+
+        progress.start(metadata.row_groups.len());
+
+        let mut df: Option<DataFrame> = None;
+        let mut offset = 0;
+        for row_group in &metadata.row_groups {
+            progress.check()?;
+
+            let mut chunk_reader = ParquetReader::new(share(&file)?);
+            chunk_reader.set_metadata(Arc::clone(&metadata));
+            let rows = row_group.num_rows();
+            let chunk = configure(chunk_reader)
+                .with_slice(Some((offset, rows)))
+                .finish()
+                .map_err(|e| format!("Error reading parquet: {}", e))?;
+
+            // The chunks of the row groups are kept, not copied into one.
+            match df.as_mut() {
+                Some(df) => {
+                    df.vstack_mut(&chunk)
+                        .map_err(|e| format!("Error reading parquet: {}", e))?;
+                }
+                None => df = Some(chunk),
+            }
+
+            offset += rows;
+            progress.advance(row_group.compressed_size() as u64);
+        }
+
+        Ok(df.unwrap_or_default())
+    }
+
+    /// Names of the columns of a Parquet file, or of the first file of a glob.
+    fn parquet_columns(filename: &str) -> Result<Vec<String>, String> {
+        let schema = match filename.contains(['*', '?', '[']) {
+            true => LazyFrame::scan_parquet(filename, ScanArgsParquet::default())
+                .and_then(|mut lazyframe| lazyframe.collect_schema())
+                .map(|schema| schema.iter_names().map(|name| name.to_string()).collect()),
+            false => File::open(filename)
+                .map_err(PolarsError::from)
+                .and_then(|file| ParquetReader::new(file).schema())
+                .map(|schema| schema.iter_names().map(|name| name.to_string()).collect()),
+        };
+        schema.map_err(|e| format!("Error reading parquet schema: {}", e))
     }
 
     /// Attempts to read a CSV file with different delimiters until successful.
@@ -339,58 +677,87 @@ impl DataFrameContainer {
             .to_string();
 
         // Load the DataFrame from the file
-        let (df, table_type): (DataFrame, String) = match get_extension(&filename).as_deref() {
-            Some("parquet") => (Self::read_parquet(&filename).await?, "parquet".to_string()),
-            Some("csv") => {
-                // Convert csv_delimiter string to u8 delimiter
-                let delimiter: u8 = match csv_delimiter.len() {
-                    1 => csv_delimiter.as_bytes()[0],
-                    _ => {
-                        let msg = "Error: The CSV delimiter must be a single character.";
-                        return Err(msg.to_string());
-                    }
-                };
-
-                // Set values that will be interpreted as missing/null.
-                let null_values: Vec<PlSmallStr> = NULL_VALUES.iter().map(|&s| s.into()).collect();
-
-                // Read CSV using the specified delimiter
-                let lazyframe = LazyCsvReader::new(&filename)
-                    .with_encoding(CsvEncoding::LossyUtf8) // Handle various encodings
-                    .with_try_parse_dates(true) // use regex
-                    .with_has_header(true) // Assume the first row is a header
-                    .with_separator(delimiter) // Set the delimiter
-                    .with_infer_schema_length(Some(200)) // Limit schema inference to the first 200 rows.
-                    .with_ignore_errors(true) // Ignore parsing errors
-                    .with_missing_is_null(true) // Treat missing values as null
-                    .with_null_values(Some(NullValues::AllColumns(null_values)))
-                    .finish()
-                    .map_err(|e| {
-                        format!(
-                            "Error reading CSV with delimiter '{}': {}",
-                            delimiter as char, e
-                        )
-                    })?;
-
-                (
-                    lazyframe.collect().map_err(|e| format!("Error: {}", e))?,
-                    "csv".to_string(),
-                )
-            }
-            _ => {
-                let msg = format!("Unknown file type: {}", filename);
-                return Err(msg);
-            }
-        };
+        let extension = get_extension(&filename);
+        let (df, table_type): (DataFrame, String) =
+            match (extension.as_deref(), &filters.csv_options) {
+                (Some("parquet"), _) => {
+                    // Only the columns the query may use are decoded.
+                    let columns = Self::parquet_columns(&filename)
+                        .ok()
+                        .and_then(|columns| referenced_columns(query, &columns));
+                    (
+                        Self::read_parquet_columns(&filename, filters.engine, columns).await?,
+                        "parquet".to_string(),
+                    )
+                }
+                (Some("csv"), Some(options)) => {
+                    let df = options
+                        .lazy_frame(&filename)
+                        .and_then(|lazyframe| lazyframe.collect())
+                        .map_err(|e| format!("Error reading CSV: {}", e))?;
+                    (df, "csv".to_string())
+                }
+                (Some("csv"), None) => {
+                    // Convert csv_delimiter string to u8 delimiter
+                    let delimiter: u8 = match csv_delimiter.len() {
+                        1 => csv_delimiter.as_bytes()[0],
+                        _ => {
+                            let msg = "Error: The CSV delimiter must be a single character.";
+                            return Err(msg.to_string());
+                        }
+                    };
+
+                    // Set values that will be interpreted as missing/null.
+                    let null_values: Vec<PlSmallStr> =
+                        NULL_VALUES.iter().map(|&s| s.into()).collect();
+
+                    // Read CSV using the specified delimiter
+                    let lazyframe = LazyCsvReader::new(&filename)
+                        .with_encoding(CsvEncoding::LossyUtf8) // Handle various encodings
+                        .with_try_parse_dates(true) // use regex
+                        .with_has_header(true) // Assume the first row is a header
+                        .with_separator(delimiter) // Set the delimiter
+                        .with_infer_schema_length(Some(200)) // Limit schema inference to the first 200 rows.
+                        .with_ignore_errors(true) // Ignore parsing errors
+                        .with_missing_is_null(true) // Treat missing values as null
+                        .with_null_values(Some(NullValues::AllColumns(null_values)))
+                        .finish()
+                        .map_err(|e| {
+                            format!(
+                                "Error reading CSV with delimiter '{}': {}",
+                                delimiter as char, e
+                            )
+                        })?;
+
+                    (
+                        lazyframe.collect().map_err(|e| format!("Error: {}", e))?,
+                        "csv".to_string(),
+                    )
+                }
+                _ => load_with_plugin(&filename)?, // Formats added by plugins.
+            };
 
         // Create a SQL context and register the DataFrame
-        let mut ctx = SQLContext::new();
+        let mut ctx = sql_context();
         ctx.register(&table_name, df.lazy());
 
-        // Execute the query and collect the results
-        let sql_df: DataFrame = ctx
-            .execute(query)
-            .map_err(|e| format!("Polars SQL error: {}", e))?
+        // Register the further files, so that they can be joined.
+        for (path, name) in filters.tables.iter().zip(filters.table_names()) {
+            let data = Self::load_data_with_engine(path, filters.engine).await?;
+            ctx.register(&name, data.df.as_ref().clone().lazy());
+        }
+
+        // Register the views of the session.
+        for view in &filters.views {
+            ctx.register(&view.name, view.df.as_ref().clone().lazy());
+        }
+
+        // Execute the statements of the query and the pipeline of the last one, and collect the results
+        let lazyframe = execute_statements(&mut ctx, query, filters.engine.functions)?;
+        let lazyframe = apply_transforms(lazyframe, &filters.transforms)?;
+        let sql_df: DataFrame = filters
+            .engine
+            .optimize(lazyframe)
             .collect()
             .map_err(|e| format!("DataFrame error: {}", e))?;
 
@@ -399,10 +766,15 @@ impl DataFrameContainer {
             df: Arc::new(sql_df),
             filters,
             table_type,
+            sort_cache: SortCache::default(),
+            filtered: false,
         })
     }
 
     /// Sorts the data based on the provided filters.
+    ///
+    /// Sorted versions are cached, so that toggling between them is instant;
+    /// `NotSorted` restores the order of the loaded data.
     pub async fn sort(mut self, opt_filters: Option<DataFilters>) -> Result<Self, String> {
         // If no filters are provided, return the DataFrame as is.
         let Some(filters) = opt_filters else {
@@ -414,31 +786,99 @@ impl DataFrameContainer {
             return Ok(self);
         };
 
-        // Extract sort column and order from filters
-        let (col_name, ascending) = match sort {
-            SortState::Ascending(col_name) => (col_name, true),
-            SortState::Descending(col_name) => (col_name, false),
-            SortState::NotSorted(_col_name) => return Ok(self),
-        };
+        // Only the sorts on a single column are cached.
+        let cached = filters.then_by.is_empty();
+
+        // Remember the current order before replacing it.
+        let current = self
+            .filters
+            .sort
+            .clone()
+            .unwrap_or(SortState::NotSorted(String::new()));
+        if self.filters.then_by.is_empty() && self.sort_cache.get(&current).is_none() {
+            self.sort_cache.insert(&current, Arc::clone(&self.df));
+        }
+
+        if let Some(df) = self.sort_cache.get(sort).filter(|_| cached) {
+            self.df = df;
+            self.filters = filters;
+            return Ok(self);
+        }
+
+        // Extract the sort columns and orders from filters
+        let mut col_names = Vec::new();
+        let mut descending = Vec::new();
+        for sort in std::iter::once(sort).chain(&filters.then_by) {
+            let (col_name, ascending) = match sort {
+                SortState::Ascending(col_name) => (col_name, true),
+                SortState::Descending(col_name) => (col_name, false),
+                SortState::NotSorted(_col_name) => continue,
+            };
+            let dtype = self
+                .df
+                .column(col_name)
+                .map_err(|e| format!("Polars sort error: {}", e))?
+                .dtype();
+            if !is_sortable(dtype) {
+                return Err(format!(
+                    "Column '{}' of type {} cannot be sorted",
+                    col_name, dtype
+                ));
+            }
+            col_names.push(col_name.as_str());
+            descending.push(!ascending);
+        }
+        if col_names.is_empty() {
+            return Ok(self);
+        }
 
         dbg!(sort);
-        dbg!(col_name);
-        dbg!(ascending);
+        dbg!(&col_names);
+        dbg!(&descending);
 
         // Define sort options
         let sort_options = SortMultipleOptions::default()
             .with_maintain_order(true)
             .with_multithreaded(true)
-            .with_order_descending(!ascending) // Sort order: ascending or descending
+            .with_order_descending_multi(descending) // Sort order of each column
             .with_nulls_last(false);
 
-        // Sort the DataFrame using Polars
+        // Sort the DataFrame using Polars; the streaming engine sorts out of core.
+        let sorted = match filters.engine.streaming {
+            true => self
+                .df
+                .as_ref()
+                .clone()
+                .lazy()
+                .sort(col_names, sort_options)
+                .with_streaming(true)
+                .collect(),
+            false => self.df.sort(col_names, sort_options),
+        };
+        self.df = Arc::new(sorted.map_err(|e| format!("Polars sort error: {}", e))?);
+        if cached {
+            self.sort_cache.insert(sort, Arc::clone(&self.df));
+        }
+        self.filters = filters; //Update filters
+
+        Ok(self)
+    }
+}
+
+impl DataFrameContainer {
+    /// Keeps only the rows duplicated over the key columns (all columns if `keys` is empty).
+    pub async fn filter_duplicates(mut self, keys: Vec<String>) -> Result<Self, String> {
+        let mask = duplicated_mask(&self.df, &keys)
+            .map_err(|e| format!("Duplicate detection error: {}", e))?;
+
         self.df = Arc::new(
             self.df
-                .sort([col_name], sort_options)
-                .map_err(|e| format!("Polars sort error: {}", e))?,
+                .filter(&mask)
+                .map_err(|e| format!("Polars filter error: {}", e))?,
         );
-        self.filters = filters; //Update filters
+        // The sorted versions of the result still hold the rows filtered out.
+        self.sort_cache = SortCache::default();
+        self.filtered = true;
 
         Ok(self)
     }
@@ -463,3 +903,352 @@ fn test_quoted_bool_ints() -> PolarsResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_read_parquet_row_groups() -> Result<(), String> {
+    let mut df = df!["id" => (0..1000).collect::<Vec<i64>>()].map_err(|e| e.to_string())?;
+    let path = std::env::temp_dir().join("polars_view_test_row_groups.parquet");
+    let file = File::create(&path).map_err(|e| e.to_string())?;
+    ParquetWriter::new(file)
+        .with_row_group_size(Some(100))
+        .finish(&mut df)
+        .map_err(|e| e.to_string())?;
+
+    let progress = Arc::new(LoadProgress::default());
+    let filename = path.display().to_string();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .map_err(|e| e.to_string())?;
+    let read = runtime.block_on(progress.scope(DataFrameContainer::read_parquet(
+        &filename,
+        EngineOptions::default(),
+    )));
+    let _ = std::fs::remove_file(&path);
+
+    let read = read?;
+    assert!(read.equals(&df));
+    // One chunk per row group, rather than a copy into a single chunk.
+    assert_eq!(read.first_col_n_chunks(), 10);
+    assert_eq!(progress.fraction(), Some(1.0));
+    assert!(progress.text().starts_with("10/10 row groups"));
+    Ok(())
+}
+
+#[test]
+fn test_sort_cache() -> Result<(), String> {
+    let df = df!["id" => [2, 3, 1]].map_err(|e| e.to_string())?;
+    let data = DataFrameContainer {
+        filename: "data.parquet".to_string(),
+        df: Arc::new(df.clone()),
+        filters: DataFilters::default(),
+        table_type: "parquet".to_string(),
+        sort_cache: SortCache::default(),
+        filtered: false,
+    };
+    let sorted = |sort: SortState| DataFilters {
+        sort: Some(sort),
+        ..Default::default()
+    };
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .map_err(|e| e.to_string())?;
+    runtime.block_on(async {
+        let asc = data
+            .sort(Some(sorted(SortState::Ascending("id".into()))))
+            .await?;
+        let desc = asc
+            .clone()
+            .sort(Some(sorted(SortState::Descending("id".into()))))
+            .await?;
+        let again = desc
+            .clone()
+            .sort(Some(sorted(SortState::Ascending("id".into()))))
+            .await?;
+
+        // Toggling back reuses the sorted data.
+        assert!(Arc::ptr_eq(&asc.df, &again.df));
+        assert_eq!(desc.df.column("id").unwrap().i32().unwrap().get(0), Some(3));
+
+        // Not sorted restores the loaded order.
+        let restored = again
+            .sort(Some(sorted(SortState::NotSorted("id".into()))))
+            .await?;
+        assert!(restored.df.equals(&df));
+
+        // The streaming engine sorts the same way.
+        let streaming = DataFilters {
+            engine: EngineOptions {
+                streaming: true,
+                ..Default::default()
+            },
+            ..sorted(SortState::Descending("id".into()))
+        };
+        let fresh = DataFrameContainer {
+            sort_cache: SortCache::default(),
+            ..restored
+        };
+        assert!(fresh.sort(Some(streaming)).await?.df.equals(&desc.df));
+        Ok(())
+    })
+}
+
+#[test]
+fn test_sort_after_filter_duplicates() -> Result<(), String> {
+    let df = df!["id" => [2, 3, 1, 3]].map_err(|e| e.to_string())?;
+    let data = DataFrameContainer::from_dataframe(df);
+    let sorted = |sort: SortState| DataFilters {
+        sort: Some(sort),
+        ..Default::default()
+    };
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .map_err(|e| e.to_string())?;
+    runtime.block_on(async {
+        let asc = data
+            .sort(Some(sorted(SortState::Ascending("id".into()))))
+            .await?;
+        let duplicates = asc.filter_duplicates(vec!["id".to_string()]).await?;
+        let desc = duplicates
+            .sort(Some(sorted(SortState::Descending("id".into()))))
+            .await?;
+        let again = desc
+            .sort(Some(sorted(SortState::Ascending("id".into()))))
+            .await?;
+
+        // Sorting again does not bring back the rows filtered out.
+        let expected = df!["id" => [3, 3]].map_err(|e| e.to_string())?;
+        assert!(again.df.equals(&expected));
+        Ok(())
+    })
+}
+
+#[test]
+fn test_read_parquet_glob() -> Result<(), String> {
+    let dir = std::env::temp_dir().join("polars_view_test_glob");
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    for part in 0..3 {
+        let mut df = df!["id" => [part, part + 10]].map_err(|e| e.to_string())?;
+        let file =
+            File::create(dir.join(format!("part{part}.parquet"))).map_err(|e| e.to_string())?;
+        ParquetWriter::new(file)
+            .finish(&mut df)
+            .map_err(|e| e.to_string())?;
+    }
+
+    let engine = EngineOptions {
+        parallel: crate::ParquetParallelism::RowGroups,
+        low_memory: true,
+        use_statistics: false,
+        projection_pushdown: false,
+        ..Default::default()
+    };
+    let pattern = dir.join("*.parquet").display().to_string();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .map_err(|e| e.to_string())?;
+    let read = runtime.block_on(DataFrameContainer::read_parquet(&pattern, engine));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert_eq!(read?.height(), 6);
+    Ok(())
+}
+
+#[test]
+fn test_sort_then_by() -> Result<(), String> {
+    assert_eq!(
+        "amount:desc".parse(),
+        Ok(SortState::Descending("amount".into()))
+    );
+    assert_eq!("id".parse(), Ok(SortState::Ascending("id".into())));
+    assert_eq!("a:b".parse(), Ok(SortState::Ascending("a:b".into())));
+    assert!(":desc".parse::<SortState>().is_err());
+
+    let df = df!["group" => [1, 2, 1, 2], "id" => [1, 2, 3, 4]].map_err(|e| e.to_string())?;
+    let data = DataFrameContainer {
+        filename: "data.parquet".to_string(),
+        df: Arc::new(df),
+        filters: DataFilters::default(),
+        table_type: "parquet".to_string(),
+        sort_cache: SortCache::default(),
+        filtered: false,
+    };
+    let filters = DataFilters {
+        sort: Some("group:desc".parse()?),
+        then_by: vec!["id:desc".parse()?],
+        ..Default::default()
+    };
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .map_err(|e| e.to_string())?;
+    let sorted = runtime.block_on(data.sort(Some(filters)))?;
+    let ids: Vec<Option<i32>> = sorted
+        .df
+        .column("id")
+        .unwrap()
+        .i32()
+        .unwrap()
+        .iter()
+        .collect();
+    assert_eq!(ids, [Some(4), Some(2), Some(3), Some(1)]);
+    Ok(())
+}
+
+#[test]
+fn test_sort_unsortable() -> Result<(), String> {
+    let tags = Series::new("tags".into(), [Series::new("".into(), [1, 2])]);
+    let bytes = Series::new("bytes".into(), [b"ab".as_slice()]);
+    let df = DataFrame::new(vec![tags.into(), bytes.into()]).map_err(|e| e.to_string())?;
+    assert!(!is_sortable(df["tags"].dtype()));
+    assert!(!is_sortable(df["bytes"].dtype()));
+    assert!(is_sortable(&DataType::String));
+
+    let data = DataFrameContainer {
+        filename: "data.parquet".to_string(),
+        df: Arc::new(df),
+        filters: DataFilters::default(),
+        table_type: "parquet".to_string(),
+        sort_cache: SortCache::default(),
+        filtered: false,
+    };
+    let filters = DataFilters {
+        sort: Some("tags".parse()?),
+        ..Default::default()
+    };
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .map_err(|e| e.to_string())?;
+    let error = runtime.block_on(data.sort(Some(filters))).err();
+    assert_eq!(
+        error.as_deref(),
+        Some("Column 'tags' of type list[i32] cannot be sorted")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_further_tables() -> Result<(), String> {
+    assert_eq!(sql_table_name("/data/sales 2024.csv"), "sales_2024");
+
+    // The names of the further files are unique, and never the main table.
+    let names = DataFilters {
+        tables: ["/a/alldata.csv", "/a/sales.csv", "/b/sales.parquet"]
+            .map(String::from)
+            .to_vec(),
+        ..DataFilters::new("/a/main.parquet")
+    };
+    assert_eq!(names.table_names(), ["alldata_2", "sales", "sales_2"]);
+
+    let dir = std::env::temp_dir();
+    let main = dir.join("polars_view_test_main.parquet");
+    let other = dir.join("polars_view_test_names.parquet");
+    for (path, df) in [
+        (&main, df!["id" => [1i64, 2], "amount" => [10i64, 20]]),
+        (&other, df!["id" => [2i64, 1], "name" => ["b", "a"]]),
+    ] {
+        let mut df = df.map_err(|e| e.to_string())?;
+        let file = File::create(path).map_err(|e| e.to_string())?;
+        ParquetWriter::new(file)
+            .finish(&mut df)
+            .map_err(|e| e.to_string())?;
+    }
+
+    let filters = DataFilters {
+        tables: vec![other.display().to_string()],
+        query: Some(
+            "SELECT name, amount FROM AllData JOIN polars_view_test_names USING (id) ORDER BY id;"
+                .to_string(),
+        ),
+        ..DataFilters::new(main.display().to_string())
+    };
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .map_err(|e| e.to_string())?;
+    let data = runtime.block_on(DataFrameContainer::load_data_with_sql(filters));
+    let _ = std::fs::remove_file(&main);
+    let _ = std::fs::remove_file(&other);
+
+    let expected = df!["name" => ["a", "b"], "amount" => [10i64, 20]].map_err(|e| e.to_string())?;
+    assert!(data?.df.equals(&expected));
+    Ok(())
+}
+
+#[test]
+fn test_empty_result() -> Result<(), String> {
+    let dir = std::env::temp_dir();
+    let parquet = dir.join("polars_view_test_empty.parquet");
+    let csv = dir.join("polars_view_test_empty.csv");
+    let mut df = df!["id" => [1i64, 2], "name" => ["a", "b"]].map_err(|e| e.to_string())?;
+    let file = File::create(&parquet).map_err(|e| e.to_string())?;
+    ParquetWriter::new(file)
+        .finish(&mut df)
+        .map_err(|e| e.to_string())?;
+    std::fs::write(&csv, "id;name\n").map_err(|e| e.to_string())?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .map_err(|e| e.to_string())?;
+    let filters = DataFilters {
+        query: Some("SELECT * FROM AllData WHERE id > 10;".to_string()),
+        sort: Some(SortState::Descending("name".to_string())),
+        ..DataFilters::new(parquet.display().to_string())
+    };
+    let queried = runtime.block_on(DataFrameContainer::load_data_with_sort(filters));
+    let read_csv = runtime.block_on(DataFrameContainer::load_data(csv.display().to_string()));
+    let _ = std::fs::remove_file(&parquet);
+    let _ = std::fs::remove_file(&csv);
+
+    // A query matching no rows, and a CSV file with a header only, give empty tables.
+    let queried = queried?;
+    assert_eq!(queried.df.shape(), (0, 2));
+    assert_eq!(queried.df.schema().get("id"), Some(&DataType::Int64));
+    assert_eq!(read_csv?.df.shape(), (0, 2));
+    Ok(())
+}
+
+#[test]
+fn test_query_referenced_columns() -> Result<(), String> {
+    let path = std::env::temp_dir().join("polars_view_test_columns.parquet");
+    let mut df = df![
+        "id" => [1i64, 2, 3],
+        "notes" => ["long text", "more text", "even more"],
+        "amount" => [10i64, 20, 30],
+    ]
+    .map_err(|e| e.to_string())?;
+    let file = File::create(&path).map_err(|e| e.to_string())?;
+    ParquetWriter::new(file)
+        .finish(&mut df)
+        .map_err(|e| e.to_string())?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .map_err(|e| e.to_string())?;
+    let query = |sql: &str| {
+        let filters = DataFilters {
+            query: Some(sql.to_string()),
+            ..DataFilters::new(path.display().to_string())
+        };
+        runtime.block_on(DataFrameContainer::load_data_with_sql(filters))
+    };
+
+    // The string column is not read by the queries that do not use it.
+    let amounts = query("SELECT SUM(amount) AS total FROM AllData WHERE id > 1;");
+    let count = query("SELECT COUNT(*) AS n FROM AllData;");
+    let all = query("SELECT * FROM AllData;");
+    let _ = std::fs::remove_file(&path);
+
+    let expected = df!["total" => [50i64]].map_err(|e| e.to_string())?;
+    assert!(amounts?.df.equals(&expected));
+    let count = count?;
+    let n = count
+        .df
+        .column("n")
+        .and_then(|n| n.get(0))
+        .map_err(|e| e.to_string())?;
+    assert_eq!(n.extract::<u32>(), Some(3));
+    assert_eq!(all?.df.shape(), (3, 3));
+    Ok(())
+}
@@ -0,0 +1,201 @@
+use crate::{ColumnTransform, DataFilters, Popover};
+
+use egui::{Context, Grid, TextEdit, Window};
+use polars::{prelude::*, sql::sql_expr};
+use serde::{Deserialize, Serialize};
+
+/// A column computed from the others with a SQL expression, such as `price * qty`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DerivedColumn {
+    pub name: String,
+    pub expression: String,
+}
+
+impl DerivedColumn {
+    /// A column named `name`; without a name, the expression may end with `AS name`.
+    pub fn new(name: &str, expression: &str) -> Self {
+        let (name, expression) = (name.trim(), expression.trim());
+        let lowercase = expression.to_ascii_lowercase(); // Same byte offsets.
+
+        match lowercase.rfind(" as ") {
+            Some(idx) if name.is_empty() => DerivedColumn {
+                name: expression[idx + 4..].trim().trim_matches('`').to_string(),
+                expression: expression[..idx].trim().to_string(),
+            },
+            _ => DerivedColumn {
+                name: name.to_string(),
+                expression: expression.to_string(),
+            },
+        }
+    }
+
+    /// Parses the expression, named after the column.
+    pub fn expr(&self) -> Result<Expr, String> {
+        sql_expr(&self.expression)
+            .map(|expr| expr.alias(self.name.as_str()))
+            .map_err(|e| format!("Invalid expression '{}': {}", self.expression, e))
+    }
+
+    /// Checks that the expression can be computed from columns of the schema.
+    pub fn validate(&self, schema: &Schema) -> Result<DataType, String> {
+        if self.name.trim().is_empty() {
+            return Err("The column needs a name".to_string());
+        }
+
+        let expr = self.expr()?;
+        DataFrame::empty_with_schema(schema)
+            .lazy()
+            .select([expr])
+            .collect_schema()
+            .map_err(|e| format!("Invalid expression '{}': {}", self.expression, e))
+            .and_then(|schema| {
+                schema
+                    .get(self.name.as_str())
+                    .cloned()
+                    .ok_or_else(|| format!("Invalid expression '{}'", self.expression))
+            })
+    }
+}
+
+/// Popover listing the computed columns of the pipeline, where new ones are defined.
+pub struct DerivedColumns {
+    filters: DataFilters,
+    /// Schema of the current result, to check the expressions.
+    schema: Schema,
+    name: String,
+    expression: String,
+    error: Option<String>,
+    /// Filters with the updated columns, once applied.
+    requested_filters: Option<DataFilters>,
+}
+
+impl DerivedColumns {
+    pub fn new(filters: &DataFilters, schema: Schema) -> Self {
+        DerivedColumns {
+            filters: filters.clone(),
+            schema,
+            name: String::new(),
+            expression: String::new(),
+            error: None,
+            requested_filters: None,
+        }
+    }
+}
+
+impl Popover for DerivedColumns {
+    /// Shows the derived columns, with a row to add one.
+    fn show(&mut self, ctx: &Context) -> bool {
+        let mut open = true;
+        let mut apply = false;
+
+        Window::new("Computed columns")
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label("Columns computed with a SQL expression over the query result.");
+                ui.separator();
+
+                let mut removed = None;
+                Grid::new("derived_columns_grid")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (idx, transform) in self.filters.transforms.iter().enumerate() {
+                            let ColumnTransform::Compute(column) = transform else {
+                                continue;
+                            };
+                            ui.strong(&column.name);
+                            ui.monospace(&column.expression);
+                            if ui.small_button("Remove").clicked() {
+                                removed = Some(idx);
+                            }
+                            ui.end_row();
+                        }
+
+                        ui.add(
+                            TextEdit::singleline(&mut self.name)
+                                .hint_text("total")
+                                .desired_width(120.0),
+                        );
+                        ui.add(
+                            TextEdit::singleline(&mut self.expression)
+                                .hint_text("price * qty")
+                                .code_editor()
+                                .desired_width(300.0),
+                        );
+                        if ui.button("Add").clicked() {
+                            let column = DerivedColumn::new(&self.name, &self.expression);
+                            match column.validate(&self.schema) {
+                                Ok(dtype) => {
+                                    self.schema.with_column(column.name.as_str().into(), dtype);
+                                    self.filters
+                                        .transforms
+                                        .push(ColumnTransform::Compute(column));
+                                    self.name.clear();
+                                    self.expression.clear();
+                                    self.error = None;
+                                }
+                                Err(message) => self.error = Some(message),
+                            }
+                        }
+                        ui.end_row();
+                    });
+
+                if let Some(idx) = removed {
+                    self.filters.transforms.remove(idx);
+                }
+
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+
+                ui.separator();
+                apply = ui.button("Apply").clicked();
+            });
+
+        if apply {
+            self.requested_filters = Some(self.filters.clone());
+            return false;
+        }
+
+        open
+    }
+
+    fn take_filters(&mut self) -> Option<DataFilters> {
+        self.requested_filters.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derived_columns() -> Result<(), String> {
+        let df = df!["price" => [2.0, 3.0], "qty" => [10i64, 20]].map_err(|e| e.to_string())?;
+
+        let columns = vec![
+            DerivedColumn::new("", "price * qty AS total"),
+            DerivedColumn::new("large", "total > 50"),
+        ];
+        assert_eq!(columns[0].name, "total");
+        assert_eq!(columns[0].expression, "price * qty");
+        assert_eq!(columns[0].validate(df.schema()), Ok(DataType::Float64));
+        assert!(columns[1].validate(df.schema()).is_err()); // `total` is not in the file.
+
+        let computed: Vec<ColumnTransform> =
+            columns.into_iter().map(ColumnTransform::Compute).collect();
+        let result = crate::apply_transforms(df.lazy(), &computed)?
+            .collect()
+            .map_err(|e| e.to_string())?;
+        let expected = df![
+            "price" => [2.0, 3.0],
+            "qty" => [10i64, 20],
+            "total" => [20.0, 60.0],
+            "large" => [false, true],
+        ]
+        .map_err(|e| e.to_string())?;
+        assert!(result.equals(&expected));
+        Ok(())
+    }
+}
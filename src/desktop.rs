@@ -0,0 +1,302 @@
+use crate::APP_NAME;
+
+use egui::Context;
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    path::{Path, PathBuf},
+    process,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+};
+
+/// MIME type of the Parquet files.
+const PARQUET_MIME: &str = "application/vnd.apache.parquet";
+
+/// Name of the desktop entry (Linux) and of the file type (Windows).
+const DESKTOP_ID: &str = "polars-view";
+
+/// Receives the files that later invocations hand over to this instance.
+pub struct InstanceServer {
+    receiver: Receiver<String>,
+}
+
+impl InstanceServer {
+    /// Listens for the files of later invocations; fails if another instance is listening.
+    pub fn start(ctx: &Context) -> Result<Self, String> {
+        let address = ipc::address().map_err(|e| format!("Error locating the socket: {}", e))?;
+        Self::start_at(&address, ctx)
+    }
+
+    fn start_at(address: &Path, ctx: &Context) -> Result<Self, String> {
+        let listener = ipc::bind(address)
+            .map_err(|e| format!("Error listening on {}: {}", address.display(), e))?;
+        let (sender, receiver) = mpsc::channel();
+        let ctx = ctx.clone();
+        thread::spawn(move || serve(listener.incoming(), &sender, &ctx));
+        Ok(InstanceServer { receiver })
+    }
+
+    /// The next file handed over, if any.
+    pub fn next_file(&self) -> Option<String> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+/// Reads a file name per line of each connection, until the app closes.
+fn serve<S: Read>(
+    streams: impl Iterator<Item = io::Result<S>>,
+    sender: &Sender<String>,
+    ctx: &Context,
+) {
+    for stream in streams.flatten() {
+        for filename in BufReader::new(stream).lines().map_while(Result::ok) {
+            if sender.send(filename).is_err() {
+                return;
+            }
+            ctx.request_repaint();
+        }
+    }
+}
+
+/// Hands a file over to the running instance; false if none is running.
+pub fn send_to_running_instance(filename: &str) -> bool {
+    ipc::address()
+        .and_then(|address| send_to(&address, filename))
+        .is_ok()
+}
+
+fn send_to(address: &Path, filename: &str) -> io::Result<()> {
+    // The running instance has its own working directory.
+    let path = Path::new(filename).canonicalize()?;
+    let mut stream = ipc::connect(address)?;
+    writeln!(stream, "{}", path.display())
+}
+
+/// Local sockets on Unix: a socket file in a directory only the user can write to, so that
+/// no other user can create the socket first or replace it.
+#[cfg(unix)]
+mod ipc {
+    use std::{
+        fs::{self, DirBuilder, Permissions},
+        io,
+        os::unix::{
+            fs::{DirBuilderExt, PermissionsExt},
+            net::{UnixListener, UnixStream},
+        },
+        path::{Path, PathBuf},
+    };
+
+    /// The socket of the runtime directory of the user, or else of a private directory
+    /// of its cache.
+    pub fn address() -> io::Result<PathBuf> {
+        let name = format!("{}.sock", super::DESKTOP_ID);
+        if let Some(dir) = std::env::var_os("XDG_RUNTIME_DIR").filter(|dir| !dir.is_empty()) {
+            return Ok(PathBuf::from(dir).join(name));
+        }
+
+        let dir = dirs::cache_dir()
+            .ok_or(io::ErrorKind::NotFound)?
+            .join(super::DESKTOP_ID);
+        DirBuilder::new().recursive(true).mode(0o700).create(&dir)?;
+        // Fails unless the user owns the directory, if it was there before.
+        fs::set_permissions(&dir, Permissions::from_mode(0o700))?;
+        Ok(dir.join(name))
+    }
+
+    pub fn bind(address: &Path) -> io::Result<UnixListener> {
+        if address.exists() {
+            if UnixStream::connect(address).is_ok() {
+                return Err(io::ErrorKind::AddrInUse.into());
+            }
+            std::fs::remove_file(address)?; // Left by an instance that did not exit cleanly.
+        }
+        UnixListener::bind(address)
+    }
+
+    pub fn connect(address: &Path) -> io::Result<UnixStream> {
+        UnixStream::connect(address)
+    }
+}
+
+/// Elsewhere, a loopback TCP socket whose port is written to a file of the temporary
+/// directory of the user.
+#[cfg(not(unix))]
+mod ipc {
+    use std::{
+        fs, io,
+        net::{Ipv4Addr, TcpListener, TcpStream},
+        path::{Path, PathBuf},
+    };
+
+    pub fn address() -> io::Result<PathBuf> {
+        Ok(std::env::temp_dir().join(format!("{}.port", super::DESKTOP_ID)))
+    }
+
+    pub fn bind(address: &Path) -> io::Result<TcpListener> {
+        if connect(address).is_ok() {
+            return Err(io::ErrorKind::AddrInUse.into());
+        }
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        fs::write(address, listener.local_addr()?.port().to_string())?;
+        Ok(listener)
+    }
+
+    pub fn connect(address: &Path) -> io::Result<TcpStream> {
+        let port: u16 = fs::read_to_string(address)?
+            .trim()
+            .parse()
+            .map_err(|_| io::Error::from(io::ErrorKind::InvalidData))?;
+        TcpStream::connect((Ipv4Addr::LOCALHOST, port))
+    }
+}
+
+/// Desktop entry of the app, opening the Parquet files given to it.
+fn desktop_entry(exe: &Path) -> String {
+    format!(
+        "[Desktop Entry]\n\
+        Type=Application\n\
+        Name={APP_NAME}\n\
+        Comment=View and query Parquet and CSV files\n\
+        Exec=\"{}\" %f\n\
+        MimeType={PARQUET_MIME};\n\
+        Terminal=false\n\
+        Categories=Development;Office;\n",
+        exe.display()
+    )
+}
+
+/// Shared MIME-info definition of the `.parquet` extension.
+fn mime_package() -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <mime-info xmlns=\"http://www.freedesktop.org/standards/shared-mime-info\">\n  \
+        <mime-type type=\"{PARQUET_MIME}\">\n    \
+        <comment>Apache Parquet file</comment>\n    \
+        <glob pattern=\"*.parquet\"/>\n  \
+        </mime-type>\n\
+        </mime-info>\n"
+    )
+}
+
+/// Runs a program, failing with its error output.
+fn run(program: &str, args: &[&str]) -> Result<(), String> {
+    let output = process::Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Error running {}: {}", program, e))?;
+    match output.status.success() {
+        true => Ok(()),
+        false => Err(format!(
+            "Error running {}: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+    }
+}
+
+/// Registers the app as the handler of the `.parquet` files of the current user.
+pub fn register_file_association() -> Result<String, String> {
+    let exe = std::env::current_exe().map_err(|e| format!("Executable path error: {}", e))?;
+
+    if cfg!(windows) {
+        let file_type = format!("{APP_NAME}.parquet");
+        let command = format!("\"{}\" \"%1\"", exe.display());
+        let classes = r"HKCU\Software\Classes";
+        run(
+            "reg",
+            &[
+                "add",
+                &format!(r"{classes}\.parquet"),
+                "/ve",
+                "/d",
+                &file_type,
+                "/f",
+            ],
+        )?;
+        run(
+            "reg",
+            &[
+                "add",
+                &format!(r"{classes}\{file_type}\shell\open\command"),
+                "/ve",
+                "/d",
+                &command,
+                "/f",
+            ],
+        )?;
+        return Ok(format!("Registered {} for .parquet files", exe.display()));
+    }
+
+    if !cfg!(target_os = "linux") {
+        return Err("File associations are only registered on Linux and Windows".to_string());
+    }
+
+    let data_dir = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))
+        .ok_or("Neither XDG_DATA_HOME nor HOME is set")?;
+    let write = |path: PathBuf, contents: String| {
+        let dir = path.parent().unwrap_or(&data_dir);
+        std::fs::create_dir_all(dir)
+            .and_then(|_| std::fs::write(&path, contents))
+            .map_err(|e| format!("Error writing {}: {}", path.display(), e))
+    };
+
+    let mime_dir = data_dir.join("mime");
+    let applications = data_dir.join("applications");
+    let entry = format!("{DESKTOP_ID}.desktop");
+    write(
+        mime_dir.join("packages").join(format!("{DESKTOP_ID}.xml")),
+        mime_package(),
+    )?;
+    write(applications.join(&entry), desktop_entry(&exe))?;
+
+    // The databases are refreshed when available; the default handler is required.
+    let _ = run("update-mime-database", &[&mime_dir.to_string_lossy()]);
+    let _ = run(
+        "update-desktop-database",
+        &[&applications.to_string_lossy()],
+    );
+    run("xdg-mime", &["default", &entry, PARQUET_MIME])?;
+
+    Ok(format!(
+        "Registered {} for .parquet files ({})",
+        exe.display(),
+        applications.join(&entry).display()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hand_over_to_running_instance() -> Result<(), String> {
+        let dir = std::env::temp_dir();
+        let address = dir.join(format!("polars_view_test_{}.sock", process::id()));
+        let file = dir.join("polars_view_test_instance.parquet");
+        std::fs::write(&file, b"").map_err(|e| e.to_string())?;
+
+        let ctx = Context::default();
+        let server = InstanceServer::start_at(&address, &ctx)?;
+        assert!(InstanceServer::start_at(&address, &ctx).is_err()); // Already running.
+
+        let expected = file.canonicalize().map_err(|e| e.to_string())?;
+        let sent = send_to(&address, &file.to_string_lossy());
+        let received = (0..100).find_map(|_| {
+            thread::sleep(std::time::Duration::from_millis(10));
+            server.next_file()
+        });
+        let _ = std::fs::remove_file(&file);
+        let _ = std::fs::remove_file(&address);
+
+        sent.map_err(|e| e.to_string())?;
+        assert_eq!(received, Some(expected.display().to_string()));
+
+        assert!(
+            desktop_entry(Path::new("/usr/bin/polars-view"))
+                .contains("Exec=\"/usr/bin/polars-view\" %f")
+        );
+        Ok(())
+    }
+}
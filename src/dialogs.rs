@@ -0,0 +1,99 @@
+use egui::Context;
+use std::{future::Future, path::PathBuf};
+use tokio::{
+    runtime::Runtime,
+    sync::oneshot::{self, error::TryRecvError},
+};
+
+/// What the application does with the files chosen in a dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DialogPurpose {
+    OpenFile,
+    OpenSession,
+    SaveSession,
+    ImportCsv,
+    /// Two Parquet files whose metadata are compared.
+    CompareFiles,
+    /// An old and a new file whose schemas are compared.
+    SchemaDiff,
+    /// An old and a new Parquet file whose rows are compared.
+    RowDiff,
+    ExportReport,
+    /// The HTML page where the visible rows, or all rows, of the result are exported.
+    ExportHtml {
+        visible: bool,
+    },
+    /// The PDF document where the result is printed.
+    ExportPdf,
+    /// The PNG file where the image of the visible table is saved.
+    SaveTableImage,
+    /// The file where the edited result is saved.
+    SaveEdits,
+    OpenRecipe,
+    SaveRecipe,
+    /// The directory shown in the Files pane.
+    BrowseFolder,
+}
+
+/// A native file dialog running as a Tokio task, so that the UI thread is never blocked.
+///
+/// Like the data operations, the result is sent back through a oneshot channel.
+pub struct PendingDialog {
+    pub purpose: DialogPurpose,
+    receiver: oneshot::Receiver<Result<Vec<PathBuf>, String>>,
+}
+
+impl PendingDialog {
+    /// Spawns the dialogs on the runtime; the UI is repainted when the user has chosen.
+    pub fn spawn<F>(purpose: DialogPurpose, dialogs: F, runtime: &Runtime, ctx: &Context) -> Self
+    where
+        F: Future<Output = Result<Vec<PathBuf>, String>> + Send + 'static,
+    {
+        let (tx, receiver) = oneshot::channel();
+        let ctx = ctx.clone();
+
+        runtime.spawn(async move {
+            let _ = tx.send(dialogs.await);
+            ctx.request_repaint();
+        });
+
+        PendingDialog { purpose, receiver }
+    }
+
+    /// The chosen files once the dialogs are closed; an error if they were cancelled.
+    pub fn try_paths(&mut self) -> Option<Result<Vec<PathBuf>, String>> {
+        match self.receiver.try_recv() {
+            Ok(paths) => Some(paths),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Closed) => Some(Err("File dialog was interrupted.".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_dialog() {
+        let runtime = Runtime::new().unwrap();
+        let ctx = Context::default();
+
+        let paths = async {
+            Ok(vec![
+                PathBuf::from("old.parquet"),
+                PathBuf::from("new.parquet"),
+            ])
+        };
+        let mut dialog = PendingDialog::spawn(DialogPurpose::SchemaDiff, paths, &runtime, &ctx);
+
+        let result = loop {
+            if let Some(result) = dialog.try_paths() {
+                break result;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        };
+        assert_eq!(dialog.purpose, DialogPurpose::SchemaDiff);
+        assert_eq!(result.map(|paths| paths.len()), Ok(2));
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{Language, language, tr};
+
+use egui::Ui;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A section of the side panels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Pane {
+    Metadata,
+    Query,
+    Schema,
+    RowGroups,
+    BloomFilters,
+    Statistics,
+    MissingValues,
+    Duplicates,
+    Pivot,
+    Compression,
+    Benchmark,
+    Tasks,
+    Pipeline,
+    Files,
+}
+
+impl Pane {
+    /// All panes, in their default order.
+    pub const ALL: [Pane; 14] = [
+        Pane::Metadata,
+        Pane::Query,
+        Pane::Schema,
+        Pane::RowGroups,
+        Pane::BloomFilters,
+        Pane::Statistics,
+        Pane::MissingValues,
+        Pane::Duplicates,
+        Pane::Pivot,
+        Pane::Compression,
+        Pane::Benchmark,
+        Pane::Tasks,
+        Pane::Pipeline,
+        Pane::Files,
+    ];
+
+    /// Title of the pane, in `lang`.
+    pub fn title(&self, lang: Language) -> &'static str {
+        match self {
+            Pane::Metadata => tr("Metadata", lang),
+            Pane::Query => tr("Query", lang),
+            Pane::Schema => tr("Schema", lang),
+            Pane::RowGroups => tr("Row Groups", lang),
+            Pane::BloomFilters => tr("Bloom Filters", lang),
+            Pane::Statistics => tr("Statistics", lang),
+            Pane::MissingValues => tr("Missing Values", lang),
+            Pane::Duplicates => tr("Duplicates", lang),
+            Pane::Pivot => tr("Pivot", lang),
+            Pane::Compression => tr("Compression", lang),
+            Pane::Benchmark => tr("Benchmark", lang),
+            Pane::Tasks => tr("Tasks", lang),
+            Pane::Pipeline => tr("Pipeline", lang),
+            Pane::Files => tr("Files", lang),
+        }
+    }
+}
+
+/// Where a pane is displayed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaneDock {
+    /// In the left side panel.
+    #[default]
+    Left,
+    /// In the right side panel.
+    Right,
+    /// In its own floating window.
+    Floating,
+    /// Not displayed.
+    Hidden,
+}
+
+impl PaneDock {
+    pub const ALL: [PaneDock; 4] = [
+        PaneDock::Left,
+        PaneDock::Right,
+        PaneDock::Floating,
+        PaneDock::Hidden,
+    ];
+}
+
+impl fmt::Display for PaneDock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PaneDock::Left => write!(f, "Left panel"),
+            PaneDock::Right => write!(f, "Right panel"),
+            PaneDock::Floating => write!(f, "Floating window"),
+            PaneDock::Hidden => write!(f, "Hidden"),
+        }
+    }
+}
+
+/// Arrangement of the panes chosen by the user: their order and where each is docked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DockLayout {
+    /// Panes in display order, with their dock.
+    pub panes: Vec<(Pane, PaneDock)>,
+}
+
+impl Default for DockLayout {
+    fn default() -> Self {
+        DockLayout {
+            panes: Pane::ALL.map(|pane| (pane, PaneDock::Left)).to_vec(),
+        }
+    }
+}
+
+impl DockLayout {
+    /// Panes displayed in the given dock, in order.
+    ///
+    /// Panes missing from a layout saved by an older version are docked to the left.
+    pub fn panes_in(&self, dock: PaneDock) -> Vec<Pane> {
+        let missing = Pane::ALL
+            .into_iter()
+            .filter(|pane| !self.panes.iter().any(|(p, _)| p == pane))
+            .map(|pane| (pane, PaneDock::Left));
+
+        self.panes
+            .iter()
+            .copied()
+            .chain(missing)
+            .filter(|(_, d)| *d == dock)
+            .map(|(pane, _)| pane)
+            .collect()
+    }
+
+    /// The dock of a pane.
+    pub fn dock_of(&self, pane: Pane) -> PaneDock {
+        self.panes
+            .iter()
+            .find(|(p, _)| *p == pane)
+            .map_or(PaneDock::Left, |(_, dock)| *dock)
+    }
+
+    /// Moves a pane to another dock, at the end of it.
+    pub fn set_dock(&mut self, pane: Pane, dock: PaneDock) {
+        self.panes.retain(|(p, _)| *p != pane);
+        self.panes.push((pane, dock));
+    }
+
+    /// Moves a pane before the previous pane of the same dock (`up`) or after the next one.
+    pub fn move_pane(&mut self, pane: Pane, up: bool) {
+        let Some(idx) = self.panes.iter().position(|(p, _)| *p == pane) else {
+            return;
+        };
+        let dock = self.panes[idx].1;
+
+        let neighbor = match up {
+            true => self.panes[..idx].iter().rposition(|(_, d)| *d == dock),
+            false => self.panes[idx + 1..]
+                .iter()
+                .position(|(_, d)| *d == dock)
+                .map(|offset| idx + 1 + offset),
+        };
+        if let Some(neighbor) = neighbor {
+            self.panes.swap(idx, neighbor);
+        }
+    }
+
+    /// Menu entries to move a pane; returns `true` if the layout changed.
+    pub fn pane_menu(&mut self, ui: &mut Ui, pane: Pane) -> bool {
+        let mut changed = false;
+        let current = self.dock_of(pane);
+        let lang = language(ui.ctx());
+
+        for dock in PaneDock::ALL {
+            let label = match dock {
+                PaneDock::Left => tr("Move to Left panel", lang),
+                PaneDock::Right => tr("Move to Right panel", lang),
+                PaneDock::Floating => tr("Move to Floating window", lang),
+                PaneDock::Hidden => tr("Move to Hidden", lang),
+            };
+            if dock != current && ui.button(label).clicked() {
+                self.set_dock(pane, dock);
+                changed = true;
+            }
+        }
+
+        if matches!(current, PaneDock::Left | PaneDock::Right) {
+            ui.separator();
+            if ui.button(tr("Move up", lang)).clicked() {
+                self.move_pane(pane, true);
+                changed = true;
+            }
+            if ui.button(tr("Move down", lang)).clicked() {
+                self.move_pane(pane, false);
+                changed = true;
+            }
+        }
+
+        if changed {
+            ui.close_menu();
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dock_layout() {
+        let mut layout = DockLayout::default();
+        assert_eq!(layout.panes_in(PaneDock::Left), Pane::ALL.to_vec());
+
+        layout.set_dock(Pane::Query, PaneDock::Right);
+        layout.set_dock(Pane::Schema, PaneDock::Right);
+        layout.set_dock(Pane::Pivot, PaneDock::Floating);
+        assert_eq!(
+            layout.panes_in(PaneDock::Right),
+            vec![Pane::Query, Pane::Schema]
+        );
+        assert_eq!(layout.panes_in(PaneDock::Floating), vec![Pane::Pivot]);
+
+        // Moving skips the panes of the other docks.
+        layout.move_pane(Pane::Schema, true);
+        assert_eq!(
+            layout.panes_in(PaneDock::Right),
+            vec![Pane::Schema, Pane::Query]
+        );
+        layout.move_pane(Pane::Metadata, true); // Already first.
+        layout.move_pane(Pane::Metadata, false);
+        assert_eq!(
+            layout.panes_in(PaneDock::Left)[..2],
+            [Pane::RowGroups, Pane::Metadata]
+        );
+
+        // Panes missing from an older layout are shown on the left.
+        let old = DockLayout {
+            panes: vec![(Pane::Query, PaneDock::Right)],
+        };
+        assert_eq!(old.panes_in(PaneDock::Left).len(), Pane::ALL.len() - 1);
+    }
+}
@@ -0,0 +1,441 @@
+use crate::{Codec, write_dataframe};
+
+use parquet::{
+    column::writer::ColumnCloseResult,
+    errors::ParquetError,
+    file::{
+        metadata::KeyValue,
+        properties::WriterProperties,
+        reader::{FileReader, SerializedFileReader},
+        writer::SerializedFileWriter,
+    },
+};
+use polars::prelude::*;
+use std::{collections::BTreeMap, fs, path::Path, sync::Arc};
+
+/// Parses the text typed in a cell as a value of the column type.
+///
+/// Strings are kept as typed, even empty; text that does not fit the type is an error.
+/// A null is set with [`Changeset::set_null`].
+pub fn parse_cell(dtype: &DataType, text: &str) -> Result<Series, String> {
+    if dtype == &DataType::String {
+        return Ok(Series::new("".into(), [text]));
+    }
+
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(format!(
+            "An empty text is not a valid {dtype}; set the cell to null instead"
+        ));
+    }
+
+    if dtype.is_nested() {
+        return Err(format!("Cells of type {dtype} cannot be edited"));
+    }
+
+    if dtype == &DataType::Boolean {
+        return match text.to_lowercase().as_str() {
+            "true" | "1" => Ok(Series::new("".into(), [true])),
+            "false" | "0" => Ok(Series::new("".into(), [false])),
+            _ => Err(format!("'{text}' is not a boolean (true or false)")),
+        };
+    }
+
+    Series::new("".into(), [text])
+        .strict_cast(dtype)
+        .map_err(|_| format!("'{text}' is not a valid {dtype}"))
+}
+
+/// Text of a cell as typed to edit it: strings without quotes, null as empty.
+pub fn cell_text(value: &AnyValue) -> String {
+    match value {
+        AnyValue::String(s) => s.to_string(),
+        AnyValue::StringOwned(s) => s.to_string(),
+        AnyValue::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+/// Cells modified by the user, not yet saved; each holds a single value of its column type.
+#[derive(Debug, Clone, Default)]
+pub struct Changeset {
+    cells: BTreeMap<(String, usize), Series>,
+}
+
+impl Changeset {
+    /// Validates and records the new value of a cell.
+    pub fn set(
+        &mut self,
+        df: &DataFrame,
+        column: &str,
+        row: usize,
+        text: &str,
+    ) -> Result<(), String> {
+        let dtype = self.cell_dtype(df, column, row)?;
+        let value = parse_cell(dtype, text)?;
+        self.cells.insert((column.to_string(), row), value);
+        Ok(())
+    }
+
+    /// Records a null as the new value of a cell.
+    pub fn set_null(&mut self, df: &DataFrame, column: &str, row: usize) -> Result<(), String> {
+        let dtype = self.cell_dtype(df, column, row)?;
+        let value = Series::full_null("".into(), 1, dtype);
+        self.cells.insert((column.to_string(), row), value);
+        Ok(())
+    }
+
+    /// Type of the column of a cell, checking that the cell exists.
+    fn cell_dtype<'a>(
+        &self,
+        df: &'a DataFrame,
+        column: &str,
+        row: usize,
+    ) -> Result<&'a DataType, String> {
+        let dtype = df
+            .column(column)
+            .map_err(|e| format!("Column error: {}", e))?
+            .dtype();
+        if row >= df.height() {
+            return Err(format!("Row {row} is out of range"));
+        }
+        Ok(dtype)
+    }
+
+    /// The new value of a cell, if it was edited.
+    pub fn get(&self, column: &str, row: usize) -> Option<AnyValue<'static>> {
+        self.cells
+            .get(&(column.to_string(), row))
+            .and_then(|value| value.get(0).ok())
+            .map(|value| value.into_static())
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    /// The DataFrame with the edited cells replaced.
+    pub fn apply(&self, df: &DataFrame) -> Result<DataFrame, String> {
+        let mut by_column: BTreeMap<&str, Vec<(usize, &Series)>> = BTreeMap::new();
+        for ((column, row), value) in &self.cells {
+            by_column.entry(column).or_default().push((*row, value));
+        }
+
+        let mut edited = df.clone();
+        for (column, cells) in by_column {
+            let original = df
+                .column(column)
+                .map_err(|e| format!("Column error: {}", e))?
+                .as_materialized_series();
+
+            // Rebuild the column from the unchanged slices around the edited rows (sorted).
+            let mut series = original.clear();
+            let mut start = 0;
+            for (row, value) in cells {
+                let before = original.slice(start as i64, row - start);
+                series
+                    .append(&before)
+                    .and_then(|series| series.append(value))
+                    .map_err(|e| format!("Edit error: {}", e))?;
+                start = row + 1;
+            }
+            let after = original.slice(start as i64, original.len() - start);
+            series
+                .append(&after)
+                .map_err(|e| format!("Edit error: {}", e))?;
+
+            edited
+                .with_column(series.rechunk())
+                .map_err(|e| format!("Edit error: {}", e))?;
+        }
+
+        Ok(edited)
+    }
+}
+
+/// Writes the edited data in the format of the file extension.
+///
+/// The data is written to a temporary file next to `path`, then renamed,
+/// so that the original file is only replaced once fully written. With `keep_footer`,
+/// a Parquet file replaced keeps its codec and the key-value metadata of its footer;
+/// a file saved as another one does not inherit them from the file it overwrites.
+pub fn save_edits(df: &mut DataFrame, path: &Path, keep_footer: bool) -> Result<(), String> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| format!("Invalid file name: {}", path.display()))?;
+    let temporary = path.with_file_name(format!(".~{file_name}"));
+
+    let is_parquet = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("parquet"));
+    let footer = match keep_footer && is_parquet && path.is_file() {
+        true => Some(ParquetFooter::read(path)?),
+        false => None,
+    };
+
+    let written = write_dataframe(
+        df,
+        &temporary.display().to_string(),
+        footer.as_ref().and_then(|footer| footer.codec),
+    )
+    .and_then(|_| match footer {
+        Some(footer) if !footer.key_value.is_empty() => footer.append_to(&temporary),
+        _ => Ok(()),
+    });
+    if let Err(message) = written {
+        let _ = fs::remove_file(&temporary);
+        return Err(message);
+    }
+
+    fs::rename(&temporary, path).map_err(|e| {
+        let _ = fs::remove_file(&temporary);
+        format!("Error replacing {}: {}", path.display(), e)
+    })
+}
+
+/// Codec and key-value metadata of a Parquet file, kept when the file is rewritten.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParquetFooter {
+    /// Codec of the first column chunk; `None` if Polars cannot write it (Brotli, LZO).
+    pub codec: Option<Codec>,
+    pub key_value: Vec<KeyValue>,
+}
+
+impl ParquetFooter {
+    /// Reads the footer of a Parquet file.
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let file =
+            fs::File::open(path).map_err(|e| format!("Error opening {}: {}", path.display(), e))?;
+        let reader = SerializedFileReader::new(file)
+            .map_err(|e| format!("Error reading {}: {}", path.display(), e))?;
+        let metadata = reader.metadata();
+
+        let codec = metadata
+            .row_groups()
+            .first()
+            .and_then(|row_group| row_group.columns().first())
+            .and_then(|column| Codec::from_parquet(column.compression()));
+        let key_value = metadata
+            .file_metadata()
+            .key_value_metadata()
+            .cloned()
+            .unwrap_or_default();
+        Ok(ParquetFooter { codec, key_value })
+    }
+
+    /// Adds the key-value metadata to the footer of a Parquet file written by Polars.
+    ///
+    /// The column chunks are copied as they are, without decoding them; keys written by
+    /// Polars, such as the Arrow schema, take precedence over the kept ones.
+    fn append_to(&self, path: &Path) -> Result<(), String> {
+        let error = |e: ParquetError| format!("Error rewriting {}: {}", path.display(), e);
+        let file =
+            fs::File::open(path).map_err(|e| format!("Error opening {}: {}", path.display(), e))?;
+        let reader = SerializedFileReader::new(file).map_err(error)?;
+        let metadata = reader.metadata();
+
+        let mut key_value = metadata
+            .file_metadata()
+            .key_value_metadata()
+            .cloned()
+            .unwrap_or_default();
+        for kept in &self.key_value {
+            if !key_value.iter().any(|written| written.key == kept.key) {
+                key_value.push(kept.clone());
+            }
+        }
+
+        let rewritten = path.with_extension("footer");
+        let output = fs::File::create(&rewritten)
+            .map_err(|e| format!("Error creating {}: {}", rewritten.display(), e))?;
+        let properties = WriterProperties::builder()
+            .set_key_value_metadata(Some(key_value))
+            .build();
+        let copied = (|| {
+            let mut writer = SerializedFileWriter::new(
+                output,
+                metadata.file_metadata().schema_descr().root_schema_ptr(),
+                Arc::new(properties),
+            )?;
+            let input = fs::File::open(path)?;
+            for row_group in metadata.row_groups() {
+                let mut row_group_writer = writer.next_row_group()?;
+                for column in row_group.columns() {
+                    let close = ColumnCloseResult {
+                        bytes_written: column.compressed_size() as u64,
+                        rows_written: row_group.num_rows() as u64,
+                        metadata: column.clone(),
+                        bloom_filter: None,
+                        column_index: None,
+                        offset_index: None,
+                    };
+                    row_group_writer.append_column(&input, close)?;
+                }
+                row_group_writer.close()?;
+            }
+            writer.close()
+        })();
+
+        match copied {
+            Ok(_) => fs::rename(&rewritten, path)
+                .map_err(|e| format!("Error replacing {}: {}", path.display(), e)),
+            Err(e) => {
+                let _ = fs::remove_file(&rewritten);
+                Err(error(e))
+            }
+        }
+    }
+}
+
+/// Cell being edited in the table.
+#[derive(Debug, Clone)]
+pub struct EditingCell {
+    pub column: String,
+    pub row: usize,
+    /// Text typed so far.
+    pub text: String,
+    /// The cell is null and its text was not typed over: committing it keeps the null.
+    pub null: bool,
+    /// Why the text was refused, if it was.
+    pub error: Option<String>,
+}
+
+/// Edit mode of the table: cells are edited by double-clicking them.
+#[derive(Debug, Clone, Default)]
+pub struct CellEditor {
+    pub enabled: bool,
+    pub changes: Changeset,
+    pub editing: Option<EditingCell>,
+}
+
+impl CellEditor {
+    /// Starts editing a cell from its current value.
+    pub fn start(&mut self, column: &str, row: usize, value: &AnyValue) {
+        self.editing = Some(EditingCell {
+            column: column.to_string(),
+            row,
+            text: cell_text(value),
+            null: value.is_null(),
+            error: None,
+        });
+    }
+
+    /// Records the edited cell if its text is valid; otherwise keeps editing it.
+    pub fn commit(&mut self, df: &DataFrame) {
+        if let Some(mut cell) = self.editing.take() {
+            let result = match cell.null {
+                true => self.changes.set_null(df, &cell.column, cell.row),
+                false => self.changes.set(df, &cell.column, cell.row, &cell.text),
+            };
+            match result {
+                Ok(()) => {}
+                Err(message) => {
+                    cell.error = Some(message);
+                    self.editing = Some(cell);
+                }
+            }
+        }
+    }
+
+    /// Drops the edits, for example when the displayed result changes.
+    pub fn discard(&mut self) {
+        self.changes.clear();
+        self.editing = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changeset_apply() -> Result<(), String> {
+        let df = df![
+            "id" => [1i64, 2, 3, 4],
+            "name" => ["a", "b", "c", "d"],
+            "flag" => [true, false, true, false],
+        ]
+        .map_err(|e| e.to_string())?;
+
+        assert!(parse_cell(&DataType::Int64, "1.5").is_err());
+        assert!(parse_cell(&DataType::Boolean, "maybe").is_err());
+        assert!(parse_cell(&DataType::Date, "2024-02-30").is_err());
+        assert_eq!(
+            parse_cell(&DataType::Date, "2024-02-29")?.dtype(),
+            &DataType::Date
+        );
+
+        let mut changes = Changeset::default();
+        assert!(changes.set(&df, "id", 0, "x").is_err());
+        assert!(changes.set(&df, "id", 4, "5").is_err());
+        assert!(changes.set(&df, "id", 1, "").is_err());
+        changes.set(&df, "id", 3, "40")?;
+        changes.set_null(&df, "id", 1)?;
+        changes.set(&df, "name", 0, "z")?;
+        changes.set(&df, "name", 1, "")?;
+        changes.set(&df, "flag", 2, "false")?;
+        assert_eq!(changes.len(), 5);
+        assert_eq!(changes.get("id", 3), Some(AnyValue::Int64(40)));
+
+        let expected = df![
+            "id" => [Some(1i64), None, Some(3), Some(40)],
+            "name" => ["z", "", "c", "d"],
+            "flag" => [true, false, false, false],
+        ]
+        .map_err(|e| e.to_string())?;
+        let mut edited = changes.apply(&df)?;
+        assert!(edited.equals_missing(&expected));
+
+        // Saving over a file replaces it, keeping its codec and key-value metadata.
+        let path = std::env::temp_dir().join("polars_view_test_edits.parquet");
+        write_dataframe(
+            &mut df.clone(),
+            &path.display().to_string(),
+            Some(Codec::Snappy),
+        )?;
+        let origin = KeyValue::new("origin".to_string(), "erp".to_string());
+        ParquetFooter {
+            codec: Some(Codec::Snappy),
+            key_value: vec![origin.clone()],
+        }
+        .append_to(&path)?;
+        save_edits(&mut edited, &path, true)?;
+        let footer = ParquetFooter::read(&path);
+        let file = fs::File::open(&path).map_err(|e| e.to_string())?;
+        let saved = ParquetReader::new(file).finish().map_err(|e| e.to_string());
+        let _ = fs::remove_file(&path);
+        assert!(saved?.equals_missing(&expected));
+        let footer = footer?;
+        assert_eq!(footer.codec, Some(Codec::Snappy));
+        assert!(footer.key_value.contains(&origin));
+
+        // Saving as an existing unrelated file does not inherit its footer.
+        let unrelated = std::env::temp_dir().join("polars_view_test_edits_unrelated.parquet");
+        write_dataframe(
+            &mut df.clone(),
+            &unrelated.display().to_string(),
+            Some(Codec::Snappy),
+        )?;
+        ParquetFooter {
+            codec: Some(Codec::Snappy),
+            key_value: vec![origin.clone()],
+        }
+        .append_to(&unrelated)?;
+        save_edits(&mut edited, &unrelated, false)?;
+        let footer = ParquetFooter::read(&unrelated);
+        let _ = fs::remove_file(&unrelated);
+        let footer = footer?;
+        assert_ne!(footer.codec, Some(Codec::Snappy));
+        assert!(!footer.key_value.contains(&origin));
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use crate::SqlFunctions;
+
+use polars::prelude::{LazyFrame, ParallelStrategy, ScanArgsParquet};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How the Parquet reader spreads a file over the threads of the Polars pool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParquetParallelism {
+    /// Chosen by Polars from the number of columns and row groups.
+    #[default]
+    Auto,
+    /// One thread per column: suited to wide files with few row groups.
+    Columns,
+    /// One thread per row group: suited to long files.
+    RowGroups,
+    /// Row groups in parallel, reading the filtered columns first.
+    Prefiltered,
+    /// A single thread.
+    None,
+}
+
+impl ParquetParallelism {
+    pub const ALL: [ParquetParallelism; 5] = [
+        ParquetParallelism::Auto,
+        ParquetParallelism::Columns,
+        ParquetParallelism::RowGroups,
+        ParquetParallelism::Prefiltered,
+        ParquetParallelism::None,
+    ];
+
+    pub fn strategy(&self) -> ParallelStrategy {
+        match self {
+            ParquetParallelism::Auto => ParallelStrategy::Auto,
+            ParquetParallelism::Columns => ParallelStrategy::Columns,
+            ParquetParallelism::RowGroups => ParallelStrategy::RowGroups,
+            ParquetParallelism::Prefiltered => ParallelStrategy::Prefiltered,
+            ParquetParallelism::None => ParallelStrategy::None,
+        }
+    }
+}
+
+impl fmt::Display for ParquetParallelism {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParquetParallelism::Auto => write!(f, "Auto"),
+            ParquetParallelism::Columns => write!(f, "Columns"),
+            ParquetParallelism::RowGroups => write!(f, "Row groups"),
+            ParquetParallelism::Prefiltered => write!(f, "Prefiltered"),
+            ParquetParallelism::None => write!(f, "None"),
+        }
+    }
+}
+
+/// Options of the Polars engine used to scan, query and sort the data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EngineOptions {
+    /// Run queries and sorts with the Polars streaming engine, which spills to disk
+    /// (in `POLARS_TEMP_DIR`) when the data does not fit in memory.
+    pub streaming: bool,
+    /// Parallelism of the Parquet reader.
+    pub parallel: ParquetParallelism,
+    /// Read in smaller chunks, trading speed for a lower peak memory.
+    pub low_memory: bool,
+    /// Skip the Parquet row groups whose statistics rule out the query filters.
+    pub use_statistics: bool,
+    /// Apply the query filters while scanning, rather than after.
+    pub predicate_pushdown: bool,
+    /// Read only the columns the query uses.
+    pub projection_pushdown: bool,
+    /// Read only the rows a `LIMIT` or `OFFSET` keeps.
+    pub slice_pushdown: bool,
+    /// Functions added to SQL.
+    pub functions: SqlFunctions,
+}
+
+impl Default for EngineOptions {
+    fn default() -> Self {
+        EngineOptions {
+            streaming: false,
+            parallel: ParquetParallelism::default(),
+            low_memory: false,
+            use_statistics: true,
+            predicate_pushdown: true,
+            projection_pushdown: true,
+            slice_pushdown: true,
+            functions: SqlFunctions::default(),
+        }
+    }
+}
+
+impl EngineOptions {
+    /// Query plan of `lazyframe` with the optimizations and engine chosen.
+    pub fn optimize(&self, lazyframe: LazyFrame) -> LazyFrame {
+        lazyframe
+            .with_predicate_pushdown(self.predicate_pushdown)
+            .with_projection_pushdown(self.projection_pushdown)
+            .with_slice_pushdown(self.slice_pushdown)
+            .with_streaming(self.streaming)
+    }
+
+    /// Arguments to scan Parquet files; globs such as `data/*.parquet` scan several files.
+    pub fn scan_args(&self) -> ScanArgsParquet {
+        ScanArgsParquet {
+            parallel: self.parallel.strategy(),
+            low_memory: self.low_memory,
+            use_statistics: self.use_statistics,
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{Codec, get_extension};
+
+use polars::prelude::*;
+use std::{fs::File, io};
+
+/// Output name writing CSV to the standard output.
+pub const STDOUT: &str = "-";
+
+/// File formats the data can be written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Csv,
+    Parquet,
+    /// Newline-delimited JSON, one object per row.
+    Ndjson,
+    /// Arrow IPC (Feather v2).
+    Ipc,
+}
+
+impl OutputFormat {
+    /// Format of an output file given by its extension; `-` is CSV.
+    pub fn from_path(output: &str) -> Result<Self, String> {
+        if output == STDOUT {
+            return Ok(OutputFormat::Csv);
+        }
+        match get_extension(output).as_deref() {
+            Some("csv") => Ok(OutputFormat::Csv),
+            Some("parquet") => Ok(OutputFormat::Parquet),
+            Some("ndjson" | "jsonl") => Ok(OutputFormat::Ndjson),
+            Some("arrow" | "ipc" | "feather") => Ok(OutputFormat::Ipc),
+            _ => Err(format!(
+                "Unknown output format: {output} \
+                (expected .csv, .parquet, .ndjson, .arrow or -)"
+            )),
+        }
+    }
+}
+
+/// Writes a DataFrame to a file in the format of its extension; `-` writes CSV to stdout.
+///
+/// `compression` applies to Parquet files; without it, Polars uses zstd.
+pub fn write_dataframe(
+    df: &mut DataFrame,
+    output: &str,
+    compression: Option<Codec>,
+) -> Result<(), String> {
+    let format = OutputFormat::from_path(output)?;
+
+    if output == STDOUT {
+        return CsvWriter::new(io::stdout().lock())
+            .finish(df)
+            .map_err(|e| format!("Error writing CSV: {}", e));
+    }
+
+    let file = File::create(output).map_err(|e| format!("Error creating {}: {}", output, e))?;
+    match format {
+        OutputFormat::Csv => CsvWriter::new(file)
+            .finish(df)
+            .map_err(|e| format!("Error writing CSV: {}", e)),
+        OutputFormat::Parquet => {
+            let compression = match compression {
+                Some(codec) => codec.to_parquet(),
+                None => Ok(ParquetCompression::default()),
+            };
+            compression
+                .and_then(|compression| {
+                    ParquetWriter::new(file)
+                        .with_compression(compression)
+                        .finish(df)
+                })
+                .map(|_| ())
+                .map_err(|e| format!("Error writing parquet: {}", e))
+        }
+        OutputFormat::Ndjson => JsonWriter::new(file)
+            .with_json_format(JsonFormat::JsonLines)
+            .finish(df)
+            .map_err(|e| format!("Error writing JSON: {}", e)),
+        OutputFormat::Ipc => IpcWriter::new(file)
+            .finish(df)
+            .map_err(|e| format!("Error writing IPC: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_dataframe() -> Result<(), String> {
+        let mut df =
+            df!["id" => [1i64, 2, 3], "name" => ["a", "b", "c"]].map_err(|e| e.to_string())?;
+
+        for extension in ["csv", "parquet", "ndjson", "arrow"] {
+            let path = std::env::temp_dir().join(format!("polars_view_test_export.{extension}"));
+            let output = path.display().to_string();
+            write_dataframe(&mut df, &output, Some(Codec::Zstd(9)))?;
+
+            let file = File::open(&path).map_err(|e| e.to_string())?;
+            let read = match extension {
+                "csv" => CsvReader::new(file).finish(),
+                "parquet" => ParquetReader::new(file).finish(),
+                "ndjson" => JsonReader::new(file)
+                    .with_json_format(JsonFormat::JsonLines)
+                    .finish(),
+                _ => IpcReader::new(file).finish(),
+            };
+            let _ = std::fs::remove_file(&path);
+            assert!(read.map_err(|e| e.to_string())?.equals(&df));
+        }
+
+        assert_eq!(OutputFormat::from_path(STDOUT), Ok(OutputFormat::Csv));
+        assert!(OutputFormat::from_path("out.xlsx").is_err());
+        Ok(())
+    }
+}
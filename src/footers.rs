@@ -0,0 +1,192 @@
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::UNIX_EPOCH,
+};
+
+/// What the app needs from the footer of a Parquet file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FooterSummary {
+    pub num_rows: i64,
+    /// Number of rows and compressed size of each row group.
+    pub row_groups: Vec<(usize, u64)>,
+}
+
+impl FooterSummary {
+    /// Parses the footer of a Parquet file.
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let file =
+            File::open(path).map_err(|e| format!("Error opening {}: {}", path.display(), e))?;
+        let reader = SerializedFileReader::new(file)
+            .map_err(|e| format!("Error reading parquet metadata: {}", e))?;
+        let metadata = reader.metadata();
+
+        Ok(FooterSummary {
+            num_rows: metadata.file_metadata().num_rows(),
+            row_groups: metadata
+                .row_groups()
+                .iter()
+                .map(|rg| (rg.num_rows() as usize, rg.compressed_size() as u64))
+                .collect(),
+        })
+    }
+}
+
+/// A cached footer, valid while the file keeps its size and modification time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CachedFooter {
+    size: u64,
+    /// Modification time, in nanoseconds since the Unix epoch.
+    modified: u64,
+    summary: FooterSummary,
+}
+
+/// Location of the footer cache of the app, in the cache directory of the user.
+pub fn footer_cache_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("polars-view").join("footers.json"))
+}
+
+/// Parquet footers kept in a local file, so that listing and reopening large datasets
+/// does not parse them again.
+///
+/// Clones share the footers, so that they can be read and saved in the background.
+#[derive(Debug, Clone, Default)]
+pub struct FooterCache {
+    /// Cache file; without one, the footers are only kept in memory.
+    file: Option<PathBuf>,
+    footers: Arc<Mutex<Footers>>,
+}
+
+/// The cached footers, by canonical path.
+#[derive(Debug, Default)]
+struct Footers {
+    entries: BTreeMap<PathBuf, CachedFooter>,
+    /// Entries added since the cache file was written.
+    dirty: bool,
+}
+
+impl FooterCache {
+    /// Reads the cache file, if any, forgetting the files that no longer exist.
+    pub fn new(file: Option<PathBuf>) -> Self {
+        let mut entries: BTreeMap<PathBuf, CachedFooter> = file
+            .as_ref()
+            .and_then(|file| fs::read_to_string(file).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        entries.retain(|path, _| path.exists());
+
+        FooterCache {
+            file,
+            footers: Arc::new(Mutex::new(Footers {
+                entries,
+                dirty: false,
+            })),
+        }
+    }
+
+    fn footers(&self) -> MutexGuard<'_, Footers> {
+        self.footers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The footer of a Parquet file, parsed only if it changed since it was cached.
+    pub fn get(&self, path: &Path) -> Result<FooterSummary, String> {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let metadata =
+            fs::metadata(&path).map_err(|e| format!("Error opening {}: {}", path.display(), e))?;
+        let size = metadata.len();
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+
+        if let Some(cached) = self.footers().entries.get(&path) {
+            if cached.size == size && cached.modified == modified {
+                return Ok(cached.summary.clone());
+            }
+        }
+
+        // Parsed without holding the lock, so that other files can be looked up meanwhile.
+        let summary = FooterSummary::read(&path)?;
+        let cached = CachedFooter {
+            size,
+            modified,
+            summary: summary.clone(),
+        };
+        let mut footers = self.footers();
+        footers.entries.insert(path, cached);
+        footers.dirty = true;
+        Ok(summary)
+    }
+
+    /// Writes the cache file, if footers were added.
+    pub fn save(&self) -> Result<(), String> {
+        let Some(file) = self.file.as_ref() else {
+            return Ok(());
+        };
+
+        let json = {
+            let mut footers = self.footers();
+            if !std::mem::take(&mut footers.dirty) {
+                return Ok(());
+            }
+            serde_json::to_string(&footers.entries)
+                .map_err(|e| format!("Error serializing footer cache: {}", e))?
+        };
+        if let Some(dir) = file.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Error creating {}: {}", dir.display(), e))?;
+        }
+        fs::write(file, json).map_err(|e| format!("Error writing {}: {}", file.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    fn write_parquet(path: &Path, rows: i32) -> Result<(), String> {
+        let mut df = df!["id" => (0..rows).collect::<Vec<i32>>()].map_err(|e| e.to_string())?;
+        let file = File::create(path).map_err(|e| e.to_string())?;
+        ParquetWriter::new(file)
+            .finish(&mut df)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_footer_cache() -> Result<(), String> {
+        let dir =
+            std::env::temp_dir().join(format!("polars_view_test_footers_{}", std::process::id()));
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        let data = dir.join("data.parquet");
+        let cache_file = dir.join("footers.json");
+        write_parquet(&data, 3)?;
+
+        let cache = FooterCache::new(Some(cache_file.clone()));
+        let first = cache.get(&data)?;
+        cache.save()?;
+
+        // A new cache reads the footer from the file, until the data file changes.
+        let reloaded = FooterCache::new(Some(cache_file.clone()));
+        let cached = reloaded.footers().entries.len();
+        let second = reloaded.get(&data)?;
+        let clean = !reloaded.footers().dirty;
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        write_parquet(&data, 5)?;
+        let third = reloaded.get(&data);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(first.num_rows, 3);
+        assert_eq!(first.row_groups.iter().map(|rg| rg.0).sum::<usize>(), 3);
+        assert_eq!((cached, clean), (1, true));
+        assert_eq!(second, first);
+        assert_eq!(third?.num_rows, 5);
+        Ok(())
+    }
+}
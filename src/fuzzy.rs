@@ -0,0 +1,58 @@
+/// Score of `text` for a fuzzy search of `pattern`, or `None` if it does not match.
+///
+/// The characters of the pattern must appear in order, ignoring case and spaces;
+/// consecutive characters and word starts (after `_`, a space or a lowercase letter) score higher.
+pub fn fuzzy_score(pattern: &str, text: &str) -> Option<i32> {
+    let pattern: Vec<char> = pattern
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+
+    let mut score = 0;
+    let mut matched = 0;
+    let mut consecutive = false;
+    let mut previous: Option<char> = None;
+
+    for c in text.chars() {
+        let Some(&wanted) = pattern.get(matched) else {
+            break;
+        };
+
+        if c.to_lowercase().eq(std::iter::once(wanted)) {
+            let word_start = previous
+                .is_none_or(|p| !p.is_alphanumeric() || (p.is_lowercase() && c.is_uppercase()));
+            score += 1 + if consecutive { 5 } else { 0 } + if word_start { 3 } else { 0 };
+            matched += 1;
+            consecutive = true;
+        } else {
+            consecutive = false;
+        }
+        previous = Some(c);
+    }
+
+    // Shorter texts rank first among equal matches.
+    (matched == pattern.len()).then(|| score * 100 - text.chars().count() as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("", "anything").is_some());
+        assert!(fuzzy_score("cid", "customer_id").is_some());
+        assert!(fuzzy_score("CustID", "customer_id").is_some());
+        assert!(fuzzy_score("cxid", "customer_id").is_none());
+        assert!(fuzzy_score("dic", "customer_id").is_none()); // Out of order.
+
+        // Word starts and consecutive characters rank first.
+        let word_starts = fuzzy_score("cid", "customer_id");
+        let scattered = fuzzy_score("cid", "cardinality_index");
+        assert!(word_starts > scattered);
+        assert!(fuzzy_score("price", "unit_price") > fuzzy_score("price", "p_r_i_c_e"));
+        assert!(fuzzy_score("price", "price") > fuzzy_score("price", "unit_price"));
+        assert!(fuzzy_score("oid", "orderId").is_some());
+    }
+}
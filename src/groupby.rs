@@ -0,0 +1,209 @@
+use crate::{DataFilters, split_last_statement};
+
+use egui::Ui;
+use polars::prelude::DataFrame;
+use std::fmt;
+
+/// Aggregate function of a numeric column, added to a grouped result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateKind {
+    Sum,
+    Avg,
+}
+
+impl AggregateKind {
+    pub const ALL: [AggregateKind; 2] = [AggregateKind::Sum, AggregateKind::Avg];
+}
+
+impl fmt::Display for AggregateKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AggregateKind::Sum => write!(f, "SUM"),
+            AggregateKind::Avg => write!(f, "AVG"),
+        }
+    }
+}
+
+/// A result grouped by one column, counting its rows, built on the query it was grouped from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupByQuery {
+    pub column: String,
+    /// Statements run before the query of the result grouped, such as its views.
+    preamble: String,
+    /// Query of the result grouped, without its final semicolon.
+    source: String,
+    /// Numeric columns of the result grouped, which can be aggregated.
+    numeric_columns: Vec<String>,
+    /// Columns of the result grouped, whose names the aliases avoid.
+    columns: Vec<String>,
+    /// Aggregated columns, in order.
+    aggregates: Vec<(AggregateKind, String)>,
+}
+
+/// Quotes a column name for Polars SQL.
+fn quoted(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+/// `name`, or `name_2`, `name_3`... if taken; the alias returned is then taken too.
+fn unique_alias(name: &str, taken: &mut Vec<String>) -> String {
+    let alias = (1..)
+        .map(|n| match n {
+            1 => name.to_string(),
+            n => format!("{name}_{n}"),
+        })
+        .find(|alias| !taken.contains(alias))
+        .unwrap_or_default();
+    taken.push(alias.clone());
+    alias
+}
+
+impl GroupByQuery {
+    /// Groups the result `df` of `filters` by `column`.
+    pub fn new(column: impl ToString, df: &DataFrame, filters: &DataFilters) -> Self {
+        // The last statement of a script is grouped, after the others.
+        let (preamble, source) = split_last_statement(filters.query.as_deref().unwrap_or(""));
+        let source = source.unwrap_or_else(|| format!("SELECT * FROM {}", filters.table_name));
+        let column = column.to_string();
+        let numeric_columns = df
+            .get_columns()
+            .iter()
+            .filter(|c| c.dtype().is_primitive_numeric() && c.name().as_str() != column)
+            .map(|c| c.name().to_string())
+            .collect();
+        let columns = df
+            .get_column_names()
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+
+        GroupByQuery {
+            column,
+            preamble,
+            source,
+            numeric_columns,
+            columns,
+            aggregates: Vec::new(),
+        }
+    }
+
+    /// Adds an aggregated column, unless already there.
+    pub fn add(&mut self, kind: AggregateKind, column: impl ToString) {
+        let aggregate = (kind, column.to_string());
+        if !self.aggregates.contains(&aggregate) {
+            self.aggregates.push(aggregate);
+        }
+    }
+
+    /// Query of the grouped result, most frequent values first. The aliases of the count and
+    /// of the aggregates get a suffix if the result grouped has columns of the same name.
+    pub fn sql(&self) -> String {
+        let mut taken = self.columns.clone();
+        let column = quoted(&self.column);
+        let count = quoted(&unique_alias("count_rows", &mut taken));
+        let mut select = vec![column.clone(), format!("COUNT(*) AS {count}")];
+        for (kind, name) in &self.aggregates {
+            let alias = format!("{} {}", kind.to_string().to_lowercase(), name);
+            let alias = quoted(&unique_alias(&alias, &mut taken));
+            select.push(format!("{kind}({}) AS {alias}", quoted(name)));
+        }
+        format!(
+            "{}SELECT {} FROM ({}) AS grouped GROUP BY {column} ORDER BY {count} DESC;",
+            self.preamble,
+            select.join(", "),
+            self.source
+        )
+    }
+
+    /// Whether `filters` show this grouped result.
+    pub fn shown_by(&self, filters: &DataFilters) -> bool {
+        filters.query.as_deref() == Some(self.sql().as_str())
+    }
+
+    /// Menu adding SUM and AVG columns; returns true if one was added.
+    pub fn render_menu(&mut self, ui: &mut Ui) -> bool {
+        let mut added = None;
+        ui.menu_button(format!("Σ Grouped by {}", self.column), |ui| {
+            if self.numeric_columns.is_empty() {
+                ui.label("No numeric column to aggregate");
+            }
+            for kind in AggregateKind::ALL {
+                ui.add_enabled_ui(!self.numeric_columns.is_empty(), |ui| {
+                    ui.menu_button(format!("Add {kind}"), |ui| {
+                        for name in &self.numeric_columns {
+                            let present = self.aggregates.contains(&(kind, name.clone()));
+                            if ui.add_enabled(!present, egui::Button::new(name)).clicked() {
+                                added = Some((kind, name.clone()));
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                });
+            }
+        });
+
+        match added {
+            Some((kind, name)) => {
+                self.add(kind, name);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SqlFunctions, execute_statements};
+    use polars::{prelude::*, sql::SQLContext};
+
+    #[test]
+    fn test_group_by_query() -> Result<(), String> {
+        let df = df![
+            "region" => ["north", "south", "north", "north"],
+            "sales" => [10.0, 20.0, 30.0, 50.0],
+            "units" => [1, 2, 3, 4],
+            "count_rows" => [7, 7, 7, 7],
+        ]
+        .map_err(|e| e.to_string())?;
+        let filters = DataFilters {
+            query: Some(
+                "CREATE VIEW sold AS SELECT * FROM AllData WHERE units > 1;\nSELECT * FROM sold;"
+                    .to_string(),
+            ),
+            ..DataFilters::new("sales.parquet")
+        };
+
+        let mut group_by = GroupByQuery::new("region", &df, &filters);
+        assert_eq!(group_by.numeric_columns, ["sales", "units", "count_rows"]);
+        group_by.add(AggregateKind::Sum, "sales");
+        group_by.add(AggregateKind::Avg, "units");
+        group_by.add(AggregateKind::Sum, "sales");
+
+        let mut ctx = SQLContext::new();
+        ctx.register("AllData", df.lazy());
+        let result = execute_statements(&mut ctx, &group_by.sql(), SqlFunctions::default())?
+            .collect()
+            .map_err(|e| e.to_string())?;
+
+        // The filter of the source query applies: "north" keeps 2 rows, "south" 1.
+        assert_eq!(
+            result.get_column_names(),
+            ["region", "count_rows_2", "sum sales", "avg units"]
+        );
+        assert_eq!(result.height(), 2);
+        let row = result.get_row(0).map_err(|e| e.to_string())?.0;
+        assert_eq!(row[0], AnyValue::String("north"));
+        assert_eq!(row[1].extract::<u32>(), Some(2));
+        assert_eq!(row[2].extract::<f64>(), Some(80.0));
+        assert_eq!(row[3].extract::<f64>(), Some(3.5));
+
+        let shown = DataFilters {
+            query: Some(group_by.sql()),
+            ..filters.clone()
+        };
+        assert!(group_by.shown_by(&shown) && !group_by.shown_by(&filters));
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{ColumnMasks, DataFilters};
+
+/// Number of view states kept for undo.
+pub const HISTORY_SIZE: usize = 100;
+
+/// What the user chose to see of a file: its query, sorts, column transforms and masks.
+#[derive(Debug, Clone)]
+pub struct ViewState {
+    pub filters: DataFilters,
+    pub masks: ColumnMasks,
+}
+
+impl ViewState {
+    /// Whether both states show the same view, whatever the engine options.
+    pub fn same_view(&self, other: &ViewState) -> bool {
+        self.same_data(other) && self.masks == other.masks
+    }
+
+    /// Whether both states show the same rows and columns, so that only the masks may differ.
+    pub fn same_data(&self, other: &ViewState) -> bool {
+        let (a, b) = (&self.filters, &other.filters);
+        a.query == b.query
+            && a.sort == b.sort
+            && a.then_by == b.then_by
+            && a.transforms == b.transforms
+    }
+}
+
+/// Undo and redo history of the view states of the open file.
+#[derive(Debug, Default)]
+pub struct ViewHistory {
+    states: Vec<ViewState>,
+    /// Index of the state shown.
+    position: usize,
+}
+
+impl ViewHistory {
+    /// Forgets the states, as for another file.
+    pub fn clear(&mut self) {
+        self.states.clear();
+        self.position = 0;
+    }
+
+    /// Records the state shown, dropping the states undone before it.
+    ///
+    /// The state restored by an undo or redo is already the current one, and is not recorded again.
+    pub fn record(&mut self, state: ViewState) {
+        if self
+            .states
+            .get(self.position)
+            .is_some_and(|current| current.same_view(&state))
+        {
+            return;
+        }
+
+        if !self.states.is_empty() {
+            self.states.truncate(self.position + 1);
+        }
+        self.states.push(state);
+        if self.states.len() > HISTORY_SIZE {
+            self.states.remove(0);
+        }
+        self.position = self.states.len() - 1;
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.position > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.position + 1 < self.states.len()
+    }
+
+    /// The state before the current one, which becomes the current state.
+    pub fn undo(&mut self) -> Option<ViewState> {
+        self.can_undo().then(|| {
+            self.position -= 1;
+            self.states[self.position].clone()
+        })
+    }
+
+    /// The state undone last, which becomes the current state.
+    pub fn redo(&mut self) -> Option<ViewState> {
+        self.can_redo().then(|| {
+            self.position += 1;
+            self.states[self.position].clone()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MaskKind, SortState};
+
+    fn state(query: &str) -> ViewState {
+        ViewState {
+            filters: DataFilters {
+                query: Some(query.to_string()),
+                ..Default::default()
+            },
+            masks: ColumnMasks::default(),
+        }
+    }
+
+    fn query(state: Option<ViewState>) -> Option<String> {
+        state.and_then(|state| state.filters.query)
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut history = ViewHistory::default();
+        history.record(state("a"));
+        history.record(state("b"));
+        history.record(state("b")); // Reloading the same view adds nothing.
+
+        let mut sorted = state("b");
+        sorted.filters.sort = Some(SortState::Ascending("id".to_string()));
+        history.record(sorted);
+
+        assert_eq!(query(history.undo()), Some("b".to_string()));
+        assert_eq!(query(history.undo()), Some("a".to_string()));
+        assert!(history.undo().is_none());
+
+        // The result of the undo is recorded as the current state, leaving the redo.
+        history.record(state("a"));
+        assert_eq!(query(history.redo()), Some("b".to_string()));
+
+        // A new state drops the states undone.
+        let mut masked = state("b");
+        masked.masks.set("email", Some(MaskKind::Redact));
+        assert!(!masked.same_view(&state("b")) && masked.same_data(&state("b")));
+        history.record(masked);
+        assert!(!history.can_redo());
+        assert!(
+            history
+                .undo()
+                .is_some_and(|state| state.masks.masks.is_empty())
+        );
+    }
+}
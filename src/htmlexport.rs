@@ -0,0 +1,145 @@
+use crate::{TableViewOptions, cell_renderer, escape_html, format_column_cell};
+
+use polars::prelude::DataFrame;
+use std::{fmt::Write, fs, path::Path};
+
+/// Style of the exported page, kept inside it so that it displays the same anywhere.
+const HTML_STYLE: &str = "\
+    body { font-family: sans-serif; margin: 2em; color: #222; }\
+    h1 { font-size: 1.4em; margin-bottom: 0.2em; }\
+    .meta { color: #666; margin: 0.2em 0; }\
+    pre { background: #f5f5f5; padding: 0.6em; border-radius: 4px; white-space: pre-wrap; }\
+    table { border-collapse: collapse; font-size: 0.9em; }\
+    th { background: #4a90d9; color: white; text-align: left; }\
+    th, td { border: 1px solid #ccc; padding: 3px 8px; }\
+    tr:nth-child(even) td { background: #f3f7fc; }\
+    td.num { text-align: right; font-variant-numeric: tabular-nums; }";
+
+/// Renders `df` as a standalone HTML page, formatted as in the table, under a header with
+/// the title, the query of the result and the time of the export.
+///
+/// `df` holds the exported rows of a result of `total_rows` rows, from `first_row` on.
+pub fn html_page(
+    df: &DataFrame,
+    first_row: usize,
+    total_rows: usize,
+    title: &str,
+    query: Option<&str>,
+    timestamp: &str,
+    options: &TableViewOptions,
+) -> String {
+    let title = escape_html(title);
+    let mut html = String::new();
+
+    let _ = writeln!(
+        html,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>"
+    );
+    let _ = writeln!(html, "<h1>{title}</h1>");
+    let _ = writeln!(
+        html,
+        "<p class=\"meta\">Exported on {}</p>",
+        escape_html(timestamp)
+    );
+    let shown = match df.height() == total_rows {
+        true => format!("{total_rows} rows"),
+        false => format!(
+            "Rows {}–{} of {total_rows}",
+            first_row + 1,
+            first_row + df.height()
+        ),
+    };
+    let _ = writeln!(
+        html,
+        "<p class=\"meta\">{shown}, {} columns</p>",
+        df.width()
+    );
+    if let Some(query) = query {
+        let _ = writeln!(html, "<pre>{}</pre>", escape_html(query));
+    }
+
+    html.push_str("<table>\n<tr>");
+    for name in df.get_column_names() {
+        let _ = write!(html, "<th>{}</th>", escape_html(name));
+    }
+    html.push_str("</tr>\n");
+
+    let renderers: Vec<_> = df
+        .get_columns()
+        .iter()
+        .map(|column| cell_renderer(column.name(), column.dtype()))
+        .collect();
+    for row in 0..df.height() {
+        html.push_str("<tr>");
+        for (column, renderer) in df.get_columns().iter().zip(&renderers) {
+            let class = match column.dtype().is_primitive_numeric() {
+                true => " class=\"num\"",
+                false => "",
+            };
+            let text = format_column_cell(column, row, renderer.as_ref(), options);
+            let _ = write!(html, "<td{class}>{}</td>", escape_html(&text));
+        }
+        html.push_str("</tr>\n");
+    }
+
+    html.push_str("</table>\n</body>\n</html>\n");
+    html
+}
+
+/// Writes `df`, the rows of a result from `first_row` on, to `path` as a standalone HTML page,
+/// stamped with the local time.
+pub fn export_html(
+    df: &DataFrame,
+    first_row: usize,
+    total_rows: usize,
+    title: &str,
+    query: Option<&str>,
+    options: &TableViewOptions,
+    path: &Path,
+) -> Result<(), String> {
+    let timestamp = chrono::Local::now()
+        .format("%Y-%m-%d %H:%M:%S %:z")
+        .to_string();
+    let html = html_page(df, first_row, total_rows, title, query, &timestamp, options);
+    fs::write(path, html).map_err(|e| format!("Error writing {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_html_page() -> Result<(), String> {
+        let df = df![
+            "name" => ["a", "<b>", "c"],
+            "Valor" => [1234.5, 2.0, 3.25],
+        ]
+        .map_err(|e| e.to_string())?;
+        let options = TableViewOptions::default();
+
+        let html = html_page(
+            &df.slice(1, 2),
+            1,
+            3,
+            "sales.parquet",
+            Some("SELECT * FROM AllData WHERE x < 2"),
+            "2026-10-15 09:30:00 -03:00",
+            &options,
+        );
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h1>sales.parquet</h1>"));
+        assert!(html.contains("Exported on 2026-10-15 09:30:00 -03:00"));
+        assert!(html.contains("Rows 2–3 of 3, 2 columns"));
+        assert!(html.contains("<pre>SELECT * FROM AllData WHERE x &lt; 2</pre>"));
+        assert!(html.contains("<th>name</th><th>Valor</th>"));
+        // Only the rows asked for, escaped and formatted as in the table.
+        assert!(html.contains("<td>&lt;b&gt;</td><td class=\"num\">2"));
+        assert!(!html.contains("<td>a</td>"));
+        assert_eq!(html.matches("<tr>").count(), 3);
+
+        let html = html_page(&df, 0, 3, "sales.parquet", None, "now", &options);
+        assert!(html.contains("3 rows, 2 columns") && !html.contains("<pre>"));
+        Ok(())
+    }
+}
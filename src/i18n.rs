@@ -0,0 +1,642 @@
+use egui::{Context, Id};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt, sync::LazyLock};
+
+/// Language of the user interface.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    /// Brazilian Portuguese.
+    Portuguese,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::English, Language::Portuguese, Language::Spanish];
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Language::English => write!(f, "English"),
+            Language::Portuguese => write!(f, "Português (Brasil)"),
+            Language::Spanish => write!(f, "Español"),
+        }
+    }
+}
+
+/// Id of the language in the data of the egui context, read by the widgets of every window.
+fn language_id() -> Id {
+    Id::new("language")
+}
+
+/// Switches the user interface of the egui context to another language.
+pub fn set_language(ctx: &Context, language: Language) {
+    ctx.data_mut(|data| data.insert_temp(language_id(), language));
+}
+
+/// Language of the user interface, as set in the settings applied last.
+pub fn language(ctx: &Context) -> Language {
+    ctx.data(|data| data.get_temp(language_id()))
+        .unwrap_or_default()
+}
+
+/// Text of the user interface in English, with its Portuguese and Spanish translations.
+const CATALOG: &[(&str, &str, &str)] = &[
+    // Menus.
+    ("File", "Arquivo", "Archivo"),
+    ("Edit", "Editar", "Editar"),
+    ("View", "Exibir", "Ver"),
+    ("Open", "Abrir", "Abrir"),
+    ("Close", "Fechar", "Cerrar"),
+    ("Open recent", "Abrir recente", "Abrir reciente"),
+    ("Clear list", "Limpar lista", "Borrar lista"),
+    ("Open session…", "Abrir sessão…", "Abrir sesión…"),
+    ("Save session…", "Salvar sessão…", "Guardar sesión…"),
+    ("Import CSV…", "Importar CSV…", "Importar CSV…"),
+    ("Compare files…", "Comparar arquivos…", "Comparar archivos…"),
+    (
+        "Schema diff…",
+        "Diferenças de esquema…",
+        "Diferencias de esquema…",
+    ),
+    (
+        "Row diff…",
+        "Diferenças de linhas…",
+        "Diferencias de filas…",
+    ),
+    ("Export report…", "Exportar relatório…", "Exportar informe…"),
+    ("Export HTML", "Exportar HTML", "Exportar HTML"),
+    ("Visible rows…", "Linhas visíveis…", "Filas visibles…"),
+    ("All rows…", "Todas as linhas…", "Todas las filas…"),
+    ("Export PDF…", "Exportar PDF…", "Exportar PDF…"),
+    ("Settings", "Configurações", "Configuración"),
+    ("About", "Sobre", "Acerca de"),
+    ("A fork of ", "Uma derivação de ", "Una bifurcación de "),
+    ("Powered by ", "Movido por ", "Impulsado por "),
+    ("Built with ", "Feito com ", "Hecho con "),
+    ("Quit", "Sair", "Salir"),
+    ("Panels", "Painéis", "Paneles"),
+    (
+        "Reset layout",
+        "Restaurar disposição",
+        "Restablecer disposición",
+    ),
+    ("Log panel", "Painel de log", "Panel de registro"),
+    ("Mask columns", "Mascarar colunas", "Enmascarar columnas"),
+    ("Split view", "Visão dividida", "Vista dividida"),
+    ("Table image", "Imagem da tabela", "Imagen de la tabla"),
+    (
+        "Copy to clipboard",
+        "Copiar para a área de transferência",
+        "Copiar al portapapeles",
+    ),
+    ("Save as PNG…", "Salvar como PNG…", "Guardar como PNG…"),
+    ("Apply masks", "Aplicar máscaras", "Aplicar máscaras"),
+    ("Striped rows", "Linhas zebradas", "Filas alternadas"),
+    (
+        "Column type icons",
+        "Ícones de tipo das colunas",
+        "Iconos de tipo de columna",
+    ),
+    (
+        "Highlight hovered row",
+        "Destacar linha sob o cursor",
+        "Resaltar fila bajo el cursor",
+    ),
+    ("Zoom in", "Ampliar", "Acercar"),
+    ("Zoom out", "Reduzir", "Alejar"),
+    ("Reset", "Restaurar", "Restablecer"),
+    ("Undo", "Desfazer", "Deshacer"),
+    ("Redo", "Refazer", "Rehacer"),
+    ("Edit mode", "Modo de edição", "Modo de edición"),
+    (
+        "Computed columns…",
+        "Colunas calculadas…",
+        "Columnas calculadas…",
+    ),
+    (
+        "Find and replace…",
+        "Localizar e substituir…",
+        "Buscar y reemplazar…",
+    ),
+    ("Apply recipe…", "Aplicar receita…", "Aplicar receta…"),
+    ("Save recipe…", "Salvar receita…", "Guardar receta…"),
+    ("Scripts", "Scripts", "Scripts"),
+    ("No scripts", "Nenhum script", "Ningún script"),
+    (
+        "Save over original",
+        "Salvar sobre o original",
+        "Guardar sobre el original",
+    ),
+    ("Save as…", "Salvar como…", "Guardar como…"),
+    ("Discard edits", "Descartar edições", "Descartar ediciones"),
+    // Context menus.
+    (
+        "Move to Left panel",
+        "Mover para o painel esquerdo",
+        "Mover al panel izquierdo",
+    ),
+    (
+        "Move to Right panel",
+        "Mover para o painel direito",
+        "Mover al panel derecho",
+    ),
+    (
+        "Move to Floating window",
+        "Mover para uma janela flutuante",
+        "Mover a una ventana flotante",
+    ),
+    ("Move to Hidden", "Ocultar", "Ocultar"),
+    ("Move up", "Mover para cima", "Subir"),
+    ("Move down", "Mover para baixo", "Bajar"),
+    ("Histogram", "Histograma", "Histograma"),
+    (
+        "Only available for numeric columns",
+        "Disponível apenas para colunas numéricas",
+        "Disponible solo para columnas numéricas",
+    ),
+    (
+        "Set the cell to null",
+        "Definir a célula como nula",
+        "Establecer la celda como nula",
+    ),
+    (
+        "Unavailable while the column is masked",
+        "Indisponível enquanto a coluna estiver mascarada",
+        "No disponible mientras la columna esté enmascarada",
+    ),
+    ("Value counts", "Contagem de valores", "Recuento de valores"),
+    (
+        "Group by this column",
+        "Agrupar por esta coluna",
+        "Agrupar por esta columna",
+    ),
+    (
+        "Only available without renamed or cast columns",
+        "Disponível apenas sem colunas renomeadas ou convertidas",
+        "Disponible solo sin columnas renombradas o convertidas",
+    ),
+    ("Rename…", "Renomear…", "Renombrar…"),
+    ("Rename", "Renomear", "Renombrar"),
+    ("Mask", "Máscara", "Máscara"),
+    ("None", "Nenhuma", "Ninguna"),
+    ("Cast to", "Converter para", "Convertir a"),
+    ("Remove bookmark", "Remover marcador", "Quitar marcador"),
+    ("Bookmark row", "Marcar linha", "Marcar fila"),
+    // Menu hints.
+    (
+        "Close the file and release its memory",
+        "Fechar o arquivo e liberar sua memória",
+        "Cerrar el archivo y liberar su memoria",
+    ),
+    (
+        "Reopen the tables and view saved in a session file",
+        "Reabrir as tabelas e a visão salvas em um arquivo de sessão",
+        "Reabrir las tablas y la vista guardadas en un archivo de sesión",
+    ),
+    (
+        "Save the open table, its query and the view settings",
+        "Salvar a tabela aberta, sua consulta e as opções de visão",
+        "Guardar la tabla abierta, su consulta y las opciones de vista",
+    ),
+    (
+        "Review the inferred schema of a CSV file before loading it",
+        "Revisar o esquema inferido de um arquivo CSV antes de carregá-lo",
+        "Revisar el esquema inferido de un archivo CSV antes de cargarlo",
+    ),
+    (
+        "Compare the metadata of two Parquet files",
+        "Comparar os metadados de dois arquivos Parquet",
+        "Comparar los metadatos de dos archivos Parquet",
+    ),
+    (
+        "List the schema changes between two files",
+        "Listar as mudanças de esquema entre dois arquivos",
+        "Listar los cambios de esquema entre dos archivos",
+    ),
+    (
+        "List the rows added, removed or changed between two Parquet files",
+        "Listar as linhas adicionadas, removidas ou alteradas entre dois arquivos Parquet",
+        "Listar las filas añadidas, eliminadas o modificadas entre dos archivos Parquet",
+    ),
+    (
+        "Export a profiling report (HTML or Markdown)",
+        "Exportar um relatório de perfil (HTML ou Markdown)",
+        "Exportar un informe de perfil (HTML o Markdown)",
+    ),
+    (
+        "Export the rows shown in the table as an HTML page",
+        "Exportar as linhas exibidas na tabela como uma página HTML",
+        "Exportar las filas mostradas en la tabla como una página HTML",
+    ),
+    (
+        "Export the whole result as an HTML page",
+        "Exportar todo o resultado como uma página HTML",
+        "Exportar todo el resultado como una página HTML",
+    ),
+    (
+        "Print the result on paginated pages, with the header row on each page",
+        "Imprimir o resultado em páginas, com o cabeçalho em cada página",
+        "Imprimir el resultado en páginas, con la fila de encabezado en cada página",
+    ),
+    (
+        "Hide the values of the columns masked from their header menu, also in the exports",
+        "Ocultar os valores das colunas mascaradas no menu do cabeçalho, também nas exportações",
+        "Ocultar los valores de las columnas enmascaradas desde su menú de encabezado, también en las exportaciones",
+    ),
+    (
+        "Mask a column from its header menu first",
+        "Mascare antes uma coluna no menu do cabeçalho",
+        "Enmascare antes una columna desde su menú de encabezado",
+    ),
+    (
+        "Pin the current result beside the table, to compare it with the next query or file",
+        "Fixar o resultado atual ao lado da tabela, para compará-lo com a próxima consulta ou arquivo",
+        "Fijar el resultado actual junto a la tabla, para compararlo con la siguiente consulta o archivo",
+    ),
+    (
+        "Mask the masked columns in the image, even while masking is off",
+        "Mascarar as colunas mascaradas na imagem, mesmo com as máscaras desligadas",
+        "Enmascarar las columnas enmascaradas en la imagen, aun con las máscaras desactivadas",
+    ),
+    (
+        "Show the type of each column, and ∅ if it has nulls, next to its name",
+        "Mostrar o tipo de cada coluna, e ∅ se tiver nulos, ao lado do nome",
+        "Mostrar el tipo de cada columna, y ∅ si tiene nulos, junto a su nombre",
+    ),
+    (
+        "Ctrl+scroll over the table to zoom",
+        "Ctrl+rolagem sobre a tabela para ampliar",
+        "Ctrl+desplazamiento sobre la tabla para ampliar",
+    ),
+    (
+        "Step back the last query, sort, column change or mask",
+        "Desfazer a última consulta, ordenação, mudança de coluna ou máscara",
+        "Deshacer la última consulta, ordenación, cambio de columna o máscara",
+    ),
+    (
+        "Double-click a cell to edit it; Enter validates, Escape cancels",
+        "Clique duas vezes em uma célula para editá-la; Enter confirma, Escape cancela",
+        "Haga doble clic en una celda para editarla; Intro confirma, Escape cancela",
+    ),
+    (
+        "Add columns computed with a SQL expression, e.g. price * qty",
+        "Adicionar colunas calculadas com uma expressão SQL, p. ex. price * qty",
+        "Añadir columnas calculadas con una expresión SQL, p. ej. price * qty",
+    ),
+    (
+        "Replace text, or a regex, in string columns",
+        "Substituir texto, ou uma regex, em colunas de texto",
+        "Reemplazar texto, o una regex, en columnas de texto",
+    ),
+    (
+        "Append the pipeline saved in a recipe file",
+        "Acrescentar o pipeline salvo em um arquivo de receita",
+        "Añadir el pipeline guardado en un archivo de receta",
+    ),
+    (
+        "unsaved edits",
+        "edições não salvas",
+        "ediciones sin guardar",
+    ),
+    (
+        "Save the edited result to a new file",
+        "Salvar o resultado editado em um novo arquivo",
+        "Guardar el resultado editado en un archivo nuevo",
+    ),
+    (
+        "Replace the Parquet file with the edited data",
+        "Substituir o arquivo Parquet pelos dados editados",
+        "Reemplazar el archivo Parquet por los datos editados",
+    ),
+    (
+        "Only available with edits to a whole Parquet file (no query, sort or transform)",
+        "Disponível apenas com edições de um arquivo Parquet inteiro (sem consulta, ordenação ou transformação)",
+        "Disponible solo con ediciones de un archivo Parquet completo (sin consulta, ordenación ni transformación)",
+    ),
+    // Panes.
+    ("Metadata", "Metadados", "Metadatos"),
+    ("Query", "Consulta", "Consulta"),
+    ("Schema", "Esquema", "Esquema"),
+    ("Row Groups", "Grupos de linhas", "Grupos de filas"),
+    ("Bloom Filters", "Filtros de Bloom", "Filtros de Bloom"),
+    ("Statistics", "Estatísticas", "Estadísticas"),
+    ("Missing Values", "Valores ausentes", "Valores faltantes"),
+    ("Duplicates", "Duplicatas", "Duplicados"),
+    ("Pivot", "Tabela dinâmica", "Tabla dinámica"),
+    ("Compression", "Compressão", "Compresión"),
+    ("Benchmark", "Desempenho", "Rendimiento"),
+    ("Tasks", "Tarefas", "Tareas"),
+    ("Pipeline", "Pipeline", "Canalización"),
+    ("Files", "Arquivos", "Archivos"),
+    // Actions.
+    ("Open file", "Abrir arquivo", "Abrir archivo"),
+    ("Close file", "Fechar arquivo", "Cerrar archivo"),
+    ("Open session", "Abrir sessão", "Abrir sesión"),
+    ("Save session", "Salvar sessão", "Guardar sesión"),
+    ("Run query", "Executar consulta", "Ejecutar consulta"),
+    ("Edit query", "Editar consulta", "Editar consulta"),
+    ("Cancel loading", "Cancelar carregamento", "Cancelar carga"),
+    ("Export report", "Exportar relatório", "Exportar informe"),
+    (
+        "Toggle table/chart",
+        "Alternar tabela/gráfico",
+        "Alternar tabla/gráfico",
+    ),
+    (
+        "Undo view change",
+        "Desfazer mudança de visão",
+        "Deshacer cambio de vista",
+    ),
+    (
+        "Redo view change",
+        "Refazer mudança de visão",
+        "Rehacer cambio de vista",
+    ),
+    ("Next bookmark", "Próximo marcador", "Siguiente marcador"),
+    (
+        "Previous bookmark",
+        "Marcador anterior",
+        "Marcador anterior",
+    ),
+    // Query pane.
+    ("Count rows", "Contar linhas", "Contar filas"),
+    (
+        "Count the rows of the query without loading its result, to decide whether to add a LIMIT",
+        "Contar as linhas da consulta sem carregar o resultado, para decidir se é preciso um LIMIT",
+        "Contar las filas de la consulta sin cargar su resultado, para decidir si añadir un LIMIT",
+    ),
+    (
+        "Open the result in a new tab",
+        "Abrir o resultado em uma nova aba",
+        "Abrir el resultado en una pestaña nueva",
+    ),
+    (
+        "Keep the current result in its tab when applying a query, as Shift+Apply does",
+        "Manter o resultado atual em sua aba ao aplicar uma consulta, como faz Shift+Aplicar",
+        "Mantener el resultado actual en su pestaña al aplicar una consulta, como hace Mayús+Aplicar",
+    ),
+    ("Temp views", "Visões temporárias", "Vistas temporales"),
+    ("Drop the view", "Excluir a visão", "Eliminar la vista"),
+    ("Create view", "Criar visão", "Crear vista"),
+    (
+        "Keep the current result under this name, as CREATE VIEW would",
+        "Manter o resultado atual com este nome, como faria CREATE VIEW",
+        "Mantener el resultado actual con este nombre, como haría CREATE VIEW",
+    ),
+    // Pivot and duplicates panes.
+    ("Rows:", "Linhas:", "Filas:"),
+    ("Rows", "Linhas", "Filas"),
+    ("Columns:", "Colunas:", "Columnas:"),
+    ("Values:", "Valores:", "Valores:"),
+    ("(rows)", "(linhas)", "(filas)"),
+    ("Aggregation:", "Agregação:", "Agregación:"),
+    ("none", "nenhuma", "ninguna"),
+    ("Key columns:", "Colunas-chave:", "Columnas clave:"),
+    ("All columns", "Todas as colunas", "Todas las columnas"),
+    ("Count duplicates", "Contar duplicatas", "Contar duplicados"),
+    (
+        "Show only duplicates",
+        "Mostrar só duplicatas",
+        "Mostrar solo duplicados",
+    ),
+    (
+        "Filter the table to the duplicated rows",
+        "Filtrar a tabela para as linhas duplicadas",
+        "Filtrar la tabla a las filas duplicadas",
+    ),
+    ("duplicated rows", "linhas duplicadas", "filas duplicadas"),
+    ("extra copies", "cópias extras", "copias extra"),
+    // Central view.
+    ("Table", "Tabela", "Tabla"),
+    ("Chart", "Gráfico", "Gráfico"),
+    ("Time series", "Série temporal", "Serie temporal"),
+    (
+        "Unavailable while columns are masked",
+        "Indisponível enquanto houver colunas mascaradas",
+        "No disponible mientras haya columnas enmascaradas",
+    ),
+    (
+        "The result has no columns.",
+        "O resultado não tem colunas.",
+        "El resultado no tiene columnas.",
+    ),
+    (
+        "Drag and drop parquet file here.",
+        "Arraste e solte um arquivo parquet aqui.",
+        "Arrastre y suelte un archivo parquet aquí.",
+    ),
+    (
+        "Close the split view",
+        "Fechar a visão dividida",
+        "Cerrar la vista dividida",
+    ),
+    (
+        "Synchronize scrolling",
+        "Sincronizar a rolagem",
+        "Sincronizar el desplazamiento",
+    ),
+    ("(row number)", "(número da linha)", "(número de fila)"),
+    (
+        "Align the rows of both tables by the values of a column",
+        "Alinhar as linhas das duas tabelas pelos valores de uma coluna",
+        "Alinear las filas de ambas tablas por los valores de una columna",
+    ),
+    (
+        "Columns are renamed and cast in the main table only",
+        "As colunas são renomeadas e convertidas apenas na tabela principal",
+        "Las columnas se renombran y convierten solo en la tabla principal",
+    ),
+    // Status bar.
+    ("rows", "linhas", "filas"),
+    ("columns", "colunas", "columnas"),
+    (
+        "Estimated memory of the displayed result",
+        "Memória estimada do resultado exibido",
+        "Memoria estimada del resultado mostrado",
+    ),
+    (
+        "no file set",
+        "nenhum arquivo aberto",
+        "ningún archivo abierto",
+    ),
+    ("Memory", "Memória", "Memoria"),
+    ("load limit", "limite de carga", "límite de carga"),
+    (
+        "The limit refuses to load files estimated over it; it does not cap the memory used afterwards",
+        "O limite recusa carregar arquivos estimados acima dele; não limita a memória usada depois",
+        "El límite rechaza cargar archivos estimados por encima de él; no limita la memoria usada después",
+    ),
+    // Screen readers.
+    ("Column", "Coluna", "Columna"),
+    ("sorted ascending", "ordem crescente", "orden ascendente"),
+    (
+        "sorted descending",
+        "ordem decrescente",
+        "orden descendente",
+    ),
+    ("not sorted", "sem ordenação", "sin ordenar"),
+    ("row", "linha", "fila"),
+    // Settings.
+    ("Theme:", "Tema:", "Tema:"),
+    ("Dark", "Escuro", "Oscuro"),
+    ("Light", "Claro", "Claro"),
+    ("System", "Sistema", "Sistema"),
+    ("Language:", "Idioma:", "Idioma:"),
+    ("Font size:", "Tamanho da fonte:", "Tamaño de fuente:"),
+    ("Number format:", "Formato numérico:", "Formato numérico:"),
+    ("Decimals:", "Casas decimais:", "Decimales:"),
+    (
+        "Scientific notation:",
+        "Notação científica:",
+        "Notación científica:",
+    ),
+    ("≥ 1e", "≥ 1e", "≥ 1e"),
+    ("or < 1e", "ou < 1e", "o < 1e"),
+    ("Row limit:", "Limite de linhas:", "Límite de filas:"),
+    ("Notify after:", "Notificar após:", "Notificar tras:"),
+    ("Reopen files:", "Reabrir arquivos:", "Reabrir archivos:"),
+    (
+        "Restore last state",
+        "Restaurar último estado",
+        "Restaurar último estado",
+    ),
+    ("Low power:", "Economia de energia:", "Ahorro de energía:"),
+    (
+        "Reduce repaints",
+        "Reduzir redesenhos",
+        "Reducir redibujados",
+    ),
+    (
+        "Row density:",
+        "Densidade das linhas:",
+        "Densidad de filas:",
+    ),
+    ("Compact", "Compacta", "Compacta"),
+    ("Comfortable", "Confortável", "Cómoda"),
+    (
+        "Runtime threads:",
+        "Threads de execução:",
+        "Hilos de ejecución:",
+    ),
+    (
+        "Blocking threads:",
+        "Threads bloqueantes:",
+        "Hilos bloqueantes:",
+    ),
+    ("Memory limit:", "Limite de memória:", "Límite de memoria:"),
+    (
+        "Streaming engine:",
+        "Motor de streaming:",
+        "Motor de streaming:",
+    ),
+    (
+        "Spill large sorts to disk",
+        "Gravar ordenações grandes em disco",
+        "Volcar ordenaciones grandes a disco",
+    ),
+    (
+        "Parquet parallelism:",
+        "Paralelismo Parquet:",
+        "Paralelismo Parquet:",
+    ),
+    ("Low memory:", "Pouca memória:", "Poca memoria:"),
+    (
+        "Read in smaller chunks",
+        "Ler em blocos menores",
+        "Leer en bloques más pequeños",
+    ),
+    ("Statistics:", "Estatísticas:", "Estadísticas:"),
+    (
+        "Prune row groups",
+        "Ignorar grupos de linhas",
+        "Omitir grupos de filas",
+    ),
+    ("Pushdown:", "Pushdown:", "Pushdown:"),
+    ("Filters", "Filtros", "Filtros"),
+    ("Columns", "Colunas", "Columnas"),
+    ("Slices", "Fatias", "Rebanadas"),
+    ("SQL functions:", "Funções SQL:", "Funciones SQL:"),
+    (
+        "CPF and CNPJ validators",
+        "Validadores de CPF e CNPJ",
+        "Validadores de CPF y CNPJ",
+    ),
+    (
+        "Digits and regex extraction",
+        "Dígitos e extração por regex",
+        "Dígitos y extracción por regex",
+    ),
+    ("Fiscal periods", "Períodos fiscais", "Períodos fiscales"),
+    (
+        "Default CSV delimiter:",
+        "Delimitador CSV padrão:",
+        "Delimitador CSV predeterminado:",
+    ),
+    (
+        "Keyboard shortcuts",
+        "Atalhos de teclado",
+        "Atajos de teclado",
+    ),
+    ("Press a key…", "Pressione uma tecla…", "Pulse una tecla…"),
+    ("Clear", "Limpar", "Borrar"),
+    ("Apply", "Aplicar", "Aplicar"),
+    (
+        "Restore defaults",
+        "Restaurar padrões",
+        "Restablecer valores predeterminados",
+    ),
+    (
+        "The CSV delimiter must be a single character.",
+        "O delimitador CSV deve ser um único caractere.",
+        "El delimitador CSV debe ser un solo carácter.",
+    ),
+];
+
+/// Translations by English text.
+static TRANSLATIONS: LazyLock<HashMap<&str, (&str, &str)>> = LazyLock::new(|| {
+    CATALOG
+        .iter()
+        .map(|(english, portuguese, spanish)| (*english, (*portuguese, *spanish)))
+        .collect()
+});
+
+/// Translates a text of the user interface, given in English, to `language`;
+/// a text missing from the catalog stays in English.
+pub fn tr(text: &'static str, language: Language) -> &'static str {
+    match (language, TRANSLATIONS.get(text)) {
+        (Language::Portuguese, Some((portuguese, _))) => portuguese,
+        (Language::Spanish, Some((_, spanish))) => spanish,
+        _ => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tr() {
+        // Each text is translated once.
+        assert_eq!(TRANSLATIONS.len(), CATALOG.len());
+
+        assert_eq!(tr("Open file", Language::English), "Open file");
+        assert_eq!(tr("Open file", Language::Portuguese), "Abrir arquivo");
+        assert_eq!(tr("Open file", Language::Spanish), "Abrir archivo");
+        assert_eq!(tr("Not translated", Language::Spanish), "Not translated");
+
+        // The language is kept in the egui context, English until set.
+        let ctx = Context::default();
+        assert_eq!(language(&ctx), Language::English);
+        set_language(&ctx, Language::Spanish);
+        assert_eq!(language(&ctx), Language::Spanish);
+
+        let json = serde_json::to_string(&Language::Portuguese).unwrap_or_default();
+        assert_eq!(
+            serde_json::from_str::<Language>(&json).ok(),
+            Some(Language::Portuguese)
+        );
+    }
+}
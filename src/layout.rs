@@ -1,16 +1,60 @@
 use crate::{
-    Error, MyStyle, Popover, Settings,
-    components::{FileMetadata, file_dialog},
+    AUTOSAVE_INTERVAL, Action, AppConfig, AppState, Autosave, BenchPane, BloomFilterTester,
+    BrowserAction, CONFIG_POLL_INTERVAL, CellEditor, ChartPane, CodecComparison, ColumnMasks,
+    ColumnSizeChart, ConfigFile, CorrelationHeatmap, CsvImport, DataFrameWindow, DataTask,
+    DerivedColumns, DialogPurpose, DirectoryBrowser, DistinctEstimates, DockLayout, DuplicatesPane,
+    Error, FileComparison, FindReplace, FooterCache, GroupByQuery, Histogram, InstanceServer,
+    LONG_TASK_DURATION, LogBuffer, LogPanel, MissingnessPane, MyStyle, Pane, PaneDock,
+    PendingDialog, PipelineAction, PipelinePane, PivotPane, Popover, RECIPE_EXTENSION, Recipe,
+    ResultTab, ResultTabs, RowCountPreview, RowDiffWindow, RowGroupAdvisor, RssSample,
+    SESSION_EXTENSION, SchemaDiffWindow, Script, Session, SessionRecovery, Settings,
+    SnapshotTarget, SplitSide, SplitView, SqlError, TabAction, TableScroll, TableSnapshot,
+    TimeSeriesPane, Toasts, ValueCounts, ViewHistory, ViewState, autosave_path, busy_indicator,
+    cell_text, check_memory_budget,
+    components::{
+        FileMetadata, RowDensity, TABLE_ZOOM_RANGE, TableAction, file_dialog, pick_file_dialog,
+        save_file_dialog,
+    },
+    config_file_path,
     data::{DataFilters, DataFrameContainer, DataFuture},
+    export_html, export_pdf, footer_cache_path, format_bytes, is_sortable, language, list_scripts,
+    notify_finished, pick_folder_dialog, progress_repaint_interval,
+    report::export_report,
+    rowgroups::column_sizes,
+    save_edits, script_name, scripts_dir,
+    stats::SummaryStatistics,
+    tr, write_autosave,
 };
 
 use egui::{
-    CentralPanel, Color32, Context, Direction, FontId, Frame, Grid, Hyperlink, Layout, RichText,
-    ScrollArea, SidePanel, Stroke, TopBottomPanel, ViewportCommand, menu, style::Visuals,
-    warn_if_debug_build, widgets,
+    CentralPanel, CollapsingHeader, Color32, ComboBox, Context, Direction, FontId, Frame, Grid,
+    Hyperlink, Id, Label, Layout, ProgressBar, RichText, ScrollArea, SidePanel, Stroke, TextEdit,
+    TopBottomPanel, Ui, ViewportCommand, Window, menu, warn_if_debug_build, widgets,
 };
-use std::sync::Arc;
-use tokio::sync::oneshot::{self, error::TryRecvError};
+use polars::prelude::DataFrame;
+use serde::{Deserialize, Serialize};
+use std::{
+    future::Future,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+
+/// Name of the application, shown in the window title.
+pub const APP_NAME: &str = "PolarsView";
+
+/// View displayed in the central panel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CentralView {
+    /// The data table.
+    #[default]
+    Table,
+    /// Charts of the data.
+    Chart,
+    /// A date or datetime column grouped by day, week or month.
+    TimeSeries,
+}
 
 /// The main application struct for PolarsView.
 pub struct PolarsViewApp {
@@ -20,176 +64,1888 @@ pub struct PolarsViewApp {
     pub data_filters: DataFilters,
     /// Metadata extracted from the loaded file (if available).
     pub metadata: Option<FileMetadata>,
+    /// Summary statistics (`describe`) of the current result, computed on demand.
+    pub statistics: SummaryStatistics,
+    /// Overview of the missing values of the current result.
+    pub missingness: MissingnessPane,
+    /// Approximate distinct counts of the Statistics panel.
+    pub distinct_estimates: DistinctEstimates,
+    /// Duplicate rows detection for the current result.
+    pub duplicates: DuplicatesPane,
+    /// Pivot table builder for the current result.
+    pub pivot: PivotPane,
+    /// Benchmark of the current query.
+    pub bench: BenchPane,
+    /// Row group analysis of the current Parquet file.
+    pub row_groups: RowGroupAdvisor,
+    /// Bloom filter membership tester of the current Parquet file.
+    pub bloom_filters: BloomFilterTester,
+    /// Charts of the current result.
+    pub chart: ChartPane,
+    /// Time series resampled from the current result.
+    pub time_series: TimeSeriesPane,
+    /// Steps applied to the query result.
+    pub pipeline: PipelinePane,
+    /// Tree of the data files of a directory.
+    pub browser: DirectoryBrowser,
+    /// Parquet footers parsed by the app, kept between sessions.
+    pub footers: FooterCache,
     /// Optional popover window for displaying errors, settings, or other notifications.
     pub popover: Option<Box<dyn Popover>>,
+    /// Application settings, including the table view settings; persisted between sessions.
+    pub config: AppConfig,
+    /// Defaults of the TOML config file, reloaded when it changes.
+    pub config_file: ConfigFile,
+    /// Recent files, last queries and view, restored on startup.
+    pub state: AppState,
+    /// Notifications of the completed background operations.
+    pub toasts: Toasts,
+    /// Log panel showing the tracing events.
+    pub log_panel: LogPanel,
+    /// Edit mode of the table and its unsaved edits.
+    pub editor: CellEditor,
+    /// Columns masked in the table and in the exported reports.
+    pub masks: ColumnMasks,
+    /// Vertical position of the table.
+    pub scroll: TableScroll,
+    /// Result pinned beside the table, if the central panel is split.
+    pub split: Option<SplitView>,
+    /// Image of the visible table, copied or saved from a screenshot.
+    pub snapshot: TableSnapshot,
+    /// View states of the open file, for undo and redo.
+    pub history: ViewHistory,
+    /// Fuzzy filter of the columns listed in the Schema panel.
+    pub schema_filter: String,
+    /// Last result grouped from the table header, whose aggregates can be extended.
+    pub group_by: Option<GroupByQuery>,
+    /// Name of the next temp view, typed in the Query panel.
+    pub view_name: String,
+    /// Error of the last query, shown in the Query panel.
+    pub sql_error: Option<SqlError>,
+    /// Applying a query opens its result in a new tab.
+    pub result_in_new_tab: bool,
+    /// Results open in tabs; the fields above hold the shown one.
+    pub tabs: ResultTabs,
+    /// Files to open in new tabs, one after the other, once the shown one is loaded.
+    pub queued_tabs: Vec<DataFilters>,
+    /// Open the Query pane and focus its editor in the next frame.
+    pub focus_query: bool,
+    /// Rows of the query typed, counted on demand.
+    pub row_count: RowCountPreview,
 
     /// Tokio runtime for asynchronous operations (file loading, queries).
     runtime: tokio::runtime::Runtime,
-    /// Channel for receiving the result of asynchronous data loading.
-    pipe: Option<tokio::sync::oneshot::Receiver<Result<DataFrameContainer, String>>>,
 
-    /// Vector of active asynchronous tasks.  Used to prevent the application from hanging if a task fails.
-    tasks: Vec<tokio::task::JoinHandle<()>>,
+    /// Loads and queries running in the background, the most recent last.
+    tasks: Vec<DataTask>,
+
+    /// File dialog waiting for the user, if any.
+    dialog: Option<PendingDialog>,
+
+    /// Window title last sent to the viewport.
+    title: String,
+
+    /// Receives the files opened by later invocations, in this single instance.
+    pub instance: Option<InstanceServer>,
+    /// Session saved periodically, restored after an abnormal exit.
+    pub autosave: Autosave,
+    /// Memory of the process shown in the status bar.
+    rss: RssSample,
 }
 
 impl Default for PolarsViewApp {
     fn default() -> Self {
+        Self::with_config(AppConfig::default(), None)
+    }
+}
+
+impl PolarsViewApp {
+    /// Creates a `PolarsViewApp` with the given settings and no data; `threads`, if given,
+    /// overrides the worker threads of the settings.
+    pub fn with_config(config: AppConfig, threads: Option<usize>) -> Self {
+        let runtime = config.build_runtime(threads);
+        let mut scroll = TableScroll::default();
+        scroll.set_runtime(runtime.handle().clone());
+
         Self {
             table: Arc::new(None),
             data_filters: DataFilters::default(),
-            runtime: tokio::runtime::Builder::new_multi_thread()
-                .enable_all()
-                .build()
-                .expect("Failed to build Tokio runtime"),
-            pipe: None,
+            runtime,
             popover: None,
+            config,
+            state: AppState::default(),
+            toasts: Toasts::default(),
+            config_file: ConfigFile::default(),
+            log_panel: LogPanel::default(),
+            editor: CellEditor::default(),
+            scroll,
+            split: None,
+            snapshot: TableSnapshot::default(),
+            history: ViewHistory::default(),
+            schema_filter: String::new(),
+            group_by: None,
+            view_name: String::new(),
+            sql_error: None,
+            result_in_new_tab: false,
+            tabs: ResultTabs::default(),
+            queued_tabs: Vec::new(),
+            focus_query: false,
+            row_count: RowCountPreview::default(),
+            masks: ColumnMasks::default(),
             metadata: None,
+            statistics: SummaryStatistics::default(),
+            missingness: MissingnessPane::default(),
+            distinct_estimates: DistinctEstimates::default(),
+            duplicates: DuplicatesPane::default(),
+            pivot: PivotPane::default(),
+            bench: BenchPane::default(),
+            row_groups: RowGroupAdvisor::default(),
+            bloom_filters: BloomFilterTester::default(),
+            chart: ChartPane::default(),
+            time_series: TimeSeriesPane::default(),
+            pipeline: PipelinePane::default(),
+            browser: DirectoryBrowser::default(),
+            footers: FooterCache::default(),
             tasks: Vec::new(),
+            title: String::new(),
+            instance: None,
+            autosave: Autosave::default(),
+            rss: RssSample::default(),
+            dialog: None,
         }
     }
-}
 
-impl PolarsViewApp {
-    /// Creates a new `PolarsViewApp` instance.
-    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
-        cc.egui_ctx.set_visuals(Visuals::dark()); // Set dark theme.
+    /// Creates a new `PolarsViewApp` instance, restoring the persisted settings.
+    pub fn new(cc: &eframe::CreationContext<'_>, threads: Option<usize>) -> Self {
+        let mut config = AppConfig::load(cc.storage);
+
+        // The config file, if any, takes precedence over the saved settings.
+        let mut config_file = ConfigFile::new(config_file_path());
+        let loaded = config_file.load(&mut config);
+
         cc.egui_ctx.set_style_init(); // Apply custom styles.
-        Default::default()
+        config.apply(&cc.egui_ctx); // Apply the theme and font size.
+
+        let mut app = Self::with_config(config, threads);
+        app.state = AppState::load(cc.storage);
+        app.config_file = config_file;
+        app.footers = FooterCache::new(footer_cache_path());
+        if let Err(message) = loaded {
+            app.toasts.error(message);
+        }
+        app
+    }
+
+    /// Creates a new `PolarsViewApp` and restores a session.
+    pub fn new_with_session(
+        cc: &eframe::CreationContext<'_>,
+        session: Session,
+        threads: Option<usize>,
+    ) -> Self {
+        let mut app = Self::new(cc, threads);
+        app.restore_session(session, &cc.egui_ctx);
+        app
+    }
+
+    /// Starts autosaving the session, first offering, if `offer_recovery`,
+    /// to restore the one left by an abnormal exit.
+    pub fn start_autosave(&mut self, offer_recovery: bool) {
+        self.autosave = Autosave::new(autosave_path());
+        if !offer_recovery {
+            return;
+        }
+        if let Some(session) = self.autosave.recover() {
+            self.popover = Some(Box::new(SessionRecovery::new(session)));
+        }
+    }
+
+    /// Captures the open table and the view settings.
+    pub fn session(&self) -> Session {
+        let mut session = Session {
+            central_view: self.state.central_view,
+            table_view: self.config.table,
+            table_zoom: self.state.table_zoom,
+            layout: Some(self.state.layout.clone()),
+            ..Default::default()
+        };
+        if let Some(table) = self.table.as_ref() {
+            session.add_table(&table.filename, &table.filters);
+        }
+        session
+    }
+
+    /// Applies the view settings of a session and reopens its table.
+    pub fn restore_session(&mut self, session: Session, ctx: &Context) {
+        self.state.central_view = session.central_view;
+        self.state.table_zoom = session.table_zoom;
+        self.config.table = session.table_view;
+        if let Some(layout) = session.layout {
+            self.state.layout = layout;
+        }
+
+        // A single table is displayed at a time.
+        if let Some(table) = session.tables.first() {
+            let label = format!("Open {}", file_label(&table.filename));
+            self.run_data_filters(label, table.to_filters(), ctx);
+        }
+    }
+
+    /// Whether a pane has something to show for the current data.
+    fn pane_available(&self, pane: Pane) -> bool {
+        let parquet = self.metadata.as_ref().and_then(|m| m.parquet()).is_some();
+        match pane {
+            Pane::Query | Pane::Tasks | Pane::Files => true,
+            Pane::Metadata | Pane::Schema => self.metadata.is_some(),
+            Pane::RowGroups | Pane::BloomFilters => parquet,
+            Pane::Statistics
+            | Pane::MissingValues
+            | Pane::Duplicates
+            | Pane::Pivot
+            | Pane::Compression
+            | Pane::Benchmark
+            | Pane::Pipeline => self.table.is_some(),
+        }
+    }
+
+    /// Shows the left and right side panels and the floating panes.
+    fn render_docks(&mut self, ctx: &Context) {
+        let lang = language(ctx);
+        let layout = self.state.layout.clone();
+        let available = |app: &Self, dock: PaneDock| -> Vec<Pane> {
+            layout
+                .panes_in(dock)
+                .into_iter()
+                .filter(|pane| app.pane_available(*pane))
+                .collect()
+        };
+
+        let docks = [
+            (PaneDock::Left, SidePanel::left("side_panel")),
+            (PaneDock::Right, SidePanel::right("right_panel")),
+        ];
+        for (dock, panel) in docks {
+            let panes = available(self, dock);
+            if panes.is_empty() {
+                continue;
+            }
+
+            panel.resizable(true).show(ctx, |ui| {
+                ScrollArea::vertical().show(ui, |ui| {
+                    for pane in panes {
+                        let open = pane == Pane::Query && self.focus_query;
+                        let response = CollapsingHeader::new(pane.title(lang))
+                            .open(open.then_some(true))
+                            .show(ui, |ui| self.render_pane(pane, ui, ctx));
+                        response.header_response.context_menu(|ui| {
+                            self.state.layout.pane_menu(ui, pane);
+                        });
+                    }
+                });
+            });
+        }
+
+        for pane in available(self, PaneDock::Floating) {
+            let mut open = true;
+            Window::new(pane.title(lang))
+                .id(Id::new(("floating_pane", pane)))
+                .open(&mut open)
+                .default_width(350.0)
+                .show(ctx, |ui| {
+                    ScrollArea::vertical().show(ui, |ui| self.render_pane(pane, ui, ctx));
+                });
+
+            // Closing a floating pane docks it back to the left panel.
+            if !open {
+                self.state.layout.set_dock(pane, PaneDock::Left);
+            }
+        }
+    }
+
+    /// Renders the content of a pane.
+    fn render_pane(&mut self, pane: Pane, ui: &mut Ui, ctx: &Context) {
+        // Hold a reference to the current result, so that the panes may load new data.
+        let current = Arc::clone(&self.table);
+
+        match pane {
+            Pane::Metadata => {
+                if let Some(metadata) = &mut self.metadata {
+                    metadata.render_metadata(ui, self.runtime.handle(), &self.masks);
+                }
+            }
+            Pane::Query => {
+                let lang = language(ctx);
+                if std::mem::take(&mut self.focus_query) {
+                    ui.memory_mut(|m| m.request_focus(DataFilters::query_editor_id()));
+                }
+                if let Some(filters) = self.data_filters.render_filter(ui, self.sql_error.as_ref())
+                {
+                    // Load data with the applied query, in a new tab if asked for.
+                    let new_tab = self.result_in_new_tab || ui.input(|i| i.modifiers.shift);
+                    match new_tab {
+                        true => self.run_in_new_tab("Query", filters, ctx),
+                        false => {
+                            self.run_data_filters("Query", filters, ctx);
+                        }
+                    }
+                }
+                ui.horizontal(|ui| {
+                    let enabled = self.data_filters.is_complete();
+                    let count = ui
+                        .add_enabled(enabled, widgets::Button::new(tr("Count rows", lang)))
+                        .on_hover_text(tr(
+                            "Count the rows of the query without loading its result, \
+                            to decide whether to add a LIMIT",
+                            lang,
+                        ));
+                    if count.clicked() {
+                        let handle = self.runtime.handle().clone();
+                        self.row_count.start(&self.data_filters, &handle, ctx);
+                    }
+                    self.row_count
+                        .render(ui, self.data_filters.query.as_deref());
+                });
+                ui.checkbox(
+                    &mut self.result_in_new_tab,
+                    tr("Open the result in a new tab", lang),
+                )
+                .on_hover_text(tr(
+                    "Keep the current result in its tab when applying a query, as Shift+Apply does",
+                    lang,
+                ));
+                ui.separator();
+                self.render_views(ui);
+            }
+            Pane::Schema => {
+                let Some(metadata) = &self.metadata else {
+                    return;
+                };
+                if let Some(column) =
+                    metadata.render_schema(ui, &mut self.schema_filter, &self.masks)
+                {
+                    self.state.central_view = CentralView::Table;
+                    self.scroll.reveal_column(column);
+                }
+            }
+            Pane::RowGroups => {
+                if let Some(metadata) = self.metadata.as_ref().and_then(|m| m.parquet()) {
+                    if ui
+                        .button("Column sizes chart")
+                        .on_hover_text("Compressed vs uncompressed bytes per column")
+                        .clicked()
+                    {
+                        self.popover = Some(Box::new(ColumnSizeChart {
+                            sizes: column_sizes(metadata),
+                        }));
+                    }
+
+                    self.row_groups
+                        .render(ui, metadata, &self.data_filters, &self.runtime);
+                }
+            }
+            Pane::BloomFilters => {
+                if let Some(metadata) = &self.metadata {
+                    if let (Some(parquet), Some(filename)) =
+                        (metadata.parquet(), metadata.parquet_filename())
+                    {
+                        self.bloom_filters
+                            .render(ui, parquet, filename, self.runtime.handle());
+                    }
+                }
+            }
+            Pane::Statistics => {
+                let Some(table) = current.as_ref() else {
+                    return;
+                };
+
+                // The statistics would show the raw values of the masked columns.
+                if self.masks.is_active() {
+                    ui.label("Unavailable while columns are masked");
+                    return;
+                }
+
+                ui.horizontal_wrapped(|ui| {
+                    let label = match self.statistics.is_computed() {
+                        true => "Refresh",
+                        false => "Compute",
+                    };
+                    if ui
+                        .add_enabled(!self.statistics.is_running(), widgets::Button::new(label))
+                        .on_hover_text("Compute summary statistics of the current result")
+                        .clicked()
+                    {
+                        let handle = self.runtime.handle().clone();
+                        self.statistics.start(&table.df, &handle, ui.ctx());
+                    }
+
+                    if ui
+                        .button("Correlation matrix")
+                        .on_hover_text("Pearson correlation of the numeric columns")
+                        .clicked()
+                    {
+                        let handle = self.runtime.handle();
+                        let heatmap = CorrelationHeatmap::new(&table.df, handle, ui.ctx());
+                        self.popover = Some(Box::new(heatmap));
+                    }
+                });
+
+                self.statistics.render(ui);
+
+                // Estimated only while shown, as the exact distinct count needs a full scan.
+                ui.collapsing("Approximate distinct counts", |ui| {
+                    let handle = self.runtime.handle().clone();
+                    self.distinct_estimates.render(ui, &table.df, &handle);
+                })
+                .header_response
+                .on_hover_text("HyperLogLog estimates, computed in the background");
+            }
+            Pane::MissingValues => {
+                if let Some(table) = current.as_ref() {
+                    self.missingness.render(ui, &table.df);
+                }
+            }
+            Pane::Duplicates => {
+                if let Some(table) = current.as_ref() {
+                    if let Some(keys) = self.duplicates.render(ui, &table.df, self.runtime.handle())
+                    {
+                        // Filter the table to the duplicated rows.
+                        let future = table.clone().filter_duplicates(keys);
+                        self.run_data_future("Filter duplicates", Box::new(Box::pin(future)), ctx);
+                    }
+                }
+            }
+            Pane::Pivot if self.masks.is_active() => {
+                ui.label("Unavailable while columns are masked");
+            }
+            Pane::Pivot => {
+                if let Some(table) = current.as_ref() {
+                    if let Some(result) = self.pivot.render(ui, &table.df, self.runtime.handle()) {
+                        self.popover = Some(match result {
+                            Ok(df) => Box::new(DataFrameWindow {
+                                title: "Pivot table".to_string(),
+                                df,
+                            }),
+                            Err(error) => Box::new(Error::new(format!("Pivot error: {}", error))),
+                        });
+                    }
+                }
+            }
+            Pane::Compression => {
+                if let Some(table) = current.as_ref() {
+                    if ui
+                        .button("Compare codecs")
+                        .on_hover_text(
+                            "Rewrite the current result as Parquet with each codec and compare size and speed",
+                        )
+                        .clicked()
+                    {
+                        self.popover = Some(Box::new(CodecComparison::new(
+                            Arc::clone(&table.df),
+                            &self.runtime,
+                            ctx,
+                        )));
+                    }
+                }
+            }
+            Pane::Benchmark => {
+                self.bench.render(ui, &self.data_filters, &self.runtime);
+            }
+            Pane::Tasks => {
+                if let Some(idx) = DataTask::render_list(&self.tasks, ui) {
+                    self.tasks.remove(idx).cancel();
+                }
+            }
+            Pane::Pipeline => {
+                let Some(table) = current.as_ref() else {
+                    return;
+                };
+
+                // Only the columns that can be ordered are offered to the sort steps.
+                let columns: Vec<String> = table
+                    .df
+                    .get_columns()
+                    .iter()
+                    .filter(|column| is_sortable(column.dtype()))
+                    .map(|column| column.name().to_string())
+                    .collect();
+                match self
+                    .pipeline
+                    .render(ui, &table.filters.transforms, &columns)
+                {
+                    Some(PipelineAction::Run(transforms)) => {
+                        let filters = DataFilters {
+                            transforms,
+                            ..table.filters.clone()
+                        };
+                        self.run_data_filters("Pipeline", filters, ctx);
+                    }
+                    Some(PipelineAction::Open) => self.open_recipe(ctx),
+                    Some(PipelineAction::Save) => self.save_recipe(ctx),
+                    None => {}
+                }
+            }
+            Pane::Files => match self.browser.render(
+                ui,
+                self.state.browse_root.as_deref(),
+                &self.footers,
+                self.runtime.handle(),
+            ) {
+                Some(BrowserAction::ChooseRoot) => {
+                    let dialog = pick_folder_dialog("Choose the folder to browse");
+                    self.open_file_dialog(DialogPurpose::BrowseFolder, dialog, ctx);
+                }
+                Some(BrowserAction::Open(filename)) => self.open_file(filename, ctx),
+                None => {}
+            },
+        }
+    }
+
+    /// Opens file dialogs in the background; their files are used once chosen.
+    ///
+    /// A dialog is opened only if no other is waiting for the user.
+    fn open_dialog<F>(&mut self, purpose: DialogPurpose, dialogs: F, ctx: &Context)
+    where
+        F: Future<Output = Result<Vec<PathBuf>, String>> + Send + 'static,
+    {
+        if self.dialog.is_none() {
+            self.dialog = Some(PendingDialog::spawn(purpose, dialogs, &self.runtime, ctx));
+        }
+    }
+
+    /// Opens a dialog choosing one file.
+    fn open_file_dialog<F>(&mut self, purpose: DialogPurpose, dialog: F, ctx: &Context)
+    where
+        F: Future<Output = Result<PathBuf, String>> + Send + 'static,
+    {
+        self.open_dialog(purpose, async { dialog.await.map(|path| vec![path]) }, ctx);
+    }
+
+    /// Opens two dialogs one after the other, choosing the files to compare.
+    fn open_pair_dialog(
+        &mut self,
+        purpose: DialogPurpose,
+        titles: [&'static str; 2],
+        filters: &'static [(&'static str, &'static [&'static str])],
+        ctx: &Context,
+    ) {
+        let dialogs = async move {
+            let first = pick_file_dialog(titles[0], filters).await?;
+            let second = pick_file_dialog(titles[1], filters).await?;
+            Ok(vec![first, second])
+        };
+        self.open_dialog(purpose, dialogs, ctx);
+    }
+
+    /// Uses the files chosen in the pending dialog, once it is closed.
+    fn check_dialog_pending(&mut self, ctx: &Context) {
+        let Some(dialog) = self.dialog.as_mut() else {
+            return;
+        };
+        let Some(result) = dialog.try_paths() else {
+            return; // Still waiting for the user.
+        };
+        let purpose = dialog.purpose;
+        self.dialog = None;
+
+        let Ok(paths) = result else {
+            return; // The dialog was cancelled.
+        };
+
+        match (purpose, paths.as_slice()) {
+            (DialogPurpose::OpenFile, [path]) => self.open_file(path.display().to_string(), ctx),
+            (DialogPurpose::OpenSession, [path]) => match Session::load(path) {
+                Ok(session) => self.restore_session(session, ctx),
+                Err(message) => self.popover = Some(Box::new(Error::new(message))),
+            },
+            (DialogPurpose::SaveSession, [path]) => {
+                if let Err(message) = self.session().save(path) {
+                    self.popover = Some(Box::new(Error::new(message)));
+                }
+            }
+            (DialogPurpose::ImportCsv, [path]) => {
+                self.popover = Some(Box::new(CsvImport::new(path.display())));
+            }
+            (DialogPurpose::CompareFiles, [left, right]) => {
+                let handle = self.runtime.handle();
+                self.popover = Some(Box::new(FileComparison::new(left, right, handle, ctx)));
+            }
+            (DialogPurpose::SchemaDiff, [old, new]) => {
+                self.popover = Some(Box::new(SchemaDiffWindow::new(old, new)));
+            }
+            (DialogPurpose::RowDiff, [old, new]) => {
+                let handle = self.runtime.handle().clone();
+                self.popover = Some(Box::new(RowDiffWindow::new(old, new, handle)));
+            }
+            (DialogPurpose::ExportReport, [path]) => self.export_report_to(path.clone(), ctx),
+            (DialogPurpose::ExportHtml { visible }, [path]) => {
+                self.export_html_to(path.clone(), visible, ctx)
+            }
+            (DialogPurpose::ExportPdf, [path]) => self.export_pdf_to(path.clone(), ctx),
+            (DialogPurpose::SaveTableImage, [path]) => {
+                self.snapshot.request(SnapshotTarget::File(path.clone()));
+            }
+            (DialogPurpose::SaveEdits, [path]) => self.save_edits_to(path.clone(), false, ctx),
+            (DialogPurpose::OpenRecipe, [path]) => match Recipe::load(path) {
+                Ok(recipe) => {
+                    let mut filters = self.data_filters.clone();
+                    filters.transforms.extend(recipe.transforms);
+                    self.run_data_filters("Apply recipe", filters, ctx);
+                }
+                Err(message) => self.popover = Some(Box::new(Error::new(message))),
+            },
+            (DialogPurpose::SaveRecipe, [path]) => {
+                let recipe = Recipe {
+                    transforms: self.data_filters.transforms.clone(),
+                };
+                if let Err(message) = recipe.save(path) {
+                    self.popover = Some(Box::new(Error::new(message)));
+                }
+            }
+            (DialogPurpose::BrowseFolder, [path]) => {
+                self.state.browse_root = Some(path.clone());
+                self.browser.refresh();
+            }
+            _ => {}
+        }
+    }
+
+    /// Opens a file, reapplying its last query, sort and filters if it was opened before
+    /// and restoring them is enabled.
+    fn open_file(&mut self, filename: String, ctx: &Context) {
+        let label = format!("Open {}", file_label(&filename));
+        let saved = match self.config.restore_file_state {
+            true => self.state.saved_filters(&filename),
+            false => None,
+        };
+        let filters = saved.unwrap_or(DataFilters {
+            filename: Some(filename),
+            ..Default::default()
+        });
+        self.run_data_filters(label, filters, ctx);
     }
 
     /// Creates a new `PolarsViewApp` with a pre-existing `DataFuture`.  Used for asynchronous loading when the filename is known in advance.
-    pub fn new_with_future(cc: &eframe::CreationContext<'_>, future: DataFuture) -> Self {
-        let mut app: Self = Default::default();
-        cc.egui_ctx.set_visuals(Visuals::dark());
-        cc.egui_ctx.set_style_init();
-        app.run_data_future(future, &cc.egui_ctx);
+    pub fn new_with_future(
+        cc: &eframe::CreationContext<'_>,
+        future: DataFuture,
+        threads: Option<usize>,
+    ) -> Self {
+        let mut app = Self::new(cc, threads);
+        app.run_data_future("Load", future, &cc.egui_ctx);
         app
     }
 
     /// Checks if a popover is active and displays it.  If the popover is closed by the user, it is removed.
     fn check_popover(&mut self, ctx: &Context) {
+        let mut requested_filters = None;
+        let mut requested_session = None;
+
         if let Some(popover) = &mut self.popover {
-            if !popover.show(ctx) {
+            let open = popover.show(ctx);
+            requested_filters = popover.take_filters(); // Filters requested by the popover.
+            requested_session = popover.take_session(); // Session restored through the popover.
+            if let Some(config) = popover.take_config() {
+                // Settings applied through the popover.
+                config.apply(ctx);
+                self.config = config;
+            }
+            if !open {
                 self.popover = None; // Remove closed popover.
             }
         }
+
+        if let Some(filters) = requested_filters {
+            // Load data with the filters requested by the popover.
+            self.run_data_filters("Load", filters, ctx);
+        }
+        if let Some(session) = requested_session {
+            self.restore_session(session, ctx);
+        }
+    }
+
+    /// Runs an action of the menus or of a keyboard shortcut.
+    fn run_action(&mut self, action: Action, ctx: &Context) {
+        match action {
+            Action::Open => {
+                // Open a file dialog to select a file.
+                let dialog = async { file_dialog().await.map(PathBuf::from) };
+                self.open_file_dialog(DialogPurpose::OpenFile, dialog, ctx);
+            }
+            Action::Close => self.close_table(),
+            Action::OpenSession => {
+                let dialog = pick_file_dialog("Open session", &[("Session", &["json"])]);
+                self.open_file_dialog(DialogPurpose::OpenSession, dialog, ctx);
+            }
+            Action::SaveSession => {
+                if self.table.is_some() {
+                    let dialog = async {
+                        let file_name = format!("session.{SESSION_EXTENSION}");
+                        save_file_dialog(&file_name, &[("Session", &["json"])]).await
+                    };
+                    self.open_file_dialog(DialogPurpose::SaveSession, dialog, ctx);
+                }
+            }
+            Action::RunQuery => {
+                if self.data_filters.is_complete() {
+                    let filters = self.data_filters.clone();
+                    self.run_data_filters("Query", filters, ctx);
+                }
+            }
+            Action::FocusQuery => {
+                // A hidden Query pane is docked back to show its editor.
+                if self.state.layout.dock_of(Pane::Query) == PaneDock::Hidden {
+                    self.state.layout.set_dock(Pane::Query, PaneDock::Left);
+                }
+                self.focus_query = true;
+            }
+            Action::Cancel => self.cancel_loading(),
+            Action::ExportReport => self.export_report(ctx),
+            Action::ToggleView => {
+                self.state.central_view = match self.state.central_view {
+                    CentralView::Table => CentralView::Chart,
+                    CentralView::Chart | CentralView::TimeSeries => CentralView::Table,
+                };
+            }
+            Action::Undo => {
+                if let Some(state) = self.history.undo() {
+                    self.restore_view("Undo", state, ctx);
+                }
+            }
+            Action::Redo => {
+                if let Some(state) = self.history.redo() {
+                    self.restore_view("Redo", state, ctx);
+                }
+            }
+            Action::NextBookmark => {
+                if let Some(table) = self.table.as_ref() {
+                    self.scroll.next_bookmark(&table.df);
+                }
+            }
+            Action::PreviousBookmark => {
+                if let Some(table) = self.table.as_ref() {
+                    self.scroll.previous_bookmark(&table.df);
+                }
+            }
+            Action::Settings => {
+                // Show the settings popover.
+                self.popover = Some(Box::new(Settings::new(&self.config)));
+            }
+            Action::Quit => {
+                // Close the application.
+                ctx.send_viewport_cmd(ViewportCommand::Close);
+            }
+        }
+    }
+
+    /// Closes the displayed data, releasing its memory and the results derived from it;
+    /// with several tabs, closes the shown tab.
+    fn close_table(&mut self) {
+        self.cancel_loading();
+        if self.tabs.len() > 1 {
+            self.close_tab(self.tabs.active());
+            return;
+        }
+
+        self.table = Arc::new(None);
+        self.data_filters = DataFilters::default();
+        self.metadata = None;
+        self.group_by = None;
+        self.reset_derived();
+        self.editor.discard();
+        self.scroll.reset();
     }
 
-    /// Checks if there is a data loading operation pending (asynchronous).
+    /// Drops the results derived from the shown data, such as its statistics and charts.
+    fn reset_derived(&mut self) {
+        self.statistics = SummaryStatistics::default();
+        self.missingness = MissingnessPane::default();
+        self.distinct_estimates = DistinctEstimates::default();
+        self.sql_error = None;
+        self.duplicates = DuplicatesPane::default();
+        self.pivot = PivotPane::default();
+        self.bench = BenchPane::default();
+        self.row_groups = RowGroupAdvisor::default();
+        self.bloom_filters = BloomFilterTester::default();
+        self.chart = ChartPane::default();
+        self.time_series = TimeSeriesPane::default();
+    }
+
+    /// Drops the unsaved edits, which refer to the rows of the shown result.
+    fn discard_edits(&mut self) {
+        if !self.editor.changes.is_empty() {
+            self.toasts.error(format!(
+                "{} unsaved edits discarded",
+                self.editor.changes.len()
+            ));
+        }
+        self.editor.discard();
+    }
+
+    /// Takes the state of the shown tab out of the app, leaving it empty.
+    fn take_tab(&mut self) -> ResultTab {
+        ResultTab {
+            table: std::mem::replace(&mut self.table, Arc::new(None)),
+            data_filters: std::mem::take(&mut self.data_filters),
+            metadata: self.metadata.take(),
+            scroll: std::mem::take(&mut self.scroll),
+            history: std::mem::take(&mut self.history),
+            group_by: self.group_by.take(),
+        }
+    }
+
+    /// Shows the state of a tab, dropping the results derived from the previous one.
+    fn show_tab(&mut self, tab: ResultTab) {
+        self.discard_edits();
+        self.reset_derived();
+        self.put_tab(tab);
+    }
+
+    /// Puts the state of the shown tab back in the app.
+    fn put_tab(&mut self, tab: ResultTab) {
+        self.table = tab.table;
+        self.data_filters = tab.data_filters;
+        self.metadata = tab.metadata;
+        self.scroll = tab.scroll;
+        self.scroll.set_runtime(self.runtime.handle().clone());
+        self.history = tab.history;
+        self.group_by = tab.group_by;
+    }
+
+    /// Shows the result of the tab `index`.
+    fn select_tab(&mut self, index: usize) {
+        let current = self.take_tab();
+        let tab = self.tabs.select(index, current);
+        self.show_tab(tab);
+    }
+
+    /// Closes the tab `index`, showing its neighbour if it was shown.
+    fn close_tab(&mut self, index: usize) {
+        let shown = index == self.tabs.active();
+        let current = self.take_tab();
+        let tab = self.tabs.close(index, current);
+        match shown {
+            true => self.show_tab(tab),
+            false => self.put_tab(tab),
+        }
+    }
+
+    /// Loads the data with the filters into a new tab, once loaded, keeping the shown result.
+    fn run_in_new_tab(&mut self, label: impl ToString, filters: DataFilters, ctx: &Context) {
+        if self.run_data_filters(label, filters, ctx) {
+            if let Some(task) = self.tasks.last_mut() {
+                task.new_tab = true;
+            }
+        }
+    }
+
+    /// Stops the data operations in progress, keeping the current result.
+    fn cancel_loading(&mut self) {
+        self.tasks.drain(..).for_each(DataTask::cancel);
+    }
+
+    /// Shows the progress of the latest data operation, with a button to cancel them all.
+    fn render_progress(&mut self, ui: &mut Ui, ctx: &Context) {
+        let Some(progress) = self.tasks.last().map(|task| Arc::clone(&task.progress)) else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            match progress.fraction() {
+                Some(fraction) => {
+                    let bar = ProgressBar::new(fraction)
+                        .text(progress.text())
+                        .desired_width(300.0);
+                    ui.add(bar);
+                }
+                None => {
+                    busy_indicator(ui);
+                    ui.label(progress.text());
+                }
+            }
+
+            let cancel = widgets::Button::new("Cancel")
+                .shortcut_text(self.config.keymap.text(ctx, Action::Cancel));
+            if ui.add(cancel).clicked() {
+                self.cancel_loading();
+            }
+
+            if self.tasks.len() > 1 {
+                ui.label(format!("{} tasks running", self.tasks.len()));
+            }
+        });
+
+        // The progress is updated by the loading task.
+        ctx.request_repaint_after(progress_repaint_interval(ctx));
+    }
+
+    /// Asks for the destination of an export of the current result, named after its file
+    /// (or `default_stem`) with `suffix`.
+    fn ask_export_path(
+        &mut self,
+        purpose: DialogPurpose,
+        default_stem: &str,
+        suffix: &str,
+        filters: &'static [(&'static str, &'static [&'static str])],
+        ctx: &Context,
+    ) {
+        let Some(table) = self.table.as_ref() else {
+            return;
+        };
+
+        let stem = Path::new(&table.filename)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| default_stem.to_string());
+
+        let file_name = format!("{stem}{suffix}");
+        let dialog = async move { save_file_dialog(&file_name, filters).await };
+        self.open_file_dialog(purpose, dialog, ctx);
+    }
+
+    /// Exports the `rows` of the current result (all if `None`) in the background with `export`,
+    /// which returns the message of its outcome; the active masks are applied to the exported
+    /// rows first, in the background too.
+    fn spawn_export<F>(&self, rows: Option<Range<usize>>, ctx: &Context, export: F)
+    where
+        F: FnOnce(&DataFrame, &str) -> Result<String, String> + Send + 'static,
+    {
+        let Some(table) = self.table.as_ref() else {
+            return;
+        };
+
+        let df = Arc::clone(&table.df);
+        let masks = self.masks.is_active().then(|| self.masks.clone());
+        let title = table.filename.clone();
+        let toasts = self.toasts.clone();
+        let ctx = ctx.clone();
+        let started = Instant::now();
+        let notify_after = self.config.notify_after_secs;
+
+        self.runtime.spawn_blocking(move || {
+            let outcome = export_frame(df, rows, masks.as_ref()).and_then(|df| export(&df, &title));
+            toasts.notify_export(&ctx, started, notify_after, outcome);
+        });
+    }
+
+    /// Asks for the destination of a profiling report of the current result.
+    fn export_report(&mut self, ctx: &Context) {
+        let filters: &[(&str, &[&str])] = &[("HTML", &["html"]), ("Markdown", &["md"])];
+        self.ask_export_path(
+            DialogPurpose::ExportReport,
+            "report",
+            "_profile.html",
+            filters,
+            ctx,
+        );
+    }
+
+    /// Exports a profiling report of the current result in the background.
+    fn export_report_to(&mut self, path: PathBuf, ctx: &Context) {
+        self.spawn_export(None, ctx, move |df, title| {
+            export_report(df, title, &path).map(|()| {
+                format!(
+                    "Report exported: {} rows → {}",
+                    df.height(),
+                    file_label(&path.display().to_string())
+                )
+            })
+        });
+    }
+
+    /// Asks for the destination of an HTML page with the visible rows, or all rows, of the result.
+    fn export_html(&mut self, visible: bool, ctx: &Context) {
+        let purpose = DialogPurpose::ExportHtml { visible };
+        self.ask_export_path(purpose, "table", ".html", &[("HTML", &["html"])], ctx);
+    }
+
+    /// Exports the visible rows, or all rows, of the result as an HTML page in the background.
+    fn export_html_to(&mut self, path: PathBuf, visible: bool, ctx: &Context) {
+        let Some(table) = self.table.as_ref() else {
+            return;
+        };
+
+        let total_rows = table.df.height();
+        let rows = visible
+            .then(|| self.scroll.anchor_row..self.scroll.anchor_row + self.scroll.visible_rows);
+        let first_row = rows.as_ref().map_or(0, |rows| rows.start.min(total_rows));
+        let query = table.filters.query.clone();
+        let options = self.config.table;
+
+        self.spawn_export(rows, ctx, move |df, title| {
+            let html = export_html(
+                df,
+                first_row,
+                total_rows,
+                title,
+                query.as_deref(),
+                &options,
+                &path,
+            );
+            html.map(|()| {
+                format!(
+                    "HTML exported: {} rows → {}",
+                    df.height(),
+                    file_label(&path.display().to_string())
+                )
+            })
+        });
+    }
+
+    /// Asks for the destination of a printable PDF document of the result.
+    fn export_pdf(&mut self, ctx: &Context) {
+        self.ask_export_path(
+            DialogPurpose::ExportPdf,
+            "table",
+            ".pdf",
+            &[("PDF", &["pdf"])],
+            ctx,
+        );
+    }
+
+    /// Prints the result to a PDF document in the background.
+    fn export_pdf_to(&mut self, path: PathBuf, ctx: &Context) {
+        let Some(table) = self.table.as_ref() else {
+            return;
+        };
+
+        let query = table.filters.query.clone();
+        let options = self.config.table;
+
+        self.spawn_export(None, ctx, move |df, title| {
+            export_pdf(df, title, query.as_deref(), &options, &path).map(|pages| {
+                format!(
+                    "PDF exported: {} rows, {pages} pages → {}",
+                    df.height(),
+                    file_label(&path.display().to_string())
+                )
+            })
+        });
+    }
+
+    /// Asks for a recipe file, whose pipeline is appended to the current one.
+    fn open_recipe(&mut self, ctx: &Context) {
+        let dialog = pick_file_dialog("Apply recipe", &[("Recipe", &["json"])]);
+        self.open_file_dialog(DialogPurpose::OpenRecipe, dialog, ctx);
+    }
+
+    /// Asks for the file where the current pipeline is saved.
+    fn save_recipe(&mut self, ctx: &Context) {
+        let dialog = async {
+            let file_name = format!("pipeline.{RECIPE_EXTENSION}");
+            save_file_dialog(&file_name, &[("Recipe", &["json"])]).await
+        };
+        self.open_file_dialog(DialogPurpose::SaveRecipe, dialog, ctx);
+    }
+
+    /// Asks for the file where the edited result is saved.
+    fn save_edits_as(&mut self, ctx: &Context) {
+        let Some(table) = self.table.as_ref() else {
+            return;
+        };
+
+        let stem = Path::new(&table.filename)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "data".to_string());
+
+        let dialog = async move {
+            let file_name = format!("{stem}_edited.parquet");
+            save_file_dialog(&file_name, &[("Parquet", &["parquet"]), ("CSV", &["csv"])]).await
+        };
+        self.open_file_dialog(DialogPurpose::SaveEdits, dialog, ctx);
+    }
+
+    /// Whether the result is a whole Parquet file, which the edits can be saved over: not a
+    /// query, sort, transform, or subset of its rows such as the duplicates.
+    fn can_save_over_original(&self) -> bool {
+        let Some(table) = self.table.as_ref() else {
+            return false;
+        };
+        let whole_file = match &table.filters.query {
+            Some(query) => {
+                let query = query.trim().trim_end_matches(';').to_lowercase();
+                query == format!("select * from {}", table.filters.table_name.to_lowercase())
+            }
+            None => true,
+        };
+
+        whole_file
+            && !table.filtered
+            && table.table_type == "parquet"
+            && Path::new(&table.filename).is_file()
+            && table.filters.tables.is_empty()
+            && table.filters.sort.is_none()
+            && table.filters.then_by.is_empty()
+            && table.filters.transforms.is_empty()
+    }
+
+    /// Applies the edits to the displayed result, then writes it to a file in the background;
+    /// saved over the original file, the codec and key-value metadata of its footer are kept.
+    fn save_edits_to(&mut self, path: PathBuf, over_original: bool, ctx: &Context) {
+        let Some(table) = self.table.as_ref() else {
+            return;
+        };
+
+        let df = match self.editor.changes.apply(&table.df) {
+            Ok(df) => Arc::new(df),
+            Err(message) => {
+                self.popover = Some(Box::new(Error::new(message)));
+                return;
+            }
+        };
+        let edits = self.editor.changes.len();
+        self.editor.discard();
+
+        // The sorted versions of the result predate the edits.
+        self.table = Arc::new(Some(DataFrameContainer {
+            df: Arc::clone(&df),
+            sort_cache: Default::default(),
+            ..table.clone()
+        }));
+
+        let toasts = self.toasts.clone();
+        let ctx = ctx.clone();
+        self.runtime.spawn_blocking(move || {
+            match save_edits(&mut df.as_ref().clone(), &path, over_original) {
+                Ok(()) => toasts.success(format!(
+                    "{edits} edits saved → {}",
+                    file_label(&path.display().to_string())
+                )),
+                Err(message) => toasts.error(format!("Save failed: {message}")),
+            }
+            ctx.request_repaint();
+        });
+    }
+
+    /// Checks if there are data operations pending (asynchronous) and applies the completed ones.
     ///
     /// Returns `true` if data is still loading, `false` otherwise.  Also handles potential errors from the loading process.
-    fn check_data_pending(&mut self) -> bool {
-        // Take the receiver out of the `Option`.  This allows us to check if the data has arrived.
-        let Some(mut output) = self.pipe.take() else {
-            return false; // No data loading in progress.
+    fn check_data_pending(&mut self, ctx: &Context) -> bool {
+        for mut task in std::mem::take(&mut self.tasks) {
+            match task.try_result() {
+                Some(Ok(data)) => {
+                    // Notify the completion of the operations long enough to browse meanwhile.
+                    let elapsed = task.started.elapsed();
+                    tracing::info!(
+                        rows = data.df.height(),
+                        seconds = elapsed.as_secs_f32(),
+                        "{} finished",
+                        task.label
+                    );
+                    let message = format!(
+                        "{} finished: {} rows in {:.1} s",
+                        task.label,
+                        data.df.height(),
+                        elapsed.as_secs_f32()
+                    );
+                    notify_finished(ctx, task.started, self.config.notify_after_secs, &message);
+                    if elapsed >= LONG_TASK_DURATION {
+                        self.toasts.success(message);
+                    }
+                    // The shown result stays in its tab; the new one opens beside it.
+                    if task.new_tab && self.table.is_some() {
+                        let mut current = self.take_tab();
+                        // A grouping of the shown result goes with the grouped result.
+                        let group_by = current
+                            .group_by
+                            .take_if(|group_by| group_by.shown_by(&data.filters));
+                        self.tabs.open(current);
+                        self.show_tab(ResultTab {
+                            group_by,
+                            ..Default::default()
+                        });
+                    }
+                    self.apply_data(data);
+                }
+                Some(Err(message)) => {
+                    // An error occurred during data loading; details are shown on click.
+                    tracing::error!("{} failed: {}", task.label, message);
+                    notify_finished(
+                        ctx,
+                        task.started,
+                        self.config.notify_after_secs,
+                        &format!("{} failed", task.label),
+                    );
+                    let query = task.retry.as_ref().and_then(|f| f.query.as_deref());
+                    if let Some(error) = SqlError::new(query, &message) {
+                        self.sql_error = Some(error);
+                    }
+                    let error = match task.retry {
+                        Some(filters) => Error::new(message).with_retry(filters),
+                        None => Error::new(message),
+                    };
+                    self.toasts.failure(&task.label, error);
+                }
+                None => self.tasks.push(task), // Data is still loading.
+            }
+        }
+
+        // The files queued for tabs load one at a time, as each load replaces the one running.
+        if self.tasks.is_empty() && !self.queued_tabs.is_empty() {
+            let filters = self.queued_tabs.remove(0);
+            let label = format!(
+                "Open {}",
+                file_label(&filters.filename.clone().unwrap_or_default())
+            );
+            self.run_in_new_tab(label, filters, ctx);
+        }
+
+        !self.tasks.is_empty()
+    }
+
+    /// Displays the data loaded by a completed operation.
+    fn apply_data(&mut self, data: DataFrameContainer) {
+        // Data loaded successfully!
+        let filename = data.filename.clone();
+        dbg!(&filename);
+
+        // Remember the file, its query and sort.
+        self.state.remember(&filename, &data.filters);
+        self.sql_error = None;
+
+        // Update data filters
+        self.data_filters = data.filters.clone();
+        if self.data_filters.csv_delimiter.is_empty() {
+            self.data_filters.csv_delimiter = self.config.default_delimiter.clone();
+        }
+        dbg!(&data.filters);
+
+        // Load metadata
+        self.metadata = match &*data.table_type {
+            "parquet" => FileMetadata::from_filename(&filename, "parquet", None, None).ok(),
+            "csv" => {
+                // let schema = (*data.df.schema().as_ref()).clone();
+                let arc_schema = data.df.schema().clone();
+                let row_count = data.df.height();
+                FileMetadata::from_filename(&filename, "csv", Some(arc_schema), Some(row_count))
+                    .ok()
+            }
+            _ => None,
         };
 
-        match output.try_recv() {
-            Ok(data) => match data {
-                Ok(data) => {
-                    // Data loaded successfully!
-                    let filename = data.filename.clone();
-                    dbg!(&filename);
-
-                    // Update data filters
-                    self.data_filters = data.filters.clone();
-                    dbg!(&data.filters);
-
-                    // Load metadata
-                    self.metadata = match &*data.table_type {
-                        "parquet" => {
-                            FileMetadata::from_filename(&filename, "parquet", None, None).ok()
-                        }
-                        "csv" => {
-                            // let schema = (*data.df.schema().as_ref()).clone();
-                            let arc_schema = data.df.schema().clone();
-                            let row_count = data.df.height();
-                            FileMetadata::from_filename(
-                                &filename,
-                                "csv",
-                                Some(arc_schema),
-                                Some(row_count),
-                            )
-                            .ok()
-                        }
-                        _ => None,
+        // Statistics refer to the previous result.
+        self.statistics = SummaryStatistics::default();
+
+        // The edits refer to the rows of the previous result.
+        self.discard_edits();
+
+        // A sort, filter or reload of the same file keeps the rows in view.
+        let same_file = self
+            .table
+            .as_ref()
+            .as_ref()
+            .is_some_and(|table| table.filename == filename);
+        match same_file {
+            true => self.scroll.keep(),
+            false => {
+                self.scroll.reset();
+                self.history.clear();
+            }
+        }
+
+        self.history.record(ViewState {
+            filters: data.filters.clone(),
+            masks: self.masks.clone(),
+        });
+        self.table = Arc::new(Some(data));
+    }
+
+    /// Records the masks changed in the view of the open file.
+    fn record_masks(&mut self) {
+        if let Some(table) = self.table.as_ref() {
+            self.history.record(ViewState {
+                filters: table.filters.clone(),
+                masks: self.masks.clone(),
+            });
+        }
+    }
+
+    /// Shows a view state again: its masks at once, its data once reloaded.
+    fn restore_view(&mut self, label: &str, state: ViewState, ctx: &Context) {
+        self.masks = state.masks.clone();
+
+        let Some(table) = self.table.as_ref() else {
+            return;
+        };
+        let current = ViewState {
+            filters: table.filters.clone(),
+            masks: state.masks.clone(),
+        };
+        if !current.same_data(&state) {
+            self.run_data_filters(label, state.filters, ctx);
+        }
+    }
+
+    /// Runs a `DataFuture` to load data asynchronously, tracked in the task list under the given label.
+    ///
+    /// The new result would replace the one of any operation still running, so those are
+    /// cancelled: otherwise a slower, older load could complete last and overwrite the table.
+    fn run_data_future(&mut self, label: impl ToString, future: DataFuture, ctx: &Context) {
+        self.cancel_loading();
+
+        let task = DataTask::spawn(label, future, &self.runtime, ctx);
+        self.tasks.push(task);
+    }
+
+    /// Loads the data with the filters (query, then sort); a failure can be retried.
+    /// Returns whether the load started.
+    fn run_data_filters(
+        &mut self,
+        label: impl ToString,
+        mut filters: DataFilters,
+        ctx: &Context,
+    ) -> bool {
+        filters.engine = self.config.engine;
+
+        // Refuse files too large for the memory limit before materializing them.
+        let filename = filters.filename.clone().unwrap_or_default();
+        if let Err(message) = check_memory_budget(&filename, self.config.memory_limit_mib) {
+            self.popover = Some(Box::new(Error::new(message)));
+            return false;
+        }
+
+        let future = DataFrameContainer::load_data_with_sort(filters.clone());
+        self.run_data_future(label, Box::new(Box::pin(future)), ctx);
+        if let Some(task) = self.tasks.last_mut() {
+            task.retry = Some(filters);
+        }
+        true
+    }
+
+    /// Menu of the bookmarked rows: navigation between them and the key column identifying them.
+    fn render_bookmarks_menu(&mut self, ui: &mut Ui, df: &Arc<DataFrame>, ctx: &Context) {
+        let keymap = self.config.keymap.clone();
+        let title = format!("🔖 Bookmarks ({})", self.scroll.bookmarks.len());
+
+        ui.menu_button(title, |ui| {
+            let bookmarks = &self.scroll.bookmarks;
+            let mut key_column = bookmarks.key_column().map(str::to_string);
+            ComboBox::from_label("Key column")
+                .selected_text(key_column.as_deref().unwrap_or("(row number)"))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut key_column, None, "(row number)");
+                    for name in df.get_column_names() {
+                        ui.selectable_value(&mut key_column, Some(name.to_string()), name.as_str());
+                    }
+                })
+                .response
+                .on_hover_text(
+                    "Bookmarks by key are kept when the file is queried or sorted again",
+                );
+            if key_column.as_deref() != bookmarks.key_column() {
+                self.scroll.bookmarks.set_key_column(key_column, df);
+            }
+
+            ui.separator();
+
+            let rows = self.scroll.bookmarks.rows(df);
+            let next =
+                widgets::Button::new("Next").shortcut_text(keymap.text(ctx, Action::NextBookmark));
+            if ui.add_enabled(!rows.is_empty(), next).clicked() {
+                self.run_action(Action::NextBookmark, ctx);
+            }
+            let previous = widgets::Button::new("Previous")
+                .shortcut_text(keymap.text(ctx, Action::PreviousBookmark));
+            if ui.add_enabled(!rows.is_empty(), previous).clicked() {
+                self.run_action(Action::PreviousBookmark, ctx);
+            }
+            if ui
+                .add_enabled(
+                    !self.scroll.bookmarks.is_empty(),
+                    widgets::Button::new("Clear"),
+                )
+                .clicked()
+            {
+                self.scroll.bookmarks.clear();
+                ui.close_menu();
+            }
+
+            if rows.is_empty() {
+                ui.weak("Right-click a row to bookmark it");
+                return;
+            }
+
+            ui.separator();
+
+            ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                for row in rows {
+                    let label = match self.scroll.bookmarks.key_column() {
+                        Some(column) => df
+                            .column(column)
+                            .and_then(|values| values.get(row))
+                            .map(|value| format!("{column} = {}", cell_text(&value)))
+                            .unwrap_or_default(),
+                        None => format!("Row {}", row + 1),
                     };
+                    if ui.button(label).clicked() {
+                        self.scroll.scroll_to(row);
+                        ui.close_menu();
+                    }
+                }
+            });
+        });
+    }
+
+    /// Applies a change of the temp views to the query filters and to those of the current result.
+    fn update_views(
+        &mut self,
+        update: impl Fn(&mut DataFilters) -> Result<(), String>,
+    ) -> Result<(), String> {
+        update(&mut self.data_filters)?;
+        if let Some(table) = self.table.as_ref() {
+            let mut table = table.clone();
+            update(&mut table.filters)?;
+            self.table = Arc::new(Some(table));
+        }
+        Ok(())
+    }
+
+    /// Temp views of the session: the current result is kept under a name, for later queries.
+    fn render_views(&mut self, ui: &mut Ui) {
+        let lang = language(ui.ctx());
+        ui.strong(format!(
+            "{} ({})",
+            tr("Temp views", lang),
+            self.data_filters.views.len()
+        ));
+
+        let mut dropped = None;
+        for view in &self.data_filters.views {
+            ui.horizontal(|ui| {
+                if ui
+                    .small_button("✖")
+                    .on_hover_text(tr("Drop the view", lang))
+                    .clicked()
+                {
+                    dropped = Some(view.name.clone());
+                }
+                let (rows, columns) = view.df.shape();
+                ui.label(&view.name).on_hover_text(format!(
+                    "{}\n{rows} {}, {columns} {}",
+                    view.query.as_deref().unwrap_or_default(),
+                    tr("rows", lang),
+                    tr("columns", lang)
+                ));
+            });
+        }
+        if let Some(name) = dropped {
+            let _ = self.update_views(|filters| {
+                filters.drop_view(&name);
+                Ok(())
+            });
+        }
+
+        let Some(table) = self.table.as_ref().clone() else {
+            return;
+        };
+        let suggested = format!("step{}", self.data_filters.views.len() + 1);
+        ui.horizontal(|ui| {
+            ui.add(
+                TextEdit::singleline(&mut self.view_name)
+                    .hint_text(&suggested)
+                    .desired_width(120.0),
+            );
+            let create = ui.button(tr("Create view", lang)).on_hover_text(tr(
+                "Keep the current result under this name, as CREATE VIEW would",
+                lang,
+            ));
+            if create.clicked() {
+                let name = match self.view_name.trim() {
+                    "" => suggested.clone(),
+                    name => name.to_string(),
+                };
+                let query = table.filters.query.clone();
+                match self.update_views(|filters| {
+                    filters.create_view(&name, query.clone(), table.df.clone())
+                }) {
+                    Ok(()) => {
+                        self.toasts.success(format!("View {name} created"));
+                        self.view_name.clear();
+                    }
+                    Err(message) => self.toasts.error(message),
+                }
+            }
+        });
+    }
+
+    /// Loads the result grouped by a column, replacing the sorts of the result it was grouped from;
+    /// in a new tab if `new_tab`.
+    fn run_group_by(
+        &mut self,
+        group_by: &GroupByQuery,
+        filters: &DataFilters,
+        new_tab: bool,
+        ctx: &Context,
+    ) {
+        let filters = DataFilters {
+            query: Some(group_by.sql()),
+            sort: None,
+            then_by: Vec::new(),
+            ..filters.clone()
+        };
+        let label = format!("Group by {}", group_by.column);
+        match new_tab {
+            true => self.run_in_new_tab(label, filters, ctx),
+            false => {
+                self.run_data_filters(label, filters, ctx);
+            }
+        }
+    }
 
-                    self.table = Arc::new(Some(data));
-                    false // Data loading complete.
+    /// Shows the data table, running the actions of its header.
+    fn render_table(&mut self, ui: &mut Ui, data: &DataFrameContainer, ctx: &Context) {
+        // The masks apply to the frame of an image of the table, if asked for.
+        let masks = match self.snapshot.masks_forced() {
+            true => ColumnMasks {
+                enabled: true,
+                ..self.masks.clone()
+            },
+            false => self.masks.clone(),
+        };
+
+        // Data loaded successfully, display the table.
+        let output = ScrollArea::horizontal().show(ui, |ui| {
+            let opt_action = data.render_table(
+                ui,
+                &self.config.table,
+                &mut self.state.table_zoom,
+                &mut self.editor,
+                &masks,
+                &mut self.scroll,
+            ); // Render the table and get any header action.
+            match opt_action {
+                Some(TableAction::Sort(mut filters)) => {
+                    filters.engine = self.config.engine;
+                    let future = data.clone().sort(Some(*filters)); // Sort the data.
+                    self.run_data_future("Sort", Box::new(Box::pin(future)), ctx); // Run the sorting task.
+                }
+                Some(TableAction::Histogram(column_name)) => {
+                    // Open the histogram popover for the selected column.
+                    let histogram = data
+                        .df
+                        .column(&column_name)
+                        .map_err(|e| e.to_string())
+                        .and_then(Histogram::new);
+                    self.popover = Some(match histogram {
+                        Ok(histogram) => Box::new(histogram),
+                        Err(message) => Box::new(Error::new(message)),
+                    });
                 }
-                Err(msg) => {
-                    // An error occurred during data loading.
-                    self.popover = Some(Box::new(Error { message: msg }));
-                    false // Data loading complete (with an error).
+                Some(TableAction::Transform(transform)) => {
+                    // Reload the result with the transform added.
+                    let mut filters = data.filters.clone();
+                    filters.sort = filters.sort.map(|s| transform.update_sort(s));
+                    let label = transform.to_string();
+                    filters.transforms.push(transform);
+                    self.run_data_filters(label, filters, ctx);
                 }
-            },
-            Err(error) => match error {
-                TryRecvError::Empty => {
-                    // Data is still loading. Put the receiver back into the `Option`.
-                    self.pipe = Some(output);
-                    true // Data loading still in progress.
-                }
-                TryRecvError::Closed => {
-                    // The data loading task was terminated unexpectedly.
-                    self.popover = Some(Box::new(Error {
-                        message: "Data operation terminated without response.".to_string(),
-                    }));
-                    false // Data loading complete (due to termination).
+                Some(TableAction::Mask(column_name, mask)) => {
+                    self.masks.set(&column_name, mask);
+                    self.record_masks();
                 }
-            },
-        }
+                Some(TableAction::ValueCounts(column_name)) => {
+                    // Open the value counts popover for the selected column.
+                    let handle = self.runtime.handle();
+                    self.popover = Some(Box::new(ValueCounts::new(
+                        &data.df,
+                        &column_name,
+                        &data.filters,
+                        handle,
+                        ctx,
+                    )));
+                }
+                Some(TableAction::GroupBy(column_name)) => {
+                    let group_by = GroupByQuery::new(column_name, &data.df, &data.filters);
+                    // The grouped result opens in a tab, beside the result grouped.
+                    self.run_group_by(&group_by, &data.filters, true, ctx);
+                    self.group_by = Some(group_by);
+                }
+                None => {}
+            }
+        });
+
+        // The area of the table, for an image of it.
+        self.snapshot.viewport = Some(output.inner_rect);
     }
 
-    /// Runs a `DataFuture` to load data asynchronously. This function takes a future, spawns a Tokio task, and sets up a channel to receive the result.
-    fn run_data_future(&mut self, future: DataFuture, ctx: &Context) {
-        // Before scheduling a new future, ensure no tasks are stuck
-        self.tasks.retain(|task| !task.is_finished());
+    /// Shows the data table beside the pinned result, scrolling them together if enabled.
+    fn render_split_view(&mut self, ui: &mut Ui, data: &DataFrameContainer, ctx: &Context) {
+        let lang = language(ctx);
+        ui.columns(2, |columns| {
+            self.render_table(&mut columns[0], data, ctx);
+
+            let Some(split) = self.split.as_mut() else {
+                return;
+            };
+            let ui = &mut columns[1];
+            let mut close = false;
+            let mut masked = false;
+            let mut grouped = None;
+            ui.horizontal(|ui| {
+                if ui
+                    .small_button("✖")
+                    .on_hover_text(tr("Close the split view", lang))
+                    .clicked()
+                {
+                    close = true;
+                }
+                ui.checkbox(&mut split.sync_scroll, tr("Synchronize scrolling", lang));
+                let columns = split.common_columns(&data.df);
+                ui.add_enabled_ui(split.sync_scroll, |ui| {
+                    ComboBox::from_id_salt("split_key_column")
+                        .selected_text(
+                            split
+                                .key_column
+                                .as_deref()
+                                .unwrap_or(tr("(row number)", lang)),
+                        )
+                        .show_ui(ui, |ui| {
+                            let row_number = tr("(row number)", lang);
+                            ui.selectable_value(&mut split.key_column, None, row_number);
+                            for name in &columns {
+                                ui.selectable_value(
+                                    &mut split.key_column,
+                                    Some(name.clone()),
+                                    name,
+                                );
+                            }
+                        })
+                        .response
+                        .on_hover_text(tr(
+                            "Align the rows of both tables by the values of a column",
+                            lang,
+                        ));
+                });
+                if split.sorting.is_some() {
+                    busy_indicator(ui);
+                }
+                ui.add(Label::new(RichText::new(&split.label).strong()).truncate());
+            });
 
-        // Create a oneshot channel for sending the data from the async task to the UI thread.
-        let (tx, rx) = oneshot::channel::<Result<DataFrameContainer, String>>();
-        self.pipe = Some(rx);
+            // The pinned table is sorted in the background, like the main one.
+            if let Some(result) = split.sorting.as_mut().and_then(DataTask::try_result) {
+                split.sorting = None;
+                match result {
+                    Ok(sorted) => split.table.set_sorted(sorted),
+                    Err(message) => self.toasts.error(message),
+                }
+            }
 
-        // Clone the context for use within the asynchronous task (to request repaints).
-        let ctx_clone = ctx.clone();
+            split.table.options = self.config.table;
+            split.table.zoom = self.state.table_zoom;
+            // The columns masked in the main table are masked in the pinned one too.
+            split.table.masks = self.masks.clone();
+            match split.table.show(ui) {
+                Some(TableAction::Sort(mut filters)) => {
+                    filters.engine = self.config.engine;
+                    let future = split.table.data.clone().sort(Some(*filters));
+                    let task =
+                        DataTask::spawn("Sort", Box::new(Box::pin(future)), &self.runtime, ctx);
+                    if let Some(previous) = split.sorting.replace(task) {
+                        previous.cancel();
+                    }
+                }
+                Some(TableAction::Histogram(column_name)) => {
+                    let histogram = split
+                        .table
+                        .data
+                        .df
+                        .column(&column_name)
+                        .map_err(|e| e.to_string())
+                        .and_then(Histogram::new);
+                    self.popover = Some(match histogram {
+                        Ok(histogram) => Box::new(histogram),
+                        Err(message) => Box::new(Error::new(message)),
+                    });
+                }
+                Some(TableAction::ValueCounts(column_name)) => {
+                    let data = &split.table.data;
+                    let handle = self.runtime.handle();
+                    self.popover = Some(Box::new(ValueCounts::new(
+                        &data.df,
+                        &column_name,
+                        &data.filters,
+                        handle,
+                        ctx,
+                    )));
+                }
+                Some(TableAction::Transform(_)) => {
+                    self.toasts.error(tr(
+                        "Columns are renamed and cast in the main table only",
+                        lang,
+                    ));
+                }
+                Some(TableAction::GroupBy(column_name)) => {
+                    let data = &split.table.data;
+                    let group_by = GroupByQuery::new(column_name, &data.df, &data.filters);
+                    grouped = Some((group_by, data.filters.clone()));
+                }
+                // The pinned table sets its masks itself; they are shared below.
+                Some(TableAction::Mask(..)) | None => {}
+            }
+            // A column masked in the pinned table is masked in the main one too.
+            if split.table.masks != self.masks {
+                self.masks = split.table.masks.clone();
+                masked = true;
+            }
 
-        // Spawn an async task to load the data.
-        let handle = self.runtime.spawn(async move {
-            let data = future.await;
-            if tx.send(data).is_err() {
-                eprintln!("Receiver dropped before data could be sent."); // Handle potential error if the receiver is dropped.
+            // The table under the pointer is the one being scrolled.
+            let scrolled = if columns[0].rect_contains_pointer(columns[0].min_rect()) {
+                Some(SplitSide::Main)
+            } else if columns[1].rect_contains_pointer(columns[1].min_rect()) {
+                Some(SplitSide::Pinned)
+            } else {
+                None
+            };
+            if split.sync(&mut self.scroll, &data.df, scrolled) {
+                ctx.request_repaint();
+            }
+            if close {
+                self.split = None;
+            }
+            if masked {
+                self.record_masks();
+            }
+            // The pinned result grouped by a column opens in a tab, like the main one.
+            if let Some((group_by, filters)) = grouped {
+                self.run_group_by(&group_by, &filters, true, ctx);
+                self.group_by = Some(group_by);
             }
-            ctx_clone.request_repaint(); // Request a repaint of the UI to display the loaded data.
         });
+    }
+}
 
-        self.tasks.push(handle); // Track the task.
+impl PolarsViewApp {
+    /// Title of the window: the open file, marked with `•` while it has unsaved edits.
+    fn window_title(&self) -> String {
+        match self.table.as_ref() {
+            Some(table) => {
+                let dirty = if self.editor.changes.is_empty() {
+                    ""
+                } else {
+                    "• "
+                };
+                format!("{dirty}{} — {APP_NAME}", file_label(&table.filename))
+            }
+            None => APP_NAME.to_string(),
+        }
+    }
+}
+
+/// The frame of an export: the `rows` of `df` (all if `None`), with the `masks` applied if any.
+fn export_frame(
+    df: Arc<DataFrame>,
+    rows: Option<Range<usize>>,
+    masks: Option<&ColumnMasks>,
+) -> Result<Arc<DataFrame>, String> {
+    let df = match rows {
+        Some(rows) => {
+            let start = rows.start.min(df.height());
+            let length = rows.end.min(df.height()).saturating_sub(start);
+            Arc::new(df.slice(start as i64, length))
+        }
+        None => df,
+    };
+    match masks {
+        Some(masks) => masks.apply(&df).map(Arc::new).map_err(|e| e.to_string()),
+        None => Ok(df),
     }
 }
 
+/// File name of a path, to label the tasks.
+pub fn file_label(filename: &str) -> String {
+    Path::new(filename)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| filename.to_string())
+}
+
 // See
 // https://github.com/emilk/egui/blob/master/examples/custom_window_frame/src/main.rs
 // https://rodneylab.com/trying-egui/
 
 impl eframe::App for PolarsViewApp {
+    /// Persists the settings and the application state.
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        self.config.save(storage);
+        self.state.save(storage);
+    }
+
+    /// A normal exit leaves no session to recover.
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.autosave.clear();
+    }
+
     fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        // The theme may also be changed with the switch of the menu bar.
+        self.config.theme = ctx.options(|options| options.theme_preference);
+
         // Check and display any active popovers (errors, settings, etc.).
         self.check_popover(ctx);
 
+        // Use the files chosen in a file dialog closed since the last frame.
+        self.check_dialog_pending(ctx);
+
+        // Set again if the table is drawn in this frame.
+        self.snapshot.viewport = None;
+
+        // Reload the config file when edited.
+        match self.config_file.check(&mut self.config) {
+            Some(Ok(())) => {
+                self.config.apply(ctx);
+                self.toasts.success("Config file reloaded");
+            }
+            Some(Err(message)) => self.toasts.error(message),
+            None => {}
+        }
+
+        // Save the session now and then, for a restore after a crash.
+        if self.autosave.due() {
+            if let Some((path, session)) = self.autosave.changed(self.session()) {
+                self.runtime.spawn_blocking(move || {
+                    if let Err(message) = write_autosave(&path, &session) {
+                        tracing::warn!("Autosave failed: {}", message);
+                    }
+                });
+            }
+        }
+
+        // In low-power mode, the config file is checked and the session saved
+        // only in the frames drawn for input, without waking the app.
+        if !self.config.low_power {
+            if self.config_file.exists() {
+                ctx.request_repaint_after(CONFIG_POLL_INTERVAL);
+            }
+            ctx.request_repaint_after(AUTOSAVE_INTERVAL);
+        }
+
+        // Run the actions of the keyboard shortcuts pressed.
+        for action in self.config.keymap.pressed(ctx) {
+            self.run_action(action, ctx);
+        }
+
+        // Open the files handed over by later invocations, raising the window.
+        while let Some(filename) = self.instance.as_ref().and_then(InstanceServer::next_file) {
+            self.open_file(filename, ctx);
+            ctx.send_viewport_cmd(ViewportCommand::Focus);
+        }
+
         // Handle dropped files.
         if let Some(dropped_file) = ctx.input(|i| i.raw.dropped_files.last().cloned()) {
             if let Some(path) = &dropped_file.path {
                 if let Some(filename) = path.to_str() {
                     // Load data from the dropped file.
-                    self.run_data_future(
-                        Box::new(Box::pin(DataFrameContainer::load_data(
-                            filename.to_string(),
-                        ))),
-                        ctx,
-                    );
+                    self.open_file(filename.to_string(), ctx);
                 }
             }
         }
 
+        // Name the open file in the window (and taskbar) title, once it changes.
+        let title = self.window_title();
+        if title != self.title {
+            ctx.send_viewport_cmd(ViewportCommand::Title(title.clone()));
+            self.title = title;
+        }
+
         // Define the main UI layout.
         //
         // Using static layout until I put together a TabTree that can make this dynamic
@@ -203,28 +1959,175 @@ impl eframe::App for PolarsViewApp {
         //  ---------------------------
         //  | notification footer     |
 
+        let lang = language(ctx);
         TopBottomPanel::top("top_panel").show(ctx, |ui| {
             menu::bar(ui, |ui| {
                 ui.horizontal(|ui| {
-                    ui.menu_button("File", |ui| {
-                        if ui.button("Open").clicked() {
-                            // Open a file dialog to select a file.
-                            if let Ok(filename) = self.runtime.block_on(file_dialog()) {
-                                self.run_data_future(
-                                    Box::new(Box::pin(DataFrameContainer::load_data(filename))),
-                                    ctx,
-                                );
-                            }
+                    ui.menu_button(tr("File", lang), |ui| {
+                        let keymap = self.config.keymap.clone();
+
+                        let open = widgets::Button::new(tr("Open", lang))
+                            .shortcut_text(keymap.text(ctx, Action::Open));
+                        if ui.add(open).clicked() {
+                            ui.close_menu();
+                            self.run_action(Action::Open, ctx);
+                        }
+
+                        let close = widgets::Button::new(tr("Close", lang))
+                            .shortcut_text(keymap.text(ctx, Action::Close));
+                        if ui
+                            .add_enabled(self.table.is_some(), close)
+                            .on_hover_text(tr("Close the file and release its memory", lang))
+                            .clicked()
+                        {
+                            ui.close_menu();
+                            self.run_action(Action::Close, ctx);
+                        }
+
+                        let recent_files = self.state.recent_files.clone();
+                        ui.add_enabled_ui(!recent_files.is_empty(), |ui| {
+                            ui.menu_button(tr("Open recent", lang), |ui| {
+                                for filename in recent_files {
+                                    if ui.button(&filename).clicked() {
+                                        self.open_file(filename, ctx);
+                                        ui.close_menu();
+                                    }
+                                }
+
+                                ui.separator();
+
+                                if ui.button(tr("Clear list", lang)).clicked() {
+                                    self.state.recent_files.clear();
+                                    self.state.queries.clear();
+                                    ui.close_menu();
+                                }
+                            });
+                        });
+
+                        let open_session = widgets::Button::new(tr("Open session…", lang))
+                            .shortcut_text(keymap.text(ctx, Action::OpenSession));
+                        if ui
+                            .add(open_session)
+                            .on_hover_text(tr("Reopen the tables and view saved in a session file", lang))
+                            .clicked()
+                        {
+                            ui.close_menu();
+                            self.run_action(Action::OpenSession, ctx);
+                        }
+
+                        let save_session = widgets::Button::new(tr("Save session…", lang))
+                            .shortcut_text(keymap.text(ctx, Action::SaveSession));
+                        if ui
+                            .add_enabled(self.table.is_some(), save_session)
+                            .on_hover_text(tr("Save the open table, its query and the view settings", lang))
+                            .clicked()
+                        {
+                            ui.close_menu();
+                            self.run_action(Action::SaveSession, ctx);
+                        }
+
+                        if ui
+                            .button(tr("Import CSV…", lang))
+                            .on_hover_text(
+                                tr("Review the inferred schema of a CSV file before loading it", lang),
+                            )
+                            .clicked()
+                        {
+                            let dialog = pick_file_dialog("Import CSV", &[("CSV", &["csv"])]);
+                            self.open_file_dialog(DialogPurpose::ImportCsv, dialog, ctx);
+                            ui.close_menu();
+                        }
+
+                        if ui
+                            .button(tr("Compare files…", lang))
+                            .on_hover_text(tr("Compare the metadata of two Parquet files", lang))
+                            .clicked()
+                        {
+                            // Select both files, then show the comparison.
+                            self.open_pair_dialog(
+                                DialogPurpose::CompareFiles,
+                                ["First file", "Second file"],
+                                &[("Parquet", &["parquet"])],
+                                ctx,
+                            );
+                            ui.close_menu();
+                        }
+
+                        if ui
+                            .button(tr("Schema diff…", lang))
+                            .on_hover_text(tr("List the schema changes between two files", lang))
+                            .clicked()
+                        {
+                            // Select the old file, then the new one.
+                            self.open_pair_dialog(
+                                DialogPurpose::SchemaDiff,
+                                ["Old file", "New file"],
+                                &[("Data", &["parquet", "csv"])],
+                                ctx,
+                            );
+                            ui.close_menu();
+                        }
+
+                        if ui
+                            .button(tr("Row diff…", lang))
+                            .on_hover_text(
+                                tr("List the rows added, removed or changed between two Parquet files", lang),
+                            )
+                            .clicked()
+                        {
+                            // Select the old file, then the new one; the keys are chosen in the window.
+                            self.open_pair_dialog(
+                                DialogPurpose::RowDiff,
+                                ["Old file", "New file"],
+                                &[("Parquet", &["parquet"])],
+                                ctx,
+                            );
+                            ui.close_menu();
+                        }
+
+                        let export = widgets::Button::new(tr("Export report…", lang))
+                            .shortcut_text(keymap.text(ctx, Action::ExportReport));
+                        if ui
+                            .add_enabled(self.table.is_some(), export)
+                            .on_hover_text(tr("Export a profiling report (HTML or Markdown)", lang))
+                            .clicked()
+                        {
+                            ui.close_menu();
+                            self.run_action(Action::ExportReport, ctx);
+                        }
+
+                        ui.add_enabled_ui(self.table.is_some(), |ui| {
+                            ui.menu_button(tr("Export HTML", lang), |ui| {
+                                let hint = tr("Export the rows shown in the table as an HTML page", lang);
+                                if ui.button(tr("Visible rows…", lang)).on_hover_text(hint).clicked() {
+                                    ui.close_menu();
+                                    self.export_html(true, ctx);
+                                }
+                                let hint = tr("Export the whole result as an HTML page", lang);
+                                if ui.button(tr("All rows…", lang)).on_hover_text(hint).clicked() {
+                                    ui.close_menu();
+                                    self.export_html(false, ctx);
+                                }
+                            });
+                        });
+
+                        if ui
+                            .add_enabled(self.table.is_some(), widgets::Button::new(tr("Export PDF…", lang)))
+                            .on_hover_text(tr("Print the result on paginated pages, with the header row on each page", lang))
+                            .clicked()
+                        {
                             ui.close_menu();
+                            self.export_pdf(ctx);
                         }
 
-                        if ui.button("Settings").clicked() {
-                            // Show the settings popover.
-                            self.popover = Some(Box::new(Settings {}));
+                        let settings = widgets::Button::new(tr("Settings", lang))
+                            .shortcut_text(keymap.text(ctx, Action::Settings));
+                        if ui.add(settings).clicked() {
                             ui.close_menu();
+                            self.run_action(Action::Settings, ctx);
                         }
 
-                        ui.menu_button("About", |ui| {
+                        ui.menu_button(tr("About", lang), |ui| {
                             // Display application information.
                             Frame::default()
                                 .stroke(Stroke::new(1.0, Color32::GRAY)) // Thin gray border for visual separation.
@@ -266,7 +2169,7 @@ impl eframe::App for PolarsViewApp {
                                                 let heading =
                                                     Hyperlink::from_label_and_url("parqbench", url);
 
-                                                ui.label("A fork of ");
+                                                ui.label(tr("A fork of ", lang));
                                                 ui.add(heading).on_hover_text(url);
                                             });
                                             ui.end_row();
@@ -276,7 +2179,7 @@ impl eframe::App for PolarsViewApp {
                                                 let heading =
                                                     Hyperlink::from_label_and_url("Polars", url);
 
-                                                ui.label("Powered by ");
+                                                ui.label(tr("Powered by ", lang));
                                                 ui.add(heading).on_hover_text(url);
                                             });
                                             ui.end_row();
@@ -286,7 +2189,7 @@ impl eframe::App for PolarsViewApp {
                                                 let heading =
                                                     Hyperlink::from_label_and_url("egui", url);
 
-                                                ui.label("Built with ");
+                                                ui.label(tr("Built with ", lang));
                                                 ui.add(heading).on_hover_text(url);
                                             });
                                             ui.end_row();
@@ -299,9 +2202,278 @@ impl eframe::App for PolarsViewApp {
                                 });
                         });
 
-                        if ui.button("Quit").clicked() {
-                            // Close the application.
-                            ui.ctx().send_viewport_cmd(ViewportCommand::Close);
+                        let quit = widgets::Button::new(tr("Quit", lang))
+                            .shortcut_text(keymap.text(ctx, Action::Quit));
+                        if ui.add(quit).clicked() {
+                            self.run_action(Action::Quit, ctx);
+                        }
+                    });
+
+                    ui.menu_button(tr("View", lang), |ui| {
+                        ui.menu_button(tr("Panels", lang), |ui| {
+                            for pane in Pane::ALL {
+                                ui.menu_button(pane.title(lang), |ui| {
+                                    self.state.layout.pane_menu(ui, pane);
+                                });
+                            }
+
+                            ui.separator();
+
+                            if ui.button(tr("Reset layout", lang)).clicked() {
+                                self.state.layout = DockLayout::default();
+                                ui.close_menu();
+                            }
+                        });
+
+                        ui.checkbox(&mut self.state.show_log, tr("Log panel", lang));
+
+                        let masking = ui.add_enabled(
+                            !self.masks.masks.is_empty(),
+                            egui::Checkbox::new(&mut self.masks.enabled, tr("Mask columns", lang)),
+                        )
+                        .on_hover_text(tr("Hide the values of the columns masked from their header menu, also in the exports", lang))
+                        .on_disabled_hover_text(tr("Mask a column from its header menu first", lang));
+                        if masking.changed() {
+                            self.record_masks();
+                        }
+
+                        let mut split = self.split.is_some();
+                        if ui
+                            .add_enabled(
+                                split || self.table.is_some(),
+                                egui::Checkbox::new(&mut split, tr("Split view", lang)),
+                            )
+                            .on_hover_text(tr("Pin the current result beside the table, to compare it with the next query or file", lang))
+                            .changed()
+                        {
+                            self.split = match split {
+                                true => (*self.table)
+                                    .clone()
+                                    .map(|data| SplitView::new(data, &self.scroll)),
+                                false => None,
+                            };
+                        }
+
+                        ui.add_enabled_ui(self.table.is_some(), |ui| {
+                            ui.menu_button(tr("Table image", lang), |ui| {
+                                if ui.button(tr("Copy to clipboard", lang)).clicked() {
+                                    self.snapshot.request(SnapshotTarget::Clipboard);
+                                    ui.close_menu();
+                                }
+                                if ui.button(tr("Save as PNG…", lang)).clicked() {
+                                    let dialog = async {
+                                        save_file_dialog("table.png", &[("PNG", &["png"])]).await
+                                    };
+                                    self.open_file_dialog(DialogPurpose::SaveTableImage, dialog, ctx);
+                                    ui.close_menu();
+                                }
+                                ui.add_enabled(
+                                    !self.masks.masks.is_empty(),
+                                    egui::Checkbox::new(&mut self.snapshot.masked, tr("Apply masks", lang)),
+                                )
+                                .on_hover_text(tr("Mask the masked columns in the image, even while masking is off", lang));
+                            });
+                        });
+
+                        ui.separator();
+
+                        ui.checkbox(&mut self.config.table.striped, tr("Striped rows", lang));
+                        ui.checkbox(&mut self.config.table.type_icons, tr("Column type icons", lang))
+                            .on_hover_text(tr("Show the type of each column, and ∅ if it has nulls, next to its name", lang));
+                        ui.checkbox(
+                            &mut self.config.table.hover_highlight,
+                            tr("Highlight hovered row", lang),
+                        );
+
+                        ui.separator();
+
+                        ui.label(tr("Row density:", lang));
+                        ui.radio_value(
+                            &mut self.config.table.density,
+                            RowDensity::Compact,
+                            tr("Compact", lang),
+                        );
+                        ui.radio_value(
+                            &mut self.config.table.density,
+                            RowDensity::Comfortable,
+                            tr("Comfortable", lang),
+                        );
+
+                        ui.separator();
+
+                        ui.label(format!("Zoom: {:.0}%", self.state.table_zoom * 100.0))
+                            .on_hover_text(tr("Ctrl+scroll over the table to zoom", lang));
+                        ui.horizontal(|ui| {
+                            if ui.button(tr("Zoom in", lang)).clicked() {
+                                self.state.table_zoom =
+                                    (self.state.table_zoom * 1.1).min(*TABLE_ZOOM_RANGE.end());
+                            }
+                            if ui.button(tr("Zoom out", lang)).clicked() {
+                                self.state.table_zoom =
+                                    (self.state.table_zoom / 1.1).max(*TABLE_ZOOM_RANGE.start());
+                            }
+                            if ui.button(tr("Reset", lang)).clicked() {
+                                self.state.table_zoom = 1.0;
+                            }
+                        });
+                    });
+
+                    ui.menu_button(tr("Edit", lang), |ui| {
+                        let keymap = self.config.keymap.clone();
+                        let undo = widgets::Button::new(tr("Undo", lang))
+                            .shortcut_text(keymap.text(ctx, Action::Undo));
+                        if ui
+                            .add_enabled(self.history.can_undo(), undo)
+                            .on_hover_text(tr("Step back the last query, sort, column change or mask", lang))
+                            .clicked()
+                        {
+                            ui.close_menu();
+                            self.run_action(Action::Undo, ctx);
+                        }
+                        let redo = widgets::Button::new(tr("Redo", lang))
+                            .shortcut_text(keymap.text(ctx, Action::Redo));
+                        if ui.add_enabled(self.history.can_redo(), redo).clicked() {
+                            ui.close_menu();
+                            self.run_action(Action::Redo, ctx);
+                        }
+
+                        ui.separator();
+
+                        ui.add_enabled(
+                            self.table.is_some(),
+                            egui::Checkbox::new(&mut self.editor.enabled, tr("Edit mode", lang)),
+                        )
+                        .on_hover_text(
+                            tr("Double-click a cell to edit it; Enter validates, Escape cancels", lang),
+                        );
+
+                        ui.separator();
+
+                        if ui
+                            .add_enabled(
+                                self.table.is_some(),
+                                widgets::Button::new(tr("Computed columns…", lang)),
+                            )
+                            .on_hover_text(
+                                tr("Add columns computed with a SQL expression, e.g. price * qty", lang),
+                            )
+                            .clicked()
+                        {
+                            ui.close_menu();
+                            if let Some(table) = self.table.as_ref() {
+                                let schema = table.df.schema().as_ref().clone();
+                                let popover = DerivedColumns::new(&table.filters, schema);
+                                self.popover = Some(Box::new(popover));
+                            }
+                        }
+
+                        if ui
+                            .add_enabled(self.table.is_some(), widgets::Button::new(tr("Find and replace…", lang)))
+                            .on_hover_text(tr("Replace text, or a regex, in string columns", lang))
+                            .clicked()
+                        {
+                            ui.close_menu();
+                            if let Some(table) = self.table.as_ref() {
+                                let df = Arc::clone(&table.df);
+                                let handle = self.runtime.handle().clone();
+                                let popover = FindReplace::new(&table.filters, df, handle);
+                                self.popover = Some(Box::new(popover));
+                            }
+                        }
+
+                        ui.separator();
+
+                        let transforms = self.data_filters.transforms.len();
+                        if ui
+                            .add_enabled(self.table.is_some(), widgets::Button::new(tr("Apply recipe…", lang)))
+                            .on_hover_text(tr("Append the pipeline saved in a recipe file", lang))
+                            .clicked()
+                        {
+                            ui.close_menu();
+                            self.open_recipe(ctx);
+                        }
+                        if ui
+                            .add_enabled(transforms > 0, widgets::Button::new(tr("Save recipe…", lang)))
+                            .on_hover_text(format!(
+                                "Save the {transforms} steps of the pipeline, to reapply them to other files"
+                            ))
+                            .clicked()
+                        {
+                            ui.close_menu();
+                            self.save_recipe(ctx);
+                        }
+
+                        // SQL queries and recipes saved in the scripts directory of the user.
+                        let dir = scripts_dir();
+                        ui.menu_button(tr("Scripts", lang), |ui| {
+                            let scripts = dir.as_deref().map(list_scripts).unwrap_or_default();
+                            if scripts.is_empty() {
+                                ui.label(tr("No scripts", lang));
+                            }
+                            for path in scripts {
+                                let name = script_name(&path);
+                                if ui
+                                    .add_enabled(self.table.is_some(), widgets::Button::new(&name))
+                                    .on_hover_text(path.display().to_string())
+                                    .clicked()
+                                {
+                                    ui.close_menu();
+                                    match Script::load(&path) {
+                                        Ok(script) => {
+                                            let filters = script.apply(&self.data_filters);
+                                            self.run_data_filters(format!("Script {name}"), filters, ctx);
+                                        }
+                                        Err(message) => {
+                                            self.popover = Some(Box::new(Error::new(message)));
+                                        }
+                                    }
+                                }
+                            }
+                            if let Some(dir) = &dir {
+                                ui.separator();
+                                ui.weak(format!(
+                                    "Add .sql queries and .{RECIPE_EXTENSION} pipelines to {}",
+                                    dir.display()
+                                ));
+                            }
+                        });
+
+                        ui.separator();
+
+                        let edits = self.editor.changes.len();
+                        ui.label(format!("{edits} {}", tr("unsaved edits", lang)));
+
+                        if ui
+                            .add_enabled(edits > 0, widgets::Button::new(tr("Save as…", lang)))
+                            .on_hover_text(tr("Save the edited result to a new file", lang))
+                            .clicked()
+                        {
+                            ui.close_menu();
+                            self.save_edits_as(ctx);
+                        }
+
+                        let save_over = edits > 0 && self.can_save_over_original();
+                        if ui
+                            .add_enabled(save_over, widgets::Button::new(tr("Save over original", lang)))
+                            .on_hover_text(tr("Replace the Parquet file with the edited data", lang))
+                            .on_disabled_hover_text(
+                                tr("Only available with edits to a whole Parquet file (no query, sort or transform)", lang),
+                            )
+                            .clicked()
+                        {
+                            ui.close_menu();
+                            if let Some(table) = self.table.as_ref() {
+                                let path = PathBuf::from(&table.filename);
+                                self.save_edits_to(path, true, ctx);
+                            }
+                        }
+
+                        if ui
+                            .add_enabled(edits > 0, widgets::Button::new(tr("Discard edits", lang)))
+                            .clicked()
+                        {
+                            ui.close_menu();
+                            self.editor.discard();
                         }
                     });
 
@@ -315,49 +2487,87 @@ impl eframe::App for PolarsViewApp {
             });
         });
 
-        SidePanel::left("side_panel")
-            .resizable(true)
-            .show(ctx, |ui| {
-                ScrollArea::vertical().show(ui, |ui| {
-                    // Add Metadata section
-                    if let Some(metadata) = &self.metadata {
-                        ui.collapsing("Metadata", |ui| {
-                            metadata.render_metadata(ui);
-                        });
-                    }
+        // Side panels and floating windows with the panes arranged by the user.
+        self.render_docks(ctx);
 
-                    // Add Query section
-                    ui.collapsing("Query", |ui| {
-                        if let Some(filters) = self.data_filters.render_filter(ui) {
-                            // Load data with the applied query.
-                            self.run_data_future(
-                                Box::new(Box::pin(DataFrameContainer::load_data_with_sql(filters))),
-                                ctx,
-                            );
-                        }
-                    });
+        // An image of the table, once its screenshot arrived.
+        match self.snapshot.check_screenshot(ctx) {
+            Some(Ok(message)) => self.toasts.success(message),
+            Some(Err(message)) => self.toasts.error(format!("Image failed: {message}")),
+            None => {}
+        }
 
-                    // Add Schema section
-                    if let Some(metadata) = &self.metadata {
-                        ui.collapsing("Schema", |ui| {
-                            metadata.render_schema(ui);
-                        });
-                    }
-                });
-            });
+        // Notifications of the background operations; an error clicked shows its details.
+        if let Some(error) = self.toasts.show(ctx) {
+            self.popover = Some(Box::new(error));
+        }
 
+        let lang = language(ctx);
         TopBottomPanel::bottom("bottom_panel").show(ctx, |ui| {
             // Display the filename of the loaded data.
-            ui.horizontal(|ui| match &*self.table {
-                Some(table) => {
-                    ui.label(format!("{:#?}", table.filename));
+            ui.horizontal(|ui| {
+                match &*self.table {
+                    Some(table) => {
+                        ui.label(format!("{:#?}", table.filename));
+                        ui.separator();
+                        ui.label(format!(
+                            "{} {} × {} {}",
+                            table.df.height(),
+                            tr("rows", lang),
+                            table.df.width(),
+                            tr("columns", lang)
+                        ));
+                        ui.separator();
+                        let size = format_bytes(table.df.estimated_size() as i64);
+                        ui.label(format!("{}: {size}", tr("Table", lang)))
+                            .on_hover_text(tr("Estimated memory of the displayed result", lang));
+                        if !self.editor.changes.is_empty() {
+                            ui.separator();
+                            ui.colored_label(
+                                ui.visuals().warn_fg_color,
+                                format!(
+                                    "{} {}",
+                                    self.editor.changes.len(),
+                                    tr("unsaved edits", lang)
+                                ),
+                            );
+                        }
+                    }
+                    None => {
+                        ui.label(tr("no file set", lang));
+                    }
                 }
-                None => {
-                    ui.label("no file set");
+
+                // Memory of the whole process, with the limit on the loaded files set in Settings.
+                if let Some(rss) = self.rss.get() {
+                    ui.separator();
+                    let memory = format!("{}: {}", tr("Memory", lang), format_bytes(rss as i64));
+                    let text = match self.config.memory_limit_mib {
+                        0 => memory,
+                        limit => format!("{memory} ({} {limit} MiB)", tr("load limit", lang)),
+                    };
+                    ui.label(text).on_hover_text(tr(
+                        "The limit refuses to load files estimated over it; \
+                        it does not cap the memory used afterwards",
+                        lang,
+                    ));
                 }
             });
+
+            // Progress of the data loading in progress.
+            if !self.tasks.is_empty() {
+                self.render_progress(ui, ctx);
+            }
         });
 
+        // Tracing events, to diagnose load failures without a terminal.
+        if self.state.show_log {
+            TopBottomPanel::bottom("log_panel")
+                .resizable(true)
+                .default_height(150.0)
+                .show(ctx, |ui| self.log_panel.render(ui, LogBuffer::global()));
+        }
+
         // Main table display area.
         // https://whoisryosuke.com/blog/2023/getting-started-with-egui-in-rust
         // https://github.com/emilk/egui/issues/1376
@@ -368,35 +2578,225 @@ impl eframe::App for PolarsViewApp {
         CentralPanel::default().show(ctx, |ui| {
             warn_if_debug_build(ui); // Show a warning in debug builds.
 
+            // Results open in tabs, switched once no result is loading into the shown one.
+            let title = ResultTab::title(&self.table);
+            match self.tabs.render(ui, &title, self.tasks.is_empty()) {
+                Some(TabAction::Select(index)) => self.select_tab(index),
+                Some(TabAction::Close(index)) => self.close_tab(index),
+                None => {}
+            }
+
             match self.table.as_ref().clone() {
                 Some(parquet_data) if parquet_data.df.width() > 0 => {
-                    // Data loaded successfully, display the table.
-                    ScrollArea::horizontal().show(ui, |ui| {
-                        let opt_filters = parquet_data.render_table(ui); // Render the table and get any filter updates.
-                        if let Some(filters) = opt_filters {
-                            let future = parquet_data.sort(Some(filters)); // Sort the data.
-                            self.run_data_future(Box::new(Box::pin(future)), ctx); // Run the sorting task.
+                    // Switch between the table and the chart views.
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(
+                            &mut self.state.central_view,
+                            CentralView::Table,
+                            tr("Table", lang),
+                        );
+                        ui.selectable_value(
+                            &mut self.state.central_view,
+                            CentralView::Chart,
+                            tr("Chart", lang),
+                        );
+                        // Offered for the results with a date or datetime column.
+                        if self.time_series.has_time_columns(&parquet_data.df) {
+                            ui.selectable_value(
+                                &mut self.state.central_view,
+                                CentralView::TimeSeries,
+                                tr("Time series", lang),
+                            );
+                        }
+
+                        if self.state.central_view == CentralView::Table {
+                            ui.separator();
+                            self.render_bookmarks_menu(ui, &parquet_data.df, ctx);
+
+                            // A grouped result gets SUM and AVG columns in one click.
+                            if let Some(group_by) = self
+                                .group_by
+                                .as_mut()
+                                .filter(|group_by| group_by.shown_by(&parquet_data.filters))
+                            {
+                                ui.separator();
+                                if group_by.render_menu(ui) {
+                                    let group_by = group_by.clone();
+                                    let filters = &parquet_data.filters;
+                                    self.run_group_by(&group_by, filters, false, ctx);
+                                }
+                            }
+                        }
+                    });
+                    ui.separator();
+
+                    match self.state.central_view {
+                        // The charts would show the raw values of the masked columns.
+                        CentralView::Chart | CentralView::TimeSeries if self.masks.is_active() => {
+                            ui.label(tr("Unavailable while columns are masked", lang));
                         }
+                        CentralView::Chart => self.chart.render(ui, &parquet_data.df),
+                        CentralView::TimeSeries => {
+                            let df = &parquet_data.df;
+                            let handle = self.runtime.handle();
+                            self.time_series.render(ui, df, &self.config.table, handle);
+                        }
+                        CentralView::Table => match self.split.is_some() {
+                            true => self.render_split_view(ui, &parquet_data, ctx),
+                            false => self.render_table(ui, &parquet_data, ctx),
+                        },
+                    }
+                }
+                Some(_) => {
+                    // A query may select no columns at all.
+                    ui.centered_and_justified(|ui| {
+                        ui.label(tr("The result has no columns.", lang));
                     });
                 }
-                _ => {
+                None => {
                     // No data loaded yet, show a prompt.
                     ui.centered_and_justified(|ui| {
-                        ui.label("Drag and drop parquet file here.");
+                        ui.label(tr("Drag and drop parquet file here.", lang));
                     });
                 }
             };
 
             // Show a loading spinner if data is currently being loaded.
-            if self.check_data_pending() {
+            if self.check_data_pending(ctx) {
                 ui.disable(); // Disable UI interaction while loading.
+                let fraction = self.tasks.last().and_then(|task| task.progress.fraction());
                 if self.table.as_ref().is_none() {
-                    ui.centered_and_justified(|ui| {
-                        // Show spinner while loading initial data.
-                        ui.spinner();
+                    ui.centered_and_justified(|ui| match fraction {
+                        // Show the progress while loading initial data.
+                        Some(fraction) => {
+                            ui.add(ProgressBar::new(fraction).desired_width(300.0));
+                        }
+                        None => {
+                            busy_indicator(ui);
+                        }
                     });
                 }
             }
         });
+
+        // An image of the table requested, now drawn, is taken from the screenshot of this frame.
+        self.snapshot.take(ctx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_load_supersedes_running_one() {
+        let mut app = PolarsViewApp::default();
+        let ctx = Context::default();
+
+        // A load that never completes, superseded by a second one.
+        let slow: DataFuture = Box::new(Box::pin(std::future::pending()));
+        app.run_data_future("Open slow.parquet", slow, &ctx);
+        let fast: DataFuture = Box::new(Box::pin(async { Err("fast".to_string()) }));
+        app.run_data_future("Open fast.parquet", fast, &ctx);
+
+        assert_eq!(app.tasks.len(), 1);
+        assert_eq!(app.tasks[0].label, "Open fast.parquet");
+
+        while app.check_data_pending(&ctx) {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(app.tasks.is_empty());
+
+        // Only the error of the second load is notified.
+        let toasts = app.toasts.current();
+        assert_eq!(toasts.len(), 1);
+        assert_eq!(toasts[0].message, "Open fast.parquet failed: fast");
+    }
+
+    #[test]
+    fn test_window_title() -> Result<(), String> {
+        let mut app = PolarsViewApp::default();
+        assert_eq!(app.window_title(), APP_NAME);
+
+        let df = polars::df!["id" => [1, 2]].map_err(|e| e.to_string())?;
+        app.table = Arc::new(Some(DataFrameContainer {
+            filename: "/data/sales.parquet".to_string(),
+            df: Arc::new(df.clone()),
+            filters: DataFilters::default(),
+            table_type: "parquet".to_string(),
+            sort_cache: crate::SortCache::default(),
+            filtered: false,
+        }));
+        assert_eq!(app.window_title(), "sales.parquet — PolarsView");
+
+        app.editor.changes.set(&df, "id", 0, "3")?;
+        assert_eq!(app.window_title(), "• sales.parquet — PolarsView");
+        Ok(())
+    }
+
+    #[test]
+    fn test_save_over_filtered_duplicates() -> Result<(), String> {
+        let mut app = PolarsViewApp::default();
+        let path = std::env::temp_dir().join(format!(
+            "polars_view_test_save_over_{}.parquet",
+            std::process::id()
+        ));
+        let mut df = polars::df!["id" => [1, 2, 2]].map_err(|e| e.to_string())?;
+        crate::write_dataframe(&mut df, &path.display().to_string(), None)?;
+
+        let data = DataFrameContainer {
+            filename: path.display().to_string(),
+            df: Arc::new(df),
+            filters: DataFilters::new(path.display().to_string()),
+            table_type: "parquet".to_string(),
+            sort_cache: crate::SortCache::default(),
+            filtered: false,
+        };
+        app.table = Arc::new(Some(data.clone()));
+        let whole = app.can_save_over_original();
+
+        // Only the duplicated rows are left: saving them over the file would lose the others.
+        let duplicates = app.runtime.block_on(data.filter_duplicates(Vec::new()));
+        let _ = std::fs::remove_file(&path);
+        app.table = Arc::new(Some(duplicates?));
+        assert!(whole && !app.can_save_over_original());
+        Ok(())
+    }
+
+    #[test]
+    fn test_result_in_new_tab() -> Result<(), String> {
+        let mut app = PolarsViewApp::default();
+        let ctx = Context::default();
+        let container = |filename: &str| -> Result<DataFrameContainer, String> {
+            Ok(DataFrameContainer {
+                filename: filename.to_string(),
+                df: Arc::new(polars::df!["id" => [1, 2]].map_err(|e| e.to_string())?),
+                filters: DataFilters::default(),
+                table_type: "query".to_string(),
+                sort_cache: crate::SortCache::default(),
+                filtered: false,
+            })
+        };
+        app.table = Arc::new(Some(container("/data/sales.parquet")?));
+
+        // A result loaded into a new tab keeps the shown one in its tab.
+        let other = container("/data/other.parquet")?;
+        let future: DataFuture = Box::new(Box::pin(async { Ok(other) }));
+        app.run_data_future("Query", future, &ctx);
+        app.tasks[0].new_tab = true;
+        while app.check_data_pending(&ctx) {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!((app.tabs.len(), app.tabs.active()), (2, 1));
+        assert_eq!(app.window_title(), "other.parquet — PolarsView");
+
+        app.select_tab(0);
+        assert_eq!(app.window_title(), "sales.parquet — PolarsView");
+
+        // Closing the shown tab shows the other one.
+        app.close_table();
+        assert_eq!(app.tabs.len(), 1);
+        assert_eq!(app.window_title(), "other.parquet — PolarsView");
+        Ok(())
     }
 }
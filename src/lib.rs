@@ -1,13 +1,136 @@
 // Modules that make up the ParqBench library.
+mod analysis;
 mod args;
+mod bench;
+mod bloom;
+mod bookmarks;
+mod browser;
+mod cells;
+mod codecs;
+mod compare;
 mod components;
+mod config;
+mod configfile;
+mod csvimport;
 mod data;
+mod derived;
+mod desktop;
+mod dialogs;
+mod dock;
+mod edits;
+mod engine;
+mod export;
+mod footers;
+mod fuzzy;
+mod groupby;
+mod history;
+mod htmlexport;
+mod i18n;
 mod layout;
+mod logs;
+mod masking;
+mod memory;
+mod metadata;
+mod notifications;
+mod pdf;
+mod pipeline;
+mod plots;
+mod plugins;
+mod power;
+mod progress;
+mod recovery;
+mod replace;
+mod report;
+mod rowcount;
+mod rowdiff;
+mod rowgroups;
+mod schema_diff;
+mod scripts;
+mod session;
+mod shortcuts;
+mod snapshot;
+mod split;
 mod sqls;
+mod state;
+mod statements;
+mod stats;
+mod tabs;
+mod tasks;
+mod timeseries;
+mod toasts;
 mod traits;
+mod transforms;
+mod udfs;
+mod views;
+mod widget;
 
 // Publicly expose the contents of these modules.
-pub use self::{args::Arguments, components::*, data::*, layout::*, sqls::*, traits::*};
+pub use self::{
+    analysis::*,
+    args::{Arguments, Command, SchemaFormat},
+    bench::*,
+    bloom::*,
+    bookmarks::*,
+    browser::*,
+    cells::*,
+    codecs::*,
+    compare::*,
+    components::*,
+    config::*,
+    configfile::*,
+    csvimport::*,
+    data::*,
+    derived::*,
+    desktop::*,
+    dialogs::*,
+    dock::*,
+    edits::*,
+    engine::*,
+    export::*,
+    footers::*,
+    fuzzy::*,
+    groupby::*,
+    history::*,
+    htmlexport::*,
+    i18n::*,
+    layout::*,
+    logs::*,
+    masking::*,
+    memory::*,
+    metadata::*,
+    notifications::*,
+    pdf::*,
+    pipeline::*,
+    plots::*,
+    plugins::*,
+    power::*,
+    progress::*,
+    recovery::*,
+    replace::*,
+    report::*,
+    rowcount::*,
+    rowdiff::*,
+    rowgroups::*,
+    schema_diff::*,
+    scripts::*,
+    session::*,
+    shortcuts::*,
+    snapshot::*,
+    split::*,
+    sqls::*,
+    state::*,
+    statements::*,
+    stats::*,
+    tabs::*,
+    tasks::*,
+    timeseries::*,
+    toasts::*,
+    traits::*,
+    transforms::*,
+    udfs::*,
+    views::*,
+    widget::*,
+};
 
 use polars::{
     error::PolarsResult,
@@ -0,0 +1,249 @@
+use egui::{Color32, ComboBox, Label, RichText, ScrollArea, Ui};
+use std::{
+    collections::VecDeque,
+    fmt::{self, Write},
+    str::FromStr,
+    sync::{Arc, Mutex, OnceLock},
+    time::Instant,
+};
+use tracing::{
+    Event, Level, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_subscriber::{
+    filter::Targets,
+    layer::{Context, Layer, SubscriberExt},
+    util::SubscriberInitExt,
+};
+
+/// Number of log entries kept for the log panel.
+pub const LOG_CAPACITY: usize = 1000;
+
+/// A tracing event captured for the log panel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    /// Seconds since the application started.
+    pub seconds: f64,
+    pub level: Level,
+    pub target: String,
+    /// The message followed by the other fields of the event.
+    pub message: String,
+}
+
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{:>9.3}s] {:<5} {}: {}",
+            self.seconds, self.level, self.target, self.message
+        )
+    }
+}
+
+/// The latest tracing events, shared by the tracing layer and the log panel.
+#[derive(Debug, Clone)]
+pub struct LogBuffer {
+    entries: Arc<Mutex<VecDeque<LogEntry>>>,
+    started: Instant,
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        LogBuffer {
+            entries: Arc::new(Mutex::new(VecDeque::with_capacity(LOG_CAPACITY))),
+            started: Instant::now(),
+        }
+    }
+}
+
+impl LogBuffer {
+    /// The buffer fed by the global tracing subscriber.
+    pub fn global() -> &'static LogBuffer {
+        static BUFFER: OnceLock<LogBuffer> = OnceLock::new();
+        BUFFER.get_or_init(LogBuffer::default)
+    }
+
+    /// Adds an entry, dropping the oldest one when the buffer is full.
+    pub fn push(&self, level: Level, target: &str, message: String) {
+        if let Ok(mut entries) = self.entries.lock() {
+            if entries.len() >= LOG_CAPACITY {
+                entries.pop_front();
+            }
+            entries.push_back(LogEntry {
+                seconds: self.started.elapsed().as_secs_f64(),
+                level,
+                target: target.to_string(),
+                message,
+            });
+        }
+    }
+
+    /// Entries at the given level or more severe, the oldest first.
+    pub fn entries(&self, max_level: Level) -> Vec<LogEntry> {
+        self.entries
+            .lock()
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter(|entry| entry.level <= max_level)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+}
+
+/// Tracing layer recording the events in a [`LogBuffer`].
+pub struct LogLayer {
+    buffer: LogBuffer,
+}
+
+impl LogLayer {
+    pub fn new(buffer: &LogBuffer) -> Self {
+        LogLayer {
+            buffer: buffer.clone(),
+        }
+    }
+}
+
+/// Collects the message and the other fields of an event.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "message" => self.message = format!("{value:?}"),
+            name => {
+                let _ = write!(self.fields, " {name}={value:?}");
+            }
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        let metadata = event.metadata();
+        self.buffer.push(
+            *metadata.level(),
+            metadata.target(),
+            visitor.message + &visitor.fields,
+        );
+    }
+}
+
+/// Installs the global tracing subscriber: events are printed to the terminal and kept
+/// for the log panel.
+///
+/// As with `tracing_subscriber::fmt::init`, `RUST_LOG` selects the events (`info` by default).
+pub fn init_tracing() {
+    let targets = std::env::var("RUST_LOG")
+        .ok()
+        .and_then(|var| Targets::from_str(&var).ok())
+        .unwrap_or_else(|| Targets::new().with_default(Level::INFO));
+
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer())
+        .with(LogLayer::new(LogBuffer::global()))
+        .with(targets)
+        .init();
+}
+
+/// Log panel: the captured events with a level filter.
+#[derive(Debug, Clone)]
+pub struct LogPanel {
+    /// Most verbose level displayed.
+    pub max_level: Level,
+}
+
+impl Default for LogPanel {
+    fn default() -> Self {
+        LogPanel {
+            max_level: Level::INFO,
+        }
+    }
+}
+
+impl LogPanel {
+    pub fn render(&mut self, ui: &mut Ui, buffer: &LogBuffer) {
+        ui.horizontal(|ui| {
+            ui.label("Level:");
+            ComboBox::from_id_salt("log_level")
+                .selected_text(self.max_level.as_str())
+                .show_ui(ui, |ui| {
+                    for level in [
+                        Level::ERROR,
+                        Level::WARN,
+                        Level::INFO,
+                        Level::DEBUG,
+                        Level::TRACE,
+                    ] {
+                        ui.selectable_value(&mut self.max_level, level, level.as_str());
+                    }
+                });
+
+            if ui.button("Clear").clicked() {
+                buffer.clear();
+            }
+        });
+
+        ui.separator();
+
+        let entries = buffer.entries(self.max_level);
+        ScrollArea::vertical()
+            .auto_shrink(false)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for entry in &entries {
+                    let color = match entry.level {
+                        Level::ERROR => ui.visuals().error_fg_color,
+                        Level::WARN => ui.visuals().warn_fg_color,
+                        Level::INFO => ui.visuals().text_color(),
+                        _ => Color32::GRAY,
+                    };
+                    let text = RichText::new(entry.to_string()).monospace().color(color);
+                    ui.add(Label::new(text).selectable(true));
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_layer() {
+        let buffer = LogBuffer::default();
+        let subscriber = tracing_subscriber::registry().with(LogLayer::new(&buffer));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(rows = 10, "Loaded data.parquet");
+            tracing::error!("Query failed");
+            tracing::debug!("Details");
+        });
+
+        let entries = buffer.entries(Level::INFO);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].message, "Loaded data.parquet rows=10");
+        assert_eq!(entries[1].level, Level::ERROR);
+        assert_eq!(buffer.entries(Level::TRACE).len(), 3);
+
+        for idx in 0..LOG_CAPACITY {
+            buffer.push(Level::INFO, "test", idx.to_string());
+        }
+        assert_eq!(buffer.entries(Level::TRACE).len(), LOG_CAPACITY);
+    }
+}
@@ -1,12 +1,28 @@
 #![warn(clippy::all)]
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
-use polars_view::{Arguments, DataFilters, DataFrameContainer, PolarsViewApp};
+use polars_view::{
+    APP_NAME, AppConfig, Arguments, Command, DataFilters, DataFrameContainer, InstanceServer,
+    PolarsViewApp, SchemaFormat, Session, diff_files, init_tracing, read_file_metadata,
+    register_file_association, run_benchmark, schema_changelog, send_to_running_instance,
+    write_dataframe,
+};
 
 /*
 cargo fmt
 cargo test -- --nocapture
 cargo run -- --help
+cargo run -- -f data.parquet -q "SELECT * FROM AllData;" --bench 10
+cargo run -- schema-diff old.parquet new.csv
+cargo run -- query data.parquet --sql "SELECT * FROM AllData LIMIT 10;" -o out.csv
+cargo run -- schema data.parquet --format json
+cargo run -- bench data.parquet --sql "SELECT * FROM AllData;" --iterations 20 --json
+cargo run -- convert data.csv data.parquet --compression zstd:9
+cargo run -- --session work.parqbench.json
+cargo run -- register
+cargo run -- --threads 4 -f data.parquet
+cargo run -- -f data.parquet --sort "amount:desc" --sort id
+cargo run -- sales.parquet customers.csv -q "SELECT * FROM AllData JOIN customers USING (id);"
 cargo run -- -f ~/Documents/Rust/projects/join_with_assignments/df_consolidacao_natureza_da_bcalc.parquet
 cargo run -- -f /home/claudio/Documents/Rust/projects/join_with_assignments/df_itens_de_docs_fiscais.csv
 cargo doc --open
@@ -15,12 +31,46 @@ cargo b -r && cargo install --path=.
 
 #[cfg(not(target_arch = "wasm32"))]
 fn main() -> eframe::Result<()> {
-    // Initialize the tracing subscriber for logging.
-    tracing_subscriber::fmt::init();
+    // Initialize the tracing subscriber for logging, also feeding the log panel.
+    init_tracing();
 
     // Parse command-line arguments.
     let args = Arguments::build();
 
+    // Headless commands.
+    if let Some(command) = &args.command {
+        if let Err(error) = run_command(command, args.threads) {
+            eprintln!("{error}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Benchmark mode: run the query without the GUI and print the results as JSON.
+    if let Some(iterations) = args.bench {
+        run_benchmark_cli(&args, iterations);
+        return Ok(());
+    }
+
+    // Read the session before opening the window, so that errors are reported in the terminal.
+    let session = match &args.session {
+        Some(path) => match Session::load(path) {
+            Ok(session) => Some(session),
+            Err(error) => {
+                eprintln!("{error}");
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    // A file given without other options opens in the running instance, if any.
+    if let Some(filename) = args.filename.as_deref().filter(|_| args.only_file()) {
+        if !args.new_window && send_to_running_instance(filename) {
+            return Ok(());
+        }
+    }
+
     // Configure the native options for the eframe application.
     let options = eframe::NativeOptions {
         centered: true,
@@ -30,25 +80,140 @@ fn main() -> eframe::Result<()> {
 
     // Run the eframe application.
     eframe::run_native(
-        "PolarsView",
+        APP_NAME,
         options,
         Box::new(move |cc| {
             // Create a new PolarsViewApp. If a filename is provided, load the data.
-            Ok(Box::new(if args.filename.is_some() {
+            let mut app = if let Some(session) = session {
+                PolarsViewApp::new_with_session(cc, session, args.threads)
+            } else if args.filename.is_some() {
                 // Log debug information about the data filters.
                 DataFilters::debug(&args);
 
                 // Create data filters from command line arguments
                 let data_filters = DataFilters::new_with_args(&args);
 
-                // Load the data from the specified filename.
-                let future = DataFrameContainer::load_data_with_filters(data_filters);
+                // Load the data from the specified filename, then apply the sort, if any.
+                let future = DataFrameContainer::load_data_with_sort(data_filters);
 
                 // Create a new PolarsViewApp with the data loading future.
-                PolarsViewApp::new_with_future(cc, Box::new(Box::pin(future)))
+                let mut app =
+                    PolarsViewApp::new_with_future(cc, Box::new(Box::pin(future)), args.threads);
+
+                // The further files open in their own tabs, besides being queryable as tables.
+                app.queued_tabs = args
+                    .files
+                    .iter()
+                    .map(|file| DataFilters {
+                        csv_delimiter: args.delimiter.clone(),
+                        csv_options: args.csv_options(),
+                        ..DataFilters::new(file)
+                    })
+                    .collect();
+                app
             } else {
-                PolarsViewApp::new(cc) // Create a new PolarsViewApp without loading data.
-            }))
+                PolarsViewApp::new(cc, args.threads) // Create a new PolarsViewApp without loading data.
+            };
+
+            // The first instance receives the files opened by the later ones.
+            app.instance = InstanceServer::start(&cc.egui_ctx).ok();
+
+            // Every instance autosaves its session; the first one recovers after a crash.
+            app.start_autosave(app.instance.is_some());
+            Ok(Box::new(app))
         }),
     )
 }
+
+/// Runs a command without the GUI.
+#[cfg(not(target_arch = "wasm32"))]
+fn run_command(command: &Command, threads: Option<usize>) -> Result<(), String> {
+    match command {
+        Command::SchemaDiff { old, new } => {
+            let changes = diff_files(old, new).map_err(|e| format!("Schema diff error: {e}"))?;
+            println!("{}", schema_changelog(&changes));
+        }
+        Command::Query {
+            file,
+            sql,
+            output,
+            table_name,
+            delimiter,
+        } => {
+            // The same pipeline as the Query pane.
+            let filters = DataFilters {
+                table_name: table_name.clone(),
+                csv_delimiter: delimiter.clone(),
+                query: Some(sql.clone()),
+                ..DataFilters::new(file)
+            };
+            let runtime = AppConfig::default().build_runtime(threads);
+            let data = runtime
+                .block_on(DataFrameContainer::load_data_with_filters(filters))
+                .map_err(|e| format!("Query error: {e}"))?;
+            write_dataframe(&mut data.df.as_ref().clone(), output, None)?;
+        }
+        Command::Convert {
+            input,
+            output,
+            compression,
+        } => {
+            let runtime = AppConfig::default().build_runtime(threads);
+            let data = runtime.block_on(DataFrameContainer::load_data(input))?;
+            write_dataframe(&mut data.df.as_ref().clone(), output, *compression)?;
+        }
+        Command::Bench {
+            file,
+            sql,
+            iterations,
+            json,
+            table_name,
+            delimiter,
+        } => {
+            let filters = DataFilters {
+                table_name: table_name.clone(),
+                csv_delimiter: delimiter.clone(),
+                query: Some(sql.clone()),
+                ..DataFilters::new(file)
+            };
+            let runtime = AppConfig::default().build_runtime(threads);
+            let summary = runtime
+                .block_on(run_benchmark(filters, *iterations))
+                .map_err(|e| format!("Benchmark error: {e}"))?
+                .summary();
+            match json {
+                true => println!("{}", summary.to_json()?),
+                false => print!("{summary}"),
+            }
+        }
+        Command::Schema { file, format } => {
+            let runtime = AppConfig::default().build_runtime(threads);
+            let summary = runtime.block_on(read_file_metadata(file))?.summary();
+            match format {
+                SchemaFormat::Text => print!("{summary}"),
+                SchemaFormat::Json => println!("{}", summary.to_json()?),
+            }
+        }
+        Command::Register => println!("{}", register_file_association()?),
+    }
+    Ok(())
+}
+
+/// Runs the benchmark of the query given on the command line and prints the summary as JSON.
+#[cfg(not(target_arch = "wasm32"))]
+fn run_benchmark_cli(args: &Arguments, iterations: usize) {
+    let runtime = AppConfig::default().build_runtime(args.threads);
+    let filters = DataFilters::new_with_args(args);
+
+    let json = runtime
+        .block_on(run_benchmark(filters, iterations))
+        .and_then(|result| result.summary().to_json());
+
+    match json {
+        Ok(json) => println!("{json}"),
+        Err(error) => {
+            eprintln!("Benchmark error: {error}");
+            std::process::exit(1);
+        }
+    }
+}
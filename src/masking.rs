@@ -0,0 +1,187 @@
+use crate::cell_text;
+
+use hmac::{Hmac, Mac};
+use polars::prelude::*;
+use sha2::Sha256;
+use std::{collections::BTreeMap, fmt, sync::LazyLock};
+
+/// Character replacing the masked characters.
+const MASK_CHAR: char = '•';
+
+/// Key of the hash masks, drawn at random once per run: equal values keep equal masks
+/// within a session, but a mask cannot be matched by hashing guessed values elsewhere,
+/// nor across sessions. Without one, the hash masks redact the values instead.
+static SESSION_KEY: LazyLock<Option<[u8; 32]>> = LazyLock::new(|| {
+    let mut key = [0; 32];
+    match getrandom::getrandom(&mut key) {
+        Ok(()) => Some(key),
+        Err(e) => {
+            tracing::error!(
+                "No random key for the hash masks, which redact instead: {}",
+                e
+            );
+            None
+        }
+    }
+});
+
+/// First 6 bytes, in hex, of the HMAC-SHA256 of `value` with `key`; without a key, the
+/// hash could be recomputed from guessed values, so the value is redacted.
+fn keyed_hash(key: Option<&[u8]>, value: &str) -> String {
+    let Some(Ok(mut mac)) = key.map(Hmac::<Sha256>::new_from_slice) else {
+        return MaskKind::Redact.apply(value);
+    };
+    mac.update(value.as_bytes());
+    mac.finalize().into_bytes()[..6]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// How the values of a masked column are hidden.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaskKind {
+    /// A short keyed hash (HMAC-SHA256): equal values keep equal masks, so that they can
+    /// still be compared.
+    Hash,
+    /// A fixed placeholder.
+    Redact,
+    /// Only the last characters are kept, as in `•••••1234`.
+    Partial,
+}
+
+impl MaskKind {
+    pub const ALL: [MaskKind; 3] = [MaskKind::Hash, MaskKind::Redact, MaskKind::Partial];
+
+    /// Masks a value; nulls (empty values) stay empty.
+    pub fn apply(&self, value: &str) -> String {
+        if value.is_empty() {
+            return String::new();
+        }
+
+        match self {
+            MaskKind::Hash => keyed_hash(SESSION_KEY.as_ref().map(|key| &key[..]), value),
+            MaskKind::Redact => MASK_CHAR.to_string().repeat(6),
+            MaskKind::Partial => {
+                let chars: Vec<char> = value.chars().collect();
+                let kept = (chars.len() / 4).min(4);
+                let hidden = chars.len() - kept;
+                std::iter::repeat_n(MASK_CHAR, hidden)
+                    .chain(chars[hidden..].iter().copied())
+                    .collect()
+            }
+        }
+    }
+}
+
+impl fmt::Display for MaskKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MaskKind::Hash => write!(f, "Hash"),
+            MaskKind::Redact => write!(f, "Redact"),
+            MaskKind::Partial => write!(f, "Partial"),
+        }
+    }
+}
+
+/// Columns masked in the table and in the exports, to show real data without its personal values.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColumnMasks {
+    /// Masking is applied only when enabled, so that it can be suspended without losing the masks.
+    pub enabled: bool,
+    pub masks: BTreeMap<String, MaskKind>,
+}
+
+impl ColumnMasks {
+    /// The mask applied to a column, if masking is enabled.
+    pub fn get(&self, column: &str) -> Option<MaskKind> {
+        match self.enabled {
+            true => self.masks.get(column).copied(),
+            false => None,
+        }
+    }
+
+    /// Masks a column, or unmasks it with `None`; masking a column enables masking.
+    pub fn set(&mut self, column: &str, mask: Option<MaskKind>) {
+        match mask {
+            Some(mask) => {
+                self.masks.insert(column.to_string(), mask);
+                self.enabled = true;
+            }
+            None => {
+                self.masks.remove(column);
+            }
+        }
+    }
+
+    /// Whether some column of the data is masked.
+    pub fn is_active(&self) -> bool {
+        self.enabled && !self.masks.is_empty()
+    }
+
+    /// The data with the masked columns replaced by strings of their masked values.
+    pub fn apply(&self, df: &DataFrame) -> PolarsResult<DataFrame> {
+        let mut masked = df.clone();
+        for column in df.get_columns() {
+            let Some(mask) = self.get(column.name()) else {
+                continue;
+            };
+
+            let series = column.as_materialized_series();
+            let values: StringChunked = (0..series.len())
+                .map(|idx| match series.get(idx)? {
+                    AnyValue::Null => Ok(None),
+                    value => Ok(Some(mask.apply(&cell_text(&value)))),
+                })
+                .collect::<PolarsResult<_>>()?;
+
+            masked.with_column(values.with_name(column.name().clone()).into_series())?;
+        }
+        Ok(masked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_masks() -> PolarsResult<()> {
+        assert_eq!(
+            MaskKind::Partial.apply("4111222233334444"),
+            "••••••••••••4444"
+        );
+        assert_eq!(MaskKind::Partial.apply("ab"), "••");
+        assert_eq!(MaskKind::Redact.apply(""), "");
+        assert_eq!(MaskKind::Hash.apply("x"), MaskKind::Hash.apply("x"));
+        assert_ne!(MaskKind::Hash.apply("x"), MaskKind::Hash.apply("y"));
+        assert_eq!(MaskKind::Hash.apply("x").len(), 12);
+
+        // Test case 2 of RFC 4231; without a key, the value is redacted.
+        assert_eq!(
+            keyed_hash(Some(b"Jefe"), "what do ya want for nothing?"),
+            "5bdcc146bf60"
+        );
+        assert_eq!(keyed_hash(None, "x"), MaskKind::Redact.apply("x"));
+
+        let df = df![
+            "email" => [Some("ann@example.com"), None],
+            "id" => [1i64, 2],
+        ]?;
+
+        let mut masks = ColumnMasks::default();
+        masks.set("email", Some(MaskKind::Redact));
+        masks.set("id", Some(MaskKind::Partial));
+        masks.set("id", None);
+        assert!(masks.is_active());
+
+        let masked = masks.apply(&df)?;
+        assert_eq!(masked["email"].str()?.get(0), Some("••••••"));
+        assert_eq!(masked["email"].null_count(), 1);
+        assert!(masked["id"].equals(&df["id"]));
+
+        masks.enabled = false;
+        assert!(masks.apply(&df)?.equals_missing(&df));
+        Ok(())
+    }
+}
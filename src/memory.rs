@@ -0,0 +1,103 @@
+use crate::{format_bytes, get_extension};
+
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use std::{
+    fs::{self, File},
+    time::{Duration, Instant},
+};
+
+/// Interval between two readings of the process memory.
+const RSS_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Resident memory of the process, in bytes; only available on Linux.
+pub fn process_rss() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kilobytes: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kilobytes * 1024)
+}
+
+/// Resident memory of the process, read at most once per [`RSS_SAMPLE_INTERVAL`].
+#[derive(Debug, Default)]
+pub struct RssSample {
+    last: Option<(Instant, Option<u64>)>,
+}
+
+impl RssSample {
+    /// The last reading, renewed when older than the interval.
+    pub fn get(&mut self) -> Option<u64> {
+        match self.last {
+            Some((read, rss)) if read.elapsed() < RSS_SAMPLE_INTERVAL => rss,
+            _ => {
+                let rss = process_rss();
+                self.last = Some((Instant::now(), rss));
+                rss
+            }
+        }
+    }
+}
+
+/// Estimated memory needed to load a file: the uncompressed size of a Parquet file,
+/// the size of other files.
+pub fn estimate_load_size(filename: &str) -> Option<u64> {
+    match get_extension(filename).as_deref() {
+        Some("parquet") => {
+            let reader = SerializedFileReader::new(File::open(filename).ok()?).ok()?;
+            let size = reader
+                .metadata()
+                .row_groups()
+                .iter()
+                .map(|rg| rg.total_byte_size().max(0) as u64)
+                .sum();
+            Some(size)
+        }
+        _ => fs::metadata(filename).ok().map(|metadata| metadata.len()),
+    }
+}
+
+/// Refuses to load a file whose estimated size exceeds the memory limit (0 for no limit).
+pub fn check_memory_budget(filename: &str, limit_mib: u64) -> Result<(), String> {
+    if limit_mib == 0 {
+        return Ok(());
+    }
+
+    let limit = limit_mib * 1024 * 1024;
+    match estimate_load_size(filename) {
+        Some(size) if size > limit => Err(format!(
+            "{} needs about {} in memory, over the limit of {}. \
+            Load a smaller extract of the file or raise the memory limit in Settings.",
+            filename,
+            format_bytes(size as i64),
+            format_bytes(limit as i64)
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_memory_budget() -> PolarsResult<()> {
+        let mut df = df!["id" => (0..300_000).collect::<Vec<i64>>()]?;
+        let path = std::env::temp_dir().join("polars_view_test_memory.parquet");
+        ParquetWriter::new(File::create(&path)?)
+            .with_compression(ParquetCompression::Uncompressed)
+            .finish(&mut df)?;
+        let filename = path.display().to_string();
+
+        let size = estimate_load_size(&filename).unwrap();
+        assert!(size >= 2_400_000, "{size}"); // 300k 8-byte integers.
+
+        assert!(check_memory_budget(&filename, 0).is_ok()); // No limit.
+        assert!(check_memory_budget(&filename, 100).is_ok());
+        let error = check_memory_budget(&filename, 1).unwrap_err();
+        assert!(error.contains("over the limit of 1.00 MiB"), "{error}");
+        let _ = std::fs::remove_file(&path);
+
+        assert!(estimate_load_size("missing.parquet").is_none());
+        Ok(())
+    }
+}
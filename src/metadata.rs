@@ -0,0 +1,191 @@
+use crate::{DataFrameContainer, FileMetadata, format_bytes, get_extension};
+
+use parquet::{file::metadata::ParquetMetaData, schema::types::ColumnDescriptor};
+use polars::prelude::Schema;
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt};
+
+/// Name and type of a column.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ColumnSummary {
+    pub name: String,
+    pub data_type: String,
+}
+
+/// Size of a row group of a Parquet file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RowGroupLayout {
+    pub rows: i64,
+    pub compressed_bytes: i64,
+    pub uncompressed_bytes: i64,
+}
+
+/// Schema, row count, row-group layout and key-value metadata of a file,
+/// independent of the UI; printed by the `schema` command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MetadataSummary {
+    /// `parquet` or `csv`.
+    pub format: String,
+    pub rows: i64,
+    pub columns: Vec<ColumnSummary>,
+    /// Empty for CSV files.
+    pub row_groups: Vec<RowGroupLayout>,
+    /// Empty for CSV files.
+    pub key_value_metadata: BTreeMap<String, Option<String>>,
+}
+
+impl MetadataSummary {
+    /// Summary of the metadata of a Parquet file.
+    pub fn from_parquet(metadata: &ParquetMetaData) -> Self {
+        let file_metadata = metadata.file_metadata();
+
+        let columns = file_metadata
+            .schema_descr()
+            .columns()
+            .iter()
+            .map(|column| ColumnSummary {
+                name: column.path().string(),
+                data_type: parquet_type(column),
+            })
+            .collect();
+
+        let row_groups = metadata
+            .row_groups()
+            .iter()
+            .map(|row_group| RowGroupLayout {
+                rows: row_group.num_rows(),
+                compressed_bytes: row_group.compressed_size(),
+                uncompressed_bytes: row_group.total_byte_size(),
+            })
+            .collect();
+
+        let key_value_metadata = file_metadata
+            .key_value_metadata()
+            .into_iter()
+            .flatten()
+            .map(|key_value| (key_value.key.clone(), key_value.value.clone()))
+            .collect();
+
+        MetadataSummary {
+            format: "parquet".to_string(),
+            rows: file_metadata.num_rows(),
+            columns,
+            row_groups,
+            key_value_metadata,
+        }
+    }
+
+    /// Summary of a file without metadata, such as a CSV file, from its loaded schema.
+    pub fn from_schema(format: &str, schema: &Schema, rows: usize) -> Self {
+        let columns = schema
+            .iter()
+            .map(|(name, dtype)| ColumnSummary {
+                name: name.to_string(),
+                data_type: dtype.to_string(),
+            })
+            .collect();
+
+        MetadataSummary {
+            format: format.to_string(),
+            rows: rows as i64,
+            columns,
+            row_groups: Vec::new(),
+            key_value_metadata: BTreeMap::new(),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| format!("JSON error: {}", e))
+    }
+}
+
+/// Physical type of a primitive column, converted type of a group, as in the Schema pane.
+fn parquet_type(column: &ColumnDescriptor) -> String {
+    let column_type = column.self_type();
+    match column_type.is_primitive() {
+        true => column_type.get_physical_type().to_string(),
+        false => column.converted_type().to_string(),
+    }
+}
+
+impl fmt::Display for MetadataSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Format: {}", self.format)?;
+        writeln!(f, "Rows: {}", self.rows)?;
+
+        writeln!(f, "Columns ({}):", self.columns.len())?;
+        for column in &self.columns {
+            writeln!(f, "  {}: {}", column.name, column.data_type)?;
+        }
+
+        if self.format == "parquet" {
+            writeln!(f, "Row groups ({}):", self.row_groups.len())?;
+            for (idx, row_group) in self.row_groups.iter().enumerate() {
+                writeln!(
+                    f,
+                    "  {}: {} rows, {} compressed, {} uncompressed",
+                    idx,
+                    row_group.rows,
+                    format_bytes(row_group.compressed_bytes),
+                    format_bytes(row_group.uncompressed_bytes)
+                )?;
+            }
+
+            writeln!(f, "Key-value metadata ({}):", self.key_value_metadata.len())?;
+            for (key, value) in &self.key_value_metadata {
+                writeln!(f, "  {}: {}", key, value.as_deref().unwrap_or("no value"))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads the metadata of a file: from its footer for Parquet, by loading it for CSV.
+pub async fn read_file_metadata(filename: &str) -> Result<FileMetadata, String> {
+    match get_extension(filename).as_deref() {
+        Some("parquet") => FileMetadata::from_filename(filename, "parquet", None, None),
+        _ => {
+            let data = DataFrameContainer::load_data(filename).await?;
+            let schema = data.df.schema().clone();
+            let rows = data.df.height();
+            FileMetadata::from_filename(&data.filename, &data.table_type, Some(schema), Some(rows))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+    use std::fs::File;
+
+    #[test]
+    fn test_metadata_summary() -> Result<(), String> {
+        let mut df = df!["id" => (0..10).collect::<Vec<i64>>()].map_err(|e| e.to_string())?;
+        let path = std::env::temp_dir().join("polars_view_test_summary.parquet");
+        let file = File::create(&path).map_err(|e| e.to_string())?;
+        ParquetWriter::new(file)
+            .with_row_group_size(Some(5))
+            .finish(&mut df)
+            .map_err(|e| e.to_string())?;
+
+        let runtime = tokio::runtime::Runtime::new().map_err(|e| e.to_string())?;
+        let metadata = runtime.block_on(read_file_metadata(&path.display().to_string()));
+        let _ = std::fs::remove_file(&path);
+        let summary = metadata?.summary();
+
+        assert_eq!(summary.rows, 10);
+        assert_eq!(summary.row_groups.len(), 2);
+        assert_eq!(
+            summary.columns,
+            vec![ColumnSummary {
+                name: "id".to_string(),
+                data_type: "INT64".to_string(),
+            }]
+        );
+        assert!(summary.to_string().contains("  1: 5 rows"));
+        assert!(summary.to_json()?.contains("\"uncompressed_bytes\""));
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use crate::APP_NAME;
+
+use egui::Context;
+use std::{
+    process::Command,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Whether an operation that took `elapsed` is notified, with the window `focused` or not,
+/// when notifying after `notify_after` seconds (0 never notifies).
+pub fn should_notify(elapsed: Duration, focused: bool, notify_after: u64) -> bool {
+    notify_after > 0 && !focused && elapsed >= Duration::from_secs(notify_after)
+}
+
+/// Notifies the desktop of an operation finished, if it took more than `notify_after`
+/// seconds (the `AppConfig::notify_after_secs` setting) since `started` and the window
+/// is not focused, so that the user may work elsewhere meanwhile.
+pub fn notify_finished(ctx: &Context, started: Instant, notify_after: u64, message: &str) {
+    let focused = ctx.input(|i| i.viewport().focused.unwrap_or(true));
+    if !should_notify(started.elapsed(), focused, notify_after) {
+        return;
+    }
+
+    // The notifier may take a moment to start.
+    let message = message.to_string();
+    thread::spawn(move || {
+        if let Err(e) = desktop_notification(APP_NAME, &message) {
+            tracing::warn!("Desktop notification failed: {}", e);
+        }
+    });
+}
+
+/// Shows a notification of the desktop with the notifier of the system.
+pub fn desktop_notification(title: &str, message: &str) -> Result<(), String> {
+    let status = notifier(title, message)
+        .status()
+        .map_err(|e| format!("Error running the notifier: {}", e))?;
+    match status.success() {
+        true => Ok(()),
+        false => Err(format!("The notifier exited with {}", status)),
+    }
+}
+
+/// `notify-send`, from libnotify.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn notifier(title: &str, message: &str) -> Command {
+    let mut command = Command::new("notify-send");
+    command.args(["--app-name", title, title, message]);
+    command
+}
+
+/// `osascript`, with the strings quoted for AppleScript.
+#[cfg(target_os = "macos")]
+fn notifier(title: &str, message: &str) -> Command {
+    let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+    let script = format!(
+        "display notification {} with title {}",
+        quote(message),
+        quote(title)
+    );
+    let mut command = Command::new("osascript");
+    command.args(["-e", &script]);
+    command
+}
+
+/// A balloon tip of the notification area, shown by PowerShell.
+#[cfg(windows)]
+fn notifier(title: &str, message: &str) -> Command {
+    let quote = |s: &str| format!("'{}'", s.replace('\'', "''"));
+    let script = format!(
+        "Add-Type -AssemblyName System.Windows.Forms; \
+         $icon = New-Object System.Windows.Forms.NotifyIcon; \
+         $icon.Icon = [System.Drawing.SystemIcons]::Information; \
+         $icon.Visible = $true; \
+         $icon.ShowBalloonTip(5000, {}, {}, 'Info'); \
+         Start-Sleep -Seconds 6; \
+         $icon.Dispose()",
+        quote(title),
+        quote(message)
+    );
+    let mut command = Command::new("powershell");
+    command.args(["-NoProfile", "-NonInteractive", "-Command", &script]);
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_notify() {
+        let long = Duration::from_secs(12);
+        assert!(!should_notify(long, false, 0));
+
+        // Only the long operations are notified, and only while working elsewhere.
+        assert!(should_notify(long, false, 10));
+        assert!(!should_notify(long, true, 10));
+        assert!(!should_notify(Duration::from_secs(3), false, 10));
+    }
+}
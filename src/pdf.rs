@@ -0,0 +1,412 @@
+use crate::{TableViewOptions, cell_renderer, format_column_cell};
+
+use polars::prelude::DataFrame;
+use std::{fmt::Write, fs, ops::Range, path::Path};
+
+/// Size of a landscape A4 page, in points.
+const PAGE_WIDTH: f32 = 842.0;
+const PAGE_HEIGHT: f32 = 595.0;
+/// Margin around the table, in points.
+const MARGIN: f32 = 36.0;
+/// Size of the table text, in points.
+const FONT_SIZE: f32 = 8.0;
+/// Height of a table row, in points.
+const ROW_HEIGHT: f32 = 11.0;
+/// Horizontal padding of a cell, in points.
+const CELL_PADDING: f32 = 3.0;
+/// Widest column, in points; longer texts are cut with an ellipsis.
+const MAX_COLUMN_WIDTH: f32 = 200.0;
+/// Lines of the query printed on the first page at most.
+const MAX_QUERY_LINES: usize = 12;
+
+/// Approximate width of a character in Helvetica, in thousandths of the font size.
+fn char_width(c: char) -> f32 {
+    match c {
+        '0'..='9' => 556.0,
+        ' ' | '.' | ',' | ':' | ';' | '!' | '/' | 'f' | 't' => 278.0,
+        'i' | 'j' | 'l' | '|' | '\'' => 222.0,
+        'm' | 'w' => 833.0,
+        'M' | 'W' | '@' | '%' => 889.0,
+        'A'..='Z' => 667.0,
+        '-' | '(' | ')' | 'r' => 333.0,
+        _ => 556.0,
+    }
+}
+
+/// Width of a text in points, at `size`.
+fn text_width(text: &str, size: f32) -> f32 {
+    text.chars().map(char_width).sum::<f32>() * size / 1000.0
+}
+
+/// Cuts `text` to fit `width` at `size`, ending it with an ellipsis.
+fn fit_text(text: &str, width: f32, size: f32) -> String {
+    if text_width(text, size) <= width {
+        return text.to_string();
+    }
+    let mut fitted = String::new();
+    let mut used = text_width("…", size);
+    for c in text.chars() {
+        used += char_width(c) * size / 1000.0;
+        if used > width {
+            break;
+        }
+        fitted.push(c);
+    }
+    fitted.push('…');
+    fitted
+}
+
+/// Encodes a text as a PDF string of the WinAnsi encoding of the standard fonts;
+/// the characters outside it print as `?`.
+fn pdf_string(text: &str) -> Vec<u8> {
+    let mut bytes = vec![b'('];
+    for c in text.chars() {
+        let byte = match c {
+            '(' | ')' | '\\' => {
+                bytes.push(b'\\');
+                c as u8
+            }
+            ' '..='~' => c as u8,
+            '\u{a0}'..='\u{ff}' => c as u32 as u8,
+            '€' => 0x80,
+            '…' => 0x85,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '–' => 0x96,
+            '—' => 0x97,
+            _ => b'?',
+        };
+        bytes.push(byte);
+    }
+    bytes.push(b')');
+    bytes
+}
+
+/// Content stream of a page, drawn from the bottom-left corner.
+#[derive(Default)]
+struct PageContent(Vec<u8>);
+
+impl PageContent {
+    /// Draws `text` at (`x`, `y`) with the regular (F1) or bold (F2) font.
+    fn text(&mut self, x: f32, y: f32, text: &str, size: f32, bold: bool) {
+        let font = if bold { "F2" } else { "F1" };
+        let _ = write!(self, "BT /{font} {size} Tf {x:.2} {y:.2} Td ");
+        self.0.extend(pdf_string(text));
+        self.0.extend(b" Tj ET\n");
+    }
+
+    /// Fills a rectangle with a gray level, 0 being black.
+    fn rect(&mut self, x: f32, y: f32, width: f32, height: f32, gray: f32) {
+        let _ = writeln!(
+            self,
+            "{gray} g {x:.2} {y:.2} {width:.2} {height:.2} re f 0 g"
+        );
+    }
+}
+
+impl std::fmt::Write for PageContent {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.0.extend(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// Columns printed on the same pages, with their widths.
+struct ColumnGroup {
+    columns: Range<usize>,
+    widths: Vec<f32>,
+}
+
+/// Splits the columns in groups as wide as the page, in order.
+fn column_groups(widths: &[f32]) -> Vec<ColumnGroup> {
+    let available = PAGE_WIDTH - 2.0 * MARGIN;
+    let mut groups: Vec<ColumnGroup> = Vec::new();
+    let mut start = 0;
+    let mut used = 0.0;
+
+    for (index, width) in widths.iter().enumerate() {
+        if used + width > available && index > start {
+            groups.push(ColumnGroup {
+                columns: start..index,
+                widths: widths[start..index].to_vec(),
+            });
+            start = index;
+            used = 0.0;
+        }
+        used += width;
+    }
+    if start < widths.len() || widths.is_empty() {
+        groups.push(ColumnGroup {
+            columns: start..widths.len(),
+            widths: widths[start..].to_vec(),
+        });
+    }
+    groups
+}
+
+/// Lays out the result `df`, formatted as in the table, on printable landscape A4 pages:
+/// the header row repeats on every page, and columns that do not fit the width of a page
+/// continue on the next pages. The first page shows the title, time and query of the export.
+///
+/// Returns the PDF document and its number of pages.
+pub fn pdf_document(
+    df: &DataFrame,
+    title: &str,
+    query: Option<&str>,
+    timestamp: &str,
+    options: &TableViewOptions,
+) -> (Vec<u8>, usize) {
+    let names: Vec<String> = df
+        .get_column_names()
+        .into_iter()
+        .map(|n| n.to_string())
+        .collect();
+    let numeric: Vec<bool> = df
+        .get_columns()
+        .iter()
+        .map(|c| c.dtype().is_primitive_numeric())
+        .collect();
+    let renderers: Vec<_> = df
+        .get_columns()
+        .iter()
+        .map(|column| cell_renderer(column.name(), column.dtype()))
+        .collect();
+    let cells: Vec<Vec<String>> = (0..df.height())
+        .map(|row| {
+            df.get_columns()
+                .iter()
+                .zip(&renderers)
+                .map(|(column, renderer)| {
+                    format_column_cell(column, row, renderer.as_ref(), options)
+                })
+                .collect()
+        })
+        .collect();
+
+    // Each column is as wide as its widest text, within bounds.
+    let widths: Vec<f32> = names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let widest = cells
+                .iter()
+                .map(|row| text_width(&row[index], FONT_SIZE))
+                .fold(text_width(name, FONT_SIZE), f32::max);
+            (widest + 2.0 * CELL_PADDING).min(MAX_COLUMN_WIDTH)
+        })
+        .collect();
+
+    // The preamble of the first page: title, time and the lines of the query.
+    let mut preamble: Vec<(String, f32, bool)> = vec![
+        (title.to_string(), 14.0, true),
+        (format!("Exported on {timestamp}"), FONT_SIZE, false),
+        (
+            format!("{} rows, {} columns", df.height(), df.width()),
+            FONT_SIZE,
+            false,
+        ),
+    ];
+    for line in query.unwrap_or_default().lines().take(MAX_QUERY_LINES) {
+        preamble.push((line.to_string(), FONT_SIZE, false));
+    }
+    let preamble_height: f32 = preamble.iter().map(|(_, size, _)| size * 1.4).sum::<f32>() + 6.0;
+
+    let mut pages: Vec<PageContent> = Vec::new();
+    for group in column_groups(&widths) {
+        let mut rows = 0..df.height();
+        loop {
+            let mut page = PageContent::default();
+            let mut y = PAGE_HEIGHT - MARGIN;
+
+            if pages.is_empty() {
+                for (text, size, bold) in &preamble {
+                    y -= size * 1.4;
+                    let fitted = fit_text(text, PAGE_WIDTH - 2.0 * MARGIN, *size);
+                    page.text(MARGIN, y, &fitted, *size, *bold);
+                }
+                y = PAGE_HEIGHT - MARGIN - preamble_height;
+            }
+
+            // The header row, on a gray background.
+            let table_width: f32 = group.widths.iter().sum();
+            y -= ROW_HEIGHT;
+            page.rect(MARGIN, y - 3.0, table_width, ROW_HEIGHT, 0.85);
+            let mut x = MARGIN;
+            for (index, width) in group.columns.clone().zip(&group.widths) {
+                let name = fit_text(&names[index], width - 2.0 * CELL_PADDING, FONT_SIZE);
+                page.text(x + CELL_PADDING, y, &name, FONT_SIZE, true);
+                x += width;
+            }
+
+            // As many rows as fit above the footer.
+            let fitting = ((y - MARGIN - ROW_HEIGHT) / ROW_HEIGHT).floor().max(1.0) as usize;
+            let end = (rows.start + fitting).min(rows.end);
+            for (stripe, row) in cells[rows.start..end].iter().enumerate() {
+                y -= ROW_HEIGHT;
+                if stripe % 2 == 1 {
+                    page.rect(MARGIN, y - 3.0, table_width, ROW_HEIGHT, 0.95);
+                }
+                let mut x = MARGIN;
+                for (index, width) in group.columns.clone().zip(&group.widths) {
+                    let text = fit_text(&row[index], width - 2.0 * CELL_PADDING, FONT_SIZE);
+                    let offset = match numeric[index] {
+                        true => width - CELL_PADDING - text_width(&text, FONT_SIZE),
+                        false => CELL_PADDING,
+                    };
+                    page.text(x + offset, y, &text, FONT_SIZE, false);
+                    x += width;
+                }
+            }
+            pages.push(page);
+
+            rows.start = end;
+            if rows.is_empty() {
+                break;
+            }
+        }
+    }
+
+    // The footer numbers the pages.
+    let count = pages.len();
+    for (index, page) in pages.iter_mut().enumerate() {
+        let footer = format!("{title} — page {} of {count}", index + 1);
+        let footer = fit_text(&footer, PAGE_WIDTH - 2.0 * MARGIN, 7.0);
+        page.text(MARGIN, MARGIN / 2.0, &footer, 7.0, false);
+    }
+
+    (write_pdf(&pages), count)
+}
+
+/// Writes the pages in a PDF document, with the standard Helvetica fonts.
+fn write_pdf(pages: &[PageContent]) -> Vec<u8> {
+    // Objects: 1 catalog, 2 page tree, 3 and 4 fonts, then a page and its content per page.
+    let mut objects: Vec<Vec<u8>> = Vec::new();
+    let kids: Vec<String> = (0..pages.len())
+        .map(|index| format!("{} 0 R", 5 + 2 * index))
+        .collect();
+    objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+    objects.push(
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            pages.len()
+        )
+        .into_bytes(),
+    );
+    for font in ["Helvetica", "Helvetica-Bold"] {
+        let font = format!(
+            "<< /Type /Font /Subtype /Type1 /BaseFont /{font} /Encoding /WinAnsiEncoding >>"
+        );
+        objects.push(font.into_bytes());
+    }
+    for (index, page) in pages.iter().enumerate() {
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                6 + 2 * index
+            )
+            .into_bytes(),
+        );
+        let mut stream = format!("<< /Length {} >>\nstream\n", page.0.len()).into_bytes();
+        stream.extend(&page.0);
+        stream.extend(b"endstream");
+        objects.push(stream);
+    }
+
+    let mut pdf = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend(format!("{} 0 obj\n", index + 1).as_bytes());
+        pdf.extend(object);
+        pdf.extend(b"\nendobj\n");
+    }
+
+    let xref = pdf.len();
+    let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(table, "{offset:010} 00000 n ");
+    }
+    let _ = write!(
+        table,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+        objects.len() + 1
+    );
+    pdf.extend(table.as_bytes());
+    pdf
+}
+
+/// Writes the result `df` to `path` as a printable PDF document, stamped with the local time;
+/// returns its number of pages.
+pub fn export_pdf(
+    df: &DataFrame,
+    title: &str,
+    query: Option<&str>,
+    options: &TableViewOptions,
+    path: &Path,
+) -> Result<usize, String> {
+    let timestamp = chrono::Local::now()
+        .format("%Y-%m-%d %H:%M:%S %:z")
+        .to_string();
+    let (pdf, pages) = pdf_document(df, title, query, &timestamp, options);
+    fs::write(path, pdf).map_err(|e| format!("Error writing {}: {}", path.display(), e))?;
+    Ok(pages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_pdf_document() -> Result<(), String> {
+        let df = df![
+            "Município" => (0..80).map(|i| format!("city {i}")).collect::<Vec<_>>(),
+            "Valor" => (0..80).map(|i| i as f64 * 1.5).collect::<Vec<_>>(),
+        ]
+        .map_err(|e| e.to_string())?;
+        let options = TableViewOptions::default();
+        let (pdf, pages) = pdf_document(
+            &df,
+            "report (2026)",
+            Some("SELECT *\nFROM AllData"),
+            "2026-10-15 09:30:00 -03:00",
+            &options,
+        );
+        let text = String::from_utf8_lossy(&pdf);
+
+        // 80 rows take two pages, each with the header row.
+        assert_eq!(pages, 2);
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.contains("/Count 2"));
+        assert_eq!(
+            pdf.windows(11).filter(|w| w == b"(Munic\xedpio)").count(),
+            2
+        );
+        assert!(text.contains("(report \\(2026\\))") && text.contains("(FROM AllData)"));
+        assert!(text.contains("page 2 of 2"));
+
+        // The cross-reference table points at the objects.
+        let startxref = text.rfind("startxref\n").ok_or("No startxref")?;
+        let xref: usize = text[startxref + 10..]
+            .lines()
+            .next()
+            .and_then(|line| line.parse().ok())
+            .ok_or("Invalid startxref")?;
+        assert!(pdf[xref..].starts_with(b"xref"));
+        let table = String::from_utf8_lossy(&pdf[xref..]);
+        let first = table.lines().nth(3).ok_or("No object offset")?;
+        let offset: usize = first[..10].parse().map_err(|_| "Invalid offset")?;
+        assert!(pdf[offset..].starts_with(b"1 0 obj"));
+
+        // Columns wider than a page together continue on the next pages.
+        let groups = column_groups(&[300.0, 300.0, 300.0, 100.0]);
+        let columns: Vec<_> = groups.iter().map(|g| g.columns.clone()).collect();
+        assert_eq!(columns, [0..2, 2..4]);
+        assert_eq!(fit_text("abcdefghij", 20.0, 8.0), "abc…");
+        Ok(())
+    }
+}
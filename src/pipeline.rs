@@ -0,0 +1,158 @@
+use crate::ColumnTransform;
+
+use egui::{Button, ComboBox, Grid, TextEdit, Ui};
+use polars::sql::sql_expr;
+
+/// Request of the Pipeline pane.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PipelineAction {
+    /// Reload the result with the edited pipeline.
+    Run(Vec<ColumnTransform>),
+    /// Append the pipeline of a recipe file.
+    Open,
+    /// Save the pipeline to a recipe file.
+    Save,
+}
+
+/// The ordered steps applied to the query result, which can be reordered, removed or added to.
+#[derive(Debug, Clone, Default)]
+pub struct PipelinePane {
+    condition: String,
+    sort_column: String,
+    descending: bool,
+    error: Option<String>,
+}
+
+impl PipelinePane {
+    /// Shows the steps; `columns` are the sortable columns of the current result, to add a sort.
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        transforms: &[ColumnTransform],
+        columns: &[String],
+    ) -> Option<PipelineAction> {
+        let mut steps = transforms.to_vec();
+        let mut changed = false;
+
+        if steps.is_empty() {
+            ui.label("No steps: rename, cast, replace or compute columns from the table header and the Edit menu, or add a filter or sort below.");
+        }
+
+        Grid::new("pipeline_grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                let count = steps.len();
+                let mut moved = None;
+                let mut removed = None;
+
+                for (idx, step) in transforms.iter().enumerate() {
+                    ui.label(format!("{}. {}", idx + 1, step));
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(idx > 0, Button::new("⬆").small()).clicked() {
+                            moved = Some((idx, idx - 1));
+                        }
+                        if ui
+                            .add_enabled(idx + 1 < count, Button::new("⬇").small())
+                            .clicked()
+                        {
+                            moved = Some((idx, idx + 1));
+                        }
+                        if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                            removed = Some(idx);
+                        }
+                    });
+                    ui.end_row();
+                }
+
+                if let Some((from, to)) = moved {
+                    steps.swap(from, to);
+                    changed = true;
+                }
+                if let Some(idx) = removed {
+                    steps.remove(idx);
+                    changed = true;
+                }
+            });
+
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.add(
+                TextEdit::singleline(&mut self.condition)
+                    .hint_text("qty > 10")
+                    .code_editor()
+                    .desired_width(160.0),
+            );
+            let ready = !self.condition.trim().is_empty();
+            if ui.add_enabled(ready, Button::new("Add filter")).clicked() {
+                let condition = self.condition.trim().to_string();
+                match sql_expr(&condition) {
+                    Ok(_) => {
+                        steps.push(ColumnTransform::Filter { condition });
+                        self.condition.clear();
+                        self.error = None;
+                        changed = true;
+                    }
+                    Err(e) => self.error = Some(format!("Invalid condition: {}", e)),
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ComboBox::from_id_salt("pipeline_sort_column")
+                .selected_text(&self.sort_column)
+                .width(160.0)
+                .show_ui(ui, |ui| {
+                    for column in columns {
+                        ui.selectable_value(&mut self.sort_column, column.clone(), column);
+                    }
+                });
+            ui.checkbox(&mut self.descending, "Desc.");
+            let ready = columns.contains(&self.sort_column);
+            if ui.add_enabled(ready, Button::new("Add sort")).clicked() {
+                steps.push(ColumnTransform::Sort {
+                    column: self.sort_column.clone(),
+                    descending: self.descending,
+                });
+                changed = true;
+            }
+        });
+
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+
+        ui.separator();
+
+        let mut action = None;
+        ui.horizontal(|ui| {
+            if ui
+                .button("Open recipe…")
+                .on_hover_text("Append the steps saved in a recipe file")
+                .clicked()
+            {
+                action = Some(PipelineAction::Open);
+            }
+            if ui
+                .add_enabled(!transforms.is_empty(), Button::new("Save recipe…"))
+                .on_hover_text("Save the steps, to reapply them to other files")
+                .clicked()
+            {
+                action = Some(PipelineAction::Save);
+            }
+            if ui
+                .add_enabled(!transforms.is_empty(), Button::new("Clear"))
+                .clicked()
+            {
+                steps.clear();
+                changed = true;
+            }
+        });
+
+        match changed {
+            true => Some(PipelineAction::Run(steps)),
+            false => action,
+        }
+    }
+}
@@ -0,0 +1,595 @@
+use crate::{
+    ColumnSize, CorrelationMatrix, Popover, busy_indicator, correlation_matrix, format_any_value,
+    histogram, numeric_values,
+};
+
+use egui::{
+    Color32, ComboBox, Context, Grid, Label, RichText, ScrollArea, Sense, Slider, Ui, Vec2, Window,
+};
+use egui_plot::{Bar, BarChart, Legend, Line, Plot, PlotPoints, Points};
+use polars::prelude::*;
+use std::{collections::HashMap, sync::Arc};
+use tokio::{
+    runtime::Handle,
+    sync::oneshot::{self, Receiver, error::TryRecvError},
+};
+
+/// Default number of bins of a histogram.
+const DEFAULT_BINS: usize = 20;
+
+/// Maximum number of bins selectable in the histogram popover.
+const MAX_BINS: usize = 200;
+
+/// Popover displaying the histogram of a numeric column.
+pub struct Histogram {
+    /// Name of the column.
+    column_name: String,
+    /// Non-null values of the column.
+    values: Vec<f64>,
+    /// Number of bins selected by the user.
+    bins: usize,
+    /// Bars of the current binning, recomputed only when `bins` changes.
+    bars: Vec<Bar>,
+}
+
+impl Histogram {
+    /// Creates a histogram popover from a numeric column of the current data.
+    pub fn new(column: &Column) -> Result<Self, String> {
+        let values = numeric_values(column)
+            .map_err(|e| format!("Histogram error for column {}: {}", column.name(), e))?;
+
+        let mut histogram = Histogram {
+            column_name: column.name().to_string(),
+            values,
+            bins: DEFAULT_BINS,
+            bars: Vec::new(),
+        };
+        histogram.update_bars();
+
+        Ok(histogram)
+    }
+
+    /// Recomputes the bars for the selected number of bins.
+    fn update_bars(&mut self) {
+        let (bins, width) = histogram(&self.values, self.bins);
+        self.bars = bins
+            .into_iter()
+            .map(|(lower_bound, count)| {
+                Bar::new(lower_bound + width / 2.0, count as f64) // Bars are centered on the bin.
+                    .width(width)
+                    .name(format!("[{:.4}, {:.4})", lower_bound, lower_bound + width))
+            })
+            .collect();
+    }
+}
+
+impl Popover for Histogram {
+    /// Shows the histogram window with a slider to adjust the bin count.
+    fn show(&mut self, ctx: &Context) -> bool {
+        let mut open = true;
+
+        Window::new(format!("Histogram: {}", self.column_name))
+            .collapsible(false)
+            .open(&mut open)
+            .default_size([500.0, 350.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} values", self.values.len()));
+
+                    let slider = Slider::new(&mut self.bins, 1..=MAX_BINS).text("bins");
+                    if ui.add(slider).changed() {
+                        self.update_bars();
+                    }
+                });
+
+                Plot::new("histogram_plot")
+                    .allow_scroll(false)
+                    .show(ui, |plot_ui| {
+                        plot_ui.bar_chart(BarChart::new(self.bars.clone()));
+                    });
+            });
+
+        open
+    }
+}
+
+/// Popover displaying the compressed and uncompressed size of each column as a bar chart.
+pub struct ColumnSizeChart {
+    /// Column sizes, largest first.
+    pub sizes: Vec<ColumnSize>,
+}
+
+impl Popover for ColumnSizeChart {
+    /// Shows grouped bars (compressed and uncompressed MB) per column.
+    fn show(&mut self, ctx: &Context) -> bool {
+        let mut open = true;
+
+        Window::new("Column sizes")
+            .collapsible(false)
+            .open(&mut open)
+            .default_size([600.0, 400.0])
+            .show(ctx, |ui| {
+                let total: u64 = self.sizes.iter().map(|size| size.compressed).sum();
+                ui.label(format!(
+                    "{} columns, {:.2} MB compressed",
+                    self.sizes.len(),
+                    total as f64 / 1e6
+                ));
+
+                let bars = |offset: f64, bytes: fn(&ColumnSize) -> u64| -> Vec<Bar> {
+                    self.sizes
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, size)| {
+                            Bar::new(idx as f64 + offset, bytes(size) as f64 / 1e6)
+                                .width(0.4)
+                                .name(format!("{} (ratio {:.2}x)", size.name, size.ratio()))
+                        })
+                        .collect()
+                };
+                let compressed = BarChart::new(bars(-0.2, |size| size.compressed))
+                    .name("compressed (MB)")
+                    .color(Color32::from_rgb(220, 120, 40));
+                let uncompressed = BarChart::new(bars(0.2, |size| size.uncompressed))
+                    .name("uncompressed (MB)")
+                    .color(Color32::from_rgb(70, 130, 200));
+
+                // Label the integer positions of the x axis with the column names.
+                let names: Vec<String> = self.sizes.iter().map(|size| size.name.clone()).collect();
+                Plot::new("column_sizes_plot")
+                    .legend(Legend::default())
+                    .allow_scroll(false)
+                    .x_axis_formatter(move |mark, _range| {
+                        let idx = mark.value.round();
+                        match (mark.value - idx).abs() < 1e-6 && idx >= 0.0 {
+                            true => names.get(idx as usize).cloned().unwrap_or_default(),
+                            false => String::new(),
+                        }
+                    })
+                    .show(ui, |plot_ui| {
+                        plot_ui.bar_chart(compressed);
+                        plot_ui.bar_chart(uncompressed);
+                    });
+            });
+
+        open
+    }
+}
+
+/// Side of a cell of the correlation heatmap, in points.
+const HEATMAP_CELL_SIZE: f32 = 48.0;
+
+/// Popover displaying a correlation matrix as a colored heatmap.
+pub struct CorrelationHeatmap {
+    /// The correlation matrix, or the error computing it; `None` while computed.
+    matrix: Option<Result<CorrelationMatrix, String>>,
+    pending: Option<Receiver<Result<CorrelationMatrix, String>>>,
+}
+
+impl CorrelationHeatmap {
+    /// Computes the correlation matrix of `df` in the background.
+    pub fn new(df: &Arc<DataFrame>, handle: &Handle, ctx: &Context) -> Self {
+        let (tx, rx) = oneshot::channel();
+        let data = Arc::clone(df);
+        let ctx = ctx.clone();
+
+        handle.spawn_blocking(move || {
+            let result = correlation_matrix(&data).map_err(|e| format!("Correlation error: {}", e));
+            let _ = tx.send(result);
+            ctx.request_repaint();
+        });
+
+        CorrelationHeatmap {
+            matrix: None,
+            pending: Some(rx),
+        }
+    }
+
+    /// Stores the matrix once computed.
+    fn check_pending(&mut self) {
+        let Some(mut pending) = self.pending.take() else {
+            return;
+        };
+
+        match pending.try_recv() {
+            Ok(result) => self.matrix = Some(result),
+            Err(TryRecvError::Empty) => self.pending = Some(pending), // Still running.
+            Err(TryRecvError::Closed) => {
+                self.matrix = Some(Err("Correlation was interrupted.".to_string()))
+            }
+        }
+    }
+}
+
+/// Maps a correlation coefficient to a color: blue for -1, gray for 0, red for +1.
+fn correlation_color(value: Option<f64>) -> Color32 {
+    let Some(value) = value else {
+        return Color32::DARK_GRAY;
+    };
+
+    let neutral = Color32::from_gray(60);
+    let extreme = if value < 0.0 {
+        Color32::from_rgb(40, 90, 220)
+    } else {
+        Color32::from_rgb(220, 50, 40)
+    };
+
+    neutral.lerp_to_gamma(extreme, value.abs().min(1.0) as f32)
+}
+
+impl Popover for CorrelationHeatmap {
+    /// Shows the heatmap; hovering a cell displays the column pair and coefficient.
+    fn show(&mut self, ctx: &Context) -> bool {
+        self.check_pending();
+        let mut open = true;
+
+        Window::new("Correlation matrix")
+            .collapsible(false)
+            .open(&mut open)
+            .default_size([600.0, 500.0])
+            .show(ctx, |ui| {
+                let matrix = match &self.matrix {
+                    Some(Ok(matrix)) => matrix,
+                    Some(Err(error)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                        return;
+                    }
+                    None => {
+                        ui.horizontal(|ui| {
+                            busy_indicator(ui);
+                            ui.label("Computing correlations…");
+                        });
+                        return;
+                    }
+                };
+
+                let names = &matrix.names;
+                if names.is_empty() {
+                    ui.label("No numeric columns.");
+                    return;
+                }
+
+                ScrollArea::both().show(ui, |ui| {
+                    Grid::new("correlation_grid")
+                        .spacing([2.0, 2.0])
+                        .show(ui, |ui| {
+                            // Header row with the (truncated) column names.
+                            ui.label("");
+                            for name in names {
+                                ui.add_sized(
+                                    [HEATMAP_CELL_SIZE, 20.0],
+                                    Label::new(RichText::new(name).small()).truncate(),
+                                )
+                                .on_hover_text(name);
+                            }
+                            ui.end_row();
+
+                            for (i, row) in matrix.values.iter().enumerate() {
+                                ui.label(&names[i]);
+                                for (j, value) in row.iter().enumerate() {
+                                    let size = Vec2::splat(HEATMAP_CELL_SIZE);
+                                    let (rect, response) =
+                                        ui.allocate_exact_size(size, Sense::hover());
+                                    ui.painter()
+                                        .rect_filled(rect, 2.0, correlation_color(*value));
+
+                                    let text = match value {
+                                        Some(value) => format!("{value:.2}"),
+                                        None => "-".to_string(),
+                                    };
+                                    ui.painter().text(
+                                        rect.center(),
+                                        egui::Align2::CENTER_CENTER,
+                                        &text,
+                                        egui::FontId::proportional(12.0),
+                                        Color32::WHITE,
+                                    );
+
+                                    response.on_hover_text(format!(
+                                        "{} × {}: {}",
+                                        names[i], names[j], text
+                                    ));
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+
+        open
+    }
+}
+
+/// Maximum number of points plotted per chart; larger data is evenly sampled.
+const MAX_CHART_POINTS: usize = 50_000;
+
+/// Maximum number of groups (series) plotted when grouping by a column.
+const MAX_CHART_GROUPS: usize = 20;
+
+/// Kind of chart drawn by the [`ChartPane`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChartKind {
+    #[default]
+    Scatter,
+    Line,
+    Bar,
+}
+
+impl ChartKind {
+    /// All chart kinds, in display order.
+    pub const ALL: [ChartKind; 3] = [ChartKind::Scatter, ChartKind::Line, ChartKind::Bar];
+}
+
+impl std::fmt::Display for ChartKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ChartKind::Scatter => "Scatter",
+            ChartKind::Line => "Line",
+            ChartKind::Bar => "Bar",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Columns and kind of chart selected by the user.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChartSettings {
+    /// Kind of chart.
+    pub kind: ChartKind,
+    /// X axis column (numeric or temporal). Uses the row index if `None`.
+    pub x: Option<String>,
+    /// Y axis column (numeric).
+    pub y: Option<String>,
+    /// Optional categorical column splitting the data into one series per value.
+    pub group_by: Option<String>,
+}
+
+/// A named series of points to plot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartSeries {
+    /// Name shown in the legend.
+    pub name: String,
+    /// The `[x, y]` points.
+    pub points: Vec<[f64; 2]>,
+}
+
+/// Converts a numeric or temporal column to `f64` values (dates become days, datetimes their time unit).
+fn column_to_f64(column: &Column) -> PolarsResult<Float64Chunked> {
+    let physical = column.to_physical_repr();
+    let casted = physical.cast(&DataType::Float64)?;
+    Ok(casted.f64()?.clone())
+}
+
+/// Builds the series to plot from the data according to the chart settings.
+///
+/// Rows with null or non-finite coordinates are skipped.
+pub fn chart_series(df: &DataFrame, settings: &ChartSettings) -> PolarsResult<Vec<ChartSeries>> {
+    let Some(y_name) = &settings.y else {
+        return Ok(Vec::new());
+    };
+
+    let y = column_to_f64(df.column(y_name)?)?;
+    let x = match &settings.x {
+        Some(x_name) => Some(column_to_f64(df.column(x_name)?)?),
+        None => None,
+    };
+    let groups = match &settings.group_by {
+        Some(group_name) => Some(df.column(group_name)?.as_materialized_series().clone()),
+        None => None,
+    };
+
+    // Sample rows evenly when the data is too large to plot interactively.
+    let step = df.height().div_ceil(MAX_CHART_POINTS).max(1);
+
+    let mut series: Vec<ChartSeries> = Vec::new();
+    let mut index_by_name: HashMap<String, usize> = HashMap::new();
+
+    for row in (0..df.height()).step_by(step) {
+        let x_value = match &x {
+            Some(x) => x.get(row),
+            None => Some(row as f64),
+        };
+        let (Some(x_value), Some(y_value)) = (x_value, y.get(row)) else {
+            continue;
+        };
+        if !x_value.is_finite() || !y_value.is_finite() {
+            continue;
+        }
+
+        let name = match &groups {
+            Some(groups) => format_any_value(&groups.get(row)?),
+            None => y_name.clone(),
+        };
+
+        let index = match index_by_name.get(&name) {
+            Some(&index) => index,
+            None if series.len() < MAX_CHART_GROUPS => {
+                index_by_name.insert(name.clone(), series.len());
+                series.push(ChartSeries {
+                    name,
+                    points: Vec::new(),
+                });
+                series.len() - 1
+            }
+            None => continue, // Too many groups: ignore the remaining ones.
+        };
+
+        series[index].points.push([x_value, y_value]);
+    }
+
+    Ok(series)
+}
+
+/// Series computed for given chart settings and data.
+struct ChartCache {
+    settings: ChartSettings,
+    df: Arc<DataFrame>,
+    series: Result<Vec<ChartSeries>, String>,
+}
+
+/// Pane plotting selected columns of the current result.
+#[derive(Default)]
+pub struct ChartPane {
+    /// Columns and kind of chart selected by the user.
+    pub settings: ChartSettings,
+    /// Series computed for the last settings and data, to avoid recomputing them every frame.
+    cache: Option<ChartCache>,
+}
+
+impl ChartPane {
+    /// Renders the chart controls and the chart itself.
+    pub fn render(&mut self, ui: &mut Ui, df: &Arc<DataFrame>) {
+        let column_names: Vec<String> = df
+            .get_column_names()
+            .into_iter()
+            .map(|name| name.to_string())
+            .collect();
+
+        ui.horizontal_wrapped(|ui| {
+            ComboBox::from_label("Chart")
+                .selected_text(self.settings.kind.to_string())
+                .show_ui(ui, |ui| {
+                    for kind in ChartKind::ALL {
+                        ui.selectable_value(&mut self.settings.kind, kind, kind.to_string());
+                    }
+                });
+
+            select_column(ui, "X", "(row index)", &column_names, &mut self.settings.x);
+            select_column(ui, "Y", "(none)", &column_names, &mut self.settings.y);
+            select_column(
+                ui,
+                "Group by",
+                "(none)",
+                &column_names,
+                &mut self.settings.group_by,
+            );
+        });
+
+        ui.separator();
+
+        if self.settings.y.is_none() {
+            ui.label("Select the Y column to plot.");
+            return;
+        }
+
+        // Recompute the series only if the settings or the data changed.
+        let up_to_date = self
+            .cache
+            .as_ref()
+            .is_some_and(|cache| cache.settings == self.settings && Arc::ptr_eq(&cache.df, df));
+        if !up_to_date {
+            self.cache = Some(ChartCache {
+                settings: self.settings.clone(),
+                df: df.clone(),
+                series: chart_series(df, &self.settings).map_err(|e| e.to_string()),
+            });
+        }
+
+        let series = match self.cache.as_ref().map(|cache| &cache.series) {
+            Some(Ok(series)) => series,
+            Some(Err(message)) => {
+                ui.colored_label(ui.visuals().error_fg_color, message);
+                return;
+            }
+            None => return,
+        };
+
+        let kind = self.settings.kind;
+        Plot::new("chart_plot")
+            .legend(Legend::default())
+            .x_axis_label(self.settings.x.as_deref().unwrap_or("row index"))
+            .y_axis_label(self.settings.y.as_deref().unwrap_or_default())
+            .show(ui, |plot_ui| {
+                for chart_series in series {
+                    let name = &chart_series.name;
+                    let points = &chart_series.points;
+                    match kind {
+                        ChartKind::Scatter => {
+                            let points = PlotPoints::from(points.clone());
+                            plot_ui.points(Points::new(points).radius(2.0).name(name));
+                        }
+                        ChartKind::Line => {
+                            let points = PlotPoints::from(points.clone());
+                            plot_ui.line(Line::new(points).name(name));
+                        }
+                        ChartKind::Bar => {
+                            let bars = points
+                                .iter()
+                                .map(|&[x, y]| Bar::new(x, y).width(bar_width(points)))
+                                .collect();
+                            plot_ui.bar_chart(BarChart::new(bars).name(name));
+                        }
+                    }
+                }
+            });
+    }
+}
+
+/// Width of the bars: a fraction of the average distance between the x values.
+fn bar_width(points: &[[f64; 2]]) -> f64 {
+    let min = points.iter().map(|p| p[0]).fold(f64::INFINITY, f64::min);
+    let max = points
+        .iter()
+        .map(|p| p[0])
+        .fold(f64::NEG_INFINITY, f64::max);
+    match points.len() {
+        0 | 1 => 0.8,
+        n => 0.8 * (max - min) / (n - 1) as f64,
+    }
+}
+
+/// Renders a combo box to select an optional column.
+fn select_column(
+    ui: &mut Ui,
+    label: &str,
+    none_text: &str,
+    column_names: &[String],
+    selected: &mut Option<String>,
+) {
+    ComboBox::from_label(label)
+        .selected_text(selected.as_deref().unwrap_or(none_text))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(selected, None, none_text);
+            for name in column_names {
+                ui.selectable_value(selected, Some(name.clone()), name);
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chart_series_grouped() -> PolarsResult<()> {
+        let df = df![
+            "x" => [1, 2, 3, 4],
+            "y" => [Some(10.0), Some(20.0), None, Some(40.0)],
+            "kind" => ["a", "b", "a", "a"],
+        ]?;
+
+        let settings = ChartSettings {
+            x: Some("x".to_string()),
+            y: Some("y".to_string()),
+            group_by: Some("kind".to_string()),
+            ..Default::default()
+        };
+
+        let series = chart_series(&df, &settings)?;
+        assert_eq!(
+            series,
+            vec![
+                ChartSeries {
+                    name: "a".to_string(),
+                    points: vec![[1.0, 10.0], [4.0, 40.0]], // The null y is skipped.
+                },
+                ChartSeries {
+                    name: "b".to_string(),
+                    points: vec![[2.0, 20.0]],
+                },
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+use crate::get_extension;
+
+use polars::prelude::*;
+use std::{
+    path::Path,
+    sync::{Arc, PoisonError, RwLock},
+};
+
+// Plugins are compiled in: a crate embedding the viewer registers them before starting the
+// app. Dynamic libraries are not loaded, as that needs the unsafe code this crate forbids.
+
+/// Loader of a file format that is not built in, such as a proprietary log format.
+pub trait DataSourcePlugin: Send + Sync {
+    /// Name of the format, shown as the table type.
+    fn name(&self) -> &str;
+    /// Extensions of the files read, lowercase and without the dot.
+    fn extensions(&self) -> &[&str];
+    fn load(&self, path: &Path) -> Result<DataFrame, String>;
+}
+
+/// Custom display of the cells of some columns, such as status codes shown by name.
+pub trait CellRenderer: Send + Sync {
+    /// Whether the renderer displays the cells of the column.
+    fn applies_to(&self, column: &str, dtype: &DataType) -> bool;
+    /// Text of a cell that is not null.
+    fn render(&self, value: &AnyValue) -> String;
+}
+
+static DATA_SOURCES: RwLock<Vec<Arc<dyn DataSourcePlugin>>> = RwLock::new(Vec::new());
+static CELL_RENDERERS: RwLock<Vec<Arc<dyn CellRenderer>>> = RwLock::new(Vec::new());
+
+/// Adds a loader; the loaders registered last take precedence for an extension.
+pub fn register_data_source(plugin: impl DataSourcePlugin + 'static) {
+    let mut sources = DATA_SOURCES.write().unwrap_or_else(PoisonError::into_inner);
+    sources.insert(0, Arc::new(plugin));
+}
+
+/// Adds a cell renderer; the renderers registered last take precedence for a column.
+pub fn register_cell_renderer(renderer: impl CellRenderer + 'static) {
+    let mut renderers = CELL_RENDERERS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    renderers.insert(0, Arc::new(renderer));
+}
+
+/// The loader of the files with the extension, if any.
+pub fn data_source(extension: &str) -> Option<Arc<dyn DataSourcePlugin>> {
+    let sources = DATA_SOURCES.read().unwrap_or_else(PoisonError::into_inner);
+    sources
+        .iter()
+        .find(|plugin| plugin.extensions().contains(&extension))
+        .cloned()
+}
+
+/// The renderer of the cells of a column, if any.
+pub fn cell_renderer(column: &str, dtype: &DataType) -> Option<Arc<dyn CellRenderer>> {
+    let renderers = CELL_RENDERERS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner);
+    renderers
+        .iter()
+        .find(|renderer| renderer.applies_to(column, dtype))
+        .cloned()
+}
+
+/// Loads a file of a format that is not built in, with its table type.
+pub fn load_with_plugin(filename: &str) -> Result<(DataFrame, String), String> {
+    let plugin = get_extension(filename)
+        .as_deref()
+        .and_then(data_source)
+        .ok_or_else(|| format!("Unknown file type: {}", filename))?;
+    let df = plugin
+        .load(Path::new(filename))
+        .map_err(|e| format!("Error reading {} with {}: {}", filename, plugin.name(), e))?;
+    Ok((df, plugin.name().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DataFrameContainer;
+
+    struct KeyValueLog;
+
+    impl DataSourcePlugin for KeyValueLog {
+        fn name(&self) -> &str {
+            "kvlog"
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["kvlog"]
+        }
+
+        fn load(&self, path: &Path) -> Result<DataFrame, String> {
+            let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+            let (keys, values): (Vec<&str>, Vec<&str>) =
+                text.lines().filter_map(|line| line.split_once('=')).unzip();
+            df!["key" => keys, "value" => values].map_err(|e| e.to_string())
+        }
+    }
+
+    struct Upper;
+
+    impl CellRenderer for Upper {
+        fn applies_to(&self, column: &str, _dtype: &DataType) -> bool {
+            column == "plugin_test_key"
+        }
+
+        fn render(&self, value: &AnyValue) -> String {
+            crate::cell_text(value).to_uppercase()
+        }
+    }
+
+    #[test]
+    fn test_plugins() -> Result<(), String> {
+        register_data_source(KeyValueLog);
+        register_cell_renderer(Upper);
+
+        let path = std::env::temp_dir().join("polars_view_test_plugin.kvlog");
+        std::fs::write(&path, "a=1\nb=2\n").map_err(|e| e.to_string())?;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .map_err(|e| e.to_string())?;
+        let data = runtime.block_on(DataFrameContainer::load_data(path.display().to_string()));
+        let _ = std::fs::remove_file(&path);
+
+        let data = data?;
+        assert_eq!(data.table_type, "kvlog");
+        assert_eq!(data.df.shape(), (2, 2));
+        assert!(load_with_plugin("data.unknown").is_err());
+
+        let renderer = cell_renderer("plugin_test_key", &DataType::String);
+        let rendered = renderer.map(|renderer| renderer.render(&AnyValue::String("ok")));
+        assert_eq!(rendered.as_deref(), Some("OK"));
+        assert!(cell_renderer("other", &DataType::String).is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use egui::{Context, Id, Response, RichText, Ui};
+use std::time::Duration;
+
+/// Id of the low-power mode in the data of the egui context, read by the widgets of every window.
+fn low_power_id() -> Id {
+    Id::new("low_power")
+}
+
+/// Turns the low-power mode of the egui context on or off.
+pub fn set_low_power(ctx: &Context, enabled: bool) {
+    ctx.data_mut(|data| data.insert_temp(low_power_id(), enabled));
+}
+
+/// Whether the window repaints only on input and task completion, to save battery.
+pub fn low_power(ctx: &Context) -> bool {
+    ctx.data(|data| data.get_temp(low_power_id()))
+        .unwrap_or(false)
+}
+
+/// Interval of the repaints showing the progress of running tasks: once a second in low-power mode.
+pub fn progress_repaint_interval(ctx: &Context) -> Duration {
+    match low_power(ctx) {
+        true => Duration::from_secs(1),
+        false => Duration::from_millis(100),
+    }
+}
+
+/// Shows that a task is running: a spinner, which repaints in every frame, or an hourglass
+/// in low-power mode.
+pub fn busy_indicator(ui: &mut Ui) -> Response {
+    match low_power(ui.ctx()) {
+        true => ui
+            .label(RichText::new("⏳").weak())
+            .on_hover_text("Running…"),
+        false => ui.spinner(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_low_power_interval() {
+        let ctx = Context::default();
+        assert!(!low_power(&ctx));
+        set_low_power(&ctx, true);
+        assert!(low_power(&ctx));
+        assert_eq!(progress_repaint_interval(&ctx), Duration::from_secs(1));
+        set_low_power(&ctx, false);
+        assert_eq!(progress_repaint_interval(&ctx), Duration::from_millis(100));
+    }
+}
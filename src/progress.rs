@@ -0,0 +1,111 @@
+use std::{
+    future::Future,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    },
+};
+
+tokio::task_local! {
+    /// Progress of the load running in the current task.
+    static LOAD_PROGRESS: Arc<LoadProgress>;
+}
+
+/// Progress of a load, shared between the loading task and the UI.
+///
+/// Parquet files report the row groups read; other loads only support cancellation.
+#[derive(Debug, Default)]
+pub struct LoadProgress {
+    /// Row groups read.
+    done: AtomicUsize,
+    /// Row groups to read; 0 while unknown.
+    total: AtomicUsize,
+    /// Compressed bytes read.
+    bytes: AtomicU64,
+    /// Set by the UI to stop the load.
+    cancelled: AtomicBool,
+}
+
+impl LoadProgress {
+    /// Runs a future with this progress available through [`LoadProgress::current`].
+    pub fn scope<F: Future>(self: &Arc<Self>, future: F) -> impl Future<Output = F::Output> {
+        LOAD_PROGRESS.scope(Arc::clone(self), future)
+    }
+
+    /// Progress of the load running in the current task, if any.
+    pub fn current() -> Option<Arc<Self>> {
+        LOAD_PROGRESS.try_with(Arc::clone).ok()
+    }
+
+    /// Starts counting the given number of row groups.
+    pub fn start(&self, total: usize) {
+        self.done.store(0, Ordering::Relaxed);
+        self.bytes.store(0, Ordering::Relaxed);
+        self.total.store(total, Ordering::Relaxed);
+    }
+
+    /// Records a row group read.
+    pub fn advance(&self, bytes: u64) {
+        self.done.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Fraction of the row groups read, if their number is known.
+    pub fn fraction(&self) -> Option<f32> {
+        let total = self.total.load(Ordering::Relaxed);
+        let done = self.done.load(Ordering::Relaxed);
+        (total > 0).then(|| done.min(total) as f32 / total as f32)
+    }
+
+    /// Description of the progress, such as `3/10 row groups (12.5 MB)`.
+    pub fn text(&self) -> String {
+        let total = self.total.load(Ordering::Relaxed);
+        if total == 0 {
+            return "Loading…".to_string();
+        }
+
+        let done = self.done.load(Ordering::Relaxed);
+        let megabytes = self.bytes.load(Ordering::Relaxed) as f64 / 1_048_576.0;
+        format!("{done}/{total} row groups ({megabytes:.1} MB)")
+    }
+
+    /// Asks the load to stop.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns an error if the load was cancelled.
+    pub fn check(&self) -> Result<(), String> {
+        match self.cancelled.load(Ordering::Relaxed) {
+            true => Err("Loading cancelled.".to_string()),
+            false => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_progress() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let progress = Arc::new(LoadProgress::default());
+        assert!(progress.fraction().is_none());
+
+        let result = runtime.block_on(progress.scope(async {
+            let progress = LoadProgress::current().unwrap();
+            progress.start(4);
+            progress.advance(1_048_576);
+            progress.cancel();
+            progress.check()
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(progress.fraction(), Some(0.25));
+        assert_eq!(progress.text(), "1/4 row groups (1.0 MB)");
+        assert!(LoadProgress::current().is_none());
+    }
+}
@@ -0,0 +1,219 @@
+use crate::{Popover, SESSION_EXTENSION, Session, file_label};
+
+use egui::{Context, Window};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+/// Interval between two autosaves of the session.
+pub const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// File of the session autosaved while this process runs; removed on a normal exit.
+///
+/// Each running instance autosaves to its own file, named after its process.
+pub fn autosave_path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| {
+        dir.join("polars-view").join(format!(
+            "autosave-{}.{SESSION_EXTENSION}",
+            std::process::id()
+        ))
+    })
+}
+
+/// Writes an autosaved session, creating its directory.
+pub fn write_autosave(path: &Path, session: &Session) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Error creating {}: {}", dir.display(), e))?;
+    }
+    session.save(path)
+}
+
+/// The session saved periodically, so that a crash or an out-of-memory kill does not lose
+/// the open tables, queries and layout.
+#[derive(Debug, Default)]
+pub struct Autosave {
+    /// Autosave file; `None` disables the autosave.
+    path: Option<PathBuf>,
+    /// Session written last, not written again while unchanged.
+    saved: Option<Session>,
+    /// When the session was last checked.
+    last: Option<Instant>,
+}
+
+impl Autosave {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Autosave {
+            path,
+            ..Default::default()
+        }
+    }
+
+    /// The most recent session left by a run that did not exit normally, if it had tables open.
+    ///
+    /// The files left in the autosave directory are removed once read.
+    pub fn recover(&self) -> Option<Session> {
+        let dir = self.path.as_ref()?.parent()?;
+        let mut left: Vec<(SystemTime, PathBuf)> = fs::read_dir(dir)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                name.starts_with("autosave") && name.ends_with(SESSION_EXTENSION)
+            })
+            .map(|path| {
+                let modified = fs::metadata(&path).and_then(|metadata| metadata.modified());
+                (modified.unwrap_or(SystemTime::UNIX_EPOCH), path)
+            })
+            .collect();
+        left.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified)); // The most recent first.
+
+        let mut recovered = None;
+        for (_, path) in left {
+            match Session::load(&path) {
+                Ok(session) if recovered.is_none() && !session.tables.is_empty() => {
+                    recovered = Some(session)
+                }
+                Ok(_) => {}
+                Err(message) => tracing::warn!("Autosaved session ignored: {}", message),
+            }
+            if let Err(e) = fs::remove_file(&path) {
+                tracing::warn!("Error removing {}: {}", path.display(), e);
+            }
+        }
+        recovered
+    }
+
+    /// Whether the session is due to be saved again.
+    pub fn due(&self) -> bool {
+        self.path.is_some()
+            && self
+                .last
+                .is_none_or(|last| last.elapsed() >= AUTOSAVE_INTERVAL)
+    }
+
+    /// The file and the session to write, if changed since the last autosave;
+    /// written with [`write_autosave`], off the UI thread.
+    pub fn changed(&mut self, session: Session) -> Option<(PathBuf, Session)> {
+        self.last = Some(Instant::now());
+        let path = self.path.clone()?;
+        if self.saved.as_ref() == Some(&session) {
+            return None;
+        }
+
+        self.saved = Some(session.clone());
+        Some((path, session))
+    }
+
+    /// Removes the autosave file, on a normal exit.
+    pub fn clear(&mut self) {
+        if let Some(path) = self.path.as_ref().filter(|path| path.exists()) {
+            if let Err(e) = fs::remove_file(path) {
+                tracing::warn!("Error removing {}: {}", path.display(), e);
+            }
+        }
+        self.saved = None;
+    }
+}
+
+/// Offers to restore the session autosaved before an abnormal exit.
+pub struct SessionRecovery {
+    session: Session,
+    /// Session restored by the user, taken by the application.
+    requested: Option<Session>,
+}
+
+impl SessionRecovery {
+    pub fn new(session: Session) -> Self {
+        SessionRecovery {
+            session,
+            requested: None,
+        }
+    }
+}
+
+impl Popover for SessionRecovery {
+    fn show(&mut self, ctx: &Context) -> bool {
+        let mut open = true;
+        let mut discarded = false;
+
+        Window::new("Restore session")
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label("The application did not exit normally. Restore the last session?");
+                ui.add_space(4.0);
+                for table in &self.session.tables {
+                    let query = table.query.query.as_deref().unwrap_or("no query");
+                    ui.label(format!("• {}", file_label(&table.filename)))
+                        .on_hover_text(format!("{}\n{}", table.filename, query));
+                }
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Restore").clicked() {
+                        self.requested = Some(self.session.clone());
+                    }
+                    discarded = ui.button("Discard").clicked();
+                });
+            });
+
+        open && !discarded && self.requested.is_none()
+    }
+
+    fn take_session(&mut self) -> Option<Session> {
+        self.requested.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DataFilters;
+
+    #[test]
+    fn test_autosave_recovery() -> Result<(), String> {
+        let path = std::env::temp_dir()
+            .join(format!("polars_view_test_autosave_{}", std::process::id()))
+            .join(format!("autosave.{SESSION_EXTENSION}"));
+        let mut autosave = Autosave::new(Some(path.clone()));
+        assert!(autosave.due() && autosave.recover().is_none());
+        let save = |autosave: &mut Autosave, session| match autosave.changed(session) {
+            Some((path, session)) => write_autosave(&path, &session),
+            None => Ok(()),
+        };
+
+        // A session without tables is saved, but not offered for recovery.
+        save(&mut autosave, Session::default())?;
+        assert!(!autosave.due());
+        assert!(autosave.recover().is_none());
+
+        let mut session = Session::default();
+        let filters = DataFilters {
+            query: Some("SELECT * FROM AllData LIMIT 5;".to_string()),
+            ..DataFilters::new("data.parquet")
+        };
+        session.add_table("data.parquet", &filters);
+        save(&mut autosave, session.clone())?;
+        assert!(autosave.changed(session.clone()).is_none()); // Unchanged.
+
+        // The next run, of another process, finds the session left by a crash once.
+        let next_run = Autosave::new(Some(
+            path.with_file_name(format!("autosave-next.{SESSION_EXTENSION}")),
+        ));
+        assert_eq!(next_run.recover(), Some(session.clone()));
+        assert!(!path.exists() && next_run.recover().is_none());
+
+        // Nothing is left after a normal exit.
+        autosave.saved = None;
+        save(&mut autosave, session)?;
+        autosave.clear();
+        assert!(!path.exists() && next_run.recover().is_none());
+        let _ = fs::remove_dir(path.parent().unwrap());
+
+        assert!(!Autosave::default().due());
+        Ok(())
+    }
+}
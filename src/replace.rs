@@ -0,0 +1,207 @@
+use crate::{ColumnTransform, DataFilters, Popover, busy_indicator};
+
+use egui::{Button, Context, Grid, ScrollArea, Window};
+use polars::prelude::*;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{
+    runtime::Handle,
+    sync::oneshot::{self, Receiver, error::TryRecvError},
+};
+
+/// Pause in the typing after which the rows affected are counted.
+const PREVIEW_DELAY: Duration = Duration::from_millis(400);
+
+/// Popover replacing text (plain or regex) in the chosen string columns of the result.
+pub struct FindReplace {
+    filters: DataFilters,
+    df: Arc<DataFrame>,
+    /// String columns of the result, with whether each is selected.
+    columns: Vec<(String, bool)>,
+    find: String,
+    replace: String,
+    regex: bool,
+    /// Rows that the replacement would change, once previewed.
+    preview: Option<Result<usize, String>>,
+    /// Rows being counted in the background, for the current search.
+    pending: Option<Receiver<Result<usize, String>>>,
+    /// Last change of the search, previewed once the typing pauses.
+    edited: Option<Instant>,
+    handle: Handle,
+    /// Filters with the replacement added, once applied.
+    requested_filters: Option<DataFilters>,
+}
+
+impl FindReplace {
+    /// The rows affected are counted on `handle`, as the search is typed.
+    pub fn new(filters: &DataFilters, df: Arc<DataFrame>, handle: Handle) -> Self {
+        let columns = df
+            .get_columns()
+            .iter()
+            .filter(|column| column.dtype() == &DataType::String)
+            .map(|column| (column.name().to_string(), true))
+            .collect();
+
+        FindReplace {
+            filters: filters.clone(),
+            df,
+            columns,
+            find: String::new(),
+            replace: String::new(),
+            regex: false,
+            preview: None,
+            pending: None,
+            edited: None,
+            handle,
+            requested_filters: None,
+        }
+    }
+
+    /// Counts the rows affected by the current search in the background.
+    fn start_preview(&mut self, ctx: &Context) {
+        let (tx, rx) = oneshot::channel();
+        let (transform, df) = (self.transform(), Arc::clone(&self.df));
+        let ctx = ctx.clone();
+
+        self.handle.spawn_blocking(move || {
+            let _ = tx.send(transform.count_matches(&df));
+            ctx.request_repaint();
+        });
+
+        self.pending = Some(rx);
+    }
+
+    /// Counts the rows once the typing pauses, and stores the count once done.
+    fn check_preview(&mut self, ctx: &Context) {
+        if let Some(edited) = self.edited {
+            let elapsed = edited.elapsed();
+            if elapsed < PREVIEW_DELAY {
+                ctx.request_repaint_after(PREVIEW_DELAY - elapsed);
+                return;
+            }
+            self.edited = None;
+            if !self.find.is_empty() {
+                self.start_preview(ctx);
+            }
+        }
+
+        let Some(mut pending) = self.pending.take() else {
+            return;
+        };
+        match pending.try_recv() {
+            Ok(result) => self.preview = Some(result),
+            Err(TryRecvError::Empty) => self.pending = Some(pending), // Still running.
+            Err(TryRecvError::Closed) => {
+                self.preview = Some(Err("Preview was interrupted.".to_string()))
+            }
+        }
+    }
+
+    fn transform(&self) -> ColumnTransform {
+        ColumnTransform::Replace {
+            columns: self
+                .columns
+                .iter()
+                .filter(|(_, selected)| *selected)
+                .map(|(name, _)| name.clone())
+                .collect(),
+            pattern: self.find.clone(),
+            replacement: self.replace.clone(),
+            regex: self.regex,
+        }
+    }
+}
+
+impl Popover for FindReplace {
+    /// Shows the search, the replacement and the columns, with a preview of the changed rows.
+    fn show(&mut self, ctx: &Context) -> bool {
+        self.check_preview(ctx);
+        let mut open = true;
+        let mut apply = false;
+
+        Window::new("Find and replace")
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let mut changed = false;
+
+                Grid::new("find_replace_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Find:");
+                        changed |= ui.text_edit_singleline(&mut self.find).changed();
+                        ui.end_row();
+
+                        ui.label("Replace with:");
+                        ui.text_edit_singleline(&mut self.replace)
+                            .on_hover_text("With a regex, $1 inserts the first capture group");
+                        ui.end_row();
+
+                        ui.label("");
+                        changed |= ui.checkbox(&mut self.regex, "Regular expression").changed();
+                        ui.end_row();
+                    });
+
+                ui.separator();
+
+                if self.columns.is_empty() {
+                    ui.label("The result has no string columns.");
+                    return;
+                }
+
+                ui.label("Columns:");
+                ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                    for (name, selected) in &mut self.columns {
+                        changed |= ui.checkbox(selected, name.as_str()).changed();
+                    }
+                });
+
+                if changed {
+                    // The preview refers to the previous search; counted again after a pause.
+                    self.preview = None;
+                    self.pending = None;
+                    self.edited = Some(Instant::now());
+                    ui.ctx().request_repaint_after(PREVIEW_DELAY);
+                }
+
+                ui.separator();
+
+                ui.horizontal(|ui| match &self.preview {
+                    Some(Ok(rows)) => {
+                        ui.label(format!("{rows} of {} rows affected", self.df.height()));
+                    }
+                    Some(Err(message)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, message);
+                    }
+                    None if self.pending.is_some() || self.edited.is_some() => {
+                        busy_indicator(ui);
+                        ui.label("Counting the rows affected…");
+                    }
+                    None => {
+                        ui.weak("Type the text to find");
+                    }
+                });
+
+                let previewed = matches!(self.preview, Some(Ok(rows)) if rows > 0);
+                apply = ui
+                    .add_enabled(previewed, Button::new("Replace all"))
+                    .on_disabled_hover_text("No rows affected by the search")
+                    .clicked();
+            });
+
+        if apply {
+            let mut filters = self.filters.clone();
+            filters.transforms.push(self.transform());
+            self.requested_filters = Some(filters);
+            return false;
+        }
+
+        open
+    }
+
+    fn take_filters(&mut self) -> Option<DataFilters> {
+        self.requested_filters.take()
+    }
+}
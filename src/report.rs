@@ -0,0 +1,356 @@
+use crate::{describe, format_any_value, histogram, numeric_values, value_counts};
+
+use polars::prelude::*;
+use std::{fmt::Write, fs, path::Path};
+
+/// Number of most frequent values listed per column in a profiling report.
+pub const REPORT_TOP_VALUES: usize = 10;
+
+/// Number of histogram bins per numeric column in a profiling report.
+pub const REPORT_HISTOGRAM_BINS: usize = 10;
+
+/// Width (in characters) of the longest text bar of a Markdown histogram.
+const MARKDOWN_BAR_WIDTH: usize = 30;
+
+/// Output format of a profiling report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    /// Chooses the format from the file extension: HTML for `.html`/`.htm`, Markdown otherwise.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm") => {
+                ReportFormat::Html
+            }
+            _ => ReportFormat::Markdown,
+        }
+    }
+}
+
+/// Profile of a single column: data type, nulls, most frequent values and histogram.
+#[derive(Debug, Clone)]
+pub struct ColumnProfile {
+    pub name: String,
+    pub dtype: String,
+    pub nulls: usize,
+    /// Most frequent values (formatted) and their counts, most frequent first.
+    pub top_values: Vec<(String, u64)>,
+    /// Histogram bins (lower bound and count) and bin width, for numeric columns.
+    pub histogram: Option<(Vec<(f64, usize)>, f64)>,
+}
+
+/// Lightweight data profiling report (describe, null stats, value counts and histograms).
+#[derive(Debug, Clone)]
+pub struct ProfileReport {
+    pub title: String,
+    pub rows: usize,
+    /// Summary statistics computed by [`describe`].
+    pub statistics: DataFrame,
+    pub columns: Vec<ColumnProfile>,
+}
+
+impl ProfileReport {
+    /// Profiles all the columns of the DataFrame.
+    pub fn new(df: &DataFrame, title: &str) -> PolarsResult<Self> {
+        let mut columns = Vec::with_capacity(df.width());
+
+        for column in df.get_columns() {
+            let name = column.name().to_string();
+
+            let counts = value_counts(df, &name)?.head(Some(REPORT_TOP_VALUES));
+            let counts_values = counts.column(&name)?.as_materialized_series();
+            let counts_counts = counts.column("count")?.cast(&DataType::UInt64)?;
+            let top_values = counts_values
+                .iter()
+                .zip(counts_counts.u64()?)
+                .map(|(value, count)| {
+                    let value = match value {
+                        AnyValue::Null => "null".to_string(),
+                        value => format_any_value(&value),
+                    };
+                    (value, count.unwrap_or(0))
+                })
+                .collect();
+
+            let histogram = match column.dtype().is_primitive_numeric() {
+                true => Some(histogram(&numeric_values(column)?, REPORT_HISTOGRAM_BINS)),
+                false => None,
+            };
+
+            columns.push(ColumnProfile {
+                name,
+                dtype: column.dtype().to_string(),
+                nulls: column.null_count(),
+                top_values,
+                histogram,
+            });
+        }
+
+        Ok(ProfileReport {
+            title: title.to_string(),
+            rows: df.height(),
+            statistics: describe(df)?,
+            columns,
+        })
+    }
+
+    /// Renders the report in the requested format.
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Markdown => self.to_markdown(),
+            ReportFormat::Html => self.to_html(),
+        }
+    }
+
+    /// Renders the report as a Markdown document.
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+
+        // Writing to a String never fails.
+        let _ = writeln!(md, "# Profiling report: {}\n", self.title);
+        let _ = writeln!(md, "{} rows, {} columns\n", self.rows, self.columns.len());
+
+        let _ = writeln!(md, "## Summary statistics\n");
+        md.push_str(&dataframe_to_markdown(&self.statistics));
+
+        for column in &self.columns {
+            let _ = writeln!(md, "\n## {} ({})\n", column.name, column.dtype);
+            let _ = writeln!(
+                md,
+                "Nulls: {} ({:.2}%)\n",
+                column.nulls,
+                percentage(column.nulls, self.rows)
+            );
+
+            let _ = writeln!(md, "| value | count |\n|---|---|");
+            for (value, count) in &column.top_values {
+                let _ = writeln!(md, "| {} | {} |", escape_markdown(value), count);
+            }
+
+            if let Some((bins, width)) = &column.histogram {
+                let max = bins.iter().map(|(_, count)| *count).max().unwrap_or(0);
+                let _ = writeln!(md, "\n```text");
+                for (lower, count) in bins {
+                    let bar = "█".repeat(scale(*count, max, MARKDOWN_BAR_WIDTH));
+                    let _ = writeln!(
+                        md,
+                        "[{:>12.4}, {:>12.4}) {bar} {count}",
+                        lower,
+                        lower + width
+                    );
+                }
+                let _ = writeln!(md, "```");
+            }
+        }
+
+        md
+    }
+
+    /// Renders the report as a self-contained HTML page.
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        let title = escape_html(&self.title);
+
+        let _ = writeln!(
+            html,
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Profiling report: {title}</title>"
+        );
+        let _ = writeln!(
+            html,
+            "<style>\
+             body {{ font-family: sans-serif; margin: 2em; }}\
+             table {{ border-collapse: collapse; margin-bottom: 1em; }}\
+             th, td {{ border: 1px solid #ccc; padding: 2px 8px; text-align: right; }}\
+             .bar {{ background: #4a90d9; height: 12px; }}\
+             </style>\n</head>\n<body>"
+        );
+        let _ = writeln!(html, "<h1>Profiling report: {title}</h1>");
+        let _ = writeln!(
+            html,
+            "<p>{} rows, {} columns</p>",
+            self.rows,
+            self.columns.len()
+        );
+
+        let _ = writeln!(html, "<h2>Summary statistics</h2>");
+        html.push_str(&dataframe_to_html(&self.statistics));
+
+        for column in &self.columns {
+            let _ = writeln!(
+                html,
+                "<h2>{} ({})</h2>",
+                escape_html(&column.name),
+                escape_html(&column.dtype)
+            );
+            let _ = writeln!(
+                html,
+                "<p>Nulls: {} ({:.2}%)</p>",
+                column.nulls,
+                percentage(column.nulls, self.rows)
+            );
+
+            let _ = writeln!(html, "<table>\n<tr><th>value</th><th>count</th></tr>");
+            for (value, count) in &column.top_values {
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{}</td><td>{count}</td></tr>",
+                    escape_html(value)
+                );
+            }
+            let _ = writeln!(html, "</table>");
+
+            if let Some((bins, width)) = &column.histogram {
+                let max = bins.iter().map(|(_, count)| *count).max().unwrap_or(0);
+                let _ = writeln!(
+                    html,
+                    "<table>\n<tr><th>bin</th><th>count</th><th></th></tr>"
+                );
+                for (lower, count) in bins {
+                    let _ = writeln!(
+                        html,
+                        "<tr><td>[{:.4}, {:.4})</td><td>{count}</td><td style=\"width: 200px; text-align: left\"><div class=\"bar\" style=\"width: {}%\"></div></td></tr>",
+                        lower,
+                        lower + width,
+                        scale(*count, max, 100)
+                    );
+                }
+                let _ = writeln!(html, "</table>");
+            }
+        }
+
+        let _ = writeln!(html, "</body>\n</html>");
+        html
+    }
+}
+
+/// Profiles the DataFrame and writes the report to `path`.
+///
+/// The format is chosen from the file extension (see [`ReportFormat::from_path`]).
+pub fn export_report(df: &DataFrame, title: &str, path: &Path) -> Result<(), String> {
+    let report = ProfileReport::new(df, title).map_err(|e| format!("Profiling error: {}", e))?;
+    let contents = report.render(ReportFormat::from_path(path));
+    fs::write(path, contents).map_err(|e| format!("Error writing {}: {}", path.display(), e))
+}
+
+/// Renders a (small) DataFrame as a Markdown table.
+fn dataframe_to_markdown(df: &DataFrame) -> String {
+    let mut md = String::new();
+    let names: Vec<&str> = df
+        .get_column_names()
+        .into_iter()
+        .map(|n| n.as_str())
+        .collect();
+
+    let _ = writeln!(md, "| {} |", names.join(" | "));
+    let _ = writeln!(md, "|{}", "---|".repeat(names.len()));
+    for row in 0..df.height() {
+        let cells: Vec<String> = df
+            .get_columns()
+            .iter()
+            .map(|column| escape_markdown(&cell(column, row)))
+            .collect();
+        let _ = writeln!(md, "| {} |", cells.join(" | "));
+    }
+
+    md
+}
+
+/// Renders a (small) DataFrame as an HTML table.
+fn dataframe_to_html(df: &DataFrame) -> String {
+    let mut html = String::from("<table>\n<tr>");
+    for name in df.get_column_names() {
+        let _ = write!(html, "<th>{}</th>", escape_html(name));
+    }
+    html.push_str("</tr>\n");
+
+    for row in 0..df.height() {
+        html.push_str("<tr>");
+        for column in df.get_columns() {
+            let _ = write!(html, "<td>{}</td>", escape_html(&cell(column, row)));
+        }
+        html.push_str("</tr>\n");
+    }
+
+    html.push_str("</table>\n");
+    html
+}
+
+/// Formats a single cell of a column.
+fn cell(column: &Column, row: usize) -> String {
+    column
+        .get(row)
+        .map(|value| format_any_value(&value))
+        .unwrap_or_default()
+}
+
+/// Scales `count` to the range `0..=width` relative to `max`.
+fn scale(count: usize, max: usize, width: usize) -> usize {
+    match max {
+        0 => 0,
+        max => count * width / max,
+    }
+}
+
+fn percentage(part: usize, total: usize) -> f64 {
+    match total {
+        0 => 0.0,
+        total => 100.0 * part as f64 / total as f64,
+    }
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Escapes the characters of `text` that have a meaning in HTML.
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_profile_report() -> PolarsResult<()> {
+        let df = df![
+            "value" => [Some(1.0), Some(2.0), None, Some(2.0)],
+            "kind" => ["a", "b", "<b>", "b"],
+        ]?;
+
+        let report = ProfileReport::new(&df, "test")?;
+        assert_eq!(report.rows, 4);
+        assert_eq!(report.columns[0].nulls, 1);
+        assert_eq!(report.columns[1].top_values[0], ("b".to_string(), 2));
+        assert!(report.columns[0].histogram.is_some());
+        assert!(report.columns[1].histogram.is_none());
+
+        let md = report.to_markdown();
+        assert!(md.contains("## kind (str)"));
+        assert!(md.contains("| b | 2 |"));
+
+        // Values are escaped in HTML.
+        let html = report.to_html();
+        assert!(html.contains("&lt;b&gt;"));
+        assert!(!html.contains("<td><b></td>"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_report_format_from_path() {
+        let format = |path: &str| ReportFormat::from_path(&PathBuf::from(path));
+        assert_eq!(format("report.html"), ReportFormat::Html);
+        assert_eq!(format("report.HTM"), ReportFormat::Html);
+        assert_eq!(format("report.md"), ReportFormat::Markdown);
+        assert_eq!(format("report"), ReportFormat::Markdown);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{
+    DataFilters, DataFrameContainer, busy_indicator, execute_statements, get_extension,
+    split_last_statement, sql_context,
+};
+
+use egui::{Context, Ui};
+use polars::prelude::*;
+use tokio::{
+    runtime::Handle,
+    sync::oneshot::{self, Receiver, error::TryRecvError},
+};
+
+/// Query counting the rows of the last statement of a script, after the others.
+pub fn count_query(sql: &str) -> String {
+    let (preamble, last) = split_last_statement(sql);
+    let last = last.unwrap_or_default();
+    format!("{preamble}SELECT COUNT(*) AS count FROM ({last}) AS counted;")
+}
+
+/// Counts the rows of the query of `filters`, without materializing its result.
+///
+/// The Parquet files are scanned lazily: only the columns the query needs are read,
+/// and a query without filter is counted from the row counts of the file footers.
+/// Other files are loaded, then counted with [`count_query`].
+pub async fn count_rows(filters: DataFilters) -> Result<u64, String> {
+    let (Some(filename), Some(query)) = (&filters.filename, &filters.query) else {
+        return Err("No query provided".to_string());
+    };
+    let filename = shellexpand::full(filename).map_err(|err| err.to_string())?;
+    let parquet = |path: &str| get_extension(path).as_deref() == Some("parquet");
+    if !parquet(&filename) || !filters.tables.iter().all(|path| parquet(path)) {
+        return count_loaded(filters).await;
+    }
+
+    let scan = |path: &str| {
+        LazyFrame::scan_parquet(path, filters.engine.scan_args())
+            .map_err(|e| format!("Error scanning {path}: {e}"))
+    };
+    let mut ctx = sql_context();
+    ctx.register(&filters.table_name, scan(&filename)?);
+    for (path, name) in filters.tables.iter().zip(filters.table_names()) {
+        ctx.register(&name, scan(path)?);
+    }
+    for view in &filters.views {
+        ctx.register(&view.name, view.df.as_ref().clone().lazy());
+    }
+
+    let counted = execute_statements(&mut ctx, query, filters.engine.functions)?
+        .select([len().alias("count")])
+        .collect()
+        .map_err(|e| format!("DataFrame error: {}", e))?;
+    extract_count(&counted)
+}
+
+/// Counts the rows of the query of `filters` once its files are loaded.
+async fn count_loaded(filters: DataFilters) -> Result<u64, String> {
+    let query = filters.query.as_deref().map(count_query);
+    let filters = DataFilters { query, ..filters };
+    let data = DataFrameContainer::load_data_with_sql(filters).await?;
+    extract_count(&data.df)
+}
+
+/// The count in the `count` column of a counting query.
+fn extract_count(df: &DataFrame) -> Result<u64, String> {
+    let count = df.column("count").and_then(|count| count.get(0));
+    match count.map_err(|e| e.to_string())? {
+        AnyValue::Null => Ok(0),
+        value => value.extract::<u64>().ok_or("Invalid count".to_string()),
+    }
+}
+
+/// Number of rows of a query, counted in the background without materializing its result.
+#[derive(Default)]
+pub struct RowCountPreview {
+    /// Query counted, shown or being counted.
+    query: Option<String>,
+    pending: Option<Receiver<Result<u64, String>>>,
+    result: Option<Result<u64, String>>,
+}
+
+impl RowCountPreview {
+    /// Counts the rows of the query of `filters`; the pipeline of the result is not applied.
+    pub fn start(&mut self, filters: &DataFilters, handle: &Handle, ctx: &Context) {
+        let Some(query) = filters.query.clone() else {
+            return;
+        };
+        let filters = DataFilters {
+            sort: None,
+            then_by: Vec::new(),
+            transforms: Vec::new(),
+            ..filters.clone()
+        };
+        let (tx, rx) = oneshot::channel();
+        let ctx = ctx.clone();
+
+        handle.spawn(async move {
+            let result = count_rows(filters).await;
+            let _ = tx.send(result);
+            ctx.request_repaint();
+        });
+
+        self.query = Some(query);
+        self.pending = Some(rx);
+        self.result = None;
+    }
+
+    /// Shows the count of `query`, if it was counted.
+    pub fn render(&mut self, ui: &mut Ui, query: Option<&str>) {
+        self.check_pending();
+        if self.query.as_deref() != query {
+            return;
+        }
+
+        match &self.result {
+            None => {
+                busy_indicator(ui);
+                ui.label("Counting…");
+            }
+            Some(Ok(count)) => {
+                ui.label(format!("{count} rows"));
+            }
+            Some(Err(message)) => {
+                ui.colored_label(ui.visuals().error_fg_color, "Count failed")
+                    .on_hover_text(message);
+            }
+        }
+    }
+
+    /// Stores the count once done.
+    fn check_pending(&mut self) {
+        let Some(mut pending) = self.pending.take() else {
+            return;
+        };
+
+        match pending.try_recv() {
+            Ok(result) => self.result = Some(result),
+            Err(TryRecvError::Empty) => self.pending = Some(pending), // Still running.
+            Err(TryRecvError::Closed) => {
+                self.result = Some(Err("Row count was interrupted.".to_string()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_query() {
+        assert_eq!(
+            count_query("SELECT * FROM AllData WHERE qty > 1;"),
+            "SELECT COUNT(*) AS count FROM (SELECT * FROM AllData WHERE qty > 1) AS counted;"
+        );
+        assert_eq!(
+            count_query("CREATE VIEW v AS SELECT 1 AS a;\nSELECT * FROM v"),
+            "CREATE VIEW v AS SELECT 1 AS a;\nSELECT COUNT(*) AS count FROM (SELECT * FROM v) AS counted;"
+        );
+    }
+
+    #[test]
+    fn test_count_rows() -> Result<(), String> {
+        let path = std::env::temp_dir().join(format!(
+            "polars_view_test_count_{}.parquet",
+            std::process::id()
+        ));
+        let mut df = df!["qty" => (0..100).collect::<Vec<i64>>()].map_err(|e| e.to_string())?;
+        let file = std::fs::File::create(&path).map_err(|e| e.to_string())?;
+        ParquetWriter::new(file)
+            .finish(&mut df)
+            .map_err(|e| e.to_string())?;
+
+        let filename = path.to_string_lossy().to_string();
+        let count = |query: &str| {
+            let filters = DataFilters {
+                query: Some(query.to_string()),
+                ..DataFilters::new(&filename)
+            };
+            tokio::runtime::Runtime::new()
+                .map_err(|e| e.to_string())?
+                .block_on(count_rows(filters))
+        };
+        let counts = (
+            count("SELECT * FROM AllData"),
+            count("SELECT * FROM AllData WHERE qty >= 90"),
+        );
+        let _ = std::fs::remove_file(&path);
+        assert_eq!((counts.0?, counts.1?), (100, 10));
+        Ok(())
+    }
+}
@@ -0,0 +1,393 @@
+use crate::{Popover, busy_indicator, format_any_value};
+
+use egui::{Color32, Context, RichText, ScrollArea, TextStyle, Window};
+use egui_extras::{Column, TableBuilder};
+use polars::prelude::*;
+use std::path::Path;
+use tokio::{
+    runtime::Handle,
+    sync::oneshot::{self, Receiver, error::TryRecvError},
+};
+
+/// Name of the column holding the kind of change of each row.
+pub const ROW_CHANGE_COLUMN: &str = "change";
+
+/// Suffix of the columns holding the values of the new file.
+pub const NEW_VALUE_SUFFIX: &str = " (new)";
+
+/// Kind of change of a row between two files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowChange {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl RowChange {
+    pub const ALL: [RowChange; 3] = [RowChange::Added, RowChange::Removed, RowChange::Changed];
+
+    /// Label stored in the [`ROW_CHANGE_COLUMN`] column.
+    pub fn label(&self) -> &'static str {
+        match self {
+            RowChange::Added => "added",
+            RowChange::Removed => "removed",
+            RowChange::Changed => "changed",
+        }
+    }
+
+    /// Parses a label of the [`ROW_CHANGE_COLUMN`] column.
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|change| change.label() == label)
+    }
+
+    /// Color of the rows with this change.
+    pub fn color(&self) -> Color32 {
+        match self {
+            RowChange::Added => Color32::from_rgb(80, 200, 120),
+            RowChange::Removed => Color32::from_rgb(230, 80, 80),
+            RowChange::Changed => Color32::from_rgb(230, 180, 60),
+        }
+    }
+}
+
+/// Computes the rows added, removed or changed from `old` to `new`, matched by the `keys` columns.
+///
+/// The result starts with the [`ROW_CHANGE_COLUMN`] and the keys. Each other column of `old`
+/// follows with its old value, and, if `new` also has it, with its new value suffixed by
+/// [`NEW_VALUE_SUFFIX`]. A row is changed when any column present in both files differs.
+/// Unchanged rows are omitted.
+pub fn diff_rows(old: &DataFrame, new: &DataFrame, keys: &[String]) -> PolarsResult<DataFrame> {
+    polars_ensure!(!keys.is_empty(), InvalidOperation: "select at least one key column");
+    for key in keys {
+        polars_ensure!(
+            old.get_column_index(key).is_some() && new.get_column_index(key).is_some(),
+            ColumnNotFound: "key column `{}` must exist in both files", key
+        );
+    }
+
+    // Markers telling on which side a key was found after the full join.
+    const OLD_MARKER: &str = "__old_row__";
+    const NEW_MARKER: &str = "__new_row__";
+
+    let common: Vec<&str> = old
+        .get_column_names_str()
+        .into_iter()
+        .filter(|name| !keys.iter().any(|key| key == name))
+        .filter(|name| new.get_column_index(name).is_some())
+        .collect();
+
+    let changed = common
+        .iter()
+        .map(|name| col(*name).neq_missing(col(format!("{name}{NEW_VALUE_SUFFIX}"))))
+        .reduce(|acc, expr| acc.or(expr))
+        .unwrap_or(lit(false));
+
+    let change = when(col(OLD_MARKER).is_null())
+        .then(lit(RowChange::Added.label()))
+        .when(col(NEW_MARKER).is_null())
+        .then(lit(RowChange::Removed.label()))
+        .when(changed)
+        .then(lit(RowChange::Changed.label()))
+        .otherwise(lit(NULL).cast(DataType::String))
+        .alias(ROW_CHANGE_COLUMN);
+
+    let on: Vec<Expr> = keys.iter().map(|key| col(key.as_str())).collect();
+    let args = JoinArgs::new(JoinType::Full)
+        .with_coalesce(JoinCoalesce::CoalesceColumns)
+        .with_suffix(Some(NEW_VALUE_SUFFIX.into()));
+
+    let diff = old
+        .clone()
+        .lazy()
+        .with_column(lit(true).alias(OLD_MARKER))
+        .join(
+            new.clone().lazy().with_column(lit(true).alias(NEW_MARKER)),
+            on.clone(),
+            on,
+            args,
+        )
+        .with_column(change)
+        .filter(col(ROW_CHANGE_COLUMN).is_not_null())
+        .drop([OLD_MARKER, NEW_MARKER])
+        .sort(keys, SortMultipleOptions::default())
+        .collect()?;
+
+    // The change first, then the keys, then the other columns in join order.
+    let mut order: Vec<&str> = vec![ROW_CHANGE_COLUMN];
+    order.extend(keys.iter().map(String::as_str));
+    for name in diff.get_column_names_str() {
+        if !order.contains(&name) {
+            order.push(name);
+        }
+    }
+    diff.select(order)
+}
+
+/// Counts the rows of a [`diff_rows`] result per kind of change, in [`RowChange::ALL`] order.
+pub fn count_row_changes(diff: &DataFrame) -> PolarsResult<[usize; 3]> {
+    let labels = diff.column(ROW_CHANGE_COLUMN)?.str()?;
+    Ok(RowChange::ALL.map(|change| {
+        labels
+            .into_iter()
+            .filter(|label| *label == Some(change.label()))
+            .count()
+    }))
+}
+
+/// Reads a whole Parquet file.
+fn read_parquet(path: &Path) -> PolarsResult<DataFrame> {
+    LazyFrame::scan_parquet(path, ScanArgsParquet::default())?.collect()
+}
+
+/// Popover comparing the rows of two Parquet files by key columns.
+pub struct RowDiffWindow {
+    /// Paths of the old and new files.
+    paths: [String; 2],
+    /// Columns present in both files, which can be used as keys.
+    common_columns: Result<Vec<String>, String>,
+    /// Selection of each common column as a key.
+    selected: Vec<bool>,
+    handle: Handle,
+    pending: Option<Receiver<Result<DataFrame, String>>>,
+    result: Option<Result<DataFrame, String>>,
+}
+
+impl RowDiffWindow {
+    /// Reads the schemas of both files; the rows are compared once the keys are selected.
+    pub fn new(old: &Path, new: &Path, handle: Handle) -> Self {
+        let schema = |path: &Path| {
+            LazyFrame::scan_parquet(path, ScanArgsParquet::default())
+                .and_then(|mut lazyframe| lazyframe.collect_schema())
+                .map_err(|e| format!("Error reading {}: {}", path.display(), e))
+        };
+
+        let common_columns = schema(old).and_then(|old_schema| {
+            let new_schema = schema(new)?;
+            Ok(old_schema
+                .iter_names()
+                .filter(|name| new_schema.contains(name))
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>())
+        });
+
+        // The first common column is the most likely key.
+        let selected = match &common_columns {
+            Ok(columns) => (0..columns.len()).map(|idx| idx == 0).collect(),
+            Err(_) => Vec::new(),
+        };
+
+        RowDiffWindow {
+            paths: [old.display().to_string(), new.display().to_string()],
+            common_columns,
+            selected,
+            handle,
+            pending: None,
+            result: None,
+        }
+    }
+
+    /// Reads both files and compares their rows in the background.
+    fn start(&mut self, keys: Vec<String>, ctx: &Context) {
+        let (tx, rx) = oneshot::channel();
+        let paths = self.paths.clone();
+        let ctx = ctx.clone();
+
+        self.handle.spawn_blocking(move || {
+            let result = read_parquet(Path::new(&paths[0]))
+                .and_then(|old| {
+                    let new = read_parquet(Path::new(&paths[1]))?;
+                    diff_rows(&old, &new, &keys)
+                })
+                .map_err(|e| format!("Row diff error: {}", e));
+            let _ = tx.send(result);
+            ctx.request_repaint();
+        });
+
+        self.pending = Some(rx);
+        self.result = None;
+    }
+
+    /// Stores the result of a finished comparison.
+    fn check_pending(&mut self) {
+        let Some(mut pending) = self.pending.take() else {
+            return;
+        };
+
+        match pending.try_recv() {
+            Ok(result) => self.result = Some(result),
+            Err(TryRecvError::Empty) => self.pending = Some(pending), // Still running.
+            Err(TryRecvError::Closed) => {
+                self.result = Some(Err("Row diff was interrupted.".to_string()))
+            }
+        }
+    }
+}
+
+impl Popover for RowDiffWindow {
+    /// Shows the key selection and the changed rows, colored by kind of change.
+    fn show(&mut self, ctx: &Context) -> bool {
+        self.check_pending();
+
+        let mut open = true;
+        let mut keys_to_compare = None;
+
+        Window::new("Row diff")
+            .collapsible(false)
+            .open(&mut open)
+            .default_size([800.0, 500.0])
+            .show(ctx, |ui| {
+                ui.label(format!("Old: {}", self.paths[0]));
+                ui.label(format!("New: {}", self.paths[1]));
+
+                let columns = match &self.common_columns {
+                    Ok(columns) => columns,
+                    Err(error) => {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                        return;
+                    }
+                };
+
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Key columns:");
+                    for (column, selected) in columns.iter().zip(self.selected.iter_mut()) {
+                        ui.checkbox(selected, column);
+                    }
+                });
+
+                let keys: Vec<String> = columns
+                    .iter()
+                    .zip(&self.selected)
+                    .filter(|(_, selected)| **selected)
+                    .map(|(column, _)| column.clone())
+                    .collect();
+
+                let compare = egui::Button::new("Compare");
+                if ui
+                    .add_enabled(!keys.is_empty() && self.pending.is_none(), compare)
+                    .clicked()
+                {
+                    keys_to_compare = Some(keys);
+                }
+
+                ui.separator();
+
+                if self.pending.is_some() {
+                    ui.horizontal(|ui| {
+                        busy_indicator(ui);
+                        ui.label("Comparing rows…");
+                    });
+                    return;
+                }
+
+                let diff = match &self.result {
+                    None => return,
+                    Some(Err(error)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                        return;
+                    }
+                    Some(Ok(diff)) => diff,
+                };
+
+                if let Ok(counts) = count_row_changes(diff) {
+                    ui.horizontal(|ui| {
+                        for (change, count) in RowChange::ALL.iter().zip(counts) {
+                            ui.colored_label(change.color(), format!("{count} {}", change.label()));
+                        }
+                    });
+                }
+
+                let change_column = diff.column(ROW_CHANGE_COLUMN).ok();
+                let text_height = TextStyle::Body.resolve(ui.style()).size;
+                let row_height = text_height + ui.spacing().item_spacing.y;
+
+                ScrollArea::horizontal().show(ui, |ui| {
+                    TableBuilder::new(ui)
+                        .striped(true)
+                        .columns(
+                            Column::auto().at_least(60.0).resizable(true).clip(true),
+                            diff.width(),
+                        )
+                        .header(row_height, |mut header| {
+                            for name in diff.get_column_names() {
+                                header.col(|ui| {
+                                    ui.strong(name.as_str());
+                                });
+                            }
+                        })
+                        .body(|body| {
+                            body.rows(row_height, diff.height(), |mut row| {
+                                let index = row.index();
+                                let color = change_column
+                                    .and_then(|column| column.get(index).ok())
+                                    .and_then(|value| RowChange::from_label(value.get_str()?))
+                                    .map(|change| change.color());
+
+                                for column in diff.get_columns() {
+                                    let value = column.get(index).unwrap_or(AnyValue::Null);
+                                    let text = RichText::new(format_any_value(&value));
+                                    row.col(|ui| {
+                                        ui.label(match color {
+                                            Some(color) => text.color(color),
+                                            None => text,
+                                        });
+                                    });
+                                }
+                            });
+                        });
+                });
+            });
+
+        if let Some(keys) = keys_to_compare {
+            self.start(keys, ctx);
+        }
+
+        open
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_rows() -> PolarsResult<()> {
+        let old = df![
+            "id" => [1i64, 2, 3, 4],
+            "name" => ["a", "b", "c", "d"],
+            "score" => [Some(1.0), Some(2.0), None, Some(4.0)],
+        ]?;
+        let new = df![
+            "id" => [2i64, 3, 4, 5],
+            "name" => ["b", "c", "D", "e"],
+            "score" => [Some(2.0), Some(3.0), Some(4.0), Some(5.0)],
+        ]?;
+
+        let diff = diff_rows(&old, &new, &["id".to_string()])?;
+
+        assert_eq!(
+            diff.get_column_names_str(),
+            ["change", "id", "name", "score", "name (new)", "score (new)"]
+        );
+        assert_eq!(
+            diff.column("id")?.i64()?.to_vec(),
+            [Some(1), Some(3), Some(4), Some(5)]
+        );
+        assert_eq!(
+            diff.column(ROW_CHANGE_COLUMN)?
+                .str()?
+                .into_iter()
+                .collect::<Vec<_>>(),
+            [
+                Some("removed"),
+                Some("changed"),
+                Some("changed"),
+                Some("added")
+            ]
+        );
+        assert_eq!(count_row_changes(&diff)?, [1, 1, 2]);
+
+        assert!(diff_rows(&old, &new, &[]).is_err());
+        assert!(diff_rows(&old, &new, &["missing".to_string()]).is_err());
+        Ok(())
+    }
+}
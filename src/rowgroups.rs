@@ -0,0 +1,470 @@
+use crate::{DataFilters, busy_indicator, save_file_dialog};
+
+use egui::{Grid, Ui};
+use parquet::{basic::Compression, file::metadata::ParquetMetaData};
+use polars::prelude::*;
+use std::{
+    cmp::Reverse,
+    fs::File,
+    path::{Path, PathBuf},
+};
+use tokio::{
+    runtime::Runtime,
+    sync::oneshot::{self, Receiver, error::TryRecvError},
+};
+
+/// Target compressed size of a row group for full scans.
+const SCAN_ROW_GROUP_BYTES: u64 = 128 * 1024 * 1024;
+
+/// Target compressed size of a row group for filtered queries.
+///
+/// Smaller row groups have tighter min/max statistics, so more of them can be skipped.
+const FILTER_ROW_GROUP_BYTES: u64 = 32 * 1024 * 1024;
+
+/// Minimum compressed size of a column chunk for efficient reads of a few columns.
+const MIN_COLUMN_CHUNK_BYTES: u64 = 1024 * 1024;
+
+/// Suggested row counts are rounded to a multiple of this value.
+const ROW_GROUP_ROWS_STEP: u64 = 1024;
+
+/// Sizes of the row groups of a Parquet file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowGroupSummary {
+    pub row_groups: usize,
+    pub columns: usize,
+    pub rows: u64,
+    pub compressed_bytes: u64,
+    pub uncompressed_bytes: u64,
+    pub min_rows: u64,
+    pub max_rows: u64,
+    /// Average compressed size of a column chunk.
+    pub avg_column_chunk_bytes: u64,
+    /// Name and total compressed size of the largest column.
+    pub largest_column: Option<(String, u64)>,
+}
+
+impl RowGroupSummary {
+    /// Summarizes the row groups and column chunks of the Parquet metadata.
+    pub fn new(metadata: &ParquetMetaData) -> Self {
+        let row_groups = metadata.row_groups();
+        let columns = metadata.file_metadata().schema_descr().num_columns();
+
+        let rows: Vec<u64> = row_groups.iter().map(|rg| rg.num_rows() as u64).collect();
+        let compressed_bytes: u64 = row_groups
+            .iter()
+            .map(|rg| rg.compressed_size() as u64)
+            .sum();
+        let chunks = (row_groups.len() * columns).max(1) as u64;
+
+        let largest_column = column_sizes(metadata)
+            .into_iter()
+            .next()
+            .map(|size| (size.name, size.compressed));
+
+        RowGroupSummary {
+            row_groups: row_groups.len(),
+            columns,
+            rows: rows.iter().sum(),
+            compressed_bytes,
+            uncompressed_bytes: row_groups
+                .iter()
+                .map(|rg| rg.total_byte_size() as u64)
+                .sum(),
+            min_rows: rows.iter().copied().min().unwrap_or(0),
+            max_rows: rows.iter().copied().max().unwrap_or(0),
+            avg_column_chunk_bytes: compressed_bytes / chunks,
+            largest_column,
+        }
+    }
+}
+
+/// Compressed and uncompressed size of a column, summed over all row groups.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnSize {
+    pub name: String,
+    pub compressed: u64,
+    pub uncompressed: u64,
+}
+
+impl ColumnSize {
+    /// Compression ratio (uncompressed / compressed size).
+    pub fn ratio(&self) -> f64 {
+        self.uncompressed as f64 / self.compressed.max(1) as f64
+    }
+}
+
+/// Returns the size of each column of the file, largest (compressed) first.
+pub fn column_sizes(metadata: &ParquetMetaData) -> Vec<ColumnSize> {
+    let mut sizes: Vec<ColumnSize> = metadata
+        .file_metadata()
+        .schema_descr()
+        .columns()
+        .iter()
+        .map(|column| ColumnSize {
+            name: column.path().string(),
+            compressed: 0,
+            uncompressed: 0,
+        })
+        .collect();
+
+    for row_group in metadata.row_groups() {
+        for (size, chunk) in sizes.iter_mut().zip(row_group.columns()) {
+            size.compressed += chunk.compressed_size() as u64;
+            size.uncompressed += chunk.uncompressed_size() as u64;
+        }
+    }
+
+    sizes.sort_by_key(|size| Reverse(size.compressed));
+    sizes
+}
+
+/// How the data is queried, inferred from the SQL query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryPattern {
+    /// The query filters rows (`WHERE`), so row groups may be skipped by their statistics.
+    pub filtered: bool,
+    /// The query reads all columns (`SELECT *`).
+    pub all_columns: bool,
+}
+
+impl QueryPattern {
+    /// Infers the query pattern from the text of a SQL query.
+    pub fn from_sql(query: &str) -> Self {
+        let query = query.to_uppercase();
+        let words: Vec<&str> = query.split_whitespace().collect();
+
+        QueryPattern {
+            filtered: words.contains(&"WHERE"),
+            all_columns: words.windows(2).any(|pair| pair == ["SELECT", "*"]),
+        }
+    }
+}
+
+/// Suggested number of rows per row group, with an explanation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowGroupAdvice {
+    pub rows_per_group: u64,
+    pub reason: String,
+}
+
+/// Suggests a row group size for the file and the observed query pattern.
+///
+/// Full scans favor large row groups (about 128 MiB), filtered queries smaller ones
+/// (about 32 MiB) whose statistics allow skipping more data. Queries reading only some
+/// columns also need column chunks of at least 1 MiB to avoid many small reads.
+pub fn advise_row_group_size(summary: &RowGroupSummary, pattern: QueryPattern) -> RowGroupAdvice {
+    let bytes_per_row = (summary.compressed_bytes as f64 / summary.rows.max(1) as f64).max(1.0);
+    let bytes_per_row_per_column = bytes_per_row / summary.columns.max(1) as f64;
+
+    let (target_bytes, mut reason) = match pattern.filtered {
+        true => (
+            FILTER_ROW_GROUP_BYTES,
+            "Filtered queries: ~32 MiB row groups let the min/max statistics skip more data."
+                .to_string(),
+        ),
+        false => (
+            SCAN_ROW_GROUP_BYTES,
+            "Full scans: ~128 MiB row groups reduce the per-group overhead.".to_string(),
+        ),
+    };
+
+    let mut rows = target_bytes as f64 / bytes_per_row;
+
+    // Column projection reads chunks of single columns: keep them reasonably large.
+    let min_rows = MIN_COLUMN_CHUNK_BYTES as f64 / bytes_per_row_per_column;
+    if !pattern.all_columns && rows < min_rows {
+        rows = min_rows;
+        reason.push_str(" Raised so that column chunks are at least 1 MiB.");
+    }
+
+    // A single row group when the whole file is smaller than the target.
+    let rows = (rows as u64).min(summary.rows.max(1));
+    let rows_per_group = rows.div_ceil(ROW_GROUP_ROWS_STEP) * ROW_GROUP_ROWS_STEP;
+
+    RowGroupAdvice {
+        rows_per_group,
+        reason,
+    }
+}
+
+/// Converts the Parquet codec to the Polars setting (with the default level).
+fn polars_compression(compression: Compression) -> ParquetCompression {
+    match compression {
+        Compression::UNCOMPRESSED => ParquetCompression::Uncompressed,
+        Compression::SNAPPY => ParquetCompression::Snappy,
+        Compression::GZIP(_) => ParquetCompression::Gzip(None),
+        Compression::LZO => ParquetCompression::Lzo,
+        Compression::BROTLI(_) => ParquetCompression::Brotli(None),
+        Compression::LZ4 | Compression::LZ4_RAW => ParquetCompression::Lz4Raw,
+        Compression::ZSTD(_) => ParquetCompression::Zstd(None),
+    }
+}
+
+/// Rewrites a Parquet file with (about) the given number of rows per row group.
+///
+/// The codec of the first column chunk is kept (with its default level).
+pub fn rewrite_parquet(
+    input: &str,
+    output: &Path,
+    rows_per_group: usize,
+    compression: Compression,
+) -> Result<(), String> {
+    let file = File::open(input).map_err(|e| format!("Error opening file: {}", e))?;
+    let mut df = ParquetReader::new(file)
+        .finish()
+        .map_err(|e| format!("Error reading parquet: {}", e))?;
+
+    let file = File::create(output).map_err(|e| format!("Error creating file: {}", e))?;
+    ParquetWriter::new(file)
+        .with_compression(polars_compression(compression))
+        .with_row_group_size(Some(rows_per_group))
+        .finish(&mut df)
+        .map_err(|e| format!("Error writing parquet: {}", e))?;
+
+    Ok(())
+}
+
+/// Side-panel pane analyzing the row groups of a Parquet file.
+#[derive(Default)]
+pub struct RowGroupAdvisor {
+    /// Channel for receiving the result of a running rewrite.
+    pending: Option<Receiver<Result<PathBuf, String>>>,
+    /// Outcome of the last rewrite: the new file or an error.
+    rewritten: Option<Result<PathBuf, String>>,
+}
+
+impl RowGroupAdvisor {
+    /// Renders the row group summary and the suggested size for the current query.
+    ///
+    /// The rewrite asks for a destination and runs in the background on the given runtime.
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        metadata: &ParquetMetaData,
+        filters: &DataFilters,
+        runtime: &Runtime,
+    ) {
+        self.check_pending();
+
+        let summary = RowGroupSummary::new(metadata);
+        let pattern = QueryPattern::from_sql(filters.query.as_deref().unwrap_or_default());
+        let advice = advise_row_group_size(&summary, pattern);
+
+        Grid::new("row_groups_grid")
+            .num_columns(2)
+            .spacing([10.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                let mb = |bytes: u64| format!("{:.2} MB", bytes as f64 / 1e6);
+                let mut rows = vec![
+                    ("Row groups", summary.row_groups.to_string()),
+                    (
+                        "Rows per group",
+                        format!("{} - {}", summary.min_rows, summary.max_rows),
+                    ),
+                    ("Compressed", mb(summary.compressed_bytes)),
+                    ("Uncompressed", mb(summary.uncompressed_bytes)),
+                    ("Avg column chunk", mb(summary.avg_column_chunk_bytes)),
+                ];
+                if let Some((name, bytes)) = &summary.largest_column {
+                    rows.push(("Largest column", format!("{name} ({})", mb(*bytes))));
+                }
+                rows.push(("Suggested rows", advice.rows_per_group.to_string()));
+
+                for (label, value) in rows {
+                    ui.label(label);
+                    ui.label(value);
+                    ui.end_row();
+                }
+            });
+
+        ui.label(&advice.reason);
+
+        ui.horizontal(|ui| {
+            if self.pending.is_some() {
+                busy_indicator(ui);
+                ui.label("Rewriting…");
+            } else if ui
+                .button("Rewrite file…")
+                .on_hover_text("Write a copy of the file with the suggested row group size")
+                .clicked()
+            {
+                self.rewrite(
+                    metadata,
+                    filters,
+                    advice.rows_per_group as usize,
+                    ui,
+                    runtime,
+                );
+            }
+        });
+
+        match &self.rewritten {
+            Some(Ok(path)) => {
+                ui.label(format!("Written to {}", path.display()));
+            }
+            Some(Err(error)) => {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+            None => {}
+        }
+    }
+
+    /// Asks for a destination and starts the rewrite in the background.
+    fn rewrite(
+        &mut self,
+        metadata: &ParquetMetaData,
+        filters: &DataFilters,
+        rows_per_group: usize,
+        ui: &Ui,
+        runtime: &Runtime,
+    ) {
+        let Some(input) = filters.filename.clone() else {
+            return;
+        };
+
+        let stem = PathBuf::from(&input)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "data".to_string());
+        let file_name = format!("{stem}_tuned.parquet");
+        let dialog = save_file_dialog(&file_name, &[("Parquet", &["parquet"])]);
+        let Ok(output) = runtime.block_on(dialog) else {
+            return;
+        };
+
+        let compression = metadata
+            .row_groups()
+            .first()
+            .and_then(|rg| rg.columns().first())
+            .map(|chunk| chunk.compression())
+            .unwrap_or(Compression::ZSTD(Default::default()));
+
+        let (tx, rx) = oneshot::channel();
+        let ctx = ui.ctx().clone();
+        runtime.spawn_blocking(move || {
+            let result = rewrite_parquet(&input, &output, rows_per_group, compression);
+            let _ = tx.send(result.map(|_| output));
+            ctx.request_repaint();
+        });
+
+        self.pending = Some(rx);
+        self.rewritten = None;
+    }
+
+    /// Stores the outcome of a finished rewrite.
+    fn check_pending(&mut self) {
+        let Some(mut pending) = self.pending.take() else {
+            return;
+        };
+
+        match pending.try_recv() {
+            Ok(result) => self.rewritten = Some(result),
+            Err(TryRecvError::Empty) => self.pending = Some(pending), // Still running.
+            Err(TryRecvError::Closed) => {
+                self.rewritten = Some(Err("Rewrite was interrupted.".to_string()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    fn summary(rows: u64, compressed_bytes: u64, columns: usize) -> RowGroupSummary {
+        RowGroupSummary {
+            row_groups: 1,
+            columns,
+            rows,
+            compressed_bytes,
+            uncompressed_bytes: compressed_bytes,
+            min_rows: rows,
+            max_rows: rows,
+            avg_column_chunk_bytes: compressed_bytes / columns as u64,
+            largest_column: None,
+        }
+    }
+
+    #[test]
+    fn test_query_pattern() {
+        let pattern = QueryPattern::from_sql("select * from AllData where x > 1;");
+        assert!(pattern.filtered && pattern.all_columns);
+
+        let pattern = QueryPattern::from_sql("SELECT a, b FROM AllData;");
+        assert!(!pattern.filtered && !pattern.all_columns);
+    }
+
+    #[test]
+    fn test_advise_row_group_size() {
+        // 100 M rows of 10 bytes (1 GB), 10 columns of 1 byte per row.
+        let summary = summary(100_000_000, 1_000_000_000, 10);
+
+        let scan = QueryPattern {
+            filtered: false,
+            all_columns: true,
+        };
+        let advice = advise_row_group_size(&summary, scan);
+        assert_eq!(advice.rows_per_group, 13_422_592); // 128 MiB / 10 bytes, rounded up.
+
+        let filter = QueryPattern {
+            filtered: true,
+            all_columns: true,
+        };
+        let advice = advise_row_group_size(&summary, filter);
+        assert_eq!(advice.rows_per_group, 3_355_648); // 32 MiB / 10 bytes, rounded up.
+
+        // Wide rows: 1000 columns of 1 byte per row, only some of them are read.
+        let summary = super::tests::summary(100_000_000, 100_000_000_000, 1000);
+        let projection = QueryPattern {
+            filtered: true,
+            all_columns: false,
+        };
+        let advice = advise_row_group_size(&summary, projection);
+        assert_eq!(advice.rows_per_group, 1_048_576); // 1 MiB column chunks.
+
+        // Small files are written as a single row group.
+        let advice = advise_row_group_size(&super::tests::summary(1000, 10_000, 2), scan);
+        assert_eq!(advice.rows_per_group, 1024);
+    }
+
+    #[test]
+    fn test_rewrite_parquet() -> Result<(), String> {
+        let dir = std::env::temp_dir();
+        let input = dir.join("polars_view_rowgroups_input.parquet");
+        let output = dir.join("polars_view_rowgroups_output.parquet");
+
+        let mut df =
+            df!["value" => (0..10_000).collect::<Vec<i32>>()].map_err(|e| e.to_string())?;
+        let file = File::create(&input).map_err(|e| e.to_string())?;
+        ParquetWriter::new(file)
+            .with_row_group_size(Some(1000))
+            .finish(&mut df)
+            .map_err(|e| e.to_string())?;
+
+        rewrite_parquet(&input.to_string_lossy(), &output, 4096, Compression::SNAPPY)?;
+
+        let file = File::open(&output).map_err(|e| e.to_string())?;
+        let reader = SerializedFileReader::new(file).map_err(|e| e.to_string())?;
+        let summary = RowGroupSummary::new(reader.metadata());
+        assert_eq!(summary.rows, 10_000);
+        // Polars treats the size as a target and splits the rows evenly.
+        assert!((2..=3).contains(&summary.row_groups));
+        assert!(summary.min_rows >= 1000);
+
+        let sizes = column_sizes(reader.metadata());
+        assert_eq!(sizes.len(), 1);
+        assert_eq!(sizes[0].name, "value");
+        assert_eq!(
+            summary.largest_column,
+            Some(("value".to_string(), sizes[0].compressed))
+        );
+        assert!(sizes[0].ratio() > 0.0);
+
+        let _ = std::fs::remove_file(input);
+        let _ = std::fs::remove_file(output);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,351 @@
+use crate::{Popover, get_extension};
+
+use egui::{Color32, Context, ScrollArea, Window};
+use parquet::{
+    basic::Repetition,
+    file::reader::{FileReader, SerializedFileReader},
+};
+use polars::prelude::*;
+use std::{fmt, fs::File, path::Path};
+
+/// A column of a dataset schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaField {
+    pub name: String,
+    pub dtype: String,
+    /// Whether the column may contain nulls (always true for CSV files).
+    pub nullable: bool,
+}
+
+/// Reads the schema of a Parquet or CSV file without loading its data.
+///
+/// Parquet columns carry their nullability (`OPTIONAL` repetition); the CSV schema
+/// is inferred by Polars from the first rows, trying the usual delimiters.
+pub fn read_schema(path: &Path) -> Result<Vec<SchemaField>, String> {
+    let filename = path.to_string_lossy();
+
+    match get_extension(&filename).as_deref() {
+        Some("parquet") => {
+            let file =
+                File::open(path).map_err(|e| format!("Error opening {}: {}", path.display(), e))?;
+            let reader = SerializedFileReader::new(file)
+                .map_err(|e| format!("Error creating Parquet reader: {}", e))?;
+
+            let fields = reader
+                .metadata()
+                .file_metadata()
+                .schema_descr()
+                .columns()
+                .iter()
+                .map(|column| SchemaField {
+                    name: column.path().string(),
+                    dtype: match column.logical_type() {
+                        Some(logical) => format!("{} ({:?})", column.physical_type(), logical),
+                        None => column.physical_type().to_string(),
+                    },
+                    nullable: column.self_type().get_basic_info().repetition()
+                        != Repetition::REQUIRED,
+                })
+                .collect();
+
+            Ok(fields)
+        }
+        Some("csv") => {
+            for delimiter in [b',', b';', b'|', b'\t'] {
+                let schema = LazyCsvReader::new(&*filename)
+                    .with_has_header(true)
+                    .with_separator(delimiter)
+                    .with_infer_schema_length(Some(200))
+                    .finish()
+                    .and_then(|mut lazyframe| lazyframe.collect_schema());
+
+                // A single column means the delimiter is probably wrong.
+                if let Ok(schema) = schema {
+                    if schema.len() > 1 {
+                        let fields = schema
+                            .iter()
+                            .map(|(name, dtype)| SchemaField {
+                                name: name.to_string(),
+                                dtype: dtype.to_string(),
+                                nullable: true,
+                            })
+                            .collect();
+                        return Ok(fields);
+                    }
+                }
+            }
+            Err(format!(
+                "Could not infer the CSV schema of {}",
+                path.display()
+            ))
+        }
+        _ => Err(format!("Unknown file type: {}", path.display())),
+    }
+}
+
+/// A structural change between two schemas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+    Added {
+        name: String,
+        dtype: String,
+    },
+    Removed {
+        name: String,
+        dtype: String,
+    },
+    Renamed {
+        from: String,
+        to: String,
+    },
+    TypeChanged {
+        name: String,
+        from: String,
+        to: String,
+    },
+    NullabilityChanged {
+        name: String,
+        nullable: bool,
+    },
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaChange::Added { name, dtype } => write!(f, "+ added column `{name}` ({dtype})"),
+            SchemaChange::Removed { name, dtype } => {
+                write!(f, "- removed column `{name}` ({dtype})")
+            }
+            SchemaChange::Renamed { from, to } => write!(f, "~ renamed column `{from}` to `{to}`"),
+            SchemaChange::TypeChanged { name, from, to } => {
+                write!(f, "~ column `{name}` changed type from {from} to {to}")
+            }
+            SchemaChange::NullabilityChanged { name, nullable } => match nullable {
+                true => write!(f, "~ column `{name}` became nullable"),
+                false => write!(f, "~ column `{name}` became required"),
+            },
+        }
+    }
+}
+
+/// Computes the changes from the `old` schema to the `new` schema.
+///
+/// Columns are matched by name. A removed column and an added column at the same
+/// position with the same type and nullability are reported as a rename.
+pub fn diff_schemas(old: &[SchemaField], new: &[SchemaField]) -> Vec<SchemaChange> {
+    let find = |fields: &[SchemaField], name: &str| fields.iter().position(|f| f.name == name);
+
+    let mut removed: Vec<usize> = (0..old.len())
+        .filter(|&i| find(new, &old[i].name).is_none())
+        .collect();
+    let mut added: Vec<usize> = (0..new.len())
+        .filter(|&i| find(old, &new[i].name).is_none())
+        .collect();
+
+    let mut changes = Vec::new();
+
+    // Renames: same position, same type and nullability, different name.
+    removed.retain(|&old_idx| {
+        let renamed = added.iter().position(|&new_idx| {
+            new_idx == old_idx
+                && new[new_idx].dtype == old[old_idx].dtype
+                && new[new_idx].nullable == old[old_idx].nullable
+        });
+        match renamed {
+            Some(pos) => {
+                let new_idx = added.remove(pos);
+                changes.push(SchemaChange::Renamed {
+                    from: old[old_idx].name.clone(),
+                    to: new[new_idx].name.clone(),
+                });
+                false
+            }
+            None => true,
+        }
+    });
+
+    changes.extend(removed.into_iter().map(|idx| SchemaChange::Removed {
+        name: old[idx].name.clone(),
+        dtype: old[idx].dtype.clone(),
+    }));
+    changes.extend(added.into_iter().map(|idx| SchemaChange::Added {
+        name: new[idx].name.clone(),
+        dtype: new[idx].dtype.clone(),
+    }));
+
+    // Type and nullability changes of the columns present in both schemas.
+    for old_field in old {
+        let Some(new_field) = new.iter().find(|f| f.name == old_field.name) else {
+            continue;
+        };
+        if old_field.dtype != new_field.dtype {
+            changes.push(SchemaChange::TypeChanged {
+                name: old_field.name.clone(),
+                from: old_field.dtype.clone(),
+                to: new_field.dtype.clone(),
+            });
+        }
+        if old_field.nullable != new_field.nullable {
+            changes.push(SchemaChange::NullabilityChanged {
+                name: old_field.name.clone(),
+                nullable: new_field.nullable,
+            });
+        }
+    }
+
+    changes
+}
+
+/// Reads the schemas of two files and computes the changes from the first to the second.
+pub fn diff_files(old: &Path, new: &Path) -> Result<Vec<SchemaChange>, String> {
+    Ok(diff_schemas(&read_schema(old)?, &read_schema(new)?))
+}
+
+/// Formats the changes as a changelog, one change per line.
+pub fn schema_changelog(changes: &[SchemaChange]) -> String {
+    match changes {
+        [] => "No schema changes.".to_string(),
+        changes => changes
+            .iter()
+            .map(|change| change.to_string())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+/// Popover displaying the schema changes between two files as a changelog.
+pub struct SchemaDiffWindow {
+    /// Paths of the old and new files.
+    paths: [String; 2],
+    /// Schema changes, or an error if a schema could not be read.
+    changes: Result<Vec<SchemaChange>, String>,
+}
+
+impl SchemaDiffWindow {
+    /// Computes the schema changes from `old` to `new`.
+    pub fn new(old: &Path, new: &Path) -> Self {
+        SchemaDiffWindow {
+            paths: [old.display().to_string(), new.display().to_string()],
+            changes: diff_files(old, new),
+        }
+    }
+}
+
+impl Popover for SchemaDiffWindow {
+    /// Shows the changelog: additions in green, removals in red, other changes in yellow.
+    fn show(&mut self, ctx: &Context) -> bool {
+        let mut open = true;
+
+        Window::new("Schema diff")
+            .collapsible(false)
+            .open(&mut open)
+            .default_size([600.0, 400.0])
+            .show(ctx, |ui| {
+                ui.label(format!("From: {}", self.paths[0]));
+                ui.label(format!("To: {}", self.paths[1]));
+                ui.separator();
+
+                let changes = match &self.changes {
+                    Ok(changes) => changes,
+                    Err(error) => {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                        return;
+                    }
+                };
+
+                if changes.is_empty() {
+                    ui.label(schema_changelog(changes));
+                }
+
+                ScrollArea::vertical().show(ui, |ui| {
+                    for change in changes {
+                        let color = match change {
+                            SchemaChange::Added { .. } => Color32::from_rgb(80, 200, 120),
+                            SchemaChange::Removed { .. } => Color32::from_rgb(230, 80, 80),
+                            _ => Color32::from_rgb(230, 180, 60),
+                        };
+                        ui.colored_label(color, change.to_string());
+                    }
+                });
+            });
+
+        open
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str, dtype: &str, nullable: bool) -> SchemaField {
+        SchemaField {
+            name: name.to_string(),
+            dtype: dtype.to_string(),
+            nullable,
+        }
+    }
+
+    #[test]
+    fn test_diff_schemas() {
+        let old = vec![
+            field("id", "INT64", false),
+            field("name", "BYTE_ARRAY", true),
+            field("price", "FLOAT", true),
+            field("legacy", "INT32", true),
+        ];
+        let new = vec![
+            field("id", "INT64", true),
+            field("full_name", "BYTE_ARRAY", true),
+            field("price", "DOUBLE", true),
+            field("created", "INT64", false),
+        ];
+
+        let changes = diff_schemas(&old, &new);
+        assert_eq!(
+            changes,
+            vec![
+                SchemaChange::Renamed {
+                    from: "name".to_string(),
+                    to: "full_name".to_string()
+                },
+                SchemaChange::Removed {
+                    name: "legacy".to_string(),
+                    dtype: "INT32".to_string()
+                },
+                SchemaChange::Added {
+                    name: "created".to_string(),
+                    dtype: "INT64".to_string()
+                },
+                SchemaChange::NullabilityChanged {
+                    name: "id".to_string(),
+                    nullable: true
+                },
+                SchemaChange::TypeChanged {
+                    name: "price".to_string(),
+                    from: "FLOAT".to_string(),
+                    to: "DOUBLE".to_string()
+                },
+            ]
+        );
+
+        assert_eq!(
+            schema_changelog(&diff_schemas(&old, &old)),
+            "No schema changes."
+        );
+    }
+
+    #[test]
+    fn test_read_csv_schema() -> Result<(), String> {
+        let path = std::env::temp_dir().join("polars_view_schema_diff.csv");
+        std::fs::write(&path, "id;name\n1;a\n2;b\n").map_err(|e| e.to_string())?;
+
+        let fields = read_schema(&path)?;
+        let _ = std::fs::remove_file(path);
+
+        assert_eq!(
+            fields,
+            vec![field("id", "i64", true), field("name", "str", true)]
+        );
+        Ok(())
+    }
+}
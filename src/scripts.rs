@@ -0,0 +1,118 @@
+use crate::{DataFilters, RECIPE_EXTENSION, Recipe};
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A user script of the scripts directory: SQL is the scripting language of the app, so a
+/// script is either a query or a recipe of pipeline steps (filters, computed columns…).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Script {
+    /// A `.sql` file, run as the query of the current file.
+    Query(String),
+    /// A `.recipe.json` file, appended to the pipeline.
+    Recipe(Recipe),
+}
+
+impl Script {
+    /// Reads a script file.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if is_recipe(path) {
+            return Recipe::load(path).map(Script::Recipe);
+        }
+        let sql = fs::read_to_string(path)
+            .map_err(|e| format!("Error reading script {}: {}", path.display(), e))?;
+        Ok(Script::Query(sql.trim().to_string()))
+    }
+
+    /// The filters with the script applied.
+    pub fn apply(self, filters: &DataFilters) -> DataFilters {
+        let mut filters = filters.clone();
+        match self {
+            Script::Query(sql) => filters.query = Some(sql),
+            Script::Recipe(recipe) => filters.transforms.extend(recipe.transforms),
+        }
+        filters
+    }
+}
+
+/// Directory of the user scripts, in the configuration directory of the user.
+pub fn scripts_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("polars-view").join("scripts"))
+}
+
+/// The scripts of a directory, sorted by name; a missing directory has none.
+pub fn list_scripts(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut scripts: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| is_recipe(path) || path.extension().is_some_and(|ext| ext == "sql"))
+        .collect();
+    scripts.sort();
+    scripts
+}
+
+/// Name of a script: its file name without the extension.
+pub fn script_name(path: &Path) -> String {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let recipe = format!(".{RECIPE_EXTENSION}");
+    match name.strip_suffix(&recipe) {
+        Some(stem) => stem.to_string(),
+        None => name.trim_end_matches(".sql").to_string(),
+    }
+}
+
+fn is_recipe(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| {
+        name.to_string_lossy()
+            .ends_with(&format!(".{RECIPE_EXTENSION}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ColumnTransform;
+
+    #[test]
+    fn test_user_scripts() -> Result<(), String> {
+        let dir =
+            std::env::temp_dir().join(format!("polars_view_test_scripts_{}", std::process::id()));
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+        let sql = dir.join("large.sql");
+        let recipe = dir.join(format!("clean.{RECIPE_EXTENSION}"));
+        fs::write(&sql, "SELECT * FROM AllData WHERE qty > 10;\n").map_err(|e| e.to_string())?;
+        fs::write(dir.join("notes.txt"), "").map_err(|e| e.to_string())?;
+        let steps = Recipe {
+            transforms: vec![ColumnTransform::Filter {
+                condition: "qty > 0".to_string(),
+            }],
+        };
+        steps.save(&recipe)?;
+
+        let scripts = list_scripts(&dir);
+        let loaded = (Script::load(&sql), Script::load(&recipe));
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(scripts, [recipe.clone(), sql.clone()]);
+        assert_eq!(script_name(&recipe), "clean");
+        assert_eq!(script_name(&sql), "large");
+
+        let filters = loaded.0?.apply(&DataFilters::default());
+        assert_eq!(
+            filters.query.as_deref(),
+            Some("SELECT * FROM AllData WHERE qty > 10;")
+        );
+        let filters = loaded.1?.apply(&filters);
+        assert_eq!(filters.transforms, steps.transforms);
+        Ok(())
+    }
+}
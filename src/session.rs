@@ -0,0 +1,122 @@
+use crate::{CentralView, DataFilters, DockLayout, SavedQuery, TableViewOptions, absolute_path};
+
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+/// Extension of the session files.
+pub const SESSION_EXTENSION: &str = "parqbench.json";
+
+/// Version of the session file format.
+pub const SESSION_VERSION: u32 = 1;
+
+/// A table of a session: its file and the query and sort applied to it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionTable {
+    pub filename: String,
+    #[serde(flatten)]
+    pub query: SavedQuery,
+}
+
+impl SessionTable {
+    /// Filters reopening the table.
+    pub fn to_filters(&self) -> DataFilters {
+        self.query.to_filters(&self.filename)
+    }
+}
+
+/// The open tables and view settings, saved to a `.parqbench.json` file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub version: u32,
+    pub tables: Vec<SessionTable>,
+    pub central_view: CentralView,
+    pub table_view: TableViewOptions,
+    pub table_zoom: f32,
+    /// Arrangement of the panes; kept as is when absent, as in older session files.
+    pub layout: Option<DockLayout>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Session {
+            version: SESSION_VERSION,
+            tables: Vec::new(),
+            central_view: CentralView::default(),
+            table_view: TableViewOptions::default(),
+            table_zoom: 1.0,
+            layout: None,
+        }
+    }
+}
+
+impl Session {
+    /// Adds a table loaded from `filename` with the given filters.
+    pub fn add_table(&mut self, filename: &str, filters: &DataFilters) {
+        self.tables.push(SessionTable {
+            filename: absolute_path(filename),
+            query: SavedQuery::from_filters(filters),
+        });
+    }
+
+    /// Reads a session file.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let json = fs::read_to_string(path)
+            .map_err(|e| format!("Error reading session {}: {}", path.display(), e))?;
+        let session: Session = serde_json::from_str(&json)
+            .map_err(|e| format!("Invalid session {}: {}", path.display(), e))?;
+
+        if session.version > SESSION_VERSION {
+            let msg = format!(
+                "Session {} was saved by a newer version (format {}).",
+                path.display(),
+                session.version
+            );
+            return Err(msg);
+        }
+
+        Ok(session)
+    }
+
+    /// Writes the session file.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Error serializing session: {}", e))?;
+        fs::write(path, json)
+            .map_err(|e| format!("Error writing session {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SortState;
+
+    #[test]
+    fn test_session_round_trip() -> Result<(), String> {
+        let mut session = Session {
+            central_view: CentralView::Chart,
+            table_zoom: 1.5,
+            ..Default::default()
+        };
+        let filters = DataFilters {
+            query: Some("SELECT * FROM AllData WHERE id > 10;".to_string()),
+            sort: Some(SortState::Descending("id".to_string())),
+            ..DataFilters::new("data.parquet")
+        };
+        session.add_table("data.parquet", &filters);
+
+        let path = std::env::temp_dir().join(format!("polars_view_test.{SESSION_EXTENSION}"));
+        session.save(&path)?;
+        let loaded = Session::load(&path)?;
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(loaded, session);
+
+        let reopened = loaded.tables[0].to_filters();
+        assert_eq!(reopened.filename.as_deref(), Some("data.parquet"));
+        assert_eq!(reopened.query, filters.query);
+        assert_eq!(reopened.sort, filters.sort);
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+use crate::{Language, tr};
+
+use egui::{Context, Event, Key, KeyboardShortcut, Modifiers};
+use serde::{Deserialize, Serialize};
+use std::{cmp::Reverse, collections::BTreeMap};
+
+/// A command of the application that can be bound to a keyboard shortcut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Action {
+    Open,
+    Close,
+    OpenSession,
+    SaveSession,
+    RunQuery,
+    FocusQuery,
+    Cancel,
+    ExportReport,
+    ToggleView,
+    Undo,
+    Redo,
+    NextBookmark,
+    PreviousBookmark,
+    Settings,
+    Quit,
+}
+
+impl Action {
+    pub const ALL: [Action; 15] = [
+        Action::Open,
+        Action::Close,
+        Action::OpenSession,
+        Action::SaveSession,
+        Action::RunQuery,
+        Action::FocusQuery,
+        Action::Cancel,
+        Action::ExportReport,
+        Action::ToggleView,
+        Action::Undo,
+        Action::Redo,
+        Action::NextBookmark,
+        Action::PreviousBookmark,
+        Action::Settings,
+        Action::Quit,
+    ];
+
+    /// Shortcut bound to the action unless the user rebinds it.
+    pub fn default_shortcut(&self) -> Option<KeyboardShortcut> {
+        let command = Modifiers::COMMAND;
+        let command_shift = Modifiers::COMMAND | Modifiers::SHIFT;
+
+        let (modifiers, key) = match self {
+            Action::Open => (command, Key::O),
+            Action::Close => (command, Key::W),
+            Action::OpenSession => (command_shift, Key::O),
+            Action::SaveSession => (command, Key::S),
+            Action::RunQuery => (command, Key::Enter),
+            Action::FocusQuery => (command, Key::L),
+            Action::Cancel => (command, Key::Period),
+            Action::ExportReport => (command, Key::E),
+            Action::ToggleView => (command, Key::G),
+            Action::Undo => (command, Key::Z),
+            Action::Redo => (command_shift, Key::Z),
+            Action::NextBookmark => (Modifiers::NONE, Key::F2),
+            Action::PreviousBookmark => (Modifiers::SHIFT, Key::F2),
+            Action::Settings => (command, Key::Comma),
+            Action::Quit => (command, Key::Q),
+        };
+        Some(KeyboardShortcut::new(modifiers, key))
+    }
+
+    /// Name of the action, in `lang`.
+    pub fn label(&self, lang: Language) -> &'static str {
+        match self {
+            Action::Open => tr("Open file", lang),
+            Action::Close => tr("Close file", lang),
+            Action::OpenSession => tr("Open session", lang),
+            Action::SaveSession => tr("Save session", lang),
+            Action::RunQuery => tr("Run query", lang),
+            Action::FocusQuery => tr("Edit query", lang),
+            Action::Cancel => tr("Cancel loading", lang),
+            Action::ExportReport => tr("Export report", lang),
+            Action::ToggleView => tr("Toggle table/chart", lang),
+            Action::Undo => tr("Undo view change", lang),
+            Action::Redo => tr("Redo view change", lang),
+            Action::NextBookmark => tr("Next bookmark", lang),
+            Action::PreviousBookmark => tr("Previous bookmark", lang),
+            Action::Settings => tr("Settings", lang),
+            Action::Quit => tr("Quit", lang),
+        }
+    }
+}
+
+/// Keyboard shortcuts of the actions.
+///
+/// Only the bindings changed by the user are stored; the other actions use their default.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Keymap {
+    /// Rebound actions; `None` leaves the action without shortcut.
+    pub bindings: BTreeMap<Action, Option<KeyboardShortcut>>,
+}
+
+impl Keymap {
+    /// Shortcut of an action, if any.
+    pub fn shortcut(&self, action: Action) -> Option<KeyboardShortcut> {
+        match self.bindings.get(&action) {
+            Some(shortcut) => *shortcut,
+            None => action.default_shortcut(),
+        }
+    }
+
+    /// Binds a shortcut to an action, unbinding it from the action that used it.
+    pub fn bind(&mut self, action: Action, shortcut: Option<KeyboardShortcut>) {
+        if shortcut.is_some() {
+            for other in Action::ALL {
+                if other != action && self.shortcut(other) == shortcut {
+                    self.bindings.insert(other, None);
+                }
+            }
+        }
+
+        match shortcut == action.default_shortcut() {
+            true => self.bindings.remove(&action),
+            false => self.bindings.insert(action, shortcut),
+        };
+    }
+
+    /// Text of the shortcut of an action, to be displayed in menus.
+    pub fn text(&self, ctx: &Context, action: Action) -> String {
+        self.shortcut(action)
+            .map(|shortcut| ctx.format_shortcut(&shortcut))
+            .unwrap_or_default()
+    }
+
+    /// Consumes the shortcuts pressed in this frame and returns their actions.
+    ///
+    /// Shift and Alt are ignored when a shortcut lacks them, so the shortcuts with
+    /// more modifiers are checked first: Ctrl+Shift+O is not taken for Ctrl+O.
+    pub fn pressed(&self, ctx: &Context) -> Vec<Action> {
+        let mut actions = Action::ALL.to_vec();
+        actions.sort_by_key(|action| {
+            let modifiers = self.shortcut(*action).map(|shortcut| shortcut.modifiers);
+            Reverse(modifiers.map_or(0, |m| {
+                [m.alt, m.shift, m.command || m.ctrl]
+                    .iter()
+                    .filter(|on| **on)
+                    .count()
+            }))
+        });
+
+        actions
+            .into_iter()
+            .filter(|action| {
+                // Undo and redo are left to the text being edited.
+                !(matches!(action, Action::Undo | Action::Redo) && ctx.wants_keyboard_input())
+            })
+            .filter(|action| {
+                self.shortcut(*action)
+                    .is_some_and(|shortcut| ctx.input_mut(|i| i.consume_shortcut(&shortcut)))
+            })
+            .collect()
+    }
+}
+
+/// Consumes the next key pressed, with its modifiers, to rebind an action.
+///
+/// Returns `Some(None)` if the capture was cancelled with Escape.
+pub fn capture_shortcut(ctx: &Context) -> Option<Option<KeyboardShortcut>> {
+    let (modifiers, key) = ctx.input(|i| {
+        i.events.iter().find_map(|event| match event {
+            Event::Key {
+                key,
+                pressed: true,
+                modifiers,
+                ..
+            } => Some((*modifiers, *key)),
+            _ => None,
+        })
+    })?;
+
+    ctx.input_mut(|i| i.consume_key(modifiers, key));
+
+    match (modifiers.is_none(), key) {
+        (true, Key::Escape) => Some(None),
+        _ => Some(Some(KeyboardShortcut::new(modifiers, key))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keymap_bind() -> Result<(), serde_json::Error> {
+        let mut keymap = Keymap::default();
+
+        // The default shortcuts are all distinct.
+        for (idx, action) in Action::ALL.iter().enumerate() {
+            let shortcut = keymap.shortcut(*action);
+            assert!(shortcut.is_some());
+            assert!(
+                Action::ALL[idx + 1..]
+                    .iter()
+                    .all(|other| keymap.shortcut(*other) != shortcut)
+            );
+        }
+
+        // Rebinding takes the shortcut away from the action that used it.
+        let ctrl_o = Action::Open.default_shortcut();
+        keymap.bind(Action::RunQuery, ctrl_o);
+        assert_eq!(keymap.shortcut(Action::RunQuery), ctrl_o);
+        assert_eq!(keymap.shortcut(Action::Open), None);
+
+        // Restoring a default binding removes it from the stored map.
+        keymap.bind(Action::Open, ctrl_o);
+        keymap.bind(Action::RunQuery, Action::RunQuery.default_shortcut());
+        assert_eq!(keymap, Keymap::default());
+
+        keymap.bind(Action::Quit, None);
+        let json = serde_json::to_string(&keymap)?;
+        assert_eq!(serde_json::from_str::<Keymap>(&json)?, keymap);
+        Ok(())
+    }
+
+    /// Actions of the shortcuts pressed with a key in one frame.
+    fn press(modifiers: Modifiers, key: Key) -> Vec<Action> {
+        let mut input = egui::RawInput {
+            modifiers,
+            ..Default::default()
+        };
+        input.events.push(Event::Key {
+            key,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers,
+        });
+
+        let ctx = Context::default();
+        let mut pressed = Vec::new();
+        let _ = ctx.run(input, |ctx| pressed = Keymap::default().pressed(ctx));
+        pressed
+    }
+
+    #[test]
+    fn test_shifted_shortcut() {
+        let command_shift = Modifiers::COMMAND | Modifiers::SHIFT;
+        // Not taken for Ctrl+O.
+        assert_eq!(press(command_shift, Key::O), [Action::OpenSession]);
+        assert_eq!(press(Modifiers::COMMAND, Key::O), [Action::Open]);
+    }
+
+    #[test]
+    fn test_redo_shortcut() {
+        let command_shift = Modifiers::COMMAND | Modifiers::SHIFT;
+        // Not taken for Ctrl+Z.
+        assert_eq!(press(command_shift, Key::Z), [Action::Redo]);
+        assert_eq!(press(Modifiers::COMMAND, Key::Z), [Action::Undo]);
+    }
+}
@@ -0,0 +1,169 @@
+use egui::{ColorImage, Context, Event, Rect, UserData, ViewportCommand};
+use std::{
+    fs::File,
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
+/// Where an image of the table goes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnapshotTarget {
+    Clipboard,
+    /// A PNG file.
+    File(PathBuf),
+}
+
+/// Image of the visible part of the table, cropped from a screenshot of the window.
+#[derive(Debug, Default)]
+pub struct TableSnapshot {
+    /// Area of the window showing the table in the last frame.
+    pub viewport: Option<Rect>,
+    /// Apply the column masks to the image, even while masking is off.
+    pub masked: bool,
+    /// Image to take at the end of a coming frame.
+    requested: Option<SnapshotTarget>,
+    /// The frame of the request was drawn: the menu or dialog asking for the image is closed
+    /// by then, so the next frame shows the table alone.
+    deferred: bool,
+    /// Image waiting for the screenshot, with the area of the table.
+    waiting: Option<(SnapshotTarget, Rect)>,
+}
+
+impl TableSnapshot {
+    /// Takes an image of the table as shown in the frame after this one, once the menu
+    /// or dialog asking for it is closed.
+    pub fn request(&mut self, target: SnapshotTarget) {
+        self.requested = Some(target);
+        self.deferred = false;
+    }
+
+    /// Whether the table of this frame is shown masked for an image.
+    pub fn masks_forced(&self) -> bool {
+        self.masked && self.requested.is_some()
+    }
+
+    /// Asks for a screenshot of this frame, once the table was drawn, if an image was requested.
+    pub fn take(&mut self, ctx: &Context) {
+        if self.requested.is_none() {
+            return;
+        }
+        if !self.deferred {
+            // Skip the frame of the request, which still shows the menu.
+            self.deferred = true;
+            ctx.request_repaint();
+            return;
+        }
+        let Some(target) = self.requested.take() else {
+            return;
+        };
+        if let Some(viewport) = self.viewport {
+            ctx.send_viewport_cmd(ViewportCommand::Screenshot(UserData::default()));
+            self.waiting = Some((target, viewport));
+        }
+    }
+
+    /// Crops the screenshot, once received, to the table and copies or saves it;
+    /// returns the outcome to report.
+    pub fn check_screenshot(&mut self, ctx: &Context) -> Option<Result<String, String>> {
+        self.waiting.as_ref()?;
+        let image = ctx.input(|i| {
+            i.raw.events.iter().find_map(|event| match event {
+                Event::Screenshot { image, .. } => Some(image.clone()),
+                _ => None,
+            })
+        })?;
+        let (target, viewport) = self.waiting.take()?;
+
+        // The screenshot is in physical pixels, the viewport in points.
+        let screen = Rect::from_min_size(
+            egui::Pos2::ZERO,
+            egui::vec2(image.width() as f32, image.height() as f32) / ctx.pixels_per_point(),
+        );
+        let image = image.region(&viewport.intersect(screen), Some(ctx.pixels_per_point()));
+
+        Some(match target {
+            SnapshotTarget::Clipboard => {
+                ctx.copy_image(image);
+                Ok("Table image copied to the clipboard".to_string())
+            }
+            SnapshotTarget::File(path) => save_png(&image, &path).map(|()| {
+                format!(
+                    "Table image saved: {}×{} → {}",
+                    image.width(),
+                    image.height(),
+                    path.display()
+                )
+            }),
+        })
+    }
+}
+
+/// Writes an image to `path` as a PNG file.
+pub fn save_png(image: &ColorImage, path: &Path) -> Result<(), String> {
+    let file =
+        File::create(path).map_err(|e| format!("Error creating {}: {}", path.display(), e))?;
+    let mut encoder = png::Encoder::new(
+        BufWriter::new(file),
+        image.width() as u32,
+        image.height() as u32,
+    );
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let pixels: Vec<u8> = image
+        .pixels
+        .iter()
+        .flat_map(|pixel| pixel.to_srgba_unmultiplied())
+        .collect();
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .map_err(|e| format!("Error writing PNG: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::Color32;
+
+    #[test]
+    fn test_save_png() -> Result<(), String> {
+        let mut image = ColorImage::new([3, 2], Color32::WHITE);
+        image[(1, 0)] = Color32::from_rgb(200, 10, 20);
+        let path = std::env::temp_dir().join("polars_view_test_snapshot.png");
+        save_png(&image, &path)?;
+
+        let file = File::open(&path).map_err(|e| e.to_string())?;
+        let mut reader = png::Decoder::new(file)
+            .read_info()
+            .map_err(|e| e.to_string())?;
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).map_err(|e| e.to_string())?;
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!((info.width, info.height), (3, 2));
+        assert_eq!(&pixels[4..8], &[200, 10, 20, 255]);
+        assert_eq!(&pixels[..4], &[255, 255, 255, 255]);
+
+        // A snapshot forces the masks of its frame only when asked to.
+        let mut snapshot = TableSnapshot {
+            masked: true,
+            ..Default::default()
+        };
+        assert!(!snapshot.masks_forced());
+        snapshot.request(SnapshotTarget::File(path));
+        assert!(snapshot.masks_forced());
+
+        // The screenshot is taken one frame after the request, once the menu is closed.
+        let ctx = Context::default();
+        snapshot.viewport = Some(Rect::from_min_size(
+            egui::Pos2::ZERO,
+            egui::vec2(10.0, 10.0),
+        ));
+        snapshot.take(&ctx);
+        assert!(snapshot.masks_forced() && snapshot.waiting.is_none());
+        snapshot.take(&ctx);
+        assert!(!snapshot.masks_forced() && snapshot.waiting.is_some());
+        Ok(())
+    }
+}
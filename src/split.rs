@@ -0,0 +1,308 @@
+use crate::{DataFrameContainer, DataFrameTable, DataTask, TableScroll};
+
+use polars::prelude::{AnyValue, DataFrame};
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, OnceLock},
+    task::Poll,
+};
+use tokio::runtime::Handle;
+
+/// Table of a split view side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitSide {
+    /// The table of the current result.
+    Main,
+    /// The table pinned beside it.
+    Pinned,
+}
+
+/// A result pinned beside the table, such as the raw file while querying it
+/// or another file to compare with.
+pub struct SplitView {
+    pub table: DataFrameTable,
+    /// Name of the pinned result, shown above it.
+    pub label: String,
+    /// Scroll both tables together.
+    pub sync_scroll: bool,
+    /// Column of both tables whose values align their rows; by row number without one.
+    pub key_column: Option<String>,
+    /// First row of each table (main, pinned) after the last synchronization.
+    synced: (usize, usize),
+    /// Rows of the key values, per table (main, pinned).
+    key_rows: (Option<KeyRows>, Option<KeyRows>),
+    /// Sort of the pinned table running in the background.
+    pub sorting: Option<DataTask>,
+}
+
+/// First row of each value of a key column, indexed on the runtime if there is one.
+#[derive(Debug, Clone)]
+struct KeyRows {
+    df: Arc<DataFrame>,
+    column: String,
+    /// Rows by key value, set once the column is indexed.
+    rows: Arc<OnceLock<HashMap<String, usize>>>,
+}
+
+impl KeyRows {
+    fn new(df: &Arc<DataFrame>, column: &str, handle: Option<&Handle>) -> Self {
+        let rows = Arc::new(OnceLock::new());
+        let (data, name, slot) = (Arc::clone(df), column.to_string(), Arc::clone(&rows));
+        let index = move || {
+            let _ = slot.set(index_keys(&data, &name));
+        };
+        match handle {
+            Some(handle) => {
+                handle.spawn_blocking(index);
+            }
+            None => index(),
+        }
+
+        KeyRows {
+            df: Arc::clone(df),
+            column: column.to_string(),
+            rows,
+        }
+    }
+
+    /// Row of a key value in `df`, indexing the column again if the data changed;
+    /// pending while the column is indexed.
+    fn find(
+        cache: &mut Option<KeyRows>,
+        df: &Arc<DataFrame>,
+        column: &str,
+        key: &str,
+        handle: Option<&Handle>,
+    ) -> Poll<Option<usize>> {
+        let index = match cache.take() {
+            Some(index) if Arc::ptr_eq(&index.df, df) && index.column == column => index,
+            _ => KeyRows::new(df, column, handle),
+        };
+        let row = match index.rows.get() {
+            Some(rows) => Poll::Ready(rows.get(key).copied()),
+            None => Poll::Pending,
+        };
+        *cache = Some(index);
+        row
+    }
+}
+
+/// First row of each value of a key column.
+fn index_keys(df: &DataFrame, column: &str) -> HashMap<String, usize> {
+    let mut rows = HashMap::new();
+    if let Ok(values) = df.column(column) {
+        for (row, value) in values.as_materialized_series().iter().enumerate() {
+            rows.entry(key_value(&value)).or_insert(row);
+        }
+    }
+    rows
+}
+
+/// Key value of a cell, comparable between tables.
+fn key_value(value: &AnyValue) -> String {
+    match value.get_str() {
+        Some(text) => text.to_string(),
+        None => value.to_string(),
+    }
+}
+
+impl SplitView {
+    /// Pins a result, scrolled as the table it comes from.
+    pub fn new(data: DataFrameContainer, scroll: &TableScroll) -> Self {
+        let name = Path::new(&data.filename)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| data.filename.clone());
+        let label = match &data.filters.query {
+            Some(query) => format!("{name}: {query}"),
+            None => name,
+        };
+
+        let mut table = DataFrameTable::with_data(data);
+        table.scroll.scroll_to(scroll.anchor_row);
+        if let Some(runtime) = scroll.runtime() {
+            table.scroll.set_runtime(runtime.clone());
+        }
+
+        SplitView {
+            table,
+            label,
+            sync_scroll: true,
+            key_column: None,
+            synced: (scroll.anchor_row, scroll.anchor_row),
+            key_rows: (None, None),
+            sorting: None,
+        }
+    }
+
+    /// Columns of both tables, which can align their rows.
+    pub fn common_columns(&self, main_df: &DataFrame) -> Vec<String> {
+        let pinned = &self.table.data.df;
+        main_df
+            .get_column_names()
+            .into_iter()
+            .filter(|name| pinned.get_column_index(name).is_some())
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    /// Row of the other table aligned with `row`: the row of the same key value, if any;
+    /// pending while the key column of the other table is indexed on `handle`.
+    fn aligned_row(
+        &mut self,
+        main_df: &Arc<DataFrame>,
+        row: usize,
+        from: SplitSide,
+        handle: Option<&Handle>,
+    ) -> Poll<Option<usize>> {
+        let Some(column) = self.key_column.as_deref() else {
+            return Poll::Ready(Some(row));
+        };
+        let pinned_df = &self.table.data.df;
+        let (from_df, to_df, cache) = match from {
+            SplitSide::Main => (main_df, pinned_df, &mut self.key_rows.1),
+            SplitSide::Pinned => (pinned_df, main_df, &mut self.key_rows.0),
+        };
+        let Some(key) = from_df
+            .column(column)
+            .ok()
+            .and_then(|values| values.get(row).ok())
+        else {
+            return Poll::Ready(None);
+        };
+        KeyRows::find(cache, to_df, column, &key_value(&key), handle)
+    }
+
+    /// Scrolls the other table to the row of the table scrolled by the user, under the pointer.
+    ///
+    /// With a key column, the other table is scrolled to the row of the same key value,
+    /// once its key column is indexed on the runtime of `main`.
+    /// Returns true if the other table is to be scrolled in the next frame.
+    pub fn sync(
+        &mut self,
+        main: &mut TableScroll,
+        main_df: &Arc<DataFrame>,
+        scrolled: Option<SplitSide>,
+    ) -> bool {
+        if !self.sync_scroll {
+            return false;
+        }
+
+        let handle = main.runtime().cloned();
+        let target = match scrolled {
+            Some(SplitSide::Main) if main.anchor_row != self.synced.0 => self
+                .aligned_row(main_df, main.anchor_row, SplitSide::Main, handle.as_ref())
+                .map(|row| row.map(|row| (SplitSide::Pinned, row))),
+            Some(SplitSide::Pinned) if self.table.scroll.anchor_row != self.synced.1 => {
+                let row = self.table.scroll.anchor_row;
+                self.aligned_row(main_df, row, SplitSide::Pinned, handle.as_ref())
+                    .map(|row| row.map(|row| (SplitSide::Main, row)))
+            }
+            _ => Poll::Ready(None),
+        };
+        let Poll::Ready(target) = target else {
+            return true; // Tried again in the next frame, until the key column is indexed.
+        };
+
+        match target {
+            Some((SplitSide::Main, row)) => main.scroll_to(row),
+            Some((SplitSide::Pinned, row)) => self.table.scroll.scroll_to(row),
+            None => {}
+        }
+        self.synced = (main.anchor_row, self.table.scroll.anchor_row);
+        target.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_synchronized_scrolling() -> Result<(), String> {
+        let df = df!["id" => (0..100).collect::<Vec<i32>>()].map_err(|e| e.to_string())?;
+        let mut data = DataFrameContainer::from_dataframe(df);
+        data.filename = "/data/sales.parquet".to_string();
+        data.filters.query = Some("SELECT * FROM AllData".to_string());
+
+        let main_df = data.df.clone();
+        let mut main = TableScroll::default();
+        let mut split = SplitView::new(data, &main);
+        assert_eq!(split.label, "sales.parquet: SELECT * FROM AllData");
+
+        // The table under the pointer drives the other one.
+        main.anchor_row = 40;
+        assert!(split.sync(&mut main, &main_df, Some(SplitSide::Main)));
+        assert_eq!(split.table.scroll.anchor_row, 40);
+        assert!(!split.sync(&mut main, &main_df, Some(SplitSide::Main)));
+
+        split.table.scroll.anchor_row = 25;
+        assert!(split.sync(&mut main, &main_df, Some(SplitSide::Pinned)));
+        assert_eq!(main.anchor_row, 25);
+
+        // Without the pointer over a table, or with the option off, nothing moves.
+        main.anchor_row = 60;
+        assert!(!split.sync(&mut main, &main_df, None));
+        split.sync_scroll = false;
+        main.anchor_row = 70;
+        assert!(!split.sync(&mut main, &main_df, Some(SplitSide::Main)));
+        assert_eq!(split.table.scroll.anchor_row, 25);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rows_aligned_by_key() -> Result<(), String> {
+        let old = df!["id" => ["a", "b", "c", "d"], "price" => [1, 2, 3, 4]]
+            .map_err(|e| e.to_string())?;
+        let new = df!["id" => ["d", "c", "b"], "qty" => [5, 6, 7]].map_err(|e| e.to_string())?;
+        let main_df = Arc::new(new);
+
+        let mut main = TableScroll::default();
+        let mut split = SplitView::new(DataFrameContainer::from_dataframe(old), &main);
+        assert_eq!(split.common_columns(&main_df), ["id"]);
+        split.key_column = Some("id".to_string());
+
+        // Row 1 of the main table ("c") is row 2 of the pinned one.
+        main.anchor_row = 1;
+        assert!(split.sync(&mut main, &main_df, Some(SplitSide::Main)));
+        assert_eq!(split.table.scroll.anchor_row, 2);
+
+        // A key missing from the other table leaves it in place.
+        split.table.scroll.anchor_row = 0;
+        assert!(!split.sync(&mut main, &main_df, Some(SplitSide::Pinned)));
+        assert_eq!(main.anchor_row, 1);
+
+        split.table.scroll.anchor_row = 3;
+        assert!(split.sync(&mut main, &main_df, Some(SplitSide::Pinned)));
+        assert_eq!(main.anchor_row, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_key_column_indexed_in_background() -> Result<(), String> {
+        let old = df!["id" => (0..1000).rev().collect::<Vec<i32>>()].map_err(|e| e.to_string())?;
+        let main_df =
+            Arc::new(df!["id" => (0..1000).collect::<Vec<i32>>()].map_err(|e| e.to_string())?);
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .build()
+            .map_err(|e| e.to_string())?;
+
+        let mut main = TableScroll::default();
+        main.set_runtime(runtime.handle().clone());
+        let mut split = SplitView::new(DataFrameContainer::from_dataframe(old), &main);
+        split.key_column = Some("id".to_string());
+
+        // The pinned table follows once its key column is indexed.
+        main.anchor_row = 10;
+        let start = std::time::Instant::now();
+        while split.table.scroll.anchor_row != 989 {
+            assert!(split.sync(&mut main, &main_df, Some(SplitSide::Main)));
+            assert!(start.elapsed().as_secs() < 10, "key column not indexed");
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        assert!(!split.sync(&mut main, &main_df, Some(SplitSide::Main)));
+        Ok(())
+    }
+}
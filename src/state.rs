@@ -0,0 +1,197 @@
+use crate::{CentralView, ColumnTransform, DataFilters, DockLayout, EngineOptions, SortState};
+
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// Key of the [`AppState`] in the eframe storage.
+pub const APP_STATE_KEY: &str = "app_state";
+
+/// Number of recently opened files remembered.
+pub const RECENT_FILES_LIMIT: usize = 10;
+
+/// Absolute path of a file, so that it can be reopened from another directory.
+pub fn absolute_path(filename: &str) -> String {
+    Path::new(filename)
+        .canonicalize()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|_| filename.to_string())
+}
+
+/// Last query, sort and pipeline applied to a file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedQuery {
+    pub table_name: String,
+    pub csv_delimiter: String,
+    pub query: Option<String>,
+    pub sort: Option<SortState>,
+    /// Secondary sort columns.
+    pub then_by: Vec<SortState>,
+    /// Steps of the pipeline, such as the row filters.
+    pub transforms: Vec<ColumnTransform>,
+}
+
+impl SavedQuery {
+    pub fn from_filters(filters: &DataFilters) -> Self {
+        SavedQuery {
+            table_name: filters.table_name.clone(),
+            csv_delimiter: filters.csv_delimiter.clone(),
+            query: filters.query.clone(),
+            sort: filters.sort.clone(),
+            then_by: filters.then_by.clone(),
+            transforms: filters.transforms.clone(),
+        }
+    }
+
+    /// Whether nothing beyond the file itself is saved.
+    pub fn is_empty(&self) -> bool {
+        self.query.is_none()
+            && self.sort.is_none()
+            && self.then_by.is_empty()
+            && self.transforms.is_empty()
+    }
+
+    /// Filters reproducing the query and sort on the given file.
+    pub fn to_filters(&self, filename: impl ToString) -> DataFilters {
+        DataFilters {
+            filename: Some(filename.to_string()),
+            table_name: self.table_name.clone(),
+            tables: Vec::new(),
+            csv_delimiter: self.csv_delimiter.clone(),
+            query: self.query.clone(),
+            sort: self.sort.clone(),
+            then_by: self.then_by.clone(),
+            csv_options: None,
+            transforms: self.transforms.clone(),
+            engine: EngineOptions::default(),
+            views: Vec::new(),
+        }
+    }
+}
+
+/// Application state restored on startup: recent files, last query per file, view and panes.
+///
+/// The window size and the side-panel width are persisted by eframe itself.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppState {
+    /// Recently opened files, the most recent first.
+    pub recent_files: Vec<String>,
+    /// Last query, sort and pipeline of each recent file.
+    pub queries: BTreeMap<String, SavedQuery>,
+    /// View displayed in the central panel.
+    pub central_view: CentralView,
+    /// Zoom factor of the table text.
+    pub table_zoom: f32,
+    /// Arrangement of the side-panel panes.
+    pub layout: DockLayout,
+    /// Whether the log panel is displayed.
+    pub show_log: bool,
+    /// Directory shown in the Files pane.
+    pub browse_root: Option<PathBuf>,
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        AppState {
+            recent_files: Vec::new(),
+            queries: BTreeMap::new(),
+            central_view: CentralView::default(),
+            table_zoom: 1.0,
+            layout: DockLayout::default(),
+            show_log: false,
+            browse_root: None,
+        }
+    }
+}
+
+impl AppState {
+    /// Reads the state from the eframe storage, falling back to the defaults.
+    pub fn load(storage: Option<&dyn eframe::Storage>) -> Self {
+        storage
+            .and_then(|storage| eframe::get_value(storage, APP_STATE_KEY))
+            .unwrap_or_default()
+    }
+
+    /// Writes the state to the eframe storage.
+    pub fn save(&self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, APP_STATE_KEY, self);
+    }
+
+    /// Records a loaded file and the query, sort and pipeline applied to it.
+    pub fn remember(&mut self, filename: &str, filters: &DataFilters) {
+        let key = absolute_path(filename);
+
+        self.recent_files.retain(|file| *file != key);
+        self.recent_files.insert(0, key.clone());
+        self.recent_files.truncate(RECENT_FILES_LIMIT);
+
+        let saved = SavedQuery::from_filters(filters);
+        if !saved.is_empty() {
+            self.queries.insert(key, saved);
+        }
+
+        // Forget the queries of the files no longer in the list.
+        let recent_files = &self.recent_files;
+        self.queries.retain(|file, _| recent_files.contains(file));
+    }
+
+    /// Filters reproducing the last query, sort and pipeline of a file, if any.
+    pub fn saved_filters(&self, filename: &str) -> Option<DataFilters> {
+        let key = absolute_path(filename);
+        self.queries.get(&key).map(|saved| saved.to_filters(&key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remember_files() {
+        let mut state = AppState::default();
+
+        for idx in 0..RECENT_FILES_LIMIT + 2 {
+            let filename = format!("file_{idx}.parquet");
+            let filters = DataFilters {
+                query: Some(format!("SELECT * FROM AllData LIMIT {idx};")),
+                sort: Some(SortState::Ascending("id".to_string())),
+                ..DataFilters::new(&filename)
+            };
+            state.remember(&filename, &filters);
+        }
+
+        // Reopening a file moves it to the top without duplicating it.
+        let filters = DataFilters::new("file_5.parquet");
+        state.remember("file_5.parquet", &filters);
+
+        assert_eq!(state.recent_files.len(), RECENT_FILES_LIMIT);
+        assert_eq!(state.recent_files[0], "file_5.parquet");
+        assert_eq!(state.recent_files[1], "file_11.parquet");
+        assert_eq!(state.queries.len(), RECENT_FILES_LIMIT);
+        assert!(state.saved_filters("file_0.parquet").is_none());
+
+        let saved = state.saved_filters("file_11.parquet").unwrap();
+        assert_eq!(
+            saved.query.as_deref(),
+            Some("SELECT * FROM AllData LIMIT 11;")
+        );
+        assert_eq!(saved.sort, Some(SortState::Ascending("id".to_string())));
+
+        // The row filters of the pipeline are kept too.
+        let condition = "qty > 10".to_string();
+        let filters = DataFilters {
+            transforms: vec![ColumnTransform::Filter { condition }],
+            ..DataFilters::new("file_11.parquet")
+        };
+        state.remember("file_11.parquet", &filters);
+        let saved = state.saved_filters("file_11.parquet").unwrap();
+        assert_eq!(saved.transforms, filters.transforms);
+
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(serde_json::from_str::<AppState>(&json).unwrap(), state);
+    }
+}
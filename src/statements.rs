@@ -0,0 +1,392 @@
+use crate::SqlFunctions;
+
+use polars::{prelude::LazyFrame, sql::SQLContext};
+use std::ops::Range;
+
+/// A statement of a SQL script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlStatement {
+    /// Text of the statement, without its semicolon.
+    pub text: String,
+    /// Byte offset of the statement in the script.
+    pub offset: usize,
+}
+
+/// Splits a script into its `;`-separated statements, ignoring the semicolons
+/// of quoted strings, quoted identifiers and comments; blank statements are dropped.
+pub fn split_statements(sql: &str) -> Vec<SqlStatement> {
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut quote: Option<char> = None;
+    let mut chars = sql.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"' | '`') => quote = Some(c),
+            (None, '-') if chars.peek().is_some_and(|(_, next)| *next == '-') => {
+                // Line comment.
+                while chars.next_if(|(_, next)| *next != '\n').is_some() {}
+            }
+            (None, '/') if chars.peek().is_some_and(|(_, next)| *next == '*') => {
+                // Block comment.
+                chars.next();
+                let mut previous = ' ';
+                for (_, next) in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+            }
+            (None, ';') => {
+                push_statement(&mut statements, sql, start, index);
+                start = index + 1;
+            }
+            (None, _) => {}
+        }
+    }
+    push_statement(&mut statements, sql, start, sql.len());
+    statements
+}
+
+/// The statements of a script before the last one, each ended by a semicolon and a new line,
+/// and the last statement, whose result the script returns.
+pub fn split_last_statement(sql: &str) -> (String, Option<String>) {
+    let mut statements = split_statements(sql);
+    let last = statements.pop().map(|statement| statement.text);
+    let preamble = statements
+        .iter()
+        .map(|statement| format!("{};\n", statement.text))
+        .collect();
+    (preamble, last)
+}
+
+fn push_statement(statements: &mut Vec<SqlStatement>, sql: &str, start: usize, end: usize) {
+    let text = &sql[start..end];
+    let trimmed = text.trim_start();
+    if !trimmed.trim_end().is_empty() {
+        statements.push(SqlStatement {
+            text: trimmed.trim_end().to_string(),
+            offset: start + text.len() - trimmed.len(),
+        });
+    }
+}
+
+/// Rewrites `CREATE [OR REPLACE] [TEMP] VIEW name AS query`, which Polars SQL lacks,
+/// as the equivalent `CREATE TABLE name AS query`: both register a lazy query.
+fn rewrite_view(statement: &str) -> Option<String> {
+    let mut words = statement.split_whitespace();
+    let mut word = || words.next().map(str::to_uppercase);
+
+    if word()? != "CREATE" {
+        return None;
+    }
+    let mut next = word()?;
+    if next == "OR" {
+        (word()? == "REPLACE").then_some(())?;
+        next = word()?;
+    }
+    if next == "TEMP" || next == "TEMPORARY" {
+        next = word()?;
+    }
+    if next != "VIEW" {
+        return None;
+    }
+    let view = statement.to_uppercase().find("VIEW")?;
+    Some(format!("CREATE TABLE{}", &statement[view + "VIEW".len()..]))
+}
+
+/// Columns of a table that a script may use, in the order of the table, or `None` for all.
+///
+/// A column is used if its name appears in the script, even in a string; all columns are
+/// with a wildcard other than `COUNT(*)`, with `COLUMNS(…)`, or with a `NATURAL` join, whose
+/// key columns are not named. At least one column is kept, so that the rows can be counted.
+pub fn referenced_columns(sql: &str, columns: &[String]) -> Option<Vec<String>> {
+    let sql = sql.to_lowercase();
+    if sql.replace("count(*)", "").contains('*')
+        || sql.contains("columns(")
+        || sql.contains("natural")
+    {
+        return None;
+    }
+
+    let mut used: Vec<String> = columns
+        .iter()
+        .filter(|column| sql.contains(&column.to_lowercase()))
+        .cloned()
+        .collect();
+    if used.len() == columns.len() {
+        return None;
+    }
+    if used.is_empty() {
+        used.extend(columns.first().cloned());
+    }
+    Some(used)
+}
+
+/// Line (from 1) of a byte offset of the script.
+pub fn line_of(sql: &str, offset: usize) -> usize {
+    sql[..offset.min(sql.len())].matches('\n').count() + 1
+}
+
+/// Line and column (in characters, from 1) of a byte offset of the script.
+fn line_column(sql: &str, offset: usize) -> (usize, usize) {
+    let before = &sql[..offset.min(sql.len())];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    (
+        line_of(sql, offset),
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// Byte offset of a line and column (in characters, from 1) of the script.
+fn offset_of(sql: &str, line: usize, column: usize) -> Option<usize> {
+    let line_start = match line {
+        0 => return None,
+        1 => 0,
+        _ => sql.match_indices('\n').nth(line - 2)?.0 + 1,
+    };
+    let text = &sql[line_start..];
+    let in_line = text
+        .char_indices()
+        .nth(column.checked_sub(1)?)
+        .map_or(text.len(), |(index, _)| index);
+    Some(line_start + in_line)
+}
+
+/// Byte range of the token of the script starting at `offset`: a word, or one character.
+fn token_at(sql: &str, offset: usize) -> Range<usize> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let rest = &sql[offset.min(sql.len())..];
+    let length = match rest.chars().next() {
+        Some(c) if is_word(c) => rest.find(|c| !is_word(c)).unwrap_or(rest.len()),
+        Some(c) => c.len_utf8(),
+        None => 0,
+    };
+    offset..offset + length
+}
+
+/// Byte offset in the script of the error of a statement: the location given by the
+/// SQL parser, the end of the statement if it ended early, or the first name quoted by the error.
+///
+/// The statement executed may have been rewritten, so the token found there is searched
+/// in the statement written, nearest to the same position.
+fn error_offset(statement: &SqlStatement, executed: &str, error: &str) -> Option<usize> {
+    let found = match error.split_once(" at Line: ") {
+        Some((_, location)) => {
+            let (line, column) = location.split_once(", Column: ")?;
+            let column: String = column.chars().take_while(char::is_ascii_digit).collect();
+            let offset = offset_of(executed, line.parse().ok()?, column.parse().ok()?)?;
+            Some((offset, &executed[token_at(executed, offset)]))
+        }
+        None if error.ends_with("found: EOF") => {
+            let last = statement.text.char_indices().last()?.0;
+            return Some(statement.offset + last);
+        }
+        None => error
+            .split('\'')
+            .nth(1)
+            .filter(|name| !name.is_empty())
+            .map(|name| (0, name)),
+    };
+
+    let (offset, token) = found?;
+    statement
+        .text
+        .match_indices(token)
+        .map(|(index, _)| index)
+        .min_by_key(|index| index.abs_diff(offset))
+        .map(|index| statement.offset + index)
+}
+
+/// An error of a SQL script, located in the script when the error tells where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlError {
+    /// The script that failed.
+    pub query: String,
+    pub message: String,
+}
+
+impl SqlError {
+    /// The SQL error of a failed load of `query`, if the message is one.
+    pub fn new(query: Option<&str>, message: &str) -> Option<Self> {
+        let query = query.filter(|_| message.starts_with("Polars SQL error"))?;
+        Some(SqlError {
+            query: query.to_string(),
+            message: message.to_string(),
+        })
+    }
+
+    /// Line and column of the error in the script, as written in the message.
+    pub fn location(&self) -> Option<(usize, usize)> {
+        let (_, location) = self.message.split_once(" at line ")?;
+        let (line, rest) = location.split_once(", column ")?;
+        let (column, _) = rest.split_once(':')?;
+        Some((line.parse().ok()?, column.parse().ok()?))
+    }
+
+    /// Byte range of the token of the script where the error is.
+    pub fn range(&self) -> Option<Range<usize>> {
+        let (line, column) = self.location()?;
+        offset_of(&self.query, line, column).map(|offset| token_at(&self.query, offset))
+    }
+}
+
+/// Executes the statements of a script in order, returning the result of the last one.
+///
+/// The statements may call the enabled `functions`, if `ctx` comes from `sql_context`.
+/// The error of a failed statement tells which one failed when there are several.
+pub fn execute_statements(
+    ctx: &mut SQLContext,
+    sql: &str,
+    functions: SqlFunctions,
+) -> Result<LazyFrame, String> {
+    let statements = split_statements(sql);
+    if statements.is_empty() {
+        return Err("Polars SQL error: the query is empty".to_string());
+    }
+
+    let count = statements.len();
+    let mut result = None;
+    for (index, statement) in statements.iter().enumerate() {
+        let text = rewrite_view(&statement.text).unwrap_or_else(|| statement.text.clone());
+        let mut executed = text.clone();
+        let lazyframe = functions
+            .prepare(ctx, &text)
+            .and_then(|text| {
+                executed = text;
+                ctx.execute(&executed)
+            })
+            .map_err(|e| {
+                let error = e.to_string();
+                let statement_number = match count {
+                    1 => String::new(),
+                    _ => format!(" in statement {} of {count}", index + 1),
+                };
+                match error_offset(statement, &executed, &error) {
+                    Some(offset) => {
+                        let (line, column) = line_column(sql, offset);
+                        format!(
+                            "Polars SQL error{statement_number} at line {line}, column {column}: {error}"
+                        )
+                    }
+                    None if count > 1 => format!(
+                        "Polars SQL error{statement_number} (line {}): {error}",
+                        line_of(sql, statement.offset)
+                    ),
+                    None => format!("Polars SQL error: {error}"),
+                }
+            })?;
+        result = Some(lazyframe);
+    }
+    result.ok_or_else(|| "Polars SQL error: no statement".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_execute_statements() -> Result<(), String> {
+        let script = "CREATE VIEW big AS SELECT * FROM AllData WHERE qty > 1; -- Not a ; statement\n\
+                      SELECT name, 'a;b' AS `x;y` FROM big /* ; */ ORDER BY name;\n";
+        let statements = split_statements(script);
+        assert_eq!(statements.len(), 2);
+        assert!(
+            statements[1]
+                .text
+                .starts_with("-- Not a ; statement\nSELECT")
+        );
+        assert_eq!(line_of(script, statements[1].offset), 1);
+        assert_eq!(
+            rewrite_view("create or replace temp view v as select 1").as_deref(),
+            Some("CREATE TABLE v as select 1")
+        );
+        assert_eq!(rewrite_view("SELECT * FROM view"), None);
+
+        let df = df!["name" => ["c", "a", "b"], "qty" => [1, 2, 3]].map_err(|e| e.to_string())?;
+        let mut ctx = SQLContext::new();
+        ctx.register("AllData", df.lazy());
+        let result = execute_statements(&mut ctx, script, SqlFunctions::default())?
+            .collect()
+            .map_err(|e| e.to_string())?;
+        let expected =
+            df!["name" => ["a", "b"], "x;y" => ["a;b", "a;b"]].map_err(|e| e.to_string())?;
+        assert!(result.equals(&expected));
+
+        // The failed statement is reported.
+        let error = execute_statements(
+            &mut ctx,
+            "SELECT 1;\nSELECT * FROM missing;",
+            SqlFunctions::default(),
+        )
+        .err()
+        .unwrap_or_default();
+        assert!(
+            error.contains("statement 2 of 2 at line 2, column 15"),
+            "{error}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_referenced_columns() {
+        let columns = ["id", "price", "Tipo de Crédito", "notes"].map(String::from);
+        let used = |sql: &str| referenced_columns(sql, &columns);
+
+        assert_eq!(
+            used("SELECT id, `tipo de crédito` FROM AllData WHERE price > 1"),
+            Some(vec![
+                "id".to_string(),
+                "price".to_string(),
+                "Tipo de Crédito".to_string()
+            ])
+        );
+        assert_eq!(
+            used("SELECT COUNT(*) FROM AllData"),
+            Some(vec!["id".to_string()])
+        );
+        assert_eq!(used("SELECT * FROM AllData"), None);
+        assert_eq!(used("SELECT AllData.* FROM AllData"), None);
+        assert_eq!(used("SELECT COLUMNS('^p.*$') FROM AllData"), None);
+        assert_eq!(used("SELECT price FROM AllData NATURAL JOIN t"), None);
+        assert_eq!(
+            used("SELECT id, price, `Tipo de Crédito`, notes FROM AllData"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_sql_error_location() -> Result<(), String> {
+        let mut ctx = SQLContext::new();
+        ctx.register(
+            "AllData",
+            df!["a" => [1]].map_err(|e| e.to_string())?.lazy(),
+        );
+        let functions = SqlFunctions::default();
+        let error = |query: &str| -> Option<(Option<(usize, usize)>, String)> {
+            let message = execute_statements(&mut ctx.clone(), query, functions).err()?;
+            let error = SqlError::new(Some(query), &message)?;
+            let token = error.range().map(|range| query[range].to_string());
+            Some((error.location(), token.unwrap_or_default()))
+        };
+
+        // The location of a parse error, of a name not found and of an early end.
+        let query = "SELECT a\nFROM AllData\nWHERE a = = 1";
+        assert_eq!(error(query), Some((Some((3, 11)), "=".to_string())));
+        let query = "CREATE VIEW v AS SELECT a FROM AllData;\n  SELECT * FROM  missing";
+        assert_eq!(error(query), Some((Some((2, 18)), "missing".to_string())));
+        let query = "SELECT a FROM";
+        assert_eq!(error(query), Some((Some((1, 13)), "M".to_string())));
+
+        // The location of a view, rewritten as a table, refers to the statement written.
+        let query = "CREATE VIEW v AS SELECT a,, FROM AllData";
+        assert_eq!(error(query), Some((Some((1, 27)), ",".to_string())));
+
+        assert_eq!(SqlError::new(Some(query), "No query provided"), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,903 @@
+use crate::{ColumnTransform, DataFilters, Popover, busy_indicator, split_last_statement};
+
+use egui::{
+    Align, Color32, Context, Grid, Layout, ProgressBar, Rect, ScrollArea, Sense, TextStyle, Ui,
+    Vec2, Window, pos2,
+};
+use egui_extras::{Column as TableColumn, TableBuilder};
+use polars::prelude::*;
+use std::sync::Arc;
+use tokio::{
+    runtime::Handle,
+    sync::oneshot::{self, Receiver, error::TryRecvError},
+};
+
+/// Statistics computed by [`describe`], in display order.
+pub const DESCRIBE_STATISTICS: [&str; 10] = [
+    "count", "nulls", "mean", "std", "min", "25%", "50%", "75%", "max", "distinct",
+];
+
+/// Computes pandas-like `describe()` summary statistics for all columns of a DataFrame.
+///
+/// The result has one row per column of `df` and one column per statistic
+/// (see [`DESCRIBE_STATISTICS`]), plus a leading `column` with the column names.
+/// Statistics that don't apply to a data type (e.g. the mean of a string column) are null.
+pub fn describe(df: &DataFrame) -> PolarsResult<DataFrame> {
+    let mut names: Vec<String> = Vec::with_capacity(df.width());
+    let mut counts: Vec<u64> = Vec::with_capacity(df.width());
+    let mut nulls: Vec<u64> = Vec::with_capacity(df.width());
+    let mut means: Vec<Option<f64>> = Vec::with_capacity(df.width());
+    let mut stds: Vec<Option<f64>> = Vec::with_capacity(df.width());
+    let mut mins: Vec<Option<String>> = Vec::with_capacity(df.width());
+    let mut quartiles: [Vec<Option<f64>>; 3] = Default::default();
+    let mut maxs: Vec<Option<String>> = Vec::with_capacity(df.width());
+    let mut distinct: Vec<Option<u64>> = Vec::with_capacity(df.width());
+
+    for column in df.get_columns() {
+        let series = column.as_materialized_series();
+        let numeric = series.dtype().is_primitive_numeric();
+
+        names.push(series.name().to_string());
+        counts.push((series.len() - series.null_count()) as u64);
+        nulls.push(series.null_count() as u64);
+
+        // Mean, standard deviation and quartiles only make sense for numbers.
+        means.push(numeric.then(|| series.mean()).flatten());
+        stds.push(numeric.then(|| series.std(1)).flatten());
+        for (quartile, values) in [0.25, 0.50, 0.75].into_iter().zip(quartiles.iter_mut()) {
+            let value = match numeric {
+                true => series
+                    .quantile_reduce(quartile, QuantileMethod::Linear)
+                    .ok()
+                    .and_then(|scalar| scalar.value().extract::<f64>()),
+                false => None,
+            };
+            values.push(value);
+        }
+
+        // Min and max are computed for any ordered type (numbers, dates, strings...).
+        mins.push(reduce_to_string(series.min_reduce()));
+        maxs.push(reduce_to_string(series.max_reduce()));
+
+        distinct.push(series.n_unique().ok().map(|n| n as u64));
+    }
+
+    let [q25, q50, q75] = quartiles;
+
+    DataFrame::new(vec![
+        Column::new("column".into(), names),
+        Column::new(DESCRIBE_STATISTICS[0].into(), counts),
+        Column::new(DESCRIBE_STATISTICS[1].into(), nulls),
+        Column::new(DESCRIBE_STATISTICS[2].into(), means),
+        Column::new(DESCRIBE_STATISTICS[3].into(), stds),
+        Column::new(DESCRIBE_STATISTICS[4].into(), mins),
+        Column::new(DESCRIBE_STATISTICS[5].into(), q25),
+        Column::new(DESCRIBE_STATISTICS[6].into(), q50),
+        Column::new(DESCRIBE_STATISTICS[7].into(), q75),
+        Column::new(DESCRIBE_STATISTICS[8].into(), maxs),
+        Column::new(DESCRIBE_STATISTICS[9].into(), distinct),
+    ])
+}
+
+/// Extracts the non-null, finite values of a numeric column as `f64`.
+pub fn numeric_values(column: &Column) -> PolarsResult<Vec<f64>> {
+    let casted = column.cast(&DataType::Float64)?;
+    let values = casted
+        .f64()?
+        .into_iter()
+        .flatten()
+        .filter(|value| value.is_finite())
+        .collect();
+    Ok(values)
+}
+
+/// Bins the values into `bins` equal-width intervals spanning their range.
+///
+/// Returns the lower bound and the number of values of each bin, along with the bin width.
+pub fn histogram(values: &[f64], bins: usize) -> (Vec<(f64, usize)>, f64) {
+    let bins = bins.max(1);
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    if values.is_empty() {
+        return (Vec::new(), 1.0);
+    }
+
+    // A constant column gets a single bin of unit width.
+    if max <= min {
+        return (vec![(min - 0.5, values.len())], 1.0);
+    }
+
+    let width = (max - min) / bins as f64;
+    let mut counts = vec![0; bins];
+    for value in values {
+        // The maximum value belongs to the last (closed) bin.
+        let index = (((value - min) / width) as usize).min(bins - 1);
+        counts[index] += 1;
+    }
+
+    let bounds = counts
+        .into_iter()
+        .enumerate()
+        .map(|(index, count)| (min + index as f64 * width, count))
+        .collect();
+
+    (bounds, width)
+}
+
+/// Pearson correlation matrix of the numeric columns of a DataFrame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorrelationMatrix {
+    /// Names of the numeric columns, in the order of the matrix rows and columns.
+    pub names: Vec<String>,
+    /// Correlation coefficients; `None` where undefined (e.g. constant columns).
+    pub values: Vec<Vec<Option<f64>>>,
+}
+
+/// Computes the Pearson correlation matrix of the numeric columns.
+///
+/// All the coefficients of the upper triangle are computed in a single lazy query.
+pub fn correlation_matrix(df: &DataFrame) -> PolarsResult<CorrelationMatrix> {
+    let names: Vec<String> = df
+        .get_columns()
+        .iter()
+        .filter(|column| column.dtype().is_primitive_numeric())
+        .map(|column| column.name().to_string())
+        .collect();
+
+    let n = names.len();
+    let mut values = vec![vec![None; n]; n];
+
+    // Pairs (i, j) of the upper triangle of the matrix.
+    let pairs: Vec<(usize, usize)> = (0..n)
+        .flat_map(|i| ((i + 1)..n).map(move |j| (i, j)))
+        .collect();
+
+    let exprs: Vec<Expr> = pairs
+        .iter()
+        .map(|&(i, j)| {
+            let a = col(names[i].as_str()).cast(DataType::Float64);
+            let b = col(names[j].as_str()).cast(DataType::Float64);
+            pearson_corr(a, b).alias(format!("{i}_{j}"))
+        })
+        .collect();
+
+    if !exprs.is_empty() {
+        let coefficients = df.clone().lazy().select(exprs).collect()?;
+        for (i, j) in pairs {
+            let value = coefficients
+                .column(&format!("{i}_{j}"))?
+                .get(0)?
+                .extract::<f64>()
+                .filter(|value| value.is_finite());
+            values[i][j] = value;
+            values[j][i] = value;
+        }
+    }
+
+    // A column is perfectly correlated with itself, unless it is constant or empty.
+    for (i, name) in names.iter().enumerate() {
+        let std = df.column(name)?.as_materialized_series().std(1);
+        values[i][i] = std.filter(|std| *std > 0.0).map(|_| 1.0);
+    }
+
+    Ok(CorrelationMatrix { names, values })
+}
+
+/// Maximum number of rows sampled for the missingness heatmap.
+const MISSINGNESS_SAMPLE_ROWS: usize = 200;
+
+/// Samples up to `max_rows` evenly spaced rows and reports which cells are null.
+///
+/// Returns one vector per column, with `true` for each sampled null cell.
+pub fn missingness_sample(df: &DataFrame, max_rows: usize) -> Vec<Vec<bool>> {
+    let step = df.height().div_ceil(max_rows.max(1)).max(1);
+
+    df.get_columns()
+        .iter()
+        .map(|column| {
+            let is_null = column.is_null();
+            (0..df.height())
+                .step_by(step)
+                .map(|row| is_null.get(row).unwrap_or(false))
+                .collect()
+        })
+        .collect()
+}
+
+/// Side-panel pane giving an overview of the missing (null) values.
+#[derive(Default)]
+pub struct MissingnessPane {
+    /// Show the row-sampled missingness heatmap below the bars.
+    pub show_heatmap: bool,
+    /// Heatmap sample computed for the current data, to avoid recomputing it every frame.
+    sample: Option<(Arc<DataFrame>, Vec<Vec<bool>>)>,
+}
+
+impl MissingnessPane {
+    /// Renders the null percentage of each column as a bar, and optionally the heatmap.
+    pub fn render(&mut self, ui: &mut Ui, df: &Arc<DataFrame>) {
+        let height = df.height().max(1) as f32;
+
+        Grid::new("missingness_grid")
+            .num_columns(2)
+            .spacing([10.0, 4.0])
+            .show(ui, |ui| {
+                for column in df.get_columns() {
+                    let null_count = column.null_count();
+                    let fraction = null_count as f32 / height;
+
+                    ui.label(column.name().as_str());
+                    ui.add(
+                        ProgressBar::new(fraction)
+                            .desired_width(150.0)
+                            .text(format!("{:.1}% ({} nulls)", fraction * 100.0, null_count)),
+                    );
+                    ui.end_row();
+                }
+            });
+
+        ui.checkbox(&mut self.show_heatmap, "Show missingness heatmap")
+            .on_hover_text(format!(
+                "Null cells of up to {} evenly sampled rows",
+                MISSINGNESS_SAMPLE_ROWS
+            ));
+
+        if !self.show_heatmap || df.width() == 0 {
+            return;
+        }
+
+        // Sample the data only when it changes.
+        if !self
+            .sample
+            .as_ref()
+            .is_some_and(|(data, _)| Arc::ptr_eq(data, df))
+        {
+            let sample = missingness_sample(df, MISSINGNESS_SAMPLE_ROWS);
+            self.sample = Some((df.clone(), sample));
+        }
+
+        let Some((_, sample)) = &self.sample else {
+            return;
+        };
+
+        // One vertical stripe per column, one horizontal line per sampled row.
+        let rows = sample.first().map_or(0, |column| column.len()).max(1);
+        let size = Vec2::new(ui.available_width(), 200.0);
+        let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+        let cell = Vec2::new(
+            rect.width() / sample.len() as f32,
+            rect.height() / rows as f32,
+        );
+
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 0.0, Color32::from_gray(70));
+        for (column_index, column) in sample.iter().enumerate() {
+            for (row_index, _) in column.iter().enumerate().filter(|(_, is_null)| **is_null) {
+                let min = pos2(
+                    rect.left() + column_index as f32 * cell.x,
+                    rect.top() + row_index as f32 * cell.y,
+                );
+                painter.rect_filled(Rect::from_min_size(min, cell), 0.0, Color32::LIGHT_RED);
+            }
+        }
+
+        // Identify the column under the mouse pointer.
+        if let Some(pointer) = response.hover_pos() {
+            let column_index = ((pointer.x - rect.left()) / cell.x) as usize;
+            if let Some(column) = df.get_columns().get(column_index) {
+                response.on_hover_text(column.name().as_str());
+            }
+        }
+    }
+}
+
+/// Estimates the number of distinct values of each column with a HyperLogLog sketch,
+/// in a single pass and without the hash table of an exact count.
+///
+/// The result has one row per column of `df`; the types without an estimate are null.
+pub fn approx_distinct_counts(df: &DataFrame) -> PolarsResult<DataFrame> {
+    let (names, estimates): (Vec<String>, Vec<Option<u64>>) = df
+        .get_columns()
+        .iter()
+        .map(|column| {
+            let estimate = column.as_materialized_series().approx_n_unique();
+            (column.name().to_string(), estimate.ok().map(|n| n as u64))
+        })
+        .unzip();
+
+    DataFrame::new(vec![
+        Column::new("column".into(), names),
+        Column::new("≈ distinct".into(), estimates),
+    ])
+}
+
+/// Approximate distinct counts of the current result, estimated in the background.
+#[derive(Default)]
+pub struct DistinctEstimates {
+    /// Result of the estimates, shown or being computed.
+    data: Option<Arc<DataFrame>>,
+    pending: Option<Receiver<Result<DataFrame, String>>>,
+    result: Option<Result<DataFrame, String>>,
+}
+
+impl DistinctEstimates {
+    /// Shows the estimates of `df`, computed in the background whenever the result changes.
+    pub fn render(&mut self, ui: &mut Ui, df: &Arc<DataFrame>, handle: &Handle) {
+        if !self.data.as_ref().is_some_and(|data| Arc::ptr_eq(data, df)) {
+            self.start(df, handle, ui.ctx());
+        }
+        self.check_pending();
+
+        match &self.result {
+            None => {
+                ui.horizontal(|ui| {
+                    busy_indicator(ui);
+                    ui.label("Estimating…");
+                });
+            }
+            Some(Ok(estimates)) => render_dataframe_grid(ui, "distinct_estimates", estimates),
+            Some(Err(message)) => {
+                ui.colored_label(ui.visuals().error_fg_color, message);
+            }
+        }
+    }
+
+    fn start(&mut self, df: &Arc<DataFrame>, handle: &Handle, ctx: &Context) {
+        let (tx, rx) = oneshot::channel();
+        let data = Arc::clone(df);
+        let ctx = ctx.clone();
+
+        handle.spawn_blocking(move || {
+            let result =
+                approx_distinct_counts(&data).map_err(|e| format!("Distinct count error: {}", e));
+            let _ = tx.send(result);
+            ctx.request_repaint();
+        });
+
+        self.data = Some(Arc::clone(df));
+        self.pending = Some(rx);
+        self.result = None;
+    }
+
+    /// Stores the estimates once computed.
+    fn check_pending(&mut self) {
+        let Some(mut pending) = self.pending.take() else {
+            return;
+        };
+
+        match pending.try_recv() {
+            Ok(result) => self.result = Some(result),
+            Err(TryRecvError::Empty) => self.pending = Some(pending), // Still running.
+            Err(TryRecvError::Closed) => {
+                self.result = Some(Err("Distinct count was interrupted.".to_string()))
+            }
+        }
+    }
+}
+
+/// Summary statistics ([`describe`]) of the current result, computed in the background on demand.
+#[derive(Default)]
+pub struct SummaryStatistics {
+    pending: Option<Receiver<Result<DataFrame, String>>>,
+    result: Option<Result<DataFrame, String>>,
+}
+
+impl SummaryStatistics {
+    /// Whether statistics were computed, or failed, for the current result.
+    pub fn is_computed(&self) -> bool {
+        self.result.is_some()
+    }
+
+    /// Whether the statistics are being computed.
+    pub fn is_running(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Computes the statistics of `df` in the background, replacing the shown ones once done.
+    pub fn start(&mut self, df: &Arc<DataFrame>, handle: &Handle, ctx: &Context) {
+        let (tx, rx) = oneshot::channel();
+        let data = Arc::clone(df);
+        let ctx = ctx.clone();
+
+        handle.spawn_blocking(move || {
+            let result = describe(&data).map_err(|e| format!("Statistics error: {}", e));
+            let _ = tx.send(result);
+            ctx.request_repaint();
+        });
+
+        self.pending = Some(rx);
+    }
+
+    /// Shows the statistics, or a busy indicator while they are computed.
+    pub fn render(&mut self, ui: &mut Ui) {
+        self.check_pending();
+
+        if self.is_running() {
+            ui.horizontal(|ui| {
+                busy_indicator(ui);
+                ui.label("Computing statistics…");
+            });
+        }
+        match &self.result {
+            Some(Ok(statistics)) => render_dataframe_grid(ui, "statistics_grid", statistics),
+            Some(Err(message)) => {
+                ui.colored_label(ui.visuals().error_fg_color, message);
+            }
+            None => {}
+        }
+    }
+
+    /// Stores the statistics once computed.
+    fn check_pending(&mut self) {
+        let Some(mut pending) = self.pending.take() else {
+            return;
+        };
+
+        match pending.try_recv() {
+            Ok(result) => self.result = Some(result),
+            Err(TryRecvError::Empty) => self.pending = Some(pending), // Still running.
+            Err(TryRecvError::Closed) => {
+                self.result = Some(Err("Statistics were interrupted.".to_string()))
+            }
+        }
+    }
+}
+
+/// Counts the occurrences of each distinct value of a column, most frequent first.
+///
+/// Equivalent to `SELECT col, COUNT(*) AS count FROM df GROUP BY col ORDER BY count DESC`.
+pub fn value_counts(df: &DataFrame, column_name: &str) -> PolarsResult<DataFrame> {
+    df.clone()
+        .lazy()
+        .group_by([col(column_name)])
+        .agg([len().alias("count")])
+        .sort(
+            ["count"],
+            SortMultipleOptions::default()
+                .with_order_descending(true)
+                .with_maintain_order(true),
+        )
+        .collect()
+}
+
+/// Builds a SQL condition selecting the rows where the column equals the value.
+///
+/// Numbers and booleans are compared directly, other values as quoted strings.
+pub fn sql_equals_condition(column_name: &str, value: &AnyValue) -> String {
+    let column_name = format!("`{}`", column_name.replace('`', "``"));
+    match value {
+        AnyValue::Null => format!("{column_name} IS NULL"),
+        AnyValue::Boolean(b) => format!("{column_name} = {b}"),
+        av if av.dtype().is_primitive_numeric() => format!("{column_name} = {av}"),
+        av => {
+            let literal = format_any_value(av).replace('\'', "''"); // Escape single quotes.
+            format!("{column_name} = '{literal}'")
+        }
+    }
+}
+
+/// Popover displaying the value counts (frequency table) of a column.
+pub struct ValueCounts {
+    /// Name of the column.
+    column_name: String,
+    /// Result of [`value_counts`]: the distinct values and their counts; `None` while counted.
+    counts: Option<Result<DataFrame, String>>,
+    pending: Option<Receiver<Result<DataFrame, String>>>,
+    /// Filters of the current data, used as the base for a new filter.
+    filters: DataFilters,
+    /// Filters requested through the "Filter" buttons.
+    requested_filters: Option<DataFilters>,
+}
+
+impl ValueCounts {
+    /// Computes the value counts of a column of the current data in the background.
+    pub fn new(
+        df: &DataFrame,
+        column_name: &str,
+        filters: &DataFilters,
+        handle: &Handle,
+        ctx: &Context,
+    ) -> Self {
+        let (tx, rx) = oneshot::channel();
+        let (df, name) = (df.clone(), column_name.to_string());
+        let ctx = ctx.clone();
+
+        handle.spawn_blocking(move || {
+            let counts = value_counts(&df, &name)
+                .map_err(|e| format!("Value counts error for column {}: {}", name, e));
+            let _ = tx.send(counts);
+            ctx.request_repaint();
+        });
+
+        ValueCounts {
+            column_name: column_name.to_string(),
+            counts: None,
+            pending: Some(rx),
+            filters: filters.clone(),
+            requested_filters: None,
+        }
+    }
+
+    /// Stores the value counts once computed.
+    fn check_pending(&mut self) {
+        let Some(mut pending) = self.pending.take() else {
+            return;
+        };
+
+        match pending.try_recv() {
+            Ok(counts) => self.counts = Some(counts),
+            Err(TryRecvError::Empty) => self.pending = Some(pending), // Still running.
+            Err(TryRecvError::Closed) => {
+                self.counts = Some(Err("Value counts were interrupted.".to_string()))
+            }
+        }
+    }
+
+    /// Creates filters that keep, of the current result, only the rows with the given value.
+    ///
+    /// The condition wraps the last statement of the query, or follows the pipeline when
+    /// there is one, since the counted columns may have been renamed or computed by it.
+    fn filter_by(&self, value: &AnyValue) -> DataFilters {
+        let condition = sql_equals_condition(&self.column_name, value);
+        let mut filters = DataFilters {
+            sort: None,
+            then_by: Vec::new(),
+            ..self.filters.clone()
+        };
+        match filters.transforms.is_empty() {
+            true => {
+                let (preamble, source) =
+                    split_last_statement(filters.query.as_deref().unwrap_or(""));
+                let source =
+                    source.unwrap_or_else(|| format!("SELECT * FROM {}", filters.table_name));
+                filters.query = Some(format!(
+                    "{preamble}SELECT * FROM ({source}) AS counted WHERE {condition};"
+                ));
+            }
+            false => filters
+                .transforms
+                .push(ColumnTransform::Filter { condition }),
+        }
+        filters
+    }
+}
+
+impl Popover for ValueCounts {
+    /// Shows the frequency table with a "Filter" button per value.
+    fn show(&mut self, ctx: &Context) -> bool {
+        self.check_pending();
+        let mut open = true;
+        let mut requested_filters = None;
+
+        Window::new(format!("Value counts: {}", self.column_name))
+            .collapsible(false)
+            .open(&mut open)
+            .default_size([400.0, 400.0])
+            .show(ctx, |ui| {
+                let counts = match &self.counts {
+                    Some(Ok(counts)) => counts,
+                    None => {
+                        ui.horizontal(|ui| {
+                            busy_indicator(ui);
+                            ui.label("Counting the values…");
+                        });
+                        return;
+                    }
+                    Some(Err(error)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                        return;
+                    }
+                };
+                ui.label(format!("{} distinct values", counts.height()));
+
+                let text_height = TextStyle::Body.resolve(ui.style()).size;
+                let row_height = text_height + ui.spacing().item_spacing.y;
+                let (Ok(values), Ok(counts)) =
+                    (counts.column(&self.column_name), counts.column("count"))
+                else {
+                    return;
+                };
+
+                TableBuilder::new(ui)
+                    .striped(true)
+                    .column(TableColumn::remainder().at_least(100.0).clip(true))
+                    .column(TableColumn::auto())
+                    .column(TableColumn::auto())
+                    .cell_layout(Layout::left_to_right(Align::Center))
+                    .header(row_height, |mut header| {
+                        header.col(|ui| {
+                            ui.strong(self.column_name.as_str());
+                        });
+                        header.col(|ui| {
+                            ui.strong("count");
+                        });
+                        header.col(|_ui| {});
+                    })
+                    .body(|body| {
+                        body.rows(row_height, values.len(), |mut row| {
+                            let index = row.index();
+                            let value = values.get(index).unwrap_or(AnyValue::Null);
+                            let count = counts.get(index).unwrap_or(AnyValue::Null);
+
+                            row.col(|ui| {
+                                ui.label(format_any_value(&value));
+                            });
+                            row.col(|ui| {
+                                ui.label(format_any_value(&count));
+                            });
+                            row.col(|ui| {
+                                if ui
+                                    .small_button("Filter")
+                                    .on_hover_text("Show only the rows with this value")
+                                    .clicked()
+                                {
+                                    requested_filters = Some(self.filter_by(&value));
+                                }
+                            });
+                        });
+                    });
+            });
+
+        if requested_filters.is_some() {
+            self.requested_filters = requested_filters;
+        }
+
+        open
+    }
+
+    fn take_filters(&mut self) -> Option<DataFilters> {
+        self.requested_filters.take()
+    }
+}
+
+/// Converts the result of a reduction (min, max) to a display string.
+///
+/// Unsupported data types and null results yield `None`.
+fn reduce_to_string(result: PolarsResult<Scalar>) -> Option<String> {
+    let scalar = result.ok()?;
+    match scalar.value() {
+        AnyValue::Null => None,
+        value => Some(format_any_value(value)),
+    }
+}
+
+/// Formats a single value for display, without the quotes Polars adds to strings.
+pub fn format_any_value(value: &AnyValue) -> String {
+    match value {
+        AnyValue::Null => "".to_string(),
+        AnyValue::String(s) => s.to_string(),
+        AnyValue::StringOwned(s) => s.to_string(),
+        AnyValue::Float32(f) => format!("{f:.4}"),
+        AnyValue::Float64(f) => format!("{f:.4}"),
+        av => av.to_string(),
+    }
+}
+
+/// Renders a (small) DataFrame as a simple striped grid with a header row.
+///
+/// Intended for summary results such as [`describe`], not for the main table.
+pub fn render_dataframe_grid(ui: &mut Ui, id_salt: &str, df: &DataFrame) {
+    ScrollArea::horizontal().id_salt(id_salt).show(ui, |ui| {
+        Grid::new(id_salt)
+            .num_columns(df.width())
+            .spacing([10.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                // Header row with the column names.
+                for name in df.get_column_names() {
+                    ui.strong(name.as_str());
+                }
+                ui.end_row();
+
+                for row_index in 0..df.height() {
+                    for column in df.get_columns() {
+                        let value = column
+                            .get(row_index)
+                            .map(|any_value| format_any_value(&any_value))
+                            .unwrap_or_default();
+                        ui.label(value);
+                    }
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SqlFunctions, apply_transforms, execute_statements};
+    use polars::sql::SQLContext;
+
+    #[test]
+    fn test_describe() -> PolarsResult<()> {
+        let df = df![
+            "number" => [Some(1.0), Some(2.0), None, Some(4.0), Some(3.0)],
+            "text" => ["b", "a", "c", "a", "b"],
+        ]?;
+
+        let stats = describe(&df)?;
+        assert_eq!(stats.shape(), (2, DESCRIBE_STATISTICS.len() + 1));
+
+        // Statistics of the numeric column.
+        assert_eq!(stats.column("count")?.get(0)?, AnyValue::UInt64(4));
+        assert_eq!(stats.column("nulls")?.get(0)?, AnyValue::UInt64(1));
+        assert_eq!(stats.column("mean")?.get(0)?, AnyValue::Float64(2.5));
+        assert_eq!(stats.column("50%")?.get(0)?, AnyValue::Float64(2.5));
+        assert_eq!(stats.column("min")?.get(0)?, AnyValue::String("1.0000"));
+        assert_eq!(stats.column("max")?.get(0)?, AnyValue::String("4.0000"));
+
+        // Statistics of the string column.
+        assert_eq!(stats.column("mean")?.get(1)?, AnyValue::Null);
+        assert_eq!(stats.column("min")?.get(1)?, AnyValue::String("a"));
+        assert_eq!(stats.column("max")?.get(1)?, AnyValue::String("c"));
+        assert_eq!(stats.column("distinct")?.get(1)?, AnyValue::UInt64(3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_approx_distinct_counts() -> PolarsResult<()> {
+        let df = df![
+            "id" => (0..10_000).collect::<Vec<i64>>(),
+            "group" => (0..10_000).map(|i| format!("g{}", i % 50)).collect::<Vec<_>>(),
+        ]?;
+
+        let estimates = approx_distinct_counts(&df)?;
+        let counts: Vec<u64> = estimates
+            .column("≈ distinct")?
+            .u64()?
+            .into_no_null_iter()
+            .collect();
+
+        // HyperLogLog estimates are within a few percent.
+        assert!(counts[0].abs_diff(10_000) < 500, "{}", counts[0]);
+        assert!(counts[1].abs_diff(50) < 3, "{}", counts[1]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_correlation_matrix() -> PolarsResult<()> {
+        let df = df![
+            "a" => [1, 2, 3, 4],
+            "b" => [2.0, 4.0, 6.0, 8.0],
+            "c" => [4.0, 3.0, 2.0, 1.0],
+            "text" => ["w", "x", "y", "z"],
+        ]?;
+
+        let matrix = correlation_matrix(&df)?;
+        assert_eq!(matrix.names, ["a", "b", "c"]);
+
+        let rounded: Vec<Vec<Option<f64>>> = matrix
+            .values
+            .iter()
+            .map(|row| row.iter().map(|v| v.map(|v| v.round())).collect())
+            .collect();
+        assert_eq!(
+            rounded,
+            vec![
+                vec![Some(1.0), Some(1.0), Some(-1.0)],
+                vec![Some(1.0), Some(1.0), Some(-1.0)],
+                vec![Some(-1.0), Some(-1.0), Some(1.0)],
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_missingness_sample() -> PolarsResult<()> {
+        let df = df![
+            "a" => [Some(1), None, Some(3), None],
+            "b" => [Some("x"), Some("y"), None, Some("z")],
+        ]?;
+
+        let sample = missingness_sample(&df, 10);
+        assert_eq!(
+            sample,
+            vec![
+                vec![false, true, false, true],
+                vec![false, false, true, false]
+            ]
+        );
+
+        // Only every other row is sampled.
+        let sample = missingness_sample(&df, 2);
+        assert_eq!(sample, vec![vec![false, false], vec![false, true]]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_value_counts() -> PolarsResult<()> {
+        let df = df![
+            "text" => [Some("b"), Some("a"), None, Some("a"), Some("a"), Some("b")],
+        ]?;
+
+        let counts = value_counts(&df, "text")?;
+        let expected = df![
+            "text" => [Some("a"), Some("b"), None],
+            "count" => [3 as IdxSize, 2, 1],
+        ]?;
+        assert!(counts.equals_missing(&expected));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sql_equals_condition() {
+        let condition = sql_equals_condition("Tipo de Crédito", &AnyValue::String("it's"));
+        assert_eq!(condition, "`Tipo de Crédito` = 'it''s'");
+
+        let condition = sql_equals_condition("Ano", &AnyValue::Int64(2020));
+        assert_eq!(condition, "`Ano` = 2020");
+
+        let condition = sql_equals_condition("Ano", &AnyValue::Null);
+        assert_eq!(condition, "`Ano` IS NULL");
+    }
+
+    #[test]
+    fn test_value_counts_filter_by() -> Result<(), String> {
+        let df = df![
+            "region" => ["north", "south", "north", "north"],
+            "units" => [1, 2, 3, 4],
+        ]
+        .map_err(|e| e.to_string())?;
+        let run = |filters: &DataFilters| -> Result<DataFrame, String> {
+            let mut ctx = SQLContext::new();
+            ctx.register("AllData", df.clone().lazy());
+            let query = filters.query.as_deref().unwrap_or_default();
+            let lazyframe = execute_statements(&mut ctx, query, SqlFunctions::default())?;
+            apply_transforms(lazyframe, &filters.transforms)?
+                .collect()
+                .map_err(|e| e.to_string())
+        };
+
+        let runtime = tokio::runtime::Runtime::new().map_err(|e| e.to_string())?;
+        let ctx = Context::default();
+
+        // The filter of the query and its aliased column are kept.
+        let filters = DataFilters {
+            query: Some("SELECT region AS area, units FROM AllData WHERE units > 1;".to_string()),
+            ..DataFilters::new("sales.parquet")
+        };
+        let counts = ValueCounts::new(&run(&filters)?, "area", &filters, runtime.handle(), &ctx);
+        let filtered = run(&counts.filter_by(&AnyValue::String("north")))?;
+        let units = filtered.column("units").map_err(|e| e.to_string())?;
+        assert_eq!(
+            units.i32().map_err(|e| e.to_string())?.to_vec(),
+            [Some(3), Some(4)]
+        );
+
+        // A column renamed by the pipeline is filtered after it.
+        let filters = DataFilters {
+            transforms: vec![ColumnTransform::Rename {
+                from: "area".to_string(),
+                to: "zone".to_string(),
+            }],
+            ..filters
+        };
+        let counts = ValueCounts::new(&run(&filters)?, "zone", &filters, runtime.handle(), &ctx);
+        let filtered = run(&counts.filter_by(&AnyValue::String("south")))?;
+        assert_eq!(filtered.height(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_histogram() {
+        let values = [0.0, 1.0, 2.0, 3.0, 4.0, 10.0];
+
+        let (bins, width) = histogram(&values, 5);
+        assert_eq!(width, 2.0);
+        assert_eq!(
+            bins,
+            vec![(0.0, 2), (2.0, 2), (4.0, 1), (6.0, 0), (8.0, 1)] // 10.0 falls in the last bin
+        );
+
+        let (bins, width) = histogram(&[7.0, 7.0], 10);
+        assert_eq!((bins, width), (vec![(6.5, 2)], 1.0));
+
+        assert!(histogram(&[], 10).0.is_empty());
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{
+    DataFilters, DataFrameContainer, FileMetadata, GroupByQuery, TableScroll, ViewHistory,
+    file_label,
+};
+
+use egui::Ui;
+use std::sync::Arc;
+
+/// A result open in a tab, with its view: filters, scroll position and history.
+#[derive(Default)]
+pub struct ResultTab {
+    pub table: Arc<Option<DataFrameContainer>>,
+    pub data_filters: DataFilters,
+    pub metadata: Option<FileMetadata>,
+    pub scroll: TableScroll,
+    pub history: ViewHistory,
+    pub group_by: Option<GroupByQuery>,
+}
+
+impl ResultTab {
+    /// Name of the tab: the file of its result, or `Empty`.
+    pub fn title(table: &Option<DataFrameContainer>) -> String {
+        match table {
+            Some(table) => file_label(&table.filename),
+            None => "Empty".to_string(),
+        }
+    }
+}
+
+/// Tab clicked in the tab bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabAction {
+    Select(usize),
+    Close(usize),
+}
+
+/// Results open side by side in tabs. The app holds the state of the active tab while it is
+/// shown; its slot here is left empty until another tab is selected.
+pub struct ResultTabs {
+    tabs: Vec<ResultTab>,
+    active: usize,
+}
+
+impl Default for ResultTabs {
+    fn default() -> Self {
+        ResultTabs {
+            tabs: vec![ResultTab::default()],
+            active: 0,
+        }
+    }
+}
+
+impl ResultTabs {
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    /// Index of the tab shown.
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// Keeps the state of the shown tab and opens an empty tab after the others.
+    pub fn open(&mut self, current: ResultTab) {
+        self.tabs[self.active] = current;
+        self.tabs.push(ResultTab::default());
+        self.active = self.tabs.len() - 1;
+    }
+
+    /// Keeps the state of the shown tab and returns the state of the tab `index` to show.
+    pub fn select(&mut self, index: usize, current: ResultTab) -> ResultTab {
+        self.tabs[self.active] = current;
+        self.active = index.min(self.tabs.len() - 1);
+        std::mem::take(&mut self.tabs[self.active])
+    }
+
+    /// Closes the tab `index`, if it is not the last one, and returns the state to show:
+    /// `current` unless the shown tab is closed, then its neighbour.
+    pub fn close(&mut self, index: usize, current: ResultTab) -> ResultTab {
+        if self.tabs.len() < 2 || index >= self.tabs.len() {
+            return current;
+        }
+
+        self.tabs.remove(index);
+        match index.cmp(&self.active) {
+            std::cmp::Ordering::Less => {
+                self.active -= 1;
+                current
+            }
+            std::cmp::Ordering::Equal => {
+                self.active = index.min(self.tabs.len() - 1);
+                std::mem::take(&mut self.tabs[self.active])
+            }
+            std::cmp::Ordering::Greater => current,
+        }
+    }
+
+    /// Shows the tab bar, when there are several tabs, with `active_title` naming the shown
+    /// tab; returns the tab clicked. The tabs are not switched while `enabled` is false,
+    /// such as while a result is loading into the shown tab.
+    pub fn render(&self, ui: &mut Ui, active_title: &str, enabled: bool) -> Option<TabAction> {
+        if self.tabs.len() < 2 {
+            return None;
+        }
+
+        let mut action = None;
+        ui.add_enabled_ui(enabled, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for (index, tab) in self.tabs.iter().enumerate() {
+                    let selected = index == self.active;
+                    let title = match selected {
+                        true => active_title.to_string(),
+                        false => ResultTab::title(&tab.table),
+                    };
+                    let mut response = ui.selectable_label(selected, title);
+                    if let Some(query) = tab
+                        .table
+                        .as_ref()
+                        .as_ref()
+                        .and_then(|t| t.filters.query.as_deref())
+                    {
+                        response = response.on_hover_text(query);
+                    }
+                    if response.clicked() && !selected {
+                        action = Some(TabAction::Select(index));
+                    }
+                    if ui
+                        .small_button("×")
+                        .on_hover_text("Close the tab")
+                        .clicked()
+                    {
+                        action = Some(TabAction::Close(index));
+                    }
+                    ui.separator();
+                }
+            });
+        });
+        action
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A tab told apart by its query.
+    fn tab(query: &str) -> ResultTab {
+        ResultTab {
+            data_filters: DataFilters {
+                query: Some(query.to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn query(tab: &ResultTab) -> Option<&str> {
+        tab.data_filters.query.as_deref()
+    }
+
+    #[test]
+    fn test_result_tabs() {
+        let mut tabs = ResultTabs::default();
+        assert_eq!((tabs.len(), tabs.active()), (1, 0));
+
+        // A new tab keeps the state of the shown one.
+        tabs.open(tab("a"));
+        tabs.open(tab("b"));
+        assert_eq!((tabs.len(), tabs.active()), (3, 2));
+        let shown = tabs.select(0, tab("c"));
+        assert_eq!(query(&shown), Some("a"));
+        let shown = tabs.select(2, shown);
+        assert_eq!(query(&shown), Some("c"));
+
+        // Closing a tab before the shown one keeps it shown.
+        let shown = tabs.close(0, shown);
+        assert_eq!((tabs.len(), tabs.active()), (2, 1));
+        assert_eq!(query(&shown), Some("c"));
+
+        // Closing the shown tab shows its neighbour; the last tab stays open.
+        let shown = tabs.close(1, shown);
+        assert_eq!((tabs.len(), tabs.active()), (1, 0));
+        assert_eq!(query(&shown), Some("b"));
+        let shown = tabs.close(0, shown);
+        assert_eq!(tabs.len(), 1);
+        assert_eq!(query(&shown), Some("b"));
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{
+    DataFilters, DataFuture, DataResult, LoadProgress, busy_indicator, progress_repaint_interval,
+};
+
+use egui::{Context, Grid, ProgressBar, Ui};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{
+    runtime::Runtime,
+    sync::oneshot::{self, error::TryRecvError},
+    task::JoinHandle,
+};
+
+/// Operations taking at least this long notify their completion.
+pub const LONG_TASK_DURATION: Duration = Duration::from_secs(2);
+
+/// A load, query or other data operation running in the background.
+pub struct DataTask {
+    /// Description of the operation, such as `Open data.parquet`.
+    pub label: String,
+    /// When the operation was started.
+    pub started: Instant,
+    /// Progress reported by the operation.
+    pub progress: Arc<LoadProgress>,
+    /// Filters running the operation again, if it is a load.
+    pub retry: Option<DataFilters>,
+    /// The result opens in a new tab, rather than replacing the shown one.
+    pub new_tab: bool,
+    /// Handle of the Tokio task.
+    handle: JoinHandle<()>,
+    /// Channel receiving the result of the operation.
+    receiver: oneshot::Receiver<DataResult>,
+}
+
+impl DataTask {
+    /// Spawns a `DataFuture` on the runtime; the UI is repainted when it completes.
+    pub fn spawn(
+        label: impl ToString,
+        future: DataFuture,
+        runtime: &Runtime,
+        ctx: &Context,
+    ) -> Self {
+        // Create a oneshot channel for sending the data from the async task to the UI thread.
+        let (tx, receiver) = oneshot::channel::<DataResult>();
+
+        // Progress reported by the future, read by the UI.
+        let progress = Arc::new(LoadProgress::default());
+        let task_progress = Arc::clone(&progress);
+
+        // Clone the context for use within the asynchronous task (to request repaints).
+        let ctx = ctx.clone();
+
+        let handle = runtime.spawn(async move {
+            let data = task_progress.scope(future).await;
+            if tx.send(data).is_err() {
+                eprintln!("Receiver dropped before data could be sent."); // The task was cancelled.
+            }
+            ctx.request_repaint(); // Request a repaint of the UI to display the loaded data.
+        });
+
+        DataTask {
+            label: label.to_string(),
+            started: Instant::now(),
+            progress,
+            retry: None,
+            new_tab: false,
+            handle,
+            receiver,
+        }
+    }
+
+    /// The result of the operation, once it has completed.
+    pub fn try_result(&mut self) -> Option<DataResult> {
+        match self.receiver.try_recv() {
+            Ok(data) => Some(data),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Closed) => {
+                // The task was terminated unexpectedly.
+                let msg = "Data operation terminated without response.";
+                Some(Err(msg.to_string()))
+            }
+        }
+    }
+
+    /// Stops the operation; its result, if any, is discarded.
+    pub fn cancel(self) {
+        self.progress.cancel(); // Stops reading at the next row group.
+        self.handle.abort();
+    }
+
+    /// Shows the running tasks with their elapsed time; returns the index of the task to cancel.
+    pub fn render_list(tasks: &[DataTask], ui: &mut Ui) -> Option<usize> {
+        if tasks.is_empty() {
+            ui.label("No running tasks.");
+            return None;
+        }
+
+        let mut cancel = None;
+
+        Grid::new("tasks_grid")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                for (idx, task) in tasks.iter().enumerate() {
+                    ui.label(&task.label);
+                    ui.label(format!("{:.1} s", task.started.elapsed().as_secs_f32()));
+                    match task.progress.fraction() {
+                        Some(fraction) => {
+                            let bar = ProgressBar::new(fraction).desired_width(120.0);
+                            ui.add(bar).on_hover_text(task.progress.text());
+                        }
+                        None => {
+                            busy_indicator(ui);
+                        }
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancel = Some(idx);
+                    }
+                    ui.end_row();
+                }
+            });
+
+        // Keep the elapsed times up to date.
+        ui.ctx()
+            .request_repaint_after(progress_repaint_interval(ui.ctx()));
+
+        cancel
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_task_result() {
+        let runtime = Runtime::new().unwrap();
+        let ctx = Context::default();
+
+        let future: DataFuture = Box::new(Box::pin(async { Err("failed".to_string()) }));
+        let mut task = DataTask::spawn("Query", future, &runtime, &ctx);
+
+        let result = loop {
+            if let Some(result) = task.try_result() {
+                break result;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        };
+        assert_eq!(result.err().as_deref(), Some("failed"));
+        assert_eq!(task.label, "Query");
+    }
+}
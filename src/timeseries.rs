@@ -0,0 +1,449 @@
+use crate::{TableViewOptions, busy_indicator, format_column_cell};
+
+use chrono::{Duration, NaiveDate};
+use egui::{ComboBox, Context, Ui};
+use egui_extras::{Column, TableBuilder};
+use egui_plot::{GridMark, Legend, Line, Plot, PlotPoints, Points};
+use polars::prelude::*;
+use std::{fmt, ops::RangeInclusive, sync::Arc};
+use tokio::{
+    runtime::Handle,
+    sync::oneshot::{self, Receiver, error::TryRecvError},
+};
+
+/// Time bucket of a resampled series.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimeBucket {
+    #[default]
+    Day,
+    /// Weeks starting on Monday.
+    Week,
+    Month,
+}
+
+impl TimeBucket {
+    pub const ALL: [TimeBucket; 3] = [TimeBucket::Day, TimeBucket::Week, TimeBucket::Month];
+
+    /// Duration of the bucket, in the Polars duration language.
+    fn every(&self) -> &'static str {
+        match self {
+            TimeBucket::Day => "1d",
+            TimeBucket::Week => "1w",
+            TimeBucket::Month => "1mo",
+        }
+    }
+}
+
+impl fmt::Display for TimeBucket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeBucket::Day => write!(f, "Day"),
+            TimeBucket::Week => write!(f, "Week"),
+            TimeBucket::Month => write!(f, "Month"),
+        }
+    }
+}
+
+/// Aggregate of the values of each time bucket.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimeAggregation {
+    #[default]
+    Sum,
+    Mean,
+    Min,
+    Max,
+    /// Rows of the bucket, with or without a value column.
+    Count,
+}
+
+impl TimeAggregation {
+    pub const ALL: [TimeAggregation; 5] = [
+        TimeAggregation::Sum,
+        TimeAggregation::Mean,
+        TimeAggregation::Min,
+        TimeAggregation::Max,
+        TimeAggregation::Count,
+    ];
+
+    /// Aggregate of `value`, named like the columns of a grouped result: "sum amount".
+    fn expr(&self, value: Option<&str>) -> Option<Expr> {
+        let expr = match (self, value) {
+            (TimeAggregation::Count, _) => return Some(len().alias("count")),
+            (_, None) => return None,
+            (TimeAggregation::Sum, Some(value)) => col(value).sum(),
+            (TimeAggregation::Mean, Some(value)) => col(value).mean(),
+            (TimeAggregation::Min, Some(value)) => col(value).min(),
+            (TimeAggregation::Max, Some(value)) => col(value).max(),
+        };
+        let name = format!(
+            "{} {}",
+            self.to_string().to_lowercase(),
+            value.unwrap_or_default()
+        );
+        Some(expr.alias(name))
+    }
+}
+
+impl fmt::Display for TimeAggregation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeAggregation::Sum => write!(f, "Sum"),
+            TimeAggregation::Mean => write!(f, "Mean"),
+            TimeAggregation::Min => write!(f, "Min"),
+            TimeAggregation::Max => write!(f, "Max"),
+            TimeAggregation::Count => write!(f, "Count"),
+        }
+    }
+}
+
+/// Columns and buckets of the time series selected by the user.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TimeSeriesSettings {
+    /// Date or datetime column.
+    pub time: Option<String>,
+    /// Numeric column aggregated; not needed to count the rows.
+    pub value: Option<String>,
+    pub bucket: TimeBucket,
+    pub aggregation: TimeAggregation,
+}
+
+/// Date and datetime columns of `df`.
+pub fn temporal_columns(df: &DataFrame) -> Vec<String> {
+    df.get_columns()
+        .iter()
+        .filter(|column| matches!(column.dtype(), DataType::Date | DataType::Datetime(..)))
+        .map(|column| column.name().to_string())
+        .collect()
+}
+
+/// Groups the rows of `df` by time bucket, aggregating the value column, in time order.
+///
+/// The bucket column keeps the name of the time column, as the date each bucket starts on.
+pub fn resample(df: &DataFrame, settings: &TimeSeriesSettings) -> PolarsResult<DataFrame> {
+    let Some(time) = settings.time.as_deref() else {
+        polars_bail!(ComputeError: "no time column selected");
+    };
+    let Some(aggregate) = settings.aggregation.expr(settings.value.as_deref()) else {
+        polars_bail!(ComputeError: "no value column selected");
+    };
+
+    let bucket = col(time)
+        .dt()
+        .truncate(lit(settings.bucket.every()))
+        .cast(DataType::Date);
+    df.clone()
+        .lazy()
+        .filter(col(time).is_not_null())
+        .group_by([bucket])
+        .agg([aggregate])
+        .sort([time], SortMultipleOptions::default())
+        .collect()
+}
+
+/// Date of a plot coordinate, in days since the Unix epoch.
+fn format_day(days: f64) -> String {
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap_or_default();
+    Duration::try_days(days.round() as i64)
+        .and_then(|days| epoch.checked_add_signed(days))
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Resampled series computed for given settings and data.
+struct TimeSeriesCache {
+    settings: TimeSeriesSettings,
+    df: Arc<DataFrame>,
+    /// The series, once resampled in the background.
+    resampled: Option<Result<DataFrame, String>>,
+    pending: Option<Receiver<Result<DataFrame, String>>>,
+}
+
+impl TimeSeriesCache {
+    /// Resamples `df` in the background.
+    fn start(
+        settings: &TimeSeriesSettings,
+        df: &Arc<DataFrame>,
+        handle: &Handle,
+        ctx: &Context,
+    ) -> Self {
+        let (tx, rx) = oneshot::channel();
+        let (data, resampled_settings) = (Arc::clone(df), settings.clone());
+        let ctx = ctx.clone();
+
+        handle.spawn_blocking(move || {
+            let result = resample(&data, &resampled_settings).map_err(|e| e.to_string());
+            let _ = tx.send(result);
+            ctx.request_repaint();
+        });
+
+        TimeSeriesCache {
+            settings: settings.clone(),
+            df: Arc::clone(df),
+            resampled: None,
+            pending: Some(rx),
+        }
+    }
+
+    /// Stores the series once resampled.
+    fn check_pending(&mut self) {
+        let Some(mut pending) = self.pending.take() else {
+            return;
+        };
+
+        match pending.try_recv() {
+            Ok(result) => self.resampled = Some(result),
+            Err(TryRecvError::Empty) => self.pending = Some(pending), // Still running.
+            Err(TryRecvError::Closed) => {
+                self.resampled = Some(Err("Resampling was interrupted.".to_string()))
+            }
+        }
+    }
+}
+
+/// Date and numeric columns of a result, listed once per result rather than every frame.
+struct ColumnChoices {
+    df: Arc<DataFrame>,
+    time: Vec<String>,
+    values: Vec<String>,
+}
+
+/// View of a date or datetime column grouped by day, week or month, with a value aggregated
+/// in each bucket, shown as a line chart above its table.
+#[derive(Default)]
+pub struct TimeSeriesPane {
+    pub settings: TimeSeriesSettings,
+    /// Series resampled for the last settings and data, to avoid recomputing them every frame.
+    cache: Option<TimeSeriesCache>,
+    /// Columns of the last result shown.
+    columns: Option<ColumnChoices>,
+}
+
+impl TimeSeriesPane {
+    /// The date, datetime and numeric columns of `df`.
+    fn columns(&mut self, df: &Arc<DataFrame>) -> &ColumnChoices {
+        let columns = match self.columns.take() {
+            Some(columns) if Arc::ptr_eq(&columns.df, df) => columns,
+            _ => ColumnChoices {
+                df: Arc::clone(df),
+                time: temporal_columns(df),
+                values: df
+                    .get_columns()
+                    .iter()
+                    .filter(|column| column.dtype().is_primitive_numeric())
+                    .map(|column| column.name().to_string())
+                    .collect(),
+            },
+        };
+        self.columns.insert(columns)
+    }
+
+    /// Whether `df` has a date or datetime column to resample.
+    pub fn has_time_columns(&mut self, df: &Arc<DataFrame>) -> bool {
+        !self.columns(df).time.is_empty()
+    }
+
+    /// Renders the controls, the chart and the table of the resampled series;
+    /// the series is resampled on `handle`.
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        df: &Arc<DataFrame>,
+        options: &TableViewOptions,
+        handle: &Handle,
+    ) {
+        let columns = self.columns(df);
+        let (time_columns, value_columns) = (columns.time.clone(), columns.values.clone());
+        if time_columns.is_empty() {
+            ui.label("The result has no date or datetime column.");
+            return;
+        }
+        if !self
+            .settings
+            .time
+            .as_ref()
+            .is_some_and(|time| time_columns.contains(time))
+        {
+            self.settings.time = time_columns.first().cloned();
+        }
+
+        ui.horizontal_wrapped(|ui| {
+            let settings = &mut self.settings;
+            ComboBox::from_label("Time")
+                .selected_text(settings.time.as_deref().unwrap_or_default())
+                .show_ui(ui, |ui| {
+                    for name in &time_columns {
+                        ui.selectable_value(&mut settings.time, Some(name.clone()), name);
+                    }
+                });
+            ComboBox::from_label("Bucket")
+                .selected_text(settings.bucket.to_string())
+                .show_ui(ui, |ui| {
+                    for bucket in TimeBucket::ALL {
+                        ui.selectable_value(&mut settings.bucket, bucket, bucket.to_string());
+                    }
+                });
+            ComboBox::from_label("Aggregate")
+                .selected_text(settings.aggregation.to_string())
+                .show_ui(ui, |ui| {
+                    for aggregation in TimeAggregation::ALL {
+                        let text = aggregation.to_string();
+                        ui.selectable_value(&mut settings.aggregation, aggregation, text);
+                    }
+                });
+            ComboBox::from_label("Value")
+                .selected_text(settings.value.as_deref().unwrap_or("(none)"))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut settings.value, None, "(none)");
+                    for name in &value_columns {
+                        ui.selectable_value(&mut settings.value, Some(name.clone()), name);
+                    }
+                });
+        });
+
+        ui.separator();
+
+        if self.settings.value.is_none() && self.settings.aggregation != TimeAggregation::Count {
+            ui.label("Select the value column to aggregate, or count the rows.");
+            return;
+        }
+
+        // Resample only if the settings or the data changed.
+        let up_to_date = self
+            .cache
+            .as_ref()
+            .is_some_and(|cache| cache.settings == self.settings && Arc::ptr_eq(&cache.df, df));
+        if !up_to_date {
+            let cache = TimeSeriesCache::start(&self.settings, df, handle, ui.ctx());
+            self.cache = Some(cache);
+        }
+        let Some(cache) = self.cache.as_mut() else {
+            return;
+        };
+        cache.check_pending();
+
+        let resampled = match &cache.resampled {
+            Some(Ok(resampled)) => resampled,
+            Some(Err(message)) => {
+                ui.colored_label(ui.visuals().error_fg_color, message);
+                return;
+            }
+            None => {
+                ui.horizontal(|ui| {
+                    busy_indicator(ui);
+                    ui.label("Resampling…");
+                });
+                return;
+            }
+        };
+
+        // The dates are the days since the epoch; the values, the last column.
+        let points: Vec<[f64; 2]> = match (
+            resampled[0].cast(&DataType::Int32),
+            resampled[resampled.width() - 1].cast(&DataType::Float64),
+        ) {
+            (Ok(days), Ok(values)) => days
+                .i32()
+                .into_iter()
+                .flatten()
+                .zip(values.f64().into_iter().flatten())
+                .filter_map(|(day, value)| Some([day? as f64, value?]))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let name = resampled[resampled.width() - 1].name().to_string();
+
+        let chart_height = (ui.available_height() * 0.6).max(120.0);
+        Plot::new("time_series_plot")
+            .height(chart_height)
+            .legend(Legend::default())
+            .x_axis_formatter(|mark: GridMark, _range: &RangeInclusive<f64>| format_day(mark.value))
+            .label_formatter(|name, point| {
+                format!("{name}\n{}: {:.2}", format_day(point.x), point.y)
+            })
+            .show(ui, |plot_ui| {
+                let line = PlotPoints::from(points.clone());
+                plot_ui.line(Line::new(line).name(&name));
+                let markers = PlotPoints::from(points);
+                plot_ui.points(Points::new(markers).radius(2.5).name(&name));
+            });
+
+        ui.separator();
+
+        let row_height = ui.text_style_height(&egui::TextStyle::Body) + 4.0;
+        TableBuilder::new(ui)
+            .id_salt("time_series_table")
+            .striped(true)
+            .columns(
+                Column::auto().at_least(120.0).resizable(true),
+                resampled.width(),
+            )
+            .header(row_height, |mut header| {
+                for name in resampled.get_column_names() {
+                    header.col(|ui| {
+                        ui.strong(name.as_str());
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(row_height, resampled.height(), |mut row| {
+                    let index = row.index();
+                    for column in resampled.get_columns() {
+                        let text = format_column_cell(column, index, None, options);
+                        row.col(|ui| {
+                            ui.label(text);
+                        });
+                    }
+                });
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resample() -> PolarsResult<()> {
+        let dates = [
+            "2024-01-30",
+            "2024-01-31",
+            "2024-02-01",
+            "2024-02-15",
+            "2024-03-04",
+        ];
+        let df = df!["day" => dates, "amount" => [1.0, 2.0, 3.0, 4.0, 5.0]]?
+            .lazy()
+            .with_column(col("day").cast(DataType::Date))
+            .collect()?;
+        assert_eq!(temporal_columns(&df), vec!["day".to_string()]);
+
+        let mut settings = TimeSeriesSettings {
+            time: Some("day".to_string()),
+            value: Some("amount".to_string()),
+            bucket: TimeBucket::Month,
+            aggregation: TimeAggregation::Sum,
+        };
+        let monthly = resample(&df, &settings)?;
+        let days = monthly["day"].cast(&DataType::Int32)?;
+        let days: Vec<String> = days
+            .i32()?
+            .into_no_null_iter()
+            .map(|d| format_day(d as f64))
+            .collect();
+        assert_eq!(days, ["2024-01-01", "2024-02-01", "2024-03-01"]);
+        let sums: Vec<f64> = monthly["sum amount"].f64()?.into_no_null_iter().collect();
+        assert_eq!(sums, [3.0, 7.0, 5.0]);
+
+        // Weeks start on Monday; counting needs no value column.
+        settings.bucket = TimeBucket::Week;
+        settings.aggregation = TimeAggregation::Count;
+        settings.value = None;
+        let weekly = resample(&df, &settings)?;
+        assert_eq!(weekly.height(), 3);
+        assert_eq!(
+            weekly["count"].cast(&DataType::Int64)?.i64()?.get(0),
+            Some(3)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{Error, notify_finished};
+
+use egui::{Align2, Area, Color32, Context, Frame, Id, Label, RichText, Sense};
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// How long a toast is displayed.
+pub const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+/// Kind of a toast, which sets its color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Success,
+    Error,
+}
+
+/// A short notification displayed in a corner of the window, which disappears by itself.
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub kind: ToastKind,
+    pub message: String,
+    /// When the toast was added.
+    pub created: Instant,
+    /// Error shown in detail when the toast is clicked.
+    pub error: Option<Error>,
+}
+
+/// Notifications of the background operations.
+///
+/// Clones share the same toasts, so that background tasks can notify their completion.
+#[derive(Debug, Clone, Default)]
+pub struct Toasts {
+    toasts: Arc<Mutex<Vec<Toast>>>,
+}
+
+impl Toasts {
+    /// Adds a toast.
+    pub fn push(&self, kind: ToastKind, message: impl ToString) {
+        self.push_toast(Toast {
+            kind,
+            message: message.to_string(),
+            created: Instant::now(),
+            error: None,
+        });
+    }
+
+    fn push_toast(&self, toast: Toast) {
+        if let Ok(mut toasts) = self.toasts.lock() {
+            toasts.push(toast);
+        }
+    }
+
+    pub fn success(&self, message: impl ToString) {
+        self.push(ToastKind::Success, message);
+    }
+
+    pub fn error(&self, message: impl ToString) {
+        self.push(ToastKind::Error, message);
+    }
+
+    /// Notifies a failed operation; clicking the toast shows the error in detail.
+    pub fn failure(&self, label: &str, error: Error) {
+        self.push_toast(Toast {
+            kind: ToastKind::Error,
+            message: format!("{} failed: {}", label, error.message),
+            created: Instant::now(),
+            error: Some(error),
+        });
+    }
+
+    /// Notifies the outcome of a background export, on the desktop too if it took more than
+    /// `notify_after` seconds since `started` while the window was unfocused, and repaints to show it.
+    pub fn notify_export(
+        &self,
+        ctx: &Context,
+        started: Instant,
+        notify_after: u64,
+        outcome: Result<String, String>,
+    ) {
+        let outcome = outcome.map_err(|message| format!("Export failed: {message}"));
+        let message = outcome.as_ref().unwrap_or_else(|message| message);
+        notify_finished(ctx, started, notify_after, message);
+        match outcome {
+            Ok(message) => self.success(message),
+            Err(message) => self.error(message),
+        }
+        ctx.request_repaint();
+    }
+
+    /// Toasts currently displayed; expired toasts are removed.
+    pub fn current(&self) -> Vec<Toast> {
+        let Ok(mut toasts) = self.toasts.lock() else {
+            return Vec::new();
+        };
+        toasts.retain(|toast| toast.created.elapsed() < TOAST_DURATION);
+        toasts.clone()
+    }
+
+    /// Removes a toast.
+    fn dismiss(&self, created: Instant) {
+        if let Ok(mut toasts) = self.toasts.lock() {
+            toasts.retain(|toast| toast.created != created);
+        }
+    }
+
+    /// Shows the toasts in the bottom-right corner.
+    ///
+    /// Clicking a toast dismisses it; returns the error of an error toast clicked,
+    /// so that its details can be displayed.
+    pub fn show(&self, ctx: &Context) -> Option<Error> {
+        let toasts = self.current();
+        if toasts.is_empty() {
+            return None;
+        }
+
+        let mut clicked = None;
+
+        Area::new(Id::new("toasts"))
+            .anchor(Align2::RIGHT_BOTTOM, [-10.0, -40.0])
+            .show(ctx, |ui| {
+                for toast in toasts.iter() {
+                    let color = match toast.kind {
+                        ToastKind::Success => Color32::from_rgb(0, 150, 0),
+                        ToastKind::Error => ui.visuals().error_fg_color,
+                    };
+
+                    Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.set_max_width(400.0);
+                        let text = RichText::new(&toast.message).color(color);
+                        let response = ui.add(Label::new(text).sense(Sense::click()));
+                        if response.on_hover_text("Click to dismiss").clicked() {
+                            self.dismiss(toast.created);
+                            clicked = match &toast.error {
+                                Some(error) => Some(error.clone()),
+                                None if toast.kind == ToastKind::Error => {
+                                    Some(Error::new(&toast.message))
+                                }
+                                None => None,
+                            };
+                        }
+                    });
+                }
+            });
+
+        // Repaint when the oldest toast expires.
+        if let Some(oldest) = toasts.first() {
+            ctx.request_repaint_after(TOAST_DURATION.saturating_sub(oldest.created.elapsed()));
+        }
+
+        clicked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toasts_are_shared() {
+        let toasts = Toasts::default();
+        let background = toasts.clone();
+
+        std::thread::spawn(move || background.success("Report exported: 10 rows → out.html"))
+            .join()
+            .unwrap();
+        toasts.error("Query failed: syntax error");
+
+        let current = toasts.current();
+        assert_eq!(current.len(), 2);
+        assert_eq!(current[0].kind, ToastKind::Success);
+
+        toasts.dismiss(current[1].created);
+        assert_eq!(toasts.current().len(), 1);
+    }
+}
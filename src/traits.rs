@@ -1,9 +1,9 @@
-use crate::SortState;
+use crate::{AppConfig, DataFilters, Session, SortState};
 
 use egui::{
-    Align, Color32, Context,
+    Align, CollapsingHeader, Color32, Context,
     FontFamily::Proportional,
-    FontId, Frame, Layout, Response, Stroke,
+    FontId, Frame, Label, Layout, Response, RichText, Stroke,
     TextStyle::{Body, Button, Heading, Monospace, Small},
     Ui, Vec2, WidgetText, Window,
 };
@@ -12,6 +12,9 @@ use egui::{
 pub trait MyStyle {
     /// Sets the initial style for the egui context.
     fn set_style_init(&self);
+
+    /// Scales the text styles of both themes to the given body font size.
+    fn set_font_size(&self, size: f32);
 }
 
 impl MyStyle for Context {
@@ -38,37 +41,88 @@ impl MyStyle for Context {
         // Mutate global style with above changes
         self.set_style(style);
     }
+
+    fn set_font_size(&self, size: f32) {
+        // Same proportions as the initial style, whose body text is 16 points.
+        self.all_styles_mut(|style| {
+            style.text_styles = [
+                (Small, FontId::new(size * 0.75, Proportional)),
+                (Body, FontId::new(size, Proportional)),
+                (Monospace, FontId::new(size * 0.875, Proportional)),
+                (Button, FontId::new(size * 0.875, Proportional)),
+                (Heading, FontId::new(size * 0.875, Proportional)),
+            ]
+            .into();
+        });
+    }
 }
 
 // Trait for popover windows.
 pub trait Popover {
     fn show(&mut self, ctx: &Context) -> bool;
-}
-
-// Settings popover struct (currently disabled).
-pub struct Settings {}
 
-impl Popover for Settings {
-    fn show(&mut self, ctx: &Context) -> bool {
-        let mut open = true;
+    /// Takes the filters requested by the user through the popover, if any.
+    ///
+    /// The application loads the data with these filters after showing the popover.
+    fn take_filters(&mut self) -> Option<DataFilters> {
+        None
+    }
 
-        // Create a window named "Settings".
-        Window::new("Settings")
-            .collapsible(false) // Make the window non-collapsible.
-            .open(&mut open) // Control the window's open state.
-            .show(ctx, |ui| {
-                ctx.style_ui(ui, egui::Theme::Dark); // Apply dark theme.
-                ui.disable(); // Disable user interaction.
-            });
+    /// Takes the settings applied by the user through the popover, if any.
+    fn take_config(&mut self) -> Option<AppConfig> {
+        None
+    }
 
-        open // Return whether the window is open.
+    /// Takes the session restored by the user through the popover, if any.
+    fn take_session(&mut self) -> Option<Session> {
+        None
     }
 }
 
 /// Error popover struct.
+#[derive(Debug, Clone, Default)]
 pub struct Error {
     /// The error message to display.
     pub message: String,
+    /// File the failed operation was reading, if any.
+    pub path: Option<String>,
+    /// Filters reloading the data, offered as a retry for failed loads.
+    pub retry: Option<DataFilters>,
+    /// Filters requested by the Retry button, taken by the application.
+    requested: Option<DataFilters>,
+}
+
+impl Error {
+    pub fn new(message: impl ToString) -> Self {
+        Error {
+            message: message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Offers to run the load again with the given filters.
+    pub fn with_retry(mut self, filters: DataFilters) -> Self {
+        self.path = filters.filename.clone();
+        self.retry = Some(filters);
+        self
+    }
+
+    /// The error followed by its causes, from the `context: cause` parts of the message.
+    pub fn chain(&self) -> Vec<&str> {
+        self.message
+            .split(": ")
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .collect()
+    }
+
+    /// Full text of the error, with the file path, to be copied.
+    pub fn report(&self) -> String {
+        match &self.path {
+            Some(path) => format!("{}\nFile: {}", self.message, path),
+            None => self.message.clone(),
+        }
+    }
 }
 
 impl Popover for Error {
@@ -96,14 +150,52 @@ impl Popover for Error {
                             .outer_margin(2.0) // Set a margin outside the frame.
                             .inner_margin(10.0) // Set a margin inside the frame.
                             .show(ui, |ui| {
-                                ui.colored_label(Color32::BLACK, &self.message); // Display the error message in black.
-                                ui.disable(); // Disable user interaction within the frame.
+                                // Selectable, so that the message can be copied.
+                                let text = RichText::new(&self.message).color(Color32::BLACK);
+                                ui.add(Label::new(text).selectable(true));
                             });
+
+                        CollapsingHeader::new("Details").show(ui, |ui| {
+                            if let Some(path) = &self.path {
+                                ui.add(Label::new(format!("File: {path}")).selectable(true));
+                            }
+                            for (idx, cause) in self.chain().iter().enumerate() {
+                                let line = match idx {
+                                    0 => cause.to_string(),
+                                    _ => format!("{}caused by: {}", "  ".repeat(idx), cause),
+                                };
+                                ui.add(
+                                    Label::new(RichText::new(line).monospace()).selectable(true),
+                                );
+                            }
+                        });
+
+                        ui.horizontal(|ui| {
+                            if ui.button("Copy").clicked() {
+                                ui.ctx().copy_text(self.report());
+                            }
+                            if let Some(filters) = &self.retry {
+                                if ui
+                                    .button("Retry")
+                                    .on_hover_text(
+                                        "Run the load again, e.g. once the file is available",
+                                    )
+                                    .clicked()
+                                {
+                                    self.requested = Some(filters.clone());
+                                }
+                            }
+                        });
                     },
                 );
             });
 
-        open // Return whether the window is open. The window will close if `open` is set to `false` elsewhere.
+        // Retrying closes the popover.
+        open && self.requested.is_none()
+    }
+
+    fn take_filters(&mut self) -> Option<DataFilters> {
+        self.requested.take()
     }
 }
 
@@ -184,3 +276,26 @@ impl ExtraInteractions for Ui {
         response // Return the response.
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_chain() {
+        let filters = DataFilters::new("/tmp/data.parquet");
+        let error = Error::new("Error reading parquet: IO error: No such file or directory")
+            .with_retry(filters);
+
+        assert_eq!(
+            error.chain(),
+            [
+                "Error reading parquet",
+                "IO error",
+                "No such file or directory"
+            ]
+        );
+        assert!(error.report().ends_with("\nFile: /tmp/data.parquet"));
+        assert!(error.retry.is_some());
+    }
+}
@@ -0,0 +1,311 @@
+use crate::{DerivedColumn, SortState};
+
+use polars::{prelude::*, sql::sql_expr};
+use serde::{Deserialize, Serialize};
+use std::{fmt, fs, path::Path};
+
+/// Extension of the recipe files.
+pub const RECIPE_EXTENSION: &str = "recipe.json";
+
+/// Types a column can be cast to from the table header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CastType {
+    String,
+    Int64,
+    Float64,
+    Boolean,
+    Date,
+    Datetime,
+}
+
+impl CastType {
+    pub const ALL: [CastType; 6] = [
+        CastType::String,
+        CastType::Int64,
+        CastType::Float64,
+        CastType::Boolean,
+        CastType::Date,
+        CastType::Datetime,
+    ];
+
+    pub fn data_type(&self) -> DataType {
+        match self {
+            CastType::String => DataType::String,
+            CastType::Int64 => DataType::Int64,
+            CastType::Float64 => DataType::Float64,
+            CastType::Boolean => DataType::Boolean,
+            CastType::Date => DataType::Date,
+            CastType::Datetime => DataType::Datetime(TimeUnit::Milliseconds, None),
+        }
+    }
+}
+
+impl fmt::Display for CastType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.data_type())
+    }
+}
+
+/// A step of the pipeline applied to the query result, in order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColumnTransform {
+    Rename {
+        from: String,
+        to: String,
+    },
+    /// Values that cannot be cast become null.
+    Cast {
+        column: String,
+        to: CastType,
+    },
+    /// Replaces every match of `pattern` in the string columns.
+    Replace {
+        columns: Vec<String>,
+        pattern: String,
+        replacement: String,
+        /// `pattern` is a regular expression, rather than plain text.
+        regex: bool,
+    },
+    /// Keeps the rows matching a SQL condition, such as `qty > 10`.
+    Filter {
+        condition: String,
+    },
+    /// Adds a column computed with a SQL expression.
+    Compute(DerivedColumn),
+    Sort {
+        column: String,
+        descending: bool,
+    },
+}
+
+impl ColumnTransform {
+    /// Applies the step; a column missing from the data is an error once collected.
+    pub fn apply(&self, lazyframe: LazyFrame) -> Result<LazyFrame, String> {
+        Ok(match self {
+            ColumnTransform::Rename { from, to } => lazyframe.rename([from], [to], true),
+            ColumnTransform::Cast { column, to } => {
+                lazyframe.with_column(col(column.as_str()).cast(to.data_type()))
+            }
+            ColumnTransform::Filter { condition } => {
+                let condition = sql_expr(condition)
+                    .map_err(|e| format!("Invalid condition '{}': {}", condition, e))?;
+                lazyframe.filter(condition)
+            }
+            ColumnTransform::Compute(column) => lazyframe.with_column(column.expr()?),
+            ColumnTransform::Sort { column, descending } => lazyframe.sort(
+                [column.as_str()],
+                SortMultipleOptions::default()
+                    .with_order_descending(*descending)
+                    .with_maintain_order(true),
+            ),
+            ColumnTransform::Replace {
+                columns,
+                pattern,
+                replacement,
+                regex,
+            } => {
+                let replaced: Vec<Expr> = columns
+                    .iter()
+                    .map(|column| {
+                        col(column.as_str()).str().replace_all(
+                            lit(pattern.as_str()),
+                            lit(replacement.as_str()),
+                            !regex,
+                        )
+                    })
+                    .collect();
+                lazyframe.with_columns(replaced)
+            }
+        })
+    }
+
+    /// Rows of the data with a match of a `Replace` transform, which it would change.
+    pub fn count_matches(&self, df: &DataFrame) -> Result<usize, String> {
+        let ColumnTransform::Replace {
+            columns,
+            pattern,
+            regex,
+            ..
+        } = self
+        else {
+            return Ok(0);
+        };
+
+        let matched = columns
+            .iter()
+            .map(|column| match regex {
+                true => col(column.as_str())
+                    .str()
+                    .contains(lit(pattern.as_str()), true),
+                false => col(column.as_str())
+                    .str()
+                    .contains_literal(lit(pattern.as_str())),
+            })
+            .reduce(|left, right| left.or(right))
+            .ok_or_else(|| "Select at least one column".to_string())?;
+
+        let count = df
+            .clone()
+            .lazy()
+            .filter(matched)
+            .select([len()])
+            .collect()
+            .map_err(|e| format!("Invalid pattern '{}': {}", pattern, e))?;
+        let count = count[0]
+            .get(0)
+            .ok()
+            .and_then(|count| count.extract::<usize>())
+            .unwrap_or(0);
+        Ok(count)
+    }
+
+    /// The sort of a column follows the column when it is renamed.
+    pub fn update_sort(&self, sort: SortState) -> SortState {
+        let ColumnTransform::Rename { from, to } = self else {
+            return sort;
+        };
+        match sort {
+            SortState::Ascending(column) if column == *from => SortState::Ascending(to.clone()),
+            SortState::Descending(column) if column == *from => SortState::Descending(to.clone()),
+            sort => sort,
+        }
+    }
+}
+
+impl fmt::Display for ColumnTransform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColumnTransform::Rename { from, to } => write!(f, "Rename {from} → {to}"),
+            ColumnTransform::Cast { column, to } => write!(f, "Cast {column} to {to}"),
+            ColumnTransform::Replace {
+                columns,
+                pattern,
+                replacement,
+                ..
+            } => write!(
+                f,
+                "Replace '{pattern}' with '{replacement}' in {}",
+                columns.join(", ")
+            ),
+            ColumnTransform::Filter { condition } => write!(f, "Filter {condition}"),
+            ColumnTransform::Compute(column) => {
+                write!(f, "Compute {} = {}", column.name, column.expression)
+            }
+            ColumnTransform::Sort { column, descending } => match descending {
+                true => write!(f, "Sort {column} descending"),
+                false => write!(f, "Sort {column}"),
+            },
+        }
+    }
+}
+
+/// Applies the steps in turn, so that each can use the columns of the previous ones.
+pub fn apply_transforms(
+    lazyframe: LazyFrame,
+    transforms: &[ColumnTransform],
+) -> Result<LazyFrame, String> {
+    transforms
+        .iter()
+        .try_fold(lazyframe, |lazyframe, transform| transform.apply(lazyframe))
+}
+
+/// Pipeline saved to a `.recipe.json` file, to reapply it to other files.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Recipe {
+    pub transforms: Vec<ColumnTransform>,
+}
+
+impl Recipe {
+    /// Reads a recipe file.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let json = fs::read_to_string(path)
+            .map_err(|e| format!("Error reading recipe {}: {}", path.display(), e))?;
+        serde_json::from_str(&json).map_err(|e| format!("Invalid recipe {}: {}", path.display(), e))
+    }
+
+    /// Writes the recipe file.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Error serializing recipe: {}", e))?;
+        fs::write(path, json).map_err(|e| format!("Error writing recipe {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transforms_recipe() -> Result<(), String> {
+        let df =
+            df!["id" => ["1", "2", "x"], "name" => ["a", "b", "c"]].map_err(|e| e.to_string())?;
+
+        let recipe = Recipe {
+            transforms: vec![
+                ColumnTransform::Cast {
+                    column: "id".to_string(),
+                    to: CastType::Int64,
+                },
+                ColumnTransform::Rename {
+                    from: "id".to_string(),
+                    to: "code".to_string(),
+                },
+            ],
+        };
+
+        let path = std::env::temp_dir().join(format!("polars_view_test.{RECIPE_EXTENSION}"));
+        recipe.save(&path)?;
+        let loaded = Recipe::load(&path);
+        let _ = fs::remove_file(&path);
+        assert_eq!(loaded?, recipe);
+
+        let result = apply_transforms(df.lazy(), &recipe.transforms)?
+            .collect()
+            .map_err(|e| e.to_string())?;
+        let expected = df!["code" => [Some(1i64), Some(2), None], "name" => ["a", "b", "c"]]
+            .map_err(|e| e.to_string())?;
+        assert!(result.equals_missing(&expected));
+
+        let sort = recipe.transforms[1].update_sort(SortState::Descending("id".to_string()));
+        assert_eq!(sort, SortState::Descending("code".to_string()));
+
+        let replace = ColumnTransform::Replace {
+            columns: vec!["name".to_string()],
+            pattern: "^[ab]$".to_string(),
+            replacement: "z".to_string(),
+            regex: true,
+        };
+        assert_eq!(replace.count_matches(&result), Ok(2));
+        let replaced = replace
+            .apply(result.clone().lazy())?
+            .collect()
+            .map_err(|e| e.to_string())?;
+        assert_eq!(
+            replaced["name"].str().map_err(|e| e.to_string())?.get(1),
+            Some("z")
+        );
+
+        // Later steps use the columns of the earlier ones.
+        let pipeline = [
+            ColumnTransform::Compute(DerivedColumn::new("", "code * 10 AS tens")),
+            ColumnTransform::Filter {
+                condition: "tens IS NOT NULL".to_string(),
+            },
+            ColumnTransform::Sort {
+                column: "tens".to_string(),
+                descending: true,
+            },
+        ];
+        let sorted = apply_transforms(result.lazy(), &pipeline)?
+            .collect()
+            .map_err(|e| e.to_string())?;
+        let tens: Vec<Option<i64>> = sorted["tens"]
+            .i64()
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .collect();
+        assert_eq!(tens, [Some(20), Some(10)]);
+        Ok(())
+    }
+}
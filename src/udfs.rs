@@ -0,0 +1,492 @@
+use polars::{
+    prelude::*,
+    sql::{FunctionRegistry, SQLContext, sql_expr},
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{fmt, sync::Arc};
+
+/// Scalar functions added to Polars SQL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlFunction {
+    /// `is_valid_cpf(text)`: whether a CPF (individual taxpayer number) has valid check digits.
+    IsValidCpf,
+    /// `is_valid_cnpj(text)`: whether a CNPJ (company number) has valid check digits.
+    IsValidCnpj,
+    /// `digits(text)`: the digits of a text, without punctuation.
+    Digits,
+    /// `regex_extract(text, pattern)`: the first match of a regular expression, if any.
+    RegexExtract,
+    /// `fiscal_period(text)`: the first day of a period such as `03/2020`, `2020-03`,
+    /// `202003` or `março 2020`.
+    FiscalPeriod,
+}
+
+impl SqlFunction {
+    pub const ALL: [SqlFunction; 5] = [
+        SqlFunction::IsValidCpf,
+        SqlFunction::IsValidCnpj,
+        SqlFunction::Digits,
+        SqlFunction::RegexExtract,
+        SqlFunction::FiscalPeriod,
+    ];
+
+    /// Name of the function in SQL.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SqlFunction::IsValidCpf => "is_valid_cpf",
+            SqlFunction::IsValidCnpj => "is_valid_cnpj",
+            SqlFunction::Digits => "digits",
+            SqlFunction::RegexExtract => "regex_extract",
+            SqlFunction::FiscalPeriod => "fiscal_period",
+        }
+    }
+
+    fn arity(&self) -> usize {
+        match self {
+            SqlFunction::RegexExtract => 2,
+            _ => 1,
+        }
+    }
+
+    fn output_type(&self) -> DataType {
+        match self {
+            SqlFunction::IsValidCpf | SqlFunction::IsValidCnpj => DataType::Boolean,
+            SqlFunction::Digits | SqlFunction::RegexExtract => DataType::String,
+            SqlFunction::FiscalPeriod => DataType::Date,
+        }
+    }
+
+    /// Applies the function to the values of its arguments.
+    fn evaluate(&self, columns: &mut [Column]) -> PolarsResult<Option<Column>> {
+        let column = &columns[0];
+        let name = column.name().clone();
+        // Numbers lose the leading zeros of the documents.
+        let integer = column.dtype().is_integer();
+        let texts = column.cast(&DataType::String)?;
+        let texts = texts.str()?;
+
+        let result = match self {
+            SqlFunction::IsValidCpf | SqlFunction::IsValidCnpj => {
+                let length = match self {
+                    SqlFunction::IsValidCpf => 11,
+                    _ => 14,
+                };
+                let valid: BooleanChunked = texts
+                    .iter()
+                    .map(|text| {
+                        text.map(|text| {
+                            let mut digits = only_digits(text);
+                            if integer && digits.len() < length {
+                                digits = format!("{digits:0>length$}");
+                            }
+                            match self {
+                                SqlFunction::IsValidCpf => is_valid_cpf(&digits),
+                                _ => is_valid_cnpj(&digits),
+                            }
+                        })
+                    })
+                    .collect();
+                valid.into_series()
+            }
+            SqlFunction::Digits => {
+                let digits: StringChunked =
+                    texts.iter().map(|text| text.map(only_digits)).collect();
+                digits.into_series()
+            }
+            SqlFunction::RegexExtract => {
+                let pattern = columns[1].cast(&DataType::String)?;
+                let pattern = match (pattern.len(), pattern.str()?.get(0)) {
+                    (1, Some(pattern)) => pattern.to_string(),
+                    _ => polars_bail!(InvalidOperation: "regex_extract expects a literal pattern"),
+                };
+                let regex = Regex::new(&pattern)
+                    .map_err(|e| polars_err!(InvalidOperation: "invalid regex: {}", e))?;
+                let matches: StringChunked = texts
+                    .iter()
+                    .map(|text| text.and_then(|text| regex.find(text)).map(|m| m.as_str()))
+                    .collect();
+                matches.into_series()
+            }
+            SqlFunction::FiscalPeriod => {
+                let periods: StringChunked = texts
+                    .iter()
+                    .map(|text| {
+                        text.and_then(parse_fiscal_period)
+                            .map(|(year, month)| format!("{year:04}-{month:02}-01"))
+                    })
+                    .collect();
+                periods.into_series().cast(&DataType::Date)?
+            }
+        };
+        Ok(Some(result.with_name(name).into_column()))
+    }
+
+    /// The function with input fields named as its arguments, as Polars checks them.
+    fn udf(self, name: &str, arguments: &[PlSmallStr]) -> UserDefinedFunction {
+        let fields = arguments
+            .iter()
+            .map(|argument| Field::new(argument.clone(), DataType::String))
+            .collect();
+        UserDefinedFunction::new(
+            name.into(),
+            fields,
+            GetOutput::from_type(self.output_type()),
+            move |columns: &mut [Column]| self.evaluate(columns),
+        )
+    }
+}
+
+impl fmt::Display for SqlFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SqlFunction::RegexExtract => write!(f, "{}(text, pattern)", self.name()),
+            _ => write!(f, "{}(text)", self.name()),
+        }
+    }
+}
+
+fn only_digits(text: &str) -> String {
+    text.chars().filter(char::is_ascii_digit).collect()
+}
+
+/// Check digit of a CPF or CNPJ: 11 minus the remainder of the weighted sum by 11, or 0.
+fn check_digit(digits: &[u32], weights: impl Iterator<Item = u32>) -> u32 {
+    let sum: u32 = digits.iter().zip(weights).map(|(d, w)| d * w).sum();
+    match sum % 11 {
+        0 | 1 => 0,
+        remainder => 11 - remainder,
+    }
+}
+
+fn document_digits(text: &str, length: usize) -> Option<Vec<u32>> {
+    let digits: Vec<u32> = text.chars().filter_map(|c| c.to_digit(10)).collect();
+    // Repeated digits, such as 111.111.111-11, pass the check but are not valid.
+    (digits.len() == length && digits.iter().any(|d| *d != digits[0])).then_some(digits)
+}
+
+fn is_valid_cpf(text: &str) -> bool {
+    document_digits(text, 11).is_some_and(|d| {
+        d[9] == check_digit(&d[..9], (2..=10).rev())
+            && d[10] == check_digit(&d[..10], (2..=11).rev())
+    })
+}
+
+fn is_valid_cnpj(text: &str) -> bool {
+    let weights = |n: usize| {
+        (2..=9)
+            .cycle()
+            .take(n)
+            .collect::<Vec<u32>>()
+            .into_iter()
+            .rev()
+    };
+    document_digits(text, 14).is_some_and(|d| {
+        d[12] == check_digit(&d[..12], weights(12)) && d[13] == check_digit(&d[..13], weights(13))
+    })
+}
+
+const MONTHS: [&str; 12] = [
+    "janeiro",
+    "fevereiro",
+    "março",
+    "abril",
+    "maio",
+    "junho",
+    "julho",
+    "agosto",
+    "setembro",
+    "outubro",
+    "novembro",
+    "dezembro",
+];
+
+/// Year and month of a period: `MM/YYYY`, `YYYY-MM`, `YYYYMM`, a date or a Portuguese month name.
+fn parse_fiscal_period(text: &str) -> Option<(u32, u32)> {
+    let text = text.trim().to_lowercase();
+    let parts: Vec<&str> = text
+        .split(['/', '-', ' '])
+        .filter(|part| !part.is_empty() && *part != "de")
+        .collect();
+
+    let month_of = |part: &str| {
+        part.parse::<u32>().ok().or_else(|| {
+            MONTHS
+                .iter()
+                .position(|month| *month == part || (part.len() >= 3 && month.starts_with(part)))
+                .map(|index| index as u32 + 1)
+        })
+    };
+    let (year, month) = match parts.as_slice() {
+        [period] if period.len() == 6 => (period[..4].parse().ok()?, period[4..].parse().ok()?),
+        [year, month, ..] if year.len() == 4 => (year.parse().ok()?, month_of(month)?),
+        [month, year] if year.len() == 4 => (year.parse().ok()?, month_of(month)?),
+        _ => return None,
+    };
+    (1..=12).contains(&month).then_some((year, month))
+}
+
+/// Functions added to Polars SQL, by family; all are enabled by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SqlFunctions {
+    /// CPF and CNPJ validators.
+    pub documents: bool,
+    /// Text helpers: digits and regular expressions.
+    pub text: bool,
+    /// Fiscal period parsing.
+    pub periods: bool,
+}
+
+impl Default for SqlFunctions {
+    fn default() -> Self {
+        SqlFunctions {
+            documents: true,
+            text: true,
+            periods: true,
+        }
+    }
+}
+
+impl SqlFunctions {
+    pub fn enabled(&self) -> Vec<SqlFunction> {
+        SqlFunction::ALL
+            .into_iter()
+            .filter(|function| match function {
+                SqlFunction::IsValidCpf | SqlFunction::IsValidCnpj => self.documents,
+                SqlFunction::Digits | SqlFunction::RegexExtract => self.text,
+                SqlFunction::FiscalPeriod => self.periods,
+            })
+            .collect()
+    }
+
+    /// Registers the functions called by a statement, returning the statement calling them.
+    ///
+    /// Polars checks the arguments of a function against the names of its input fields,
+    /// so each call gets a function of its own, named after the call.
+    pub fn prepare(&self, ctx: &mut SQLContext, statement: &str) -> PolarsResult<String> {
+        let enabled = self.enabled();
+        let mut calls: Vec<(SqlFunction, String, Vec<PlSmallStr>)> = Vec::new();
+        let mut output = String::with_capacity(statement.len());
+        let mut rest = statement;
+
+        while let Some((before, identifier, after)) = next_identifier(rest) {
+            output.push_str(before);
+            let function = enabled
+                .iter()
+                .find(|function| function.name().eq_ignore_ascii_case(identifier));
+            match (function, call_arguments(after)) {
+                (Some(function), Some(arguments)) => {
+                    polars_ensure!(
+                        arguments.len() == function.arity(),
+                        InvalidOperation: "{} expects {} argument(s)", function, function.arity()
+                    );
+                    let name = format!("{}__{}", function.name(), calls.len());
+                    let names = arguments
+                        .iter()
+                        .map(|argument| argument_name(argument))
+                        .collect();
+                    output.push_str(&name);
+                    calls.push((*function, name, names));
+                }
+                _ => output.push_str(identifier),
+            }
+            rest = after;
+        }
+        output.push_str(rest);
+
+        for (function, name, arguments) in calls {
+            let udf = function.udf(&name, &arguments);
+            ctx.registry_mut().register(&name, udf)?;
+        }
+        Ok(output)
+    }
+}
+
+/// Output name of an argument, which Polars gives to the input field.
+fn argument_name(argument: &str) -> PlSmallStr {
+    sql_expr(argument)
+        .ok()
+        .and_then(|expr| expr.meta().output_name().ok())
+        .unwrap_or_else(|| {
+            // Arguments calling the functions added are named after their first column.
+            let mut rest = argument;
+            while let Some((_, identifier, after)) = next_identifier(rest) {
+                if call_arguments(after).is_none() {
+                    return identifier.into();
+                }
+                rest = after;
+            }
+            "literal".into()
+        })
+}
+
+/// The text before the next identifier outside quotes and comments, the identifier and the rest.
+fn next_identifier(text: &str) -> Option<(&str, &str, &str)> {
+    let mut quote: Option<char> = None;
+    let mut chars = text.char_indices().peekable();
+    let mut previous = ' ';
+
+    while let Some((index, c)) = chars.next() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '\'' || c == '"' || c == '`' => quote = Some(c),
+            None if c == '-' && chars.peek().is_some_and(|(_, next)| *next == '-') => {
+                while chars.next_if(|(_, next)| *next != '\n').is_some() {}
+            }
+            None if (c.is_alphabetic() || c == '_')
+                && !(previous.is_alphanumeric() || previous == '_' || previous == '.') =>
+            {
+                let end = text[index..]
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .map_or(text.len(), |length| index + length);
+                return Some((&text[..index], &text[index..end], &text[end..]));
+            }
+            None => {}
+        }
+        previous = c;
+    }
+    None
+}
+
+/// Arguments of a call, when `text` starts with them in parentheses.
+fn call_arguments(text: &str) -> Option<Vec<&str>> {
+    let open = text.len() - text.trim_start().len();
+    if !text[open..].starts_with('(') {
+        return None;
+    }
+
+    let mut arguments = Vec::new();
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    let mut start = open + 1;
+    for (index, c) in text.char_indices().skip_while(|(index, _)| *index <= open) {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"' | '`') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') if depth == 0 => {
+                let last = text[start..index].trim();
+                if !last.is_empty() || !arguments.is_empty() {
+                    arguments.push(last);
+                }
+                return Some(arguments);
+            }
+            (None, ')') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                arguments.push(text[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// A SQL context able to call the functions added.
+pub fn sql_context() -> SQLContext {
+    SQLContext::new().with_function_registry(Arc::new(UdfRegistry::default()))
+}
+
+/// Registry of the functions added, as called by the statements.
+#[derive(Default)]
+struct UdfRegistry {
+    functions: PlHashMap<String, UserDefinedFunction>,
+}
+
+impl FunctionRegistry for UdfRegistry {
+    fn register(&mut self, name: &str, fun: UserDefinedFunction) -> PolarsResult<()> {
+        self.functions.insert(name.to_string(), fun);
+        Ok(())
+    }
+
+    fn get_udf(&self, name: &str) -> PolarsResult<Option<UserDefinedFunction>> {
+        Ok(self.functions.get(name).cloned())
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.functions.contains_key(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execute_statements;
+
+    #[test]
+    fn test_sql_functions() -> Result<(), String> {
+        assert!(is_valid_cpf("529.982.247-25"));
+        assert!(!is_valid_cpf("529.982.247-26"));
+        assert!(!is_valid_cpf("111.111.111-11"));
+        assert!(is_valid_cnpj("11.222.333/0001-81"));
+        assert!(!is_valid_cnpj("11.222.333/0001-80"));
+        assert_eq!(parse_fiscal_period("03/2020"), Some((2020, 3)));
+        assert_eq!(parse_fiscal_period("2020-03-15"), Some((2020, 3)));
+        assert_eq!(parse_fiscal_period("202012"), Some((2020, 12)));
+        assert_eq!(parse_fiscal_period("Março de 2020"), Some((2020, 3)));
+        assert_eq!(parse_fiscal_period("13/2020"), None);
+
+        let df = df![
+            "doc" => ["529.982.247-25", "11.222.333/0001-81", "123"],
+            "period" => ["01/2021", "2021-02", "x"],
+        ]
+        .map_err(|e| e.to_string())?;
+        let mut ctx = sql_context();
+        ctx.register("AllData", df.lazy());
+
+        let sql = "SELECT is_valid_cpf(doc) AS cpf, IS_VALID_CNPJ(doc) AS cnpj, \
+                   regex_extract(digits(doc), '^\\d{3}') AS prefix, \
+                   fiscal_period(period) AS month FROM AllData";
+        let result = execute_statements(&mut ctx, sql, SqlFunctions::default())?
+            .collect()
+            .map_err(|e| e.to_string())?;
+
+        let column = |name: &str| {
+            result
+                .column(name)
+                .map(|c| c.as_materialized_series().clone())
+        };
+        let cpf = column("cpf").map_err(|e| e.to_string())?;
+        assert_eq!(
+            cpf.bool()
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .collect::<Vec<_>>(),
+            [Some(true), Some(false), Some(false)]
+        );
+        let cnpj = column("cnpj").map_err(|e| e.to_string())?;
+        assert_eq!(
+            cnpj.bool()
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .collect::<Vec<_>>(),
+            [Some(false), Some(true), Some(false)]
+        );
+        let prefix = column("prefix").map_err(|e| e.to_string())?;
+        assert_eq!(
+            prefix
+                .str()
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .collect::<Vec<_>>(),
+            [Some("529"), Some("112"), Some("123")]
+        );
+        let month = column("month").map_err(|e| e.to_string())?;
+        assert_eq!(month.dtype(), &DataType::Date);
+        assert_eq!(month.null_count(), 1);
+
+        // Disabled functions are unknown to SQL.
+        let disabled = SqlFunctions {
+            documents: false,
+            ..Default::default()
+        };
+        assert!(
+            execute_statements(&mut ctx, "SELECT is_valid_cpf(doc) FROM AllData", disabled)
+                .is_err()
+        );
+        Ok(())
+    }
+}
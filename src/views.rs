@@ -0,0 +1,130 @@
+use crate::DataFilters;
+
+use polars::prelude::DataFrame;
+use std::{fmt, sync::Arc};
+
+/// A result kept under a name, which the later queries of the session can select from.
+#[derive(Clone)]
+pub struct TempView {
+    pub name: String,
+    /// Query of the result, as a reminder.
+    pub query: Option<String>,
+    /// The result, registered with the SQL context as is.
+    pub df: Arc<DataFrame>,
+}
+
+// The data of a view is not printed with the filters.
+impl fmt::Debug for TempView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TempView")
+            .field("name", &self.name)
+            .field("query", &self.query)
+            .field("shape", &self.df.shape())
+            .finish()
+    }
+}
+
+impl DataFilters {
+    /// Registers a result as a view named `name`, replacing a view of that name
+    /// as `CREATE OR REPLACE VIEW` would.
+    pub fn create_view(
+        &mut self,
+        name: &str,
+        query: Option<String>,
+        df: Arc<DataFrame>,
+    ) -> Result<(), String> {
+        let name = name.trim();
+        let valid = name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if !valid {
+            return Err(format!(
+                "Invalid view name '{name}': use letters, digits and underscores"
+            ));
+        }
+
+        let taken = name.eq_ignore_ascii_case(&self.table_name)
+            || self
+                .table_names()
+                .iter()
+                .any(|table| name.eq_ignore_ascii_case(table));
+        if taken {
+            return Err(format!("The table name '{name}' is already used by a file"));
+        }
+
+        let view = TempView {
+            name: name.to_string(),
+            query,
+            df,
+        };
+        match self.views.iter_mut().find(|view| view.name == name) {
+            Some(existing) => *existing = view,
+            None => self.views.push(view),
+        }
+        Ok(())
+    }
+
+    /// Forgets the view named `name`.
+    pub fn drop_view(&mut self, name: &str) {
+        self.views.retain(|view| view.name != name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DataFrameContainer;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_query_temp_view() -> Result<(), String> {
+        let path = std::env::temp_dir().join("polars_view_test_views.csv");
+        std::fs::write(&path, "id;qty\n1;10\n2;20\n3;30\n").map_err(|e| e.to_string())?;
+        let filename = path.to_string_lossy().to_string();
+
+        let step1 = df!["id" => [2, 3]].map_err(|e| e.to_string())?;
+        let mut filters = DataFilters::new(&filename);
+        filters.create_view(
+            "step1",
+            Some("SELECT id FROM AllData WHERE qty > 10".to_string()),
+            Arc::new(step1),
+        )?;
+        assert!(
+            filters
+                .create_view("AllData", None, Arc::new(DataFrame::empty()))
+                .is_err()
+        );
+        assert!(
+            filters
+                .create_view("1st step", None, Arc::new(DataFrame::empty()))
+                .is_err()
+        );
+        assert!(format!("{filters:?}").contains("shape: (2, 1)"));
+
+        // Later queries build on the view.
+        filters.query = Some(
+            "SELECT SUM(qty) AS total FROM AllData WHERE id IN (SELECT id FROM step1);".to_string(),
+        );
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .map_err(|e| e.to_string())?;
+        let data = runtime.block_on(DataFrameContainer::load_data_with_sql(filters.clone()))?;
+        let total = data.df.column("total").map_err(|e| e.to_string())?.get(0);
+        assert_eq!(total.map_err(|e| e.to_string())?.extract::<i64>(), Some(50));
+
+        // A view is replaced by name, and dropped.
+        filters.create_view(
+            "step1",
+            None,
+            Arc::new(df!["id" => [1]].map_err(|e| e.to_string())?),
+        )?;
+        assert_eq!(filters.views.len(), 1);
+        assert_eq!(filters.views[0].df.height(), 1);
+        filters.drop_view("step1");
+        assert!(filters.views.is_empty());
+        let _ = std::fs::remove_file(path);
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{
+    CellEditor, ColumnMasks, DataFrameContainer, TableAction, TableScroll, TableViewOptions,
+};
+
+use egui::{ScrollArea, Ui};
+use polars::prelude::DataFrame;
+
+/// The data table of the app, to embed in other egui applications.
+///
+/// The header masks the columns; the other header actions (sort, histogram,
+/// value counts, rename and cast) are returned to the embedding application.
+/// A sort is run with [`DataFrameContainer::sort`], off the UI thread, and its result
+/// shown with [`DataFrameTable::set_sorted`].
+#[derive(Debug, Clone)]
+pub struct DataFrameTable {
+    pub data: DataFrameContainer,
+    pub options: TableViewOptions,
+    /// Scale of the table text, changed with Ctrl+scroll.
+    pub zoom: f32,
+    /// Cell edits, made in edit mode (disabled by default).
+    pub editor: CellEditor,
+    pub masks: ColumnMasks,
+    /// Vertical position of the table; given a runtime with [`TableScroll::set_runtime`],
+    /// the rows ahead of the scroll are formatted in the background.
+    pub scroll: TableScroll,
+}
+
+impl DataFrameTable {
+    pub fn new(df: DataFrame) -> Self {
+        Self::with_data(DataFrameContainer::from_dataframe(df))
+    }
+
+    /// A table of data loaded by the app functions, such as [`DataFrameContainer::load_data`].
+    pub fn with_data(data: DataFrameContainer) -> Self {
+        DataFrameTable {
+            data,
+            options: TableViewOptions::default(),
+            zoom: 1.0,
+            editor: CellEditor::default(),
+            masks: ColumnMasks::default(),
+            scroll: TableScroll::default(),
+        }
+    }
+
+    /// Replaces the data, discarding its edits and scrolling back to the top.
+    pub fn set_data(&mut self, data: DataFrameContainer) {
+        self.data = data;
+        self.editor.discard();
+        self.scroll.reset();
+    }
+
+    /// Replaces the data by a sorted version of it, keeping the scroll position.
+    pub fn set_sorted(&mut self, data: DataFrameContainer) {
+        self.data = data;
+        self.scroll.keep();
+    }
+
+    /// Shows the table, returning the header actions it does not handle itself.
+    pub fn show(&mut self, ui: &mut Ui) -> Option<TableAction> {
+        let action = ScrollArea::horizontal()
+            .show(ui, |ui| {
+                self.data.render_table(
+                    ui,
+                    &self.options,
+                    &mut self.zoom,
+                    &mut self.editor,
+                    &self.masks,
+                    &mut self.scroll,
+                )
+            })
+            .inner;
+
+        match action {
+            Some(TableAction::Mask(column, mask)) => {
+                self.masks.set(&column, mask);
+                None
+            }
+            action => action,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DataFilters, SortState};
+    use polars::prelude::*;
+
+    #[test]
+    fn test_embedded_table() -> Result<(), String> {
+        let df = df!["id" => [2, 1, 3], "name" => ["b", "a", "c"]].map_err(|e| e.to_string())?;
+        let mut table = DataFrameTable::new(df);
+
+        let ctx = egui::Context::default();
+        let mut action = None;
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| action = table.show(ui));
+        });
+        assert!(action.is_none());
+
+        // The sorts requested by the header are run by the application, as in the app.
+        let filters = DataFilters {
+            sort: Some(SortState::Descending("id".to_string())),
+            ..Default::default()
+        };
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .map_err(|e| e.to_string())?;
+        let sorted = runtime.block_on(table.data.clone().sort(Some(filters)))?;
+        table.set_sorted(sorted);
+        let ids: Vec<Option<i32>> = table.data.df["id"]
+            .i32()
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .collect();
+        assert_eq!(ids, [Some(3), Some(2), Some(1)]);
+        Ok(())
+    }
+}